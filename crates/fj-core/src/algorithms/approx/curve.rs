@@ -1,17 +1,20 @@
 use std::collections::BTreeMap;
 
-use fj_math::{Circle, Line, Point};
+use fj_math::{Circle, Ellipse, Line, NurbsCurve, Point};
 
 use crate::{
     geometry::{
         repr::tri_mesh::convert_point_surface_to_global, surfaces::SweptCurve,
-        CurveBoundary, Geometry, Path, Tolerance,
+        traits::GenTriMesh, CurveBoundary, Geometry, Path, Tolerance,
     },
     storage::Handle,
     topology::{Curve, Surface},
 };
 
-use super::{circle::approx_circle, line::approx_line, ApproxPoint};
+use super::{
+    circle::approx_circle, ellipse::approx_ellipse, line::approx_line,
+    nurbs::approx_nurbs, ApproxPoint,
+};
 
 /// Approximate the provided curve
 ///
@@ -28,18 +31,29 @@ pub fn approx_curve_with_cache(
     match cache.get(curve, boundary) {
         Some(approx) => approx,
         None => {
-            let approx = approx_curve(
-                &geometry
-                    .of_curve(curve)
-                    .unwrap()
-                    .local_on(surface)
-                    .unwrap()
-                    .path,
-                geometry.of_surface(surface),
-                boundary,
-                tolerance,
-                geometry,
-            );
+            let path = &geometry
+                .of_curve(curve)
+                .unwrap()
+                .local_on(surface)
+                .unwrap()
+                .path;
+
+            let approx = match geometry.try_of_surface(surface) {
+                Some(surface_geom) => approx_curve(
+                    path,
+                    surface_geom,
+                    boundary,
+                    tolerance,
+                    geometry,
+                ),
+                None => approx_curve_on_any_surface(
+                    path,
+                    &geometry.of_surface_2(surface).unwrap().generator,
+                    boundary,
+                    tolerance,
+                    geometry,
+                ),
+            };
 
             cache.insert(curve.clone(), boundary, approx)
         }
@@ -61,6 +75,23 @@ fn approx_curve(
                 circle, boundary, surface, tolerance, geometry,
             )
         }
+        (Path::Ellipse(ellipse), Path::Line(_)) => {
+            approx_ellipse_on_straight_surface(
+                ellipse, boundary, surface, tolerance, geometry,
+            )
+        }
+        (Path::Nurbs(curve), Path::Line(_)) => {
+            approx_nurbs_on_straight_surface(
+                curve, boundary, surface, tolerance, geometry,
+            )
+        }
+        (Path::Circle(_), Path::Ellipse(_) | Path::Nurbs(_))
+        | (Path::Ellipse(_) | Path::Nurbs(_), _) => {
+            approx_curve_on_any_surface(
+                path, surface, boundary, tolerance, geometry,
+            )
+            .points
+        }
         (Path::Line(line), _) => approx_line_on_any_surface(
             line, boundary, surface, tolerance, geometry,
         ),
@@ -110,6 +141,56 @@ fn approx_circle_on_straight_surface(
         .collect()
 }
 
+fn approx_ellipse_on_straight_surface(
+    ellipse: &Ellipse<2>,
+    boundary: CurveBoundary<Point<1>>,
+    surface: &SweptCurve,
+    tolerance: impl Into<Tolerance>,
+    geometry: &Geometry,
+) -> Vec<ApproxPoint<1>> {
+    let tolerance = tolerance.into();
+
+    // See `approx_circle_on_straight_surface` on why `point_surface` is thrown
+    // away here.
+    approx_ellipse(ellipse, boundary, tolerance)
+        .into_iter()
+        .map(|(point_curve, point_surface)| {
+            let point_global = convert_point_surface_to_global(
+                surface,
+                point_surface,
+                tolerance,
+                geometry,
+            );
+            ApproxPoint::new(point_curve, point_global)
+        })
+        .collect()
+}
+
+fn approx_nurbs_on_straight_surface(
+    curve: &NurbsCurve<2>,
+    boundary: CurveBoundary<Point<1>>,
+    surface: &SweptCurve,
+    tolerance: impl Into<Tolerance>,
+    geometry: &Geometry,
+) -> Vec<ApproxPoint<1>> {
+    let tolerance = tolerance.into();
+
+    // See `approx_circle_on_straight_surface` on why `point_surface` is thrown
+    // away here.
+    approx_nurbs(curve, boundary, tolerance)
+        .into_iter()
+        .map(|(point_curve, point_surface)| {
+            let point_global = convert_point_surface_to_global(
+                surface,
+                point_surface,
+                tolerance,
+                geometry,
+            );
+            ApproxPoint::new(point_curve, point_global)
+        })
+        .collect()
+}
+
 fn approx_line_on_any_surface(
     line: &Line<2>,
     boundary: CurveBoundary<Point<1>>,
@@ -128,7 +209,9 @@ fn approx_line_on_any_surface(
     let SweptCurve { u, .. } = surface;
     let approx_u = match u {
         Path::Circle(circle) => approx_circle(circle, range_u, tolerance),
+        Path::Ellipse(ellipse) => approx_ellipse(ellipse, range_u, tolerance),
         Path::Line(line) => approx_line(line),
+        Path::Nurbs(curve) => approx_nurbs(curve, range_u, tolerance),
     };
 
     let mut points = Vec::new();
@@ -141,12 +224,98 @@ fn approx_line_on_any_surface(
             tolerance,
             geometry,
         );
-        points.push(ApproxPoint::new(u, point_global));
+        points.push(ApproxPoint::new([t], point_global));
     }
 
     points
 }
 
+/// Approximate a curve on a surface that only has new-style geometry
+///
+/// Without more specific knowledge about the surface, the curve is subdivided
+/// recursively, until each segment of the approximation is close enough to
+/// the curve.
+fn approx_curve_on_any_surface(
+    path: &Path<2>,
+    surface: &dyn GenTriMesh,
+    boundary: CurveBoundary<Point<1>>,
+    tolerance: impl Into<Tolerance>,
+    geometry: &Geometry,
+) -> CurveApprox {
+    let tolerance = tolerance.into();
+
+    let approx_point = |point_curve: Point<1>| {
+        let point_global = convert_point_surface_to_global(
+            surface,
+            path.point_from_path_coords(point_curve),
+            tolerance,
+            geometry,
+        );
+        ApproxPoint::new(point_curve, point_global)
+    };
+
+    let [start, end] = boundary.inner.map(approx_point);
+
+    let mut points = Vec::new();
+    subdivide(start, end, &approx_point, tolerance, 0, &mut points);
+
+    CurveApprox { points }
+}
+
+/// # The maximum depth of the recursion in [`subdivide`]
+///
+/// Limits the approximation to `2^16` segments, in case the tolerance can't be
+/// reached, for example because it is below what floating-point precision can
+/// resolve.
+const MAX_SUBDIVISION_DEPTH: u32 = 16;
+
+/// # Subdivide a segment of a curve, until it is within the tolerance
+///
+/// A segment is accepted, if the curve deviates from it by no more than the
+/// tolerance at its midpoint _and_ at its quarter points. Checking the midpoint
+/// alone would accept segments of S-shaped curves, whose midpoint happens to
+/// lie on the segment.
+///
+/// Segments are always split in half, so all resulting points are located at
+/// dyadic fractions of the boundary. This keeps the approximation of shared
+/// curves consistent.
+fn subdivide(
+    start: ApproxPoint<1>,
+    end: ApproxPoint<1>,
+    approx_point: &impl Fn(Point<1>) -> ApproxPoint<1>,
+    tolerance: Tolerance,
+    depth: u32,
+    points: &mut Vec<ApproxPoint<1>>,
+) {
+    if depth >= MAX_SUBDIVISION_DEPTH {
+        return;
+    }
+
+    let deviation_at = |fraction: f64| {
+        let point = approx_point(
+            start.local_form + (end.local_form - start.local_form) * fraction,
+        );
+        let point_on_segment = start.global_form
+            + (end.global_form - start.global_form) * fraction;
+
+        (point, (point.global_form - point_on_segment).magnitude())
+    };
+
+    let (middle, deviation) = deviation_at(0.5);
+    let within_tolerance = deviation <= tolerance.inner()
+        && [0.25, 0.75]
+            .into_iter()
+            .all(|fraction| deviation_at(fraction).1 <= tolerance.inner());
+
+    if within_tolerance {
+        return;
+    }
+
+    subdivide(start, middle, approx_point, tolerance, depth + 1, points);
+    points.push(middle);
+    subdivide(middle, end, approx_point, tolerance, depth + 1, points);
+}
+
 /// Approximation of a [`Curve`], within a specific boundary
 ///
 /// The approximation of the curve only includes points _within_ the boundary,
@@ -205,16 +374,22 @@ impl CurveApproxCache {
 mod tests {
     use std::f64::consts::TAU;
 
-    use fj_math::{Circle, Point, Vector};
+    use fj_interop::ext::SliceExt;
+    use fj_math::{Circle, NurbsCurve, Point, Vector};
     use pretty_assertions::assert_eq;
 
     use crate::{
         algorithms::approx::{
-            circle::approx_circle, curve::approx_curve, ApproxPoint,
+            circle::approx_circle,
+            curve::{
+                approx_curve, approx_curve_on_any_surface,
+                MAX_SUBDIVISION_DEPTH,
+            },
+            ApproxPoint,
         },
         geometry::{
             repr::tri_mesh::convert_point_surface_to_global,
-            surfaces::SweptCurve, CurveBoundary, Path,
+            surfaces::SweptCurve, CurveBoundary, Path, Tolerance,
         },
         operations::build::BuildSurface,
         topology::Surface,
@@ -274,7 +449,7 @@ mod tests {
             u: global_path,
             v: Vector::from([0., 0., 1.]),
         };
-        let surface = Surface::from_geometry(surface_geom, &mut core);
+        let surface = Surface::from_geometry(surface_geom.clone(), &mut core);
         let path = Path::line_from_points_with_coords([
             ([0.], [0., 1.]),
             ([TAU], [TAU, 1.]),
@@ -315,8 +490,8 @@ mod tests {
     fn approx_circle_on_flat_surface() {
         let mut core = Core::new();
 
-        let surface_geom = *core.layers.geometry.xz_plane();
-        let surface = Surface::from_geometry(surface_geom, &mut core);
+        let surface_geom = core.layers.geometry.xz_plane().clone();
+        let surface = Surface::from_geometry(surface_geom.clone(), &mut core);
         let circle = Circle::from_center_and_radius([0., 0.], 1.);
         let path = Path::Circle(circle);
         let boundary = CurveBoundary::from([[0.], [TAU]]);
@@ -350,4 +525,79 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(approx.points, expected_approx);
     }
+
+    #[test]
+    fn approx_s_curve_on_any_surface() {
+        let core = Core::new();
+        let geometry = &core.layers.geometry;
+
+        // The midpoint of this curve lies on the line between its ends, but
+        // the curve deviates from that line everywhere else.
+        let surface = geometry.xy_plane();
+        let path = Path::Nurbs(NurbsCurve::clamped(
+            3,
+            [[0., 0.], [1., 1.], [2., -1.], [3., 0.]],
+        ));
+        let boundary = CurveBoundary::from([[0.], [1.]]);
+
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+        let approx = approx_curve_on_any_surface(
+            &path, surface, boundary, tolerance, geometry,
+        );
+        assert!(!approx.points.is_empty());
+
+        let approx_point = |t: f64| {
+            let point_global = convert_point_surface_to_global(
+                surface,
+                path.point_from_path_coords([t]),
+                tolerance,
+                geometry,
+            );
+            ApproxPoint::new([t], point_global)
+        };
+        let points = [approx_point(0.)]
+            .into_iter()
+            .chain(approx.points)
+            .chain([approx_point(1.)])
+            .collect::<Vec<_>>();
+
+        for &[start, end] in points.as_slice().array_windows_ext() {
+            // All points are at dyadic fractions of the boundary.
+            let t = end.local_form.t.into_f64();
+            assert_eq!((t * f64::from(1 << MAX_SUBDIVISION_DEPTH)).fract(), 0.);
+
+            for i in 1..10 {
+                let fraction = f64::from(i) / 10.;
+                let point = approx_point(
+                    (start.local_form.t
+                        + (end.local_form.t - start.local_form.t) * fraction)
+                        .into_f64(),
+                );
+                let point_on_segment = start.global_form
+                    + (end.global_form - start.global_form) * fraction;
+
+                let distance =
+                    (point.global_form - point_on_segment).magnitude();
+                assert!(distance <= tolerance.inner());
+            }
+        }
+    }
+
+    #[test]
+    fn limit_subdivision_depth() {
+        let core = Core::new();
+        let geometry = &core.layers.geometry;
+
+        // The tolerance is too small to be reached within the maximum depth.
+        let surface = geometry.xy_plane();
+        let path = Path::Circle(Circle::from_center_and_radius([0., 0.], 1.));
+        let boundary = CurveBoundary::from([[0.], [TAU]]);
+
+        let tolerance = Tolerance::from_scalar(1e-12).unwrap();
+        let approx = approx_curve_on_any_surface(
+            &path, surface, boundary, tolerance, geometry,
+        );
+
+        assert_eq!(approx.points.len(), (1 << MAX_SUBDIVISION_DEPTH) - 1);
+    }
 }
@@ -0,0 +1,26 @@
+use fj_math::{Ellipse, Point};
+
+use crate::geometry::{traits::GenPolyline, CurveBoundary, Tolerance};
+
+/// # Approximate an ellipse
+///
+/// Like circle approximation, ellipse approximation returns points within the
+/// provided boundary, not including the boundary points themselves. And like
+/// circle approximation, it is deterministic for a given combination of ellipse
+/// and tolerance, regardless of the boundary.
+pub fn approx_ellipse<const D: usize>(
+    ellipse: &Ellipse<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: impl Into<Tolerance>,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+    let tolerance = tolerance.into();
+
+    ellipse
+        .generate_polyline(boundary, tolerance)
+        .into_iter()
+        .map(|point_curve| {
+            (point_curve, ellipse.point_from_ellipse_coords(point_curve))
+        })
+        .collect()
+}
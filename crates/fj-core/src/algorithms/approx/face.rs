@@ -92,7 +92,7 @@ pub fn approx_face(
         interiors.insert(cycle);
     }
 
-    let coord_handedness = face.coord_handedness(geometry);
+    let coord_handedness = face.coord_handedness(tolerance, geometry);
     FaceApprox {
         face,
         exterior,
@@ -8,9 +8,11 @@ pub mod sketch;
 pub mod solid;
 
 mod circle;
-mod curve;
+pub(crate) mod curve;
+mod ellipse;
 mod line;
-mod vertex;
+mod nurbs;
+pub(crate) mod vertex;
 
 use std::{
     cmp::Ordering,
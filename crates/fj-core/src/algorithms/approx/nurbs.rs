@@ -0,0 +1,26 @@
+use fj_math::{NurbsCurve, Point};
+
+use crate::geometry::{traits::GenPolyline, CurveBoundary, Tolerance};
+
+/// # Approximate a NURBS curve
+///
+/// Like circle approximation, NURBS curve approximation returns points within
+/// the provided boundary, not including the boundary points themselves. And
+/// like circle approximation, it is deterministic for a given combination of
+/// curve and tolerance, regardless of the boundary.
+pub fn approx_nurbs<const D: usize>(
+    curve: &NurbsCurve<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: impl Into<Tolerance>,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+    let tolerance = tolerance.into();
+
+    curve
+        .generate_polyline(boundary, tolerance)
+        .into_iter()
+        .map(|point_curve| {
+            (point_curve, curve.point_from_curve_coords(point_curve))
+        })
+        .collect()
+}
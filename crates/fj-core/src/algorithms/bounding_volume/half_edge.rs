@@ -12,7 +12,7 @@ impl super::BoundingVolume<2>
     fn aabb(self, geometry: &Geometry) -> Option<Aabb<2>> {
         let (half_edge, end_vertex, surface) = self;
 
-        let path = geometry
+        let path = &geometry
             .of_curve(half_edge.curve())
             .unwrap()
             .local_on(surface)
@@ -32,6 +32,11 @@ impl super::BoundingVolume<2>
                     max: circle.center() + center_to_min_max,
                 })
             }
+            Path::Ellipse(ellipse) => {
+                // Same as with the circle, calculate the AABB of the whole
+                // ellipse.
+                Some(ellipse.aabb())
+            }
             Path::Line(_) => {
                 let points =
                     [half_edge.start_vertex(), end_vertex].map(|vertex| {
@@ -47,6 +52,11 @@ impl super::BoundingVolume<2>
 
                 Some(Aabb::<2>::from_points(points))
             }
+            Path::Nurbs(curve) => {
+                // Same as with the circle, calculate the AABB of the whole
+                // curve.
+                Some(curve.aabb())
+            }
         }
     }
 }
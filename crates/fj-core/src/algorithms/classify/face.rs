@@ -0,0 +1,100 @@
+use std::ops::Deref;
+
+use fj_math::Point;
+
+use crate::{
+    algorithms::{
+        approx::{
+            cycle::{approx_cycle, CycleApprox},
+            ApproxCache,
+        },
+        triangulate::polygon::Polygon,
+    },
+    geometry::{repr::tri_mesh::convert_point_surface_to_global, Geometry},
+    storage::Handle,
+    topology::{Face, Region, Surface},
+    validation::ValidationConfig,
+};
+
+use super::{ClassifyPoint, PointClassification};
+
+impl ClassifyPoint<2> for &Face {
+    fn classify_point(
+        self,
+        point: impl Into<Point<2>>,
+        config: &ValidationConfig,
+        geometry: &Geometry,
+    ) -> PointClassification {
+        (self.region().deref(), self.surface())
+            .classify_point(point, config, geometry)
+    }
+}
+
+impl ClassifyPoint<2> for (&Region, &Handle<Surface>) {
+    fn classify_point(
+        self,
+        point: impl Into<Point<2>>,
+        config: &ValidationConfig,
+        geometry: &Geometry,
+    ) -> PointClassification {
+        let (region, surface) = self;
+        let point = point.into();
+
+        let mut cache = ApproxCache::default();
+        let cycles = region
+            .all_cycles()
+            .map(|cycle| {
+                approx_cycle(
+                    cycle,
+                    surface,
+                    config.tolerance,
+                    &mut cache,
+                    geometry,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // The distance to the boundary is checked in global coordinates, as
+        // distances in surface coordinates can be distorted arbitrarily.
+        let point_global = convert_point_surface_to_global(
+            &geometry
+                .of_surface_2(surface)
+                .expect("Expected geometry of surface to be defined")
+                .generator,
+            point,
+            config.tolerance,
+            geometry,
+        );
+        let is_on_boundary = cycles
+            .iter()
+            .flat_map(|cycle| cycle.segments())
+            .any(|segment| {
+                let closest = segment.closest_point(point_global);
+                (closest - point_global).magnitude()
+                    <= config.identical_max_distance
+            });
+        if is_on_boundary {
+            return PointClassification::Boundary;
+        }
+
+        let [exterior, interiors @ ..] = cycles.as_slice() else {
+            unreachable!("A region always has an exterior cycle");
+        };
+        let local_forms = |cycle: &CycleApprox| {
+            cycle
+                .points()
+                .into_iter()
+                .map(|point| point.local_form)
+                .collect::<Vec<_>>()
+        };
+        let polygon = Polygon::new()
+            .with_exterior(local_forms(exterior))
+            .with_interiors(interiors.iter().map(local_forms));
+
+        if polygon.contains_point(point) {
+            PointClassification::Inside
+        } else {
+            PointClassification::Outside
+        }
+    }
+}
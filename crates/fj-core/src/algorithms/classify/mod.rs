@@ -0,0 +1,154 @@
+//! Classify points relative to objects
+//!
+//! See [`ClassifyPoint`].
+
+mod face;
+mod solid;
+
+use fj_math::Point;
+
+use crate::{geometry::Geometry, validation::ValidationConfig};
+
+/// Classify a point relative to an object
+///
+/// This is generic over the dimensionality of the point. Faces and regions
+/// classify points in surface coordinates (`D == 2`), shells and solids
+/// classify points in global coordinates (`D == 3`).
+pub trait ClassifyPoint<const D: usize> {
+    /// Classify the point relative to the object
+    ///
+    /// The object is approximated, using the tolerance from `config`. A point
+    /// is considered to be on the boundary of the object, if its distance from
+    /// that approximation is no larger than `config.identical_max_distance`.
+    fn classify_point(
+        self,
+        point: impl Into<Point<D>>,
+        config: &ValidationConfig,
+        geometry: &Geometry,
+    ) -> PointClassification;
+}
+
+/// Where a point is, relative to an object
+///
+/// See [`ClassifyPoint`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum PointClassification {
+    /// The point is inside of the object
+    Inside,
+
+    /// The point is outside of the object
+    Outside,
+
+    /// The point is on the boundary of the object
+    Boundary,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildFace, BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::{UpdateFace, UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, Face, Region, Sketch},
+        validation::ValidationConfig,
+        Core,
+    };
+
+    use super::{ClassifyPoint, PointClassification};
+
+    #[test]
+    fn classify_point_in_face() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let face = Face::unbound(surface.clone(), &mut core)
+            .update_region(
+                |region, core| {
+                    region
+                        .update_exterior(
+                            |_, core| {
+                                Cycle::polygon(
+                                    [[0., 0.], [4., 0.], [4., 4.], [0., 4.]],
+                                    surface.clone(),
+                                    core,
+                                )
+                            },
+                            core,
+                        )
+                        .add_interiors(
+                            [Cycle::polygon(
+                                [[1., 1.], [1., 3.], [3., 3.], [3., 1.]],
+                                surface.clone(),
+                                core,
+                            )],
+                            core,
+                        )
+                },
+                &mut core,
+            )
+            .insert(&mut core);
+
+        let config = ValidationConfig::default();
+        let classify = |point: [f64; 2]| {
+            face.classify_point(point, &config, &core.layers.geometry)
+        };
+
+        assert_eq!(classify([0.5, 2.]), PointClassification::Inside);
+        assert_eq!(classify([2., 2.]), PointClassification::Outside);
+        assert_eq!(classify([5., 2.]), PointClassification::Outside);
+        assert_eq!(classify([4., 2.]), PointClassification::Boundary);
+        assert_eq!(classify([1., 2.]), PointClassification::Boundary);
+        assert_eq!(classify([0., 0.]), PointClassification::Boundary);
+    }
+
+    #[test]
+    fn classify_point_in_solid() {
+        let mut core = Core::new();
+
+        let cube = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            )
+            .insert(&mut core);
+
+        let config = ValidationConfig::default();
+        let classify = |point: [f64; 3]| {
+            cube.classify_point(point, &config, &core.layers.geometry)
+        };
+
+        assert_eq!(classify([0.5, 0.5, -0.5]), PointClassification::Inside);
+        assert_eq!(classify([0.9, 0.1, -0.9]), PointClassification::Inside);
+        assert_eq!(classify([1.5, 0.5, -0.5]), PointClassification::Outside);
+        assert_eq!(classify([0.5, 0.5, 0.5]), PointClassification::Outside);
+
+        // Points in the plane of a face, but outside of it, are outside.
+        assert_eq!(classify([0.5, 1.5, -1.]), PointClassification::Outside);
+        assert_eq!(classify([1., 1.5, -0.5]), PointClassification::Outside);
+        assert_eq!(classify([0., 0.5, -2.]), PointClassification::Outside);
+        assert_eq!(classify([1., 0.5, -0.5]), PointClassification::Boundary);
+        assert_eq!(classify([0., 0., 0.]), PointClassification::Boundary);
+
+        // Points that are only off by numerical noise are still on the
+        // boundary.
+        let point = Point::from([0.5, 0.5, -1.]) + [0., 0., 1e-9];
+        assert_eq!(
+            cube.classify_point(point, &config, &core.layers.geometry),
+            PointClassification::Boundary
+        );
+    }
+}
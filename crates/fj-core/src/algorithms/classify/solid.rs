@@ -0,0 +1,118 @@
+use fj_math::{Point, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{face::approx_face, ApproxCache},
+        triangulate::triangulate_face,
+    },
+    geometry::Geometry,
+    storage::Handle,
+    topology::{Face, Shell, Solid},
+    validation::ValidationConfig,
+};
+
+use super::{ClassifyPoint, PointClassification};
+
+impl ClassifyPoint<3> for &Solid {
+    fn classify_point(
+        self,
+        point: impl Into<Point<3>>,
+        config: &ValidationConfig,
+        geometry: &Geometry,
+    ) -> PointClassification {
+        classify(
+            self.shells().iter().flat_map(|shell| shell.faces()),
+            point.into(),
+            config,
+            geometry,
+        )
+    }
+}
+
+impl ClassifyPoint<3> for &Shell {
+    fn classify_point(
+        self,
+        point: impl Into<Point<3>>,
+        config: &ValidationConfig,
+        geometry: &Geometry,
+    ) -> PointClassification {
+        classify(self.faces(), point.into(), config, geometry)
+    }
+}
+
+fn classify<'r>(
+    faces: impl IntoIterator<Item = &'r Handle<Face>>,
+    point: Point<3>,
+    config: &ValidationConfig,
+    geometry: &Geometry,
+) -> PointClassification {
+    let mut cache = ApproxCache::default();
+    let triangles = faces
+        .into_iter()
+        .flat_map(|face| {
+            triangulate_face(approx_face(
+                face.clone(),
+                config.tolerance,
+                &mut cache,
+                geometry,
+            ))
+        })
+        .map(|triangle| {
+            Triangle::from_points(triangle.map(|point| point.global_form))
+        })
+        .collect::<Vec<_>>();
+
+    let is_on_boundary = triangles.iter().any(|triangle| {
+        (triangle.closest_point(point) - point).magnitude()
+            <= config.identical_max_distance
+    });
+    if is_on_boundary {
+        return PointClassification::Boundary;
+    }
+
+    // A point is inside, if a ray that starts at it crosses the boundary an
+    // odd number of times. A ray that grazes an edge or vertex of the boundary
+    // might be counted wrong, though. We cast multiple rays, in directions
+    // that are unlikely to be aligned with the edges of typical models, and
+    // let them vote.
+    let directions =
+        [[0.58, 0.61, 0.54], [-0.31, 0.79, 0.53], [0.7, -0.42, -0.58]];
+    let epsilon = config.tolerance.inner() * Scalar::from(1e-3);
+
+    let votes_inside = directions
+        .into_iter()
+        .filter(|&direction| {
+            let direction = Vector::from(direction).normalize();
+
+            let mut distances = triangles
+                .iter()
+                .filter_map(|triangle| {
+                    triangle.cast_local_ray(
+                        point,
+                        direction,
+                        f64::INFINITY,
+                        true,
+                    )
+                })
+                // The point is not on the boundary, but it might be in the
+                // plane of a triangle, outside of it. Such a triangle can still
+                // report a hit right at the start of the ray, which is not a
+                // crossing.
+                .filter(|&distance| distance > epsilon)
+                .collect::<Vec<_>>();
+
+            // A ray that passes through an edge between triangles hits all of
+            // them. That's still only one crossing of the boundary.
+            distances.sort();
+            distances.dedup_by(|b, a| *b - *a <= epsilon);
+
+            distances.len() % 2 == 1
+        })
+        .count();
+
+    if votes_inside * 2 > directions.len() {
+        PointClassification::Inside
+    } else {
+        PointClassification::Outside
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    algorithms::{
+        approx::{face::approx_face, ApproxCache},
+        triangulate::triangulate_face,
+    },
+    geometry::{Geometry, Tolerance},
+    storage::Handle,
+    topology::Face,
+};
+
+use super::{Distance, DistancePrimitive};
+
+impl Distance for &Handle<Face> {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        face_primitives(self, tolerance, &mut ApproxCache::default(), geometry)
+            .collect()
+    }
+}
+
+pub(super) fn face_primitives<'r>(
+    face: &'r Handle<Face>,
+    tolerance: Tolerance,
+    cache: &mut ApproxCache,
+    geometry: &Geometry,
+) -> impl Iterator<Item = DistancePrimitive> + 'r {
+    triangulate_face(approx_face(face.clone(), tolerance, cache, geometry))
+        .into_iter()
+        .map(|points| DistancePrimitive::Triangle {
+            face: face.clone(),
+            points,
+        })
+}
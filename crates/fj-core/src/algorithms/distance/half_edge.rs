@@ -0,0 +1,66 @@
+use std::iter;
+
+use crate::{
+    algorithms::approx::{
+        curve::approx_curve_with_cache, vertex::approx_vertex, ApproxCache,
+    },
+    geometry::{CurveBoundary, Geometry, Tolerance},
+    storage::Handle,
+    topology::{HalfEdge, Surface, Vertex},
+};
+
+use super::{Distance, DistancePrimitive};
+
+impl Distance for (&Handle<HalfEdge>, &Handle<Vertex>, &Handle<Surface>) {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        let (half_edge, end_vertex, surface) = self;
+
+        let mut cache = ApproxCache::default();
+
+        let [start, end] =
+            [half_edge.start_vertex(), end_vertex].map(|vertex| {
+                let position_curve = geometry
+                    .of_vertex(vertex)
+                    .unwrap()
+                    .local_on(half_edge.curve())
+                    .unwrap()
+                    .position;
+
+                approx_vertex(
+                    vertex.clone(),
+                    half_edge.curve(),
+                    surface,
+                    position_curve,
+                    tolerance,
+                    &mut cache.vertex,
+                    geometry,
+                )
+            });
+        let boundary = CurveBoundary {
+            inner: [start.local_form, end.local_form],
+        };
+
+        let rest = approx_curve_with_cache(
+            half_edge.curve(),
+            surface,
+            boundary,
+            tolerance,
+            &mut cache.curve,
+            geometry,
+        );
+
+        let points = iter::once(start)
+            .chain(rest.points)
+            .chain(iter::once(end))
+            .collect::<Vec<_>>();
+
+        points
+            .windows(2)
+            .map(|window| DistancePrimitive::Segment([window[0], window[1]]))
+            .collect()
+    }
+}
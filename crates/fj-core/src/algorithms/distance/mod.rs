@@ -0,0 +1,388 @@
+//! Compute the minimum distance between objects
+//!
+//! See [`Distance`].
+
+mod face;
+mod half_edge;
+mod solid;
+mod vertex;
+
+use fj_math::{Aabb, LineSegment, Point, Scalar, Triangle};
+
+use crate::{
+    geometry::{Geometry, Tolerance},
+    storage::Handle,
+    topology::Face,
+};
+
+use super::{approx::ApproxPoint, triangulate::surface_point_on_triangle};
+
+/// Compute the minimum distance between objects
+///
+/// Vertices and half-edges need more context than the objects themselves, to
+/// determine their position. They implement this trait as tuples, that provide
+/// this context:
+///
+/// - A vertex, together with a curve it is on, and a surface that curve is
+///   on: `(&Handle<Vertex>, &Handle<Curve>, &Handle<Surface>)`
+/// - A half-edge, together with its end vertex and the surface it is on:
+///   `(&Handle<HalfEdge>, &Handle<Vertex>, &Handle<Surface>)`
+pub trait Distance: Sized {
+    /// Compute the minimum distance between this object and another
+    ///
+    /// Both objects are approximated using the provided tolerance, and the
+    /// distance between those approximations is returned. For solids, this is
+    /// the distance between their boundaries. A solid that fully contains
+    /// another object is not considered to be at distance zero from it.
+    ///
+    /// Returns `None`, if either object is empty.
+    fn distance_to(
+        self,
+        other: impl Distance,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Option<DistanceResult> {
+        let tolerance = tolerance.into();
+
+        let a = self.distance_primitives(tolerance, geometry);
+        let b = other.distance_primitives(tolerance, geometry);
+
+        closest_primitives(&a, &b)
+    }
+
+    /// Approximate the object by primitives that distances can be computed to
+    ///
+    /// This is a low-level method, intended for implementation of `Distance`.
+    /// Most callers should prefer [`Distance::distance_to`].
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive>;
+}
+
+/// A primitive that approximates part of an object
+///
+/// See [`Distance::distance_primitives`].
+#[derive(Clone, Debug)]
+pub enum DistancePrimitive {
+    /// A point, in curve and global coordinates
+    Point(ApproxPoint<1>),
+
+    /// A line segment, in curve and global coordinates
+    Segment([ApproxPoint<1>; 2]),
+
+    /// A triangle, in surface and global coordinates
+    Triangle {
+        /// The face that the triangle is part of
+        face: Handle<Face>,
+
+        /// The points of the triangle
+        points: [ApproxPoint<2>; 3],
+    },
+}
+
+impl DistancePrimitive {
+    fn global_points(&self) -> Vec<Point<3>> {
+        match self {
+            Self::Point(point) => vec![point.global_form],
+            Self::Segment(points) => {
+                points.iter().map(|point| point.global_form).collect()
+            }
+            Self::Triangle { points, .. } => {
+                points.iter().map(|point| point.global_form).collect()
+            }
+        }
+    }
+
+    fn local_position(&self, point: Point<3>) -> LocalPosition {
+        match self {
+            Self::Point(point) => LocalPosition::Curve(point.local_form),
+            Self::Segment([a, b]) => {
+                let ab = b.global_form - a.global_form;
+                let length_squared = ab.dot(&ab);
+
+                let t = if length_squared == Scalar::ZERO {
+                    Scalar::ZERO
+                } else {
+                    (point - a.global_form).dot(&ab) / length_squared
+                };
+
+                LocalPosition::Curve(
+                    a.local_form + (b.local_form - a.local_form) * t,
+                )
+            }
+            Self::Triangle { face, points } => LocalPosition::Surface {
+                face: face.clone(),
+                point: surface_point_on_triangle(points, point),
+            },
+        }
+    }
+}
+
+/// The result of a distance computation
+///
+/// See [`Distance::distance_to`].
+#[derive(Clone, Debug)]
+pub struct DistanceResult {
+    /// The minimum distance between the objects
+    pub distance: Scalar,
+
+    /// The points on both objects that are closest to each other
+    ///
+    /// The point on the object that [`Distance::distance_to`] was called on
+    /// comes first, the point on the other object second.
+    pub closest_points: [ClosestPoint; 2],
+}
+
+/// A point on an object that is closest to another object
+///
+/// See [`DistanceResult`].
+#[derive(Clone, Debug)]
+pub struct ClosestPoint {
+    /// The point in global coordinates
+    pub global: Point<3>,
+
+    /// The point in the local coordinates of the object it is on
+    pub local: LocalPosition,
+}
+
+/// A position in the local coordinates of an object
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LocalPosition {
+    /// A position on a vertex or half-edge, in curve coordinates
+    Curve(Point<1>),
+
+    /// A position on a face or solid, in surface coordinates
+    Surface {
+        /// The face that the position is on
+        face: Handle<Face>,
+
+        /// The position, in the coordinates of the face's surface
+        point: Point<2>,
+    },
+}
+
+fn closest_primitives(
+    a: &[DistancePrimitive],
+    b: &[DistancePrimitive],
+) -> Option<DistanceResult> {
+    let aabbs_b = b
+        .iter()
+        .map(|primitive| Aabb::<3>::from_points(primitive.global_points()))
+        .collect::<Vec<_>>();
+
+    let mut closest: Option<(Scalar, [Point<3>; 2], [&DistancePrimitive; 2])> =
+        None;
+
+    for primitive_a in a {
+        let aabb_a = Aabb::<3>::from_points(primitive_a.global_points());
+
+        for (primitive_b, aabb_b) in b.iter().zip(&aabbs_b) {
+            if let Some((distance, _, _)) = closest {
+                // The primitives can't be closer to each other than their
+                // bounding boxes are. Skip the expensive check, if that's
+                // already too far.
+                if aabb_distance(&aabb_a, aabb_b) >= distance {
+                    continue;
+                }
+            }
+
+            let points = closest_points(primitive_a, primitive_b);
+            let [point_a, point_b] = points;
+            let distance = point_a.distance_to(&point_b);
+
+            if closest.map_or(true, |(min, _, _)| distance < min) {
+                closest = Some((distance, points, [primitive_a, primitive_b]));
+            }
+        }
+    }
+
+    let (distance, points, primitives) = closest?;
+    let [a, b] = [0, 1].map(|i| ClosestPoint {
+        global: points[i],
+        local: primitives[i].local_position(points[i]),
+    });
+
+    Some(DistanceResult {
+        distance,
+        closest_points: [a, b],
+    })
+}
+
+fn closest_points(
+    a: &DistancePrimitive,
+    b: &DistancePrimitive,
+) -> [Point<3>; 2] {
+    use DistancePrimitive::{Point, Segment, Triangle as Tri};
+
+    let segment = |points: &[ApproxPoint<1>; 2]| {
+        LineSegment::from(points.map(|point| point.global_form))
+    };
+    let triangle = |points: &[ApproxPoint<2>; 3]| {
+        Triangle::from_points(points.map(|point| point.global_form))
+    };
+
+    match (a, b) {
+        (Point(a), Point(b)) => [a.global_form, b.global_form],
+        (Point(a), Segment(b)) => {
+            [a.global_form, segment(b).closest_point(a.global_form)]
+        }
+        (Point(a), Tri { points: b, .. }) => {
+            [a.global_form, triangle(b).closest_point(a.global_form)]
+        }
+        (Segment(a), Segment(b)) => segment(a).closest_points(&segment(b)),
+        (Segment(a), Tri { points: b, .. }) => {
+            let [on_b, on_a] =
+                triangle(b).closest_points_to_segment(&segment(a));
+            [on_a, on_b]
+        }
+        (Tri { points: a, .. }, Tri { points: b, .. }) => {
+            triangle(a).closest_points(&triangle(b))
+        }
+        (a, b) => {
+            let [on_b, on_a] = closest_points(b, a);
+            [on_a, on_b]
+        }
+    }
+}
+
+fn aabb_distance(a: &Aabb<3>, b: &Aabb<3>) -> Scalar {
+    let gaps = [0, 1, 2].map(|i| {
+        let gap_a_to_b =
+            b.min.coords.components[i] - a.max.coords.components[i];
+        let gap_b_to_a =
+            a.min.coords.components[i] - b.max.coords.components[i];
+
+        gap_a_to_b.max(gap_b_to_a).max(Scalar::ZERO)
+    });
+
+    let [x, y, z] = gaps;
+    Scalar::from((x * x + y * y + z * z).into_f64().sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        geometry::repr::tri_mesh::convert_point_surface_to_global,
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        storage::Handle,
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{Distance, LocalPosition};
+
+    #[test]
+    fn distance_between_solids() {
+        let mut core = Core::new();
+
+        let a = cube([0., 0.], &mut core);
+        let b = cube([3., 0.], &mut core);
+
+        let geometry = &core.layers.geometry;
+        let result = (&*a).distance_to(&*b, 0.001, geometry).unwrap();
+
+        assert_eq!(result.distance, Scalar::from(2.));
+
+        let [a, b] = result.closest_points;
+        assert_eq!(a.global.x, Scalar::ONE);
+        assert_eq!(b.global.x, Scalar::from(3.));
+
+        // The local coordinates must refer to the same points.
+        for point in [a, b] {
+            let LocalPosition::Surface { face, point: local } = point.local
+            else {
+                panic!("Expected closest point on solid to be on a face");
+            };
+            let global = convert_point_surface_to_global(
+                &geometry.of_surface_2(face.surface()).unwrap().generator,
+                local,
+                0.001,
+                geometry,
+            );
+            assert!((global - point.global).magnitude() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn distance_from_vertex_and_half_edge_to_solid() {
+        let mut core = Core::new();
+
+        let a = cube([0., 0.], &mut core);
+        let b = cube([2., 0.], &mut core);
+
+        let face = b.shells().first().faces().first();
+        let half_edge = face.region().exterior().half_edges().first();
+        let end_vertex = face
+            .region()
+            .exterior()
+            .half_edges()
+            .after(half_edge)
+            .unwrap()
+            .start_vertex();
+
+        let geometry = &core.layers.geometry;
+        let from_vertex =
+            (half_edge.start_vertex(), half_edge.curve(), face.surface())
+                .distance_to(&*a, 0.001, geometry)
+                .unwrap();
+        let from_half_edge = (half_edge, end_vertex, face.surface())
+            .distance_to(&*a, 0.001, geometry)
+            .unwrap();
+
+        // All of `b` is right next to the face of `a` at `x == 1`, so that's
+        // where the closest points on `a` are.
+        for result in [&from_vertex, &from_half_edge] {
+            let [on_b, on_a] = &result.closest_points;
+
+            assert_eq!(
+                on_a.global,
+                Point::from([Scalar::ONE, on_b.global.y, on_b.global.z])
+            );
+            assert_eq!(result.distance, on_b.global.x - 1.);
+        }
+
+        // The half-edge can't be further away than its start vertex.
+        assert!(from_half_edge.distance <= from_vertex.distance);
+
+        let position_curve = geometry
+            .of_vertex(half_edge.start_vertex())
+            .unwrap()
+            .local_on(half_edge.curve())
+            .unwrap()
+            .position;
+        assert_eq!(
+            from_vertex.closest_points[0].local,
+            LocalPosition::Curve(position_curve),
+        );
+    }
+
+    fn cube(origin: [f64; 2], core: &mut Core) -> Handle<Solid> {
+        let [x, y] = origin;
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[x, y], [x + 1., y], [x + 1., y + 1.], [x, y + 1.]],
+                    surface,
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                core,
+            )
+            .insert(core)
+    }
+}
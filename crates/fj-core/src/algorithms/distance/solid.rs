@@ -0,0 +1,25 @@
+use crate::{
+    algorithms::approx::ApproxCache,
+    geometry::{Geometry, Tolerance},
+    topology::Solid,
+};
+
+use super::{face::face_primitives, Distance, DistancePrimitive};
+
+impl Distance for &Solid {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        let mut cache = ApproxCache::default();
+
+        self.shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .flat_map(|face| {
+                face_primitives(face, tolerance, &mut cache, geometry)
+            })
+            .collect()
+    }
+}
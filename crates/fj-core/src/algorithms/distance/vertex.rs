@@ -0,0 +1,37 @@
+use crate::{
+    algorithms::approx::vertex::{approx_vertex, VertexApproxCache},
+    geometry::{Geometry, Tolerance},
+    storage::Handle,
+    topology::{Curve, Surface, Vertex},
+};
+
+use super::{Distance, DistancePrimitive};
+
+impl Distance for (&Handle<Vertex>, &Handle<Curve>, &Handle<Surface>) {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        let (vertex, curve, surface) = self;
+
+        let position_curve = geometry
+            .of_vertex(vertex)
+            .unwrap()
+            .local_on(curve)
+            .unwrap()
+            .position;
+
+        let point = approx_vertex(
+            vertex.clone(),
+            curve,
+            surface,
+            position_curve,
+            tolerance,
+            &mut VertexApproxCache::default(),
+            geometry,
+        );
+
+        vec![DistancePrimitive::Point(point)]
+    }
+}
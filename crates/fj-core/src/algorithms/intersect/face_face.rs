@@ -0,0 +1,825 @@
+//! Intersection between two faces
+//!
+//! See [`FaceFaceIntersection`].
+
+use std::collections::BTreeSet;
+
+use fj_math::{Aabb, Line, LineSegment, Point, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{face::approx_face, ApproxCache, ApproxPoint},
+        triangulate::{surface_point_on_triangle, triangulate_face},
+    },
+    geometry::{
+        repr::polyline::Polyline, surfaces::SweptCurve, CurveBoundary,
+        Geometry, Path, Tolerance,
+    },
+    storage::Handle,
+    topology::Face,
+};
+
+use super::Intersect;
+
+impl Intersect for (&Handle<Face>, &Handle<Face>, Tolerance, &Geometry) {
+    type Intersection = FaceFaceIntersection;
+
+    fn intersect(self) -> Option<Self::Intersection> {
+        let (a, b, tolerance, geometry) = self;
+        let faces = [a, b];
+
+        // Same as what the boolean operations use. Small enough to not affect
+        // the result, large enough to absorb numerical noise.
+        let epsilon = tolerance.inner() * Scalar::from(1e-3);
+
+        if let [Some(a), Some(b)] =
+            faces.map(|face| PlanarFace::new(face, geometry))
+        {
+            let curves = intersect_planar_faces([&a, &b], epsilon);
+            return (!curves.is_empty())
+                .then_some(FaceFaceIntersection { curves });
+        }
+
+        let triangles = faces.map(|face| {
+            let approx = approx_face(
+                face.clone(),
+                tolerance,
+                &mut ApproxCache::default(),
+                geometry,
+            );
+
+            triangulate_face(approx)
+                .into_iter()
+                .map(|triangle| {
+                    let aabb = Aabb::<3>::from_points(
+                        triangle.map(|point| point.global_form),
+                    );
+                    (triangle, aabb)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut segments = Vec::new();
+        for (triangle_a, aabb_a) in &triangles[0] {
+            for (triangle_b, aabb_b) in &triangles[1] {
+                if !aabbs_overlap(aabb_a, aabb_b, epsilon) {
+                    continue;
+                }
+
+                if let Some(segment) =
+                    intersect_triangles(triangle_a, triangle_b, epsilon)
+                {
+                    segments.push(segment);
+                }
+            }
+        }
+
+        let curves = chain_segments(segments, epsilon)
+            .into_iter()
+            .map(|chain| {
+                FaceFaceIntersectionCurve::from_chain(chain, faces, geometry)
+            })
+            .collect::<Vec<_>>();
+
+        if curves.is_empty() {
+            return None;
+        }
+
+        Some(FaceFaceIntersection { curves })
+    }
+}
+
+/// The intersection between two faces
+///
+/// If both faces are planar and bounded by straight edges, the intersection is
+/// computed exactly. Otherwise, it is computed by intersecting the triangulated
+/// approximations of both faces, at the tolerance that is passed to
+/// [`Intersect::intersect`].
+///
+/// If the faces are coplanar, their overlap is an area, not a curve. Such
+/// overlaps are not reported.
+pub struct FaceFaceIntersection {
+    /// The curves along which the faces intersect
+    pub curves: Vec<FaceFaceIntersectionCurve>,
+}
+
+/// A curve along which two faces intersect
+///
+/// The curve is limited to the area where both faces overlap. The points of
+/// its approximations are given in global coordinates, as well as in the
+/// surface coordinates of each face (in the order the faces were passed to
+/// [`Intersect::intersect`]). All of those share the same curve coordinates.
+pub struct FaceFaceIntersectionCurve {
+    /// # The exact intersection curve, if available
+    ///
+    /// This is only available, if both faces are planar, in which case the
+    /// curve is a line. The curve coordinates of all approximations refer to
+    /// this line.
+    ///
+    /// Otherwise, the curve coordinates are the distance along the global
+    /// approximation, starting at its first point.
+    pub path: Option<Path<3>>,
+
+    /// # The approximation of the curve, in global coordinates
+    pub global: Polyline<3>,
+
+    /// # The approximation of the curve, in each face's surface coordinates
+    pub local: [Polyline<2>; 2],
+
+    /// # The range of curve coordinates, that the curve covers
+    pub boundary: CurveBoundary<Point<1>>,
+
+    /// # Indicate whether the curve is closed
+    ///
+    /// If it is, the first and last points of the approximations are the same.
+    pub is_closed: bool,
+}
+
+impl FaceFaceIntersectionCurve {
+    fn from_chain(
+        chain: Chain,
+        faces: [&Handle<Face>; 2],
+        geometry: &Geometry,
+    ) -> Self {
+        let Chain { points, is_closed } = chain;
+
+        let planes =
+            faces.map(|face| match geometry.try_of_surface(face.surface()) {
+                Some(SweptCurve {
+                    u: Path::Line(line),
+                    v,
+                }) => Some(line.direction().cross(v)),
+                _ => None,
+            });
+
+        let first = points[0].global;
+        let last = points[points.len() - 1].global;
+
+        let path = match planes {
+            [Some(a), Some(b)] => {
+                let mut direction = a.cross(&b).normalize();
+                if direction.dot(&(last - first)) < Scalar::ZERO {
+                    direction = -direction;
+                }
+
+                Some(Line::from_origin_and_direction(first, direction))
+            }
+            _ => None,
+        };
+
+        let mut distance = Scalar::ZERO;
+        let points_curve = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| match &path {
+                Some(line) => line.point_to_line_coords(point.global),
+                None => {
+                    if i > 0 {
+                        distance +=
+                            (point.global - points[i - 1].global).magnitude();
+                    }
+                    Point::from([distance])
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let boundary = CurveBoundary::from([
+            points_curve[0],
+            points_curve[points_curve.len() - 1],
+        ]);
+
+        let local = [0, 1].map(|i| Polyline {
+            points: points.iter().map(|point| point.local[i]).collect(),
+            points_curve: points_curve.clone(),
+        });
+        let global = Polyline {
+            points: points.iter().map(|point| point.global).collect(),
+            points_curve,
+        };
+
+        Self {
+            path: path.map(Path::Line),
+            global,
+            local,
+            boundary,
+            is_closed,
+        }
+    }
+}
+
+/// # A face that is planar and bounded by straight edges
+///
+/// The intersection of two such faces can be computed exactly, without
+/// approximating them first.
+pub(crate) struct PlanarFace {
+    origin: Point<3>,
+    axes: [Vector<3>; 2],
+
+    /// # The cycles of the face, in surface coordinates
+    ///
+    /// The exterior comes first, followed by any interiors. Each cycle is
+    /// represented by the start points of its half-edges.
+    pub cycles: Vec<Vec<Point<2>>>,
+}
+
+impl PlanarFace {
+    /// # Access the planar representation of a face
+    ///
+    /// Returns `None`, if the face is not planar, or if any of its half-edges
+    /// is not a line in surface coordinates.
+    pub fn new(face: &Face, geometry: &Geometry) -> Option<Self> {
+        let SweptCurve {
+            u: Path::Line(line),
+            v,
+        } = geometry.try_of_surface(face.surface())?
+        else {
+            return None;
+        };
+
+        let cycles = face
+            .region()
+            .all_cycles()
+            .map(|cycle| {
+                cycle
+                    .half_edges()
+                    .iter()
+                    .map(|half_edge| {
+                        let Path::Line(path) = geometry
+                            .of_curve(half_edge.curve())?
+                            .local_on(face.surface())?
+                            .path
+                        else {
+                            return None;
+                        };
+                        let position = geometry
+                            .of_vertex(half_edge.start_vertex())?
+                            .local_on(half_edge.curve())?
+                            .position;
+
+                        Some(path.point_from_line_coords(position))
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            origin: line.origin(),
+            axes: [line.direction(), *v],
+            cycles,
+        })
+    }
+
+    /// # The normal of the face's surface
+    ///
+    /// This is the cross product of the surface's axes, which means it doesn't
+    /// necessarily point to the front side of the face, nor is it normalized.
+    pub fn normal(&self) -> Vector<3> {
+        let [u, v] = self.axes;
+        u.cross(&v)
+    }
+
+    /// # Convert a point in surface coordinates into global coordinates
+    pub fn point_to_global(&self, point: Point<2>) -> Point<3> {
+        let [u, v] = self.axes;
+        self.origin + u * point.u + v * point.v
+    }
+
+    /// # Convert a point in global coordinates into surface coordinates
+    ///
+    /// The point is projected onto the surface along its normal.
+    pub fn point_from_global(&self, point: Point<3>) -> Point<2> {
+        let [u, v] = self.axes;
+        let w = point - self.origin;
+
+        let [uu, uv, vv] = [u.dot(&u), u.dot(&v), v.dot(&v)];
+        let [wu, wv] = [w.dot(&u), w.dot(&v)];
+        let det = uu * vv - uv * uv;
+
+        Point::from([(wu * vv - wv * uv) / det, (wv * uu - wu * uv) / det])
+    }
+
+    /// # The edges of all cycles, in global coordinates
+    pub fn edges_global(&self) -> impl Iterator<Item = [Point<3>; 2]> + '_ {
+        self.cycles.iter().flat_map(|cycle| {
+            (0..cycle.len()).map(|i| {
+                [cycle[i], cycle[(i + 1) % cycle.len()]]
+                    .map(|point| self.point_to_global(point))
+            })
+        })
+    }
+
+    /// # Determine whether a point is within the face, or on its boundary
+    ///
+    /// The point is expected to be on the surface of the face. Points that are
+    /// within `epsilon` of an edge are considered to be on the boundary.
+    pub fn contains(&self, point: Point<3>, epsilon: Scalar) -> bool {
+        let is_on_boundary = self.edges_global().any(|[a, b]| {
+            let closest = LineSegment::from([a, b]).closest_point(point);
+            (closest - point).magnitude() <= epsilon
+        });
+        if is_on_boundary {
+            return true;
+        }
+
+        // A horizontal ray, cast from the point, crosses the boundary an odd
+        // number of times, if the point is inside.
+        let point = self.point_from_global(point);
+        let mut inside = false;
+
+        for cycle in &self.cycles {
+            for (i, a) in cycle.iter().enumerate() {
+                let b = cycle[(i + 1) % cycle.len()];
+
+                if (a.v > point.v) != (b.v > point.v) {
+                    let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+                    if point.u < u {
+                        inside = !inside;
+                    }
+                }
+            }
+        }
+
+        inside
+    }
+
+    /// # Compute the intervals, in which a line overlaps the face
+    ///
+    /// The line is expected to be on the surface of the face, and its direction
+    /// to be normalized. Returns the intervals in line coordinates, sorted and
+    /// without overlap.
+    fn overlap_with_line(
+        &self,
+        line: &Line<3>,
+        epsilon: Scalar,
+    ) -> Vec<[Scalar; 2]> {
+        let normal = self.normal().normalize();
+        let side = |point: Point<3>| {
+            (point - line.origin())
+                .cross(&line.direction())
+                .dot(&normal)
+        };
+        let t = |point: Point<3>| line.point_to_line_coords(point).t;
+
+        let mut candidates = Vec::new();
+        for [a, b] in self.edges_global() {
+            let [side_a, side_b] = [side(a), side(b)];
+
+            if side_a.abs() <= epsilon {
+                candidates.push(t(a));
+            }
+            if side_b.abs() <= epsilon {
+                candidates.push(t(b));
+            }
+            if side_a.abs() > epsilon
+                && side_b.abs() > epsilon
+                && side_a.sign() != side_b.sign()
+            {
+                let s = side_a / (side_a - side_b);
+                candidates.push(t(a) + (t(b) - t(a)) * s);
+            }
+        }
+
+        candidates.sort();
+        candidates.dedup_by(|b, a| *b - *a <= epsilon);
+
+        // Between two candidates, the line is either completely within the
+        // face, or completely outside of it.
+        let mut intervals: Vec<[Scalar; 2]> = Vec::new();
+        for window in candidates.windows(2) {
+            let [start, end] = [window[0], window[1]];
+
+            let middle = line.point_from_line_coords([(start + end) / 2.]);
+            if !self.contains(middle, epsilon) {
+                continue;
+            }
+
+            match intervals.last_mut() {
+                Some(last) if last[1] == start => last[1] = end,
+                _ => intervals.push([start, end]),
+            }
+        }
+
+        intervals
+    }
+}
+
+/// # Compute the exact intersection of two planar faces
+fn intersect_planar_faces(
+    faces: [&PlanarFace; 2],
+    epsilon: Scalar,
+) -> Vec<FaceFaceIntersectionCurve> {
+    let [a, b] = faces;
+    let [normal_a, normal_b] = [a.normal().normalize(), b.normal().normalize()];
+
+    let direction = normal_a.cross(&normal_b);
+    if direction.magnitude() <= epsilon {
+        // The faces are parallel or coplanar. Either way, there is no
+        // intersection curve.
+        return Vec::new();
+    }
+
+    // The point on the intersection line, that is closest to the origin.
+    let [distance_a, distance_b] = [
+        normal_a.dot(&a.origin.coords),
+        normal_b.dot(&b.origin.coords),
+    ];
+    let origin = Point {
+        coords: (normal_b.cross(&direction) * distance_a
+            + direction.cross(&normal_a) * distance_b)
+            / direction.dot(&direction),
+    };
+    let line = Line::from_origin_and_direction(origin, direction.normalize());
+
+    let [intervals_a, intervals_b] =
+        faces.map(|face| face.overlap_with_line(&line, epsilon));
+
+    let mut curves = Vec::new();
+    for [start_a, end_a] in &intervals_a {
+        for [start_b, end_b] in &intervals_b {
+            let start = *start_a.max(start_b);
+            let end = *end_a.min(end_b);
+
+            if end - start <= epsilon {
+                continue;
+            }
+
+            let points_curve = vec![Point::from([start]), Point::from([end])];
+            let points_global = points_curve
+                .iter()
+                .map(|&point| line.point_from_line_coords(point))
+                .collect::<Vec<_>>();
+
+            let local = faces.map(|face| Polyline {
+                points: points_global
+                    .iter()
+                    .map(|&point| face.point_from_global(point))
+                    .collect(),
+                points_curve: points_curve.clone(),
+            });
+
+            curves.push(FaceFaceIntersectionCurve {
+                path: Some(Path::Line(line)),
+                global: Polyline {
+                    points: points_global,
+                    points_curve: points_curve.clone(),
+                },
+                local,
+                boundary: CurveBoundary::from([[start], [end]]),
+                is_closed: false,
+            });
+        }
+    }
+
+    curves
+}
+
+/// # A point on the intersection of two faces
+#[derive(Clone, Copy, Debug)]
+struct IntersectionPoint {
+    global: Point<3>,
+    local: [Point<2>; 2],
+}
+
+/// # Compute the segment along which two triangles intersect
+///
+/// Returns `None`, if the triangles don't intersect, only touch in a point, or
+/// are coplanar.
+fn intersect_triangles(
+    a: &[ApproxPoint<2>; 3],
+    b: &[ApproxPoint<2>; 3],
+    epsilon: Scalar,
+) -> Option<[IntersectionPoint; 2]> {
+    let global = |triangle: &[ApproxPoint<2>; 3]| {
+        triangle.map(|point| point.global_form)
+    };
+    let [global_a, global_b] = [global(a), global(b)];
+
+    let normal_a = Triangle::from_points(global_a).normal();
+    let normal_b = Triangle::from_points(global_b).normal();
+
+    let direction = normal_a.cross(&normal_b);
+    if direction.magnitude() <= epsilon {
+        // The triangles are parallel or coplanar. Either way, there is no
+        // intersection curve.
+        return None;
+    }
+
+    let [a_start, a_end] =
+        clip_to_plane(global_a, global_b[0], normal_b, epsilon)?;
+    let [b_start, b_end] =
+        clip_to_plane(global_b, global_a[0], normal_a, epsilon)?;
+
+    // Both segments are on the line where the planes of the triangles meet.
+    // Their overlap is the intersection.
+    let t = |point: Point<3>| direction.dot(&point.coords);
+    let sort =
+        |[p, q]: [Point<3>; 2]| if t(p) <= t(q) { [p, q] } else { [q, p] };
+
+    let [a_start, a_end] = sort([a_start, a_end]);
+    let [b_start, b_end] = sort([b_start, b_end]);
+
+    let start = if t(a_start) >= t(b_start) {
+        a_start
+    } else {
+        b_start
+    };
+    let end = if t(a_end) <= t(b_end) { a_end } else { b_end };
+
+    if (t(end) - t(start)) / direction.magnitude() <= epsilon {
+        return None;
+    }
+
+    Some([start, end].map(|point| IntersectionPoint {
+        global: point,
+        local: [
+            surface_point_on_triangle(a, point),
+            surface_point_on_triangle(b, point),
+        ],
+    }))
+}
+
+/// # Compute the segment along which a triangle meets a plane
+fn clip_to_plane(
+    triangle: [Point<3>; 3],
+    origin: Point<3>,
+    normal: Vector<3>,
+    epsilon: Scalar,
+) -> Option<[Point<3>; 2]> {
+    let distances = triangle.map(|point| normal.dot(&(point - origin)));
+
+    let mut points: Vec<Point<3>> = Vec::new();
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+        let [d_i, d_j] = [distances[i], distances[j]];
+
+        if d_i.abs() <= epsilon {
+            points.push(triangle[i]);
+        } else if d_j.abs() > epsilon && d_i.sign() != d_j.sign() {
+            let s = d_i / (d_i - d_j);
+            points.push(triangle[i] + (triangle[j] - triangle[i]) * s);
+        }
+    }
+
+    match points.as_slice() {
+        [p, q] => Some([*p, *q]),
+        _ => None,
+    }
+}
+
+fn aabbs_overlap(a: &Aabb<3>, b: &Aabb<3>, epsilon: Scalar) -> bool {
+    (0..3).all(|i| {
+        a.min.coords.components[i] <= b.max.coords.components[i] + epsilon
+            && b.min.coords.components[i]
+                <= a.max.coords.components[i] + epsilon
+    })
+}
+
+/// # A chain of connected intersection segments
+struct Chain {
+    points: Vec<IntersectionPoint>,
+    is_closed: bool,
+}
+
+/// # Connect intersection segments into chains
+///
+/// Segment end points that are within `epsilon` of each other are considered
+/// to be the same.
+fn chain_segments(
+    segments: Vec<[IntersectionPoint; 2]>,
+    epsilon: Scalar,
+) -> Vec<Chain> {
+    let mut points: Vec<IntersectionPoint> = Vec::new();
+    let mut index_of = |point: IntersectionPoint| {
+        let existing = points.iter().position(|other| {
+            (other.global - point.global).magnitude() <= epsilon
+        });
+
+        existing.unwrap_or_else(|| {
+            points.push(point);
+            points.len() - 1
+        })
+    };
+
+    let mut edges = BTreeSet::new();
+    for [start, end] in segments {
+        let [start, end] = [index_of(start), index_of(end)];
+        if start != end {
+            edges.insert([start.min(end), start.max(end)]);
+        }
+    }
+
+    let mut neighbors = vec![Vec::new(); points.len()];
+    for &[a, b] in &edges {
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut chains = Vec::new();
+
+    // Start with the open chains, from one of their ends. Whatever is left
+    // after that, must be part of a closed chain.
+    let starts = (0..points.len())
+        .filter(|&i| neighbors[i].len() == 1)
+        .chain(0..points.len())
+        .collect::<Vec<_>>();
+
+    for start in starts {
+        let Some(&next) = neighbors[start].iter().find(|&&next| {
+            !visited.contains(&[start.min(next), start.max(next)])
+        }) else {
+            continue;
+        };
+
+        let mut chain = vec![start];
+        let mut current = start;
+        let mut next = Some(next);
+
+        while let Some(n) = next {
+            visited.insert([current.min(n), current.max(n)]);
+            chain.push(n);
+            current = n;
+
+            next = neighbors[current].iter().copied().find(|&next| {
+                !visited.contains(&[current.min(next), current.max(next)])
+            });
+        }
+
+        let is_closed = chain.len() > 2 && chain.first() == chain.last();
+
+        chains.push(Chain {
+            points: chain.into_iter().map(|i| points[i]).collect(),
+            is_closed,
+        });
+    }
+
+    chains
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::intersect::Intersect,
+        geometry::Path,
+        operations::{
+            build::{
+                BuildCycle, BuildFace, BuildRegion, BuildSketch, BuildSurface,
+            },
+            insert::Insert,
+            sweep::SweepSketch,
+            update::{UpdateFace, UpdateRegion, UpdateSketch},
+        },
+        storage::Handle,
+        topology::{Cycle, Face, Region, Sketch, Surface},
+        Core,
+    };
+
+    #[test]
+    fn perpendicular_planar_faces() {
+        let mut core = Core::new();
+
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+        let xz_plane = core.layers.topology.surfaces.xz_plane();
+
+        let a = square([[0., -1.], [2., 1.]], xy_plane, &mut core);
+        let b = square([[1., -1.], [3., 1.]], xz_plane, &mut core);
+
+        let intersection = (&a, &b, core.tolerance(), &*core.layers.geometry)
+            .intersect()
+            .unwrap();
+
+        let [curve] = intersection.curves.as_slice() else {
+            panic!("Expected exactly one intersection curve");
+        };
+        assert!(matches!(curve.path, Some(Path::Line(_))));
+        assert!(!curve.is_closed);
+
+        let ends = |points: &[Point<2>]| {
+            let mut ends = [points[0], points[points.len() - 1]];
+            ends.sort();
+            ends
+        };
+        let expected = [Point::from([1., 0.]), Point::from([2., 0.])];
+        assert_eq!(ends(&curve.local[0].points), expected);
+        assert_eq!(ends(&curve.local[1].points), expected);
+
+        let length = curve.boundary.inner[1] - curve.boundary.inner[0];
+        assert_eq!(length.t.abs(), Scalar::ONE);
+    }
+
+    #[test]
+    fn cylindrical_and_planar_face() {
+        let mut core = Core::new();
+
+        let cylinder = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::circle(
+                    [0., 0.],
+                    1.,
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        // The plane cuts through the cylinder, parallel to its axis. The side
+        // of the cylinder is intersected in two vertical lines.
+        let y = Scalar::from(-0.5);
+        let plane = Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(
+                Point::from([Scalar::ZERO, y, Scalar::ZERO]),
+                Vector::unit_x(),
+            )),
+            Vector::unit_z(),
+            &mut core,
+        );
+        let plane = square([[-2., -2.], [2., 2.]], plane, &mut core);
+
+        // The side of the cylinder is made up of multiple faces. None of their
+        // boundaries are on the plane, so each line is found exactly once.
+        let curves = cylinder
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .filter(|face| {
+                core.layers
+                    .geometry
+                    .try_of_surface(face.surface())
+                    .is_some_and(|surface| matches!(surface.u, Path::Circle(_)))
+            })
+            .filter_map(|face| {
+                (face, &plane, core.tolerance(), &*core.layers.geometry)
+                    .intersect()
+            })
+            .flat_map(|intersection| intersection.curves)
+            .collect::<Vec<_>>();
+        assert_eq!(curves.len(), 2);
+
+        let x = Scalar::from(0.75_f64.sqrt());
+        for curve in curves {
+            assert!(curve.path.is_none());
+
+            let length = curve.boundary.inner[1] - curve.boundary.inner[0];
+            assert!((length.t - Scalar::ONE).abs() < Scalar::from(1e-9));
+
+            for (point, local) in
+                curve.global.points.iter().zip(&curve.local[1].points)
+            {
+                // The approximation of the cylinder deviates from it by up to
+                // the tolerance, radially.
+                let radius = Vector::from([point.x, point.y]).magnitude();
+                assert!(point.x.abs() < x);
+                assert!(
+                    (radius - Scalar::ONE).abs() <= core.tolerance().inner()
+                );
+                assert!((point.y - y).abs() < Scalar::from(1e-9));
+                assert!((local.u - point.x).abs() < Scalar::from(1e-9));
+                assert!((local.v - point.z).abs() < Scalar::from(1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn disjoint_faces() {
+        let mut core = Core::new();
+
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+        let xz_plane = core.layers.topology.surfaces.xz_plane();
+
+        let a = square([[0., -1.], [2., 1.]], xy_plane, &mut core);
+        let b = square([[3., -1.], [5., 1.]], xz_plane, &mut core);
+
+        assert!((&a, &b, core.tolerance(), &*core.layers.geometry)
+            .intersect()
+            .is_none());
+    }
+
+    fn square(
+        [min, max]: [[f64; 2]; 2],
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Handle<Face> {
+        let corners =
+            [[min[0], min[1]], [max[0], min[1]], max, [min[0], max[1]]];
+
+        Face::unbound(surface.clone(), core)
+            .update_region(
+                |region, core| {
+                    region.update_exterior(
+                        |_, core| Cycle::polygon(corners, surface, core),
+                        core,
+                    )
+                },
+                core,
+            )
+            .insert(core)
+    }
+}
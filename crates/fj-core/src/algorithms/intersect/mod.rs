@@ -1,12 +1,19 @@
 //! Intersection algorithms
 
+pub mod face_face;
 pub mod ray_segment;
 
 mod line_segment;
+mod path_path;
 
 use fj_math::{Point, Vector};
 
-pub use self::line_segment::LineSegmentIntersection;
+pub use self::{
+    face_face::{FaceFaceIntersection, FaceFaceIntersectionCurve},
+    line_segment::LineSegmentIntersection,
+};
+
+pub(crate) use self::path_path::refine_path_intersection;
 
 /// Compute the intersection between a tuple of objects
 ///
@@ -0,0 +1,50 @@
+use fj_math::{Point, Scalar, Vector};
+
+use crate::geometry::Path;
+
+/// Refine the intersection of two paths, using Newton's method
+///
+/// Starts from an approximate intersection, given as positions on both paths,
+/// and returns the refined positions. If the paths are parallel at some point
+/// during the refinement, the positions found up to then are returned.
+///
+/// If `fix_a` is `true`, only the position on the second path is refined. This
+/// finds the point on `b` that is closest to the fixed point on `a`.
+pub(crate) fn refine_path_intersection(
+    a: &Path<2>,
+    b: &Path<2>,
+    [mut s, mut t]: [Point<1>; 2],
+    fix_a: bool,
+) -> [Point<1>; 2] {
+    let cross = |a: Vector<2>, b: Vector<2>| a.u * b.v - a.v * b.u;
+    let derivative = |path: &Path<2>, point: Point<1>| {
+        let h = Vector::from([1e-7]);
+        (path.point_from_path_coords(point + h)
+            - path.point_from_path_coords(point - h))
+            / (h.t * 2.)
+    };
+
+    for _ in 0..16 {
+        let diff = b.point_from_path_coords(t) - a.point_from_path_coords(s);
+        if diff.magnitude() <= Scalar::from(1e-12) {
+            break;
+        }
+
+        let da = derivative(a, s);
+        let db = derivative(b, t);
+
+        if fix_a {
+            t -= Vector::from([diff.dot(&db) / db.dot(&db)]);
+        } else {
+            let det = cross(da, -db);
+            if det == Scalar::ZERO {
+                break;
+            }
+
+            s += Vector::from([cross(diff, -db) / det]);
+            t += Vector::from([cross(da, diff) / det]);
+        }
+    }
+
+    [s, t]
+}
@@ -0,0 +1,197 @@
+use std::iter;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::approx::{face::approx_face, ApproxCache},
+    geometry::{traits::GenPolyline, CurveBoundary, Geometry, Tolerance},
+    storage::Handle,
+    topology::{Cycle, Face, Sketch, Surface},
+};
+
+use super::{AreaProperties, FaceTriangle, MassProperties};
+
+impl MassProperties for &Handle<Face> {
+    type Properties = AreaProperties<3>;
+
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Self::Properties {
+        let tolerance = tolerance.into();
+
+        let approx = approx_face(
+            self.clone(),
+            tolerance,
+            &mut ApproxCache::default(),
+            geometry,
+        );
+        let triangles = FaceTriangle::triangulate(approx, tolerance, geometry);
+
+        let mut area = Scalar::ZERO;
+        let mut area_error = Scalar::ZERO;
+        let mut max_deviation = Scalar::ZERO;
+        let mut first_moment = Vector::from([0., 0., 0.]);
+
+        for triangle in &triangles {
+            let [a, b, c] = triangle.points.map(|point| point.coords);
+            let area_of_triangle = triangle.area();
+
+            area += area_of_triangle;
+            area_error += triangle.area_error();
+            max_deviation = max_deviation.max(triangle.deviation);
+            first_moment = first_moment + (a + b + c) * area_of_triangle / 3.;
+        }
+
+        let centroid = (area > area_error).then(|| Point {
+            coords: first_moment / area,
+        });
+
+        // Every point of the approximation is within `max_deviation` of the
+        // actual face. Moving the points of an area can't move its centroid
+        // any further than that. Whatever area the approximation is missing or
+        // adding on top of that is located within `radius` of the centroid.
+        let centroid_error = centroid.map_or(Scalar::ZERO, |centroid| {
+            let radius = triangles
+                .iter()
+                .flat_map(|triangle| triangle.points)
+                .map(|point| (point - centroid).magnitude())
+                .fold(Scalar::ZERO, Scalar::max)
+                + max_deviation;
+
+            (area_error * radius + area * max_deviation) / (area - area_error)
+        });
+
+        AreaProperties {
+            area,
+            area_error,
+            centroid,
+            centroid_error,
+        }
+    }
+}
+
+impl MassProperties for &Sketch {
+    type Properties = AreaProperties<2>;
+
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Self::Properties {
+        let tolerance = tolerance.into();
+
+        let mut area = Scalar::ZERO;
+        let mut perimeter = Scalar::ZERO;
+        let mut first_moment = Vector::from([0., 0.]);
+        let mut boundary = Vec::new();
+
+        for region in self.regions() {
+            let mut area_of_region = Scalar::ZERO;
+            let mut first_moment_of_region = Vector::from([0., 0.]);
+
+            // Interior cycles are wound opposite to the exterior, so their
+            // signed areas subtract from the area of the region.
+            for cycle in region.all_cycles() {
+                let points =
+                    cycle_polygon(cycle, self.surface(), tolerance, geometry);
+
+                let segments = points
+                    .iter()
+                    .zip(points.iter().cycle().skip(1))
+                    .map(|(&a, &b)| [a.coords, b.coords]);
+                for [a, b] in segments {
+                    let cross = a.cross2d(&b);
+
+                    area_of_region += cross / 2.;
+                    first_moment_of_region =
+                        first_moment_of_region + (a + b) * cross / 6.;
+                    perimeter += (b - a).magnitude();
+                }
+
+                boundary.extend(points);
+            }
+
+            // Depending on the handedness of the surface coordinates, the
+            // exterior might be wound clockwise. Normalize the sign, so
+            // regions don't cancel each other out.
+            let sign = area_of_region.sign().to_scalar();
+            area += area_of_region * sign;
+            first_moment = first_moment + first_moment_of_region * sign;
+        }
+
+        let area_error = perimeter * tolerance.inner();
+        let centroid = (area > area_error).then(|| Point {
+            coords: first_moment / area,
+        });
+
+        // The area that the approximation is missing or adding is located
+        // within the tolerance of the boundary.
+        let centroid_error = centroid.map_or(Scalar::ZERO, |centroid| {
+            let radius = boundary
+                .iter()
+                .map(|point| (point - centroid).magnitude())
+                .fold(Scalar::ZERO, Scalar::max)
+                + tolerance.inner();
+
+            area_error * radius / (area - area_error)
+        });
+
+        AreaProperties {
+            area,
+            area_error,
+            centroid,
+            centroid_error,
+        }
+    }
+}
+
+/// Approximate a cycle as a polygon in surface coordinates
+///
+/// Unlike [`approx_cycle`], this doesn't require the surface to have any 3D
+/// geometry, which sketch surfaces usually don't.
+///
+/// [`approx_cycle`]: crate::algorithms::approx::cycle::approx_cycle
+fn cycle_polygon(
+    cycle: &Cycle,
+    surface: &Handle<Surface>,
+    tolerance: Tolerance,
+    geometry: &Geometry,
+) -> Vec<Point<2>> {
+    cycle
+        .half_edges()
+        .pairs()
+        .flat_map(|(half_edge, next_half_edge)| {
+            let boundary = CurveBoundary {
+                inner: [half_edge, next_half_edge].map(|h| {
+                    geometry
+                        .of_vertex(h.start_vertex())
+                        .unwrap()
+                        .local_on(half_edge.curve())
+                        .unwrap()
+                        .position
+                }),
+            };
+            let path = geometry
+                .of_curve(half_edge.curve())
+                .unwrap()
+                .local_on(surface)
+                .unwrap()
+                .path
+                .clone();
+
+            // Leave out the end of the half-edge. It's the start of the next
+            // one.
+            let [start, end] = boundary.inner;
+            let rest = path
+                .generate_polyline(boundary, tolerance)
+                .into_iter()
+                .filter(move |&point| point != start && point != end);
+
+            iter::once(start)
+                .chain(rest)
+                .map(move |point| path.point_from_path_coords(point))
+        })
+        .collect()
+}
@@ -0,0 +1,383 @@
+//! Compute mass properties of objects
+//!
+//! See [`MassProperties`].
+
+mod face;
+mod solid;
+
+use fj_math::{Point, Scalar};
+
+use crate::geometry::{
+    repr::tri_mesh::convert_point_surface_to_global, Geometry, Tolerance,
+};
+
+use super::{approx::face::FaceApprox, triangulate::triangulate_face};
+
+/// Compute the mass properties of an object
+///
+/// Mass properties are computed from an approximation of the object, which is
+/// generated using the provided tolerance. The results come with error bounds,
+/// that account for the deviation of that approximation from the actual
+/// object.
+pub trait MassProperties {
+    /// The mass properties of the object
+    ///
+    /// [`VolumeProperties`] for shells and solids, [`AreaProperties`] for faces
+    /// and sketches.
+    type Properties;
+
+    /// Compute the mass properties of the object
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Self::Properties;
+}
+
+/// The mass properties of a volume
+///
+/// The mass-related properties assume a uniform density of `1`, meaning the
+/// mass equals the volume.
+///
+/// The error bounds are derived from the distance between each triangle of the
+/// approximation and the surface it approximates. They assume that the surface
+/// doesn't fold back on itself within a triangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VolumeProperties {
+    /// The enclosed volume
+    pub volume: Scalar,
+
+    /// An upper bound for the error of [`VolumeProperties::volume`]
+    ///
+    /// The actual boundary of the volume is located within a slab around each
+    /// triangle of the approximation, so the approximated volume can't deviate
+    /// from the actual one by more than the volume of those slabs.
+    pub volume_error: Scalar,
+
+    /// The area of the boundary of the volume
+    pub surface_area: Scalar,
+
+    /// An upper bound for the error of [`VolumeProperties::surface_area`]
+    pub surface_area_error: Scalar,
+
+    /// The center of mass
+    ///
+    /// `None`, if the volume is not larger than its error. The center of mass
+    /// is meaningless in that case.
+    pub center_of_mass: Option<Point<3>>,
+
+    /// An upper bound for the distance of the center of mass from the actual
+    /// one
+    pub center_of_mass_error: Scalar,
+
+    /// The inertia tensor, relative to the center of mass
+    ///
+    /// The tensor is given in global coordinates, as rows of a symmetric 3x3
+    /// matrix. Without a center of mass, it is relative to the origin.
+    pub inertia_tensor: [[Scalar; 3]; 3],
+
+    /// An upper bound for the error of each component of
+    /// [`VolumeProperties::inertia_tensor`]
+    pub inertia_tensor_error: Scalar,
+}
+
+/// The mass properties of an area
+///
+/// Faces provide these in global coordinates (`D == 3`), sketches in the
+/// coordinates of their surface (`D == 2`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AreaProperties<const D: usize> {
+    /// The area
+    pub area: Scalar,
+
+    /// An upper bound for the error of [`AreaProperties::area`]
+    ///
+    /// For sketches, every point of the approximated boundary is within the
+    /// tolerance of the actual boundary, so the approximated area can't
+    /// deviate from the actual one by more than the length of the boundary
+    /// times the tolerance.
+    ///
+    /// Faces can be curved. Each triangle of their approximation can deviate
+    /// from the actual surface by more than the tolerance, and the bound takes
+    /// that into account, the same way as [`VolumeProperties`] do.
+    pub area_error: Scalar,
+
+    /// The centroid of the area
+    ///
+    /// `None`, if the area is not larger than its error. The centroid is
+    /// meaningless in that case.
+    pub centroid: Option<Point<D>>,
+
+    /// An upper bound for the distance of the centroid from the actual one
+    pub centroid_error: Scalar,
+}
+
+/// A triangle that approximates part of a face
+struct FaceTriangle {
+    /// The points of the triangle, in global coordinates
+    points: [Point<3>; 3],
+
+    /// An upper bound for the distance of the triangle from the face
+    deviation: Scalar,
+}
+
+impl FaceTriangle {
+    /// Triangulate the approximation of a face
+    ///
+    /// The triangulation only has points on the boundary of the face. Where
+    /// the face is curved, the interior of a triangle can be much further from
+    /// it than the tolerance. To find out how far, the surface is sampled at
+    /// the centroid and the edge midpoints of each triangle.
+    fn triangulate(
+        approx: FaceApprox,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<Self> {
+        let surface = &geometry
+            .of_surface_2(approx.face.surface())
+            .expect("Expected geometry of surface to be defined")
+            .generator;
+
+        triangulate_face(approx)
+            .into_iter()
+            .map(|triangle| {
+                let [a, b, c] = &triangle;
+
+                // The samples are given by the barycentric coordinates of `b`
+                // and `c`. All of them are located on the triangle in both
+                // surface and global coordinates, so we can compare where the
+                // surface actually is.
+                let samples =
+                    [[1. / 3., 1. / 3.], [0.5, 0.], [0.5, 0.5], [0., 0.5]];
+
+                let deviation = samples
+                    .into_iter()
+                    .map(|[s, t]| {
+                        let local = a.local_form
+                            + (b.local_form - a.local_form) * s
+                            + (c.local_form - a.local_form) * t;
+                        let global = a.global_form
+                            + (b.global_form - a.global_form) * s
+                            + (c.global_form - a.global_form) * t;
+
+                        let on_surface = convert_point_surface_to_global(
+                            surface, local, tolerance, geometry,
+                        );
+
+                        (on_surface - global).magnitude()
+                    })
+                    .fold(Scalar::ZERO, Scalar::max);
+
+                Self {
+                    points: triangle.map(|point| point.global_form),
+                    deviation: tolerance.inner() + deviation,
+                }
+            })
+            .collect()
+    }
+
+    fn area(&self) -> Scalar {
+        let [a, b, c] = self.points;
+        (b - a).cross(&(c - a)).magnitude() / 2.
+    }
+
+    /// An upper bound for the error of the area of the triangle
+    ///
+    /// This is as much as the area would increase, if the triangle was raised
+    /// into a tent by twice its deviation.
+    fn area_error(&self) -> Scalar {
+        let [a, b, c] = self.points;
+        let perimeter =
+            (b - a).magnitude() + (c - b).magnitude() + (a - c).magnitude();
+
+        perimeter * self.deviation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildRegion, BuildSketch},
+            insert::Insert,
+            revolve::RevolveSketch,
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, HalfEdge, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::MassProperties;
+
+    #[test]
+    fn mass_properties_of_cube() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        let properties = (&solid).mass_properties(0.001, &core.layers.geometry);
+
+        let epsilon = Scalar::from(1e-12);
+        assert!((properties.volume - 1.).abs() < epsilon);
+        assert!((properties.surface_area - 6.).abs() < epsilon);
+        assert!((properties.volume_error - 0.006).abs() < epsilon);
+        assert!(
+            (properties.center_of_mass.unwrap()
+                - Point::from([0.5, 0.5, -0.5]))
+            .magnitude()
+                < epsilon
+        );
+
+        for (i, row) in properties.inertia_tensor.into_iter().enumerate() {
+            for (j, value) in row.into_iter().enumerate() {
+                let expected = if i == j { 1. / 6. } else { 0. };
+                assert!((value - expected).abs() < epsilon);
+            }
+        }
+    }
+
+    #[test]
+    fn mass_properties_of_sketch_with_hole() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let sketch = Sketch::empty(&core.layers.topology).add_regions(
+            [Region::polygon(
+                [[0., 0.], [4., 0.], [4., 2.], [0., 2.]],
+                surface.clone(),
+                &mut core,
+            )
+            .add_interiors(
+                [Cycle::polygon(
+                    [[1., 1.], [1., 1.5], [2., 1.5], [2., 1.]],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )],
+            &mut core,
+        );
+
+        let properties = sketch.mass_properties(0.001, &core.layers.geometry);
+
+        // A 4x2 rectangle, with a 1x0.5 hole in it.
+        assert_eq!(properties.area, Scalar::from(7.5));
+        assert_eq!(properties.area_error, Scalar::from(0.015));
+
+        // Removing the hole shifts the centroid away from it.
+        let expected = Point::from([
+            (8. * 2. - 0.5 * 1.5) / 7.5,
+            (8. * 1. - 0.5 * 1.25) / 7.5,
+        ]);
+        assert!(
+            (properties.centroid.unwrap() - expected).magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
+
+    #[test]
+    fn error_bounds_of_sphere() {
+        let mut core = Core::new();
+
+        let sketch = Sketch::empty(&core.layers.topology);
+        let surface = sketch.surface().clone();
+        let quarter = Scalar::TAU / 4.;
+        let half_circle = Cycle::from_half_edges_and_boundaries(
+            [
+                HalfEdge::arc(
+                    [0., -1.],
+                    [1., 0.],
+                    quarter,
+                    surface.clone(),
+                    &mut core,
+                ),
+                HalfEdge::arc(
+                    [1., 0.],
+                    [0., 1.],
+                    quarter,
+                    surface.clone(),
+                    &mut core,
+                ),
+                HalfEdge::line_segment(
+                    [[0., 1.], [0., -1.]],
+                    surface.clone(),
+                    &mut core,
+                ),
+            ],
+            &mut core,
+        )
+        .insert(&mut core);
+        let solid = sketch
+            .add_regions([Region::new(half_circle, [])], &mut core)
+            .revolve_sketch(
+                core.layers.topology.surfaces.xz_plane(),
+                [[0., -1.], [0., 1.]],
+                Scalar::TAU,
+                &mut core,
+            );
+
+        let properties = (&solid).mass_properties(0.001, &core.layers.geometry);
+
+        // The faces of the sphere are triangulated using only the points on
+        // their boundaries, so the approximation is far off. The error bounds
+        // need to reflect that.
+        let volume = Scalar::PI * 4. / 3.;
+        let surface_area = Scalar::PI * 4.;
+        assert!((properties.volume - volume).abs() > Scalar::from(0.01));
+        assert!((properties.volume - volume).abs() <= properties.volume_error);
+        assert!(
+            (properties.surface_area - surface_area).abs()
+                <= properties.surface_area_error
+        );
+
+        let center_of_mass = properties.center_of_mass.unwrap();
+        assert!(
+            center_of_mass.coords.magnitude()
+                <= properties.center_of_mass_error
+        );
+
+        let moment_of_inertia = volume * 2. / 5.;
+        for (i, row) in properties.inertia_tensor.into_iter().enumerate() {
+            for (j, value) in row.into_iter().enumerate() {
+                let expected = if i == j {
+                    moment_of_inertia
+                } else {
+                    Scalar::ZERO
+                };
+                assert!(
+                    (value - expected).abs() <= properties.inertia_tensor_error
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mass_properties_of_empty_solid() {
+        let core = Core::new();
+
+        let properties =
+            (&Solid::new([])).mass_properties(0.001, &core.layers.geometry);
+
+        assert_eq!(properties.volume, Scalar::ZERO);
+        assert_eq!(properties.center_of_mass, None);
+        assert_eq!(properties.center_of_mass_error, Scalar::ZERO);
+        assert_eq!(properties.inertia_tensor, [[Scalar::ZERO; 3]; 3],);
+    }
+}
@@ -0,0 +1,207 @@
+use std::ops::Add;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::approx::Approx,
+    geometry::{Geometry, Tolerance},
+    topology::{Shell, Solid},
+};
+
+use super::{FaceTriangle, MassProperties, VolumeProperties};
+
+impl MassProperties for &Solid {
+    type Properties = VolumeProperties;
+
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Self::Properties {
+        let tolerance = tolerance.into();
+
+        self.shells()
+            .iter()
+            .map(|shell| Integrals::of_shell(shell, tolerance, geometry))
+            .fold(Integrals::default(), Integrals::add)
+            .into_properties()
+    }
+}
+
+impl MassProperties for &Shell {
+    type Properties = VolumeProperties;
+
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Self::Properties {
+        Integrals::of_shell(self, tolerance.into(), geometry).into_properties()
+    }
+}
+
+/// The integrals over a volume, that the mass properties are derived from
+///
+/// Unlike the mass properties themselves, these can be added up over multiple
+/// shells.
+#[derive(Default)]
+struct Integrals {
+    volume: Scalar,
+    volume_error: Scalar,
+    surface_area: Scalar,
+    surface_area_error: Scalar,
+    first_moment: Vector<3>,
+
+    /// The covariance, relative to the origin
+    covariance: [[Scalar; 3]; 3],
+
+    /// The points of the approximation
+    points: Vec<Point<3>>,
+
+    /// An upper bound for the distance of any point of the approximation from
+    /// the actual boundary of the volume
+    max_deviation: Scalar,
+}
+
+impl Integrals {
+    fn of_shell(
+        shell: &Shell,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Self {
+        let mut integrals = Self::default();
+
+        // Every triangle of the oriented boundary forms a tetrahedron with the
+        // origin. The signed integrals over those tetrahedra add up to the
+        // integrals over the enclosed volume.
+        for approx in shell.approx(tolerance, geometry) {
+            for triangle in
+                FaceTriangle::triangulate(approx, tolerance, geometry)
+            {
+                let [a, b, c] = triangle.points.map(|point| point.coords);
+                let sum = a + b + c;
+
+                let det = a.dot(&b.cross(&c));
+                let volume_of_tetrahedron = det / 6.;
+
+                integrals.volume += volume_of_tetrahedron;
+                integrals.volume_error += triangle.area() * triangle.deviation;
+                integrals.surface_area += triangle.area();
+                integrals.surface_area_error += triangle.area_error();
+                integrals.first_moment =
+                    integrals.first_moment + sum * volume_of_tetrahedron / 4.;
+
+                // The covariance of a tetrahedron with one vertex at the
+                // origin, as derived from the covariance of the canonical
+                // tetrahedron.
+                for (i, row) in integrals.covariance.iter_mut().enumerate() {
+                    for (j, value) in row.iter_mut().enumerate() {
+                        *value += det / 120.
+                            * (a.components[i] * a.components[j]
+                                + b.components[i] * b.components[j]
+                                + c.components[i] * c.components[j]
+                                + sum.components[i] * sum.components[j]);
+                    }
+                }
+
+                integrals.points.extend(triangle.points);
+                integrals.max_deviation =
+                    integrals.max_deviation.max(triangle.deviation);
+            }
+        }
+
+        integrals
+    }
+
+    fn into_properties(self) -> VolumeProperties {
+        let Self {
+            volume,
+            volume_error,
+            surface_area,
+            surface_area_error,
+            first_moment,
+            mut covariance,
+            points,
+            max_deviation,
+        } = self;
+
+        // If the volume is within its error of zero, it might as well be zero.
+        // Dividing by it would give us garbage.
+        let center_of_mass = (volume.abs() > volume_error).then(|| Point {
+            coords: first_moment / volume,
+        });
+
+        // The volume that the approximation is missing or adding is located
+        // within `radius` of the center of mass. Its first moment, relative to
+        // the center of mass, bounds how far the center of mass can move.
+        let radius = center_of_mass.map_or(Scalar::ZERO, |center_of_mass| {
+            points
+                .iter()
+                .map(|&point| (point - center_of_mass).magnitude())
+                .fold(Scalar::ZERO, Scalar::max)
+                + max_deviation
+        });
+        let center_of_mass_error = if center_of_mass.is_some() {
+            volume_error * radius / (volume.abs() - volume_error)
+        } else {
+            Scalar::ZERO
+        };
+
+        // Move the covariance from the origin to the center of mass, then
+        // derive the inertia tensor from it.
+        let center = center_of_mass.unwrap_or_default().coords.components;
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value -= volume * center[i] * center[j];
+            }
+        }
+        let trace = covariance[0][0] + covariance[1][1] + covariance[2][2];
+        let mut inertia_tensor = covariance.map(|row| row.map(|value| -value));
+        for (i, row) in inertia_tensor.iter_mut().enumerate() {
+            row[i] += trace;
+        }
+
+        // No component of the integrand is larger than the squared distance
+        // from the center of mass. On top of that, the inertia tensor is
+        // relative to a center of mass that might be off.
+        let inertia_tensor_error = volume_error * radius * radius
+            + (volume.abs() + volume_error)
+                * center_of_mass_error
+                * center_of_mass_error;
+
+        VolumeProperties {
+            volume,
+            volume_error,
+            surface_area,
+            surface_area_error,
+            center_of_mass,
+            center_of_mass_error,
+            inertia_tensor,
+            inertia_tensor_error,
+        }
+    }
+}
+
+impl Add for Integrals {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self::Output {
+        self.volume += other.volume;
+        self.volume_error += other.volume_error;
+        self.surface_area += other.surface_area;
+        self.surface_area_error += other.surface_area_error;
+        self.first_moment = self.first_moment + other.first_moment;
+
+        for (row, other_row) in self.covariance.iter_mut().zip(other.covariance)
+        {
+            for (value, other_value) in row.iter_mut().zip(other_row) {
+                *value += other_value;
+            }
+        }
+
+        self.points.extend(other.points);
+        self.max_deviation = self.max_deviation.max(other.max_deviation);
+
+        self
+    }
+}
@@ -12,5 +12,9 @@
 
 pub mod approx;
 pub mod bounding_volume;
+pub mod classify;
+pub mod distance;
 pub mod intersect;
+pub mod mass_properties;
+pub mod ray_cast;
 pub mod triangulate;
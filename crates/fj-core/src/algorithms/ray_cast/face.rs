@@ -0,0 +1,91 @@
+use fj_math::{Aabb, Scalar, Triangle, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{face::approx_face, ApproxCache},
+        triangulate::{surface_point_on_triangle, triangulate_face},
+    },
+    geometry::{Geometry, Tolerance},
+    storage::Handle,
+    topology::Face,
+};
+
+use super::{sort_hits, FaceTriangles, Ray, RayCast, RayCastCache, RayHit};
+
+impl RayCast for &Handle<Face> {
+    fn ray_cast_with_cache(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut RayCastCache,
+        geometry: &Geometry,
+    ) -> Vec<RayHit> {
+        let tolerance = tolerance.into();
+
+        // Hits are deduplicated with this precision below. Enlarge the
+        // bounding box by the same amount, to not reject rays that barely hit
+        // the face.
+        let epsilon = tolerance.inner() * Scalar::from(1e-3);
+
+        let FaceTriangles { aabb, triangles } = cache
+            .faces
+            .entry((self.clone(), tolerance))
+            .or_insert_with(|| {
+                let approx = approx_face(
+                    self.clone(),
+                    tolerance,
+                    &mut ApproxCache::default(),
+                    geometry,
+                );
+                let triangles = triangulate_face(approx);
+
+                let aabb = Aabb::<3>::from_points(
+                    triangles.iter().flatten().map(|point| point.global_form),
+                );
+                let margin = Vector::from([epsilon; 3]);
+                let aabb = Aabb {
+                    min: aabb.min - margin,
+                    max: aabb.max + margin,
+                };
+
+                FaceTriangles { aabb, triangles }
+            });
+
+        let direction = ray.direction.normalize();
+        if !aabb.intersects_ray(ray.origin, direction, f64::INFINITY) {
+            return Vec::new();
+        }
+
+        let mut hits = triangles
+            .iter()
+            .filter_map(|triangle| {
+                let distance = Triangle::from_points(
+                    triangle.map(|point| point.global_form),
+                )
+                .cast_local_ray(
+                    ray.origin,
+                    direction,
+                    f64::INFINITY,
+                    true,
+                )?;
+
+                let point = ray.origin + direction * distance;
+
+                Some(RayHit {
+                    point,
+                    distance,
+                    face: self.clone(),
+                    point_surface: surface_point_on_triangle(triangle, point),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        sort_hits(&mut hits);
+
+        // A ray that hits an edge between two triangles of the face hits both
+        // of them. That is still only one hit on the face.
+        hits.dedup_by(|b, a| b.distance - a.distance <= epsilon);
+
+        hits
+    }
+}
@@ -0,0 +1,241 @@
+//! Cast rays against objects
+//!
+//! See [`RayCast`].
+
+mod face;
+mod shell;
+mod solid;
+
+use std::collections::BTreeMap;
+
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, Tolerance},
+    storage::Handle,
+    topology::Face,
+};
+
+use super::{approx::ApproxPoint, intersect::HorizontalRayToTheRight};
+
+/// Cast a ray against an object
+pub trait RayCast: Sized {
+    /// Cast the ray against the object
+    ///
+    /// The object is approximated with the provided tolerance, and the ray is
+    /// cast against that approximation. Returns all hits, ordered by their
+    /// distance from the origin of the ray.
+    ///
+    /// If the ray hits an edge that is shared between faces, a hit is returned
+    /// for each of those faces.
+    fn ray_cast(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Vec<RayHit> {
+        let mut cache = RayCastCache::default();
+        self.ray_cast_with_cache(ray, tolerance, &mut cache, geometry)
+    }
+
+    /// Cast the ray against the object, using the provided cache
+    ///
+    /// Casting many rays against the same object is faster this way, as each
+    /// face is only approximated once. Callers that cast a single ray might
+    /// consider using [`RayCast::ray_cast`] instead.
+    fn ray_cast_with_cache(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut RayCastCache,
+        geometry: &Geometry,
+    ) -> Vec<RayHit>;
+}
+
+/// Cache for ray casting
+///
+/// Stores the triangulated approximation of each face that a ray was cast
+/// against, for each tolerance.
+#[derive(Default)]
+pub struct RayCastCache {
+    faces: BTreeMap<(Handle<Face>, Tolerance), FaceTriangles>,
+}
+
+/// The triangulated approximation of a face
+struct FaceTriangles {
+    /// The bounding box of all triangles, for rejecting rays that miss them
+    aabb: Aabb<3>,
+
+    /// The triangles that approximate the face
+    triangles: Vec<[ApproxPoint<2>; 3]>,
+}
+
+/// A ray in 3D space
+///
+/// Unlike [`HorizontalRayToTheRight`], which is used internally where its
+/// restrictions simplify the calculations, this ray can point in any
+/// direction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ray {
+    /// The point where the ray originates
+    pub origin: Point<3>,
+
+    /// The direction of the ray
+    ///
+    /// Does not need to be normalized, but must not be zero.
+    pub direction: Vector<3>,
+}
+
+impl From<HorizontalRayToTheRight<3>> for Ray {
+    fn from(ray: HorizontalRayToTheRight<3>) -> Self {
+        Self {
+            origin: ray.origin,
+            direction: ray.direction(),
+        }
+    }
+}
+
+/// A point where a ray hits a face
+#[derive(Clone, Debug)]
+pub struct RayHit {
+    /// The point where the ray hits the face
+    pub point: Point<3>,
+
+    /// The distance of the hit from the origin of the ray
+    ///
+    /// This is the actual distance, regardless of the length of the ray's
+    /// direction vector.
+    pub distance: Scalar,
+
+    /// The face that was hit
+    pub face: Handle<Face>,
+
+    /// The point where the ray hits the face, in surface coordinates
+    pub point_surface: Point<2>,
+}
+
+/// # Sort hits by distance
+fn sort_hits(hits: &mut [RayHit]) {
+    hits.sort_by(|a, b| a.distance.cmp(&b.distance));
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::intersect::HorizontalRayToTheRight,
+        geometry::repr::tri_mesh::convert_point_surface_to_global,
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        storage::Handle,
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{Ray, RayCast, RayCastCache};
+
+    #[test]
+    fn ray_through_cube() {
+        let mut core = Core::new();
+        let cube = cube(&mut core);
+
+        let ray = Ray {
+            origin: Point::from([0.25, 0.5, 1.]),
+            direction: Vector::from([0., 0., -2.]),
+        };
+        let hits = cube.ray_cast(&ray, core.tolerance(), &core.layers.geometry);
+
+        let distances = hits.iter().map(|hit| hit.distance).collect::<Vec<_>>();
+        assert_eq!(distances, [Scalar::ONE, Scalar::from(2.)]);
+        assert_ne!(hits[0].face, hits[1].face);
+
+        for hit in hits {
+            let point = convert_point_surface_to_global(
+                &core
+                    .layers
+                    .geometry
+                    .of_surface_2(hit.face.surface())
+                    .unwrap()
+                    .generator,
+                hit.point_surface,
+                core.tolerance(),
+                &core.layers.geometry,
+            );
+            assert!((point - hit.point).magnitude() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn ray_from_inside_and_missing() {
+        let mut core = Core::new();
+        let cube = cube(&mut core);
+
+        let inside = Ray::from(HorizontalRayToTheRight {
+            origin: Point::from([0.5, 0.25, -0.5]),
+        });
+        let hits =
+            cube.ray_cast(&inside, core.tolerance(), &core.layers.geometry);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].point, Point::from([1., 0.25, -0.5]));
+
+        let missing = Ray::from(HorizontalRayToTheRight {
+            origin: Point::from([0.5, 2., -0.5]),
+        });
+        assert!(cube
+            .ray_cast(&missing, core.tolerance(), &core.layers.geometry)
+            .is_empty());
+    }
+
+    #[test]
+    fn ray_cast_with_cache() {
+        let mut core = Core::new();
+        let cube = cube(&mut core);
+
+        let mut cache = RayCastCache::default();
+        for origin in [[0.25, 0.5, 1.], [0.75, 0.5, 1.], [2., 0.5, 1.]] {
+            let ray = Ray {
+                origin: Point::from(origin),
+                direction: Vector::from([0., 0., -1.]),
+            };
+
+            let hits = cube.ray_cast_with_cache(
+                &ray,
+                core.tolerance(),
+                &mut cache,
+                &core.layers.geometry,
+            );
+            let expected =
+                cube.ray_cast(&ray, core.tolerance(), &core.layers.geometry);
+
+            let [hits, expected] = [hits, expected].map(|hits| {
+                hits.into_iter()
+                    .map(|hit| (hit.point, hit.face))
+                    .collect::<Vec<_>>()
+            });
+            assert_eq!(hits, expected);
+        }
+    }
+
+    fn cube(core: &mut Core) -> Handle<Solid> {
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                core,
+            )
+            .insert(core)
+    }
+}
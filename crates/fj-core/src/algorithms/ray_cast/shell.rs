@@ -0,0 +1,29 @@
+use crate::{
+    geometry::{Geometry, Tolerance},
+    topology::Shell,
+};
+
+use super::{sort_hits, Ray, RayCast, RayCastCache, RayHit};
+
+impl RayCast for &Shell {
+    fn ray_cast_with_cache(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut RayCastCache,
+        geometry: &Geometry,
+    ) -> Vec<RayHit> {
+        let tolerance = tolerance.into();
+
+        let mut hits = self
+            .faces()
+            .iter()
+            .flat_map(|face| {
+                face.ray_cast_with_cache(ray, tolerance, cache, geometry)
+            })
+            .collect::<Vec<_>>();
+
+        sort_hits(&mut hits);
+        hits
+    }
+}
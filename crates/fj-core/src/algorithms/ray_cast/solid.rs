@@ -0,0 +1,29 @@
+use crate::{
+    geometry::{Geometry, Tolerance},
+    topology::Solid,
+};
+
+use super::{sort_hits, Ray, RayCast, RayCastCache, RayHit};
+
+impl RayCast for &Solid {
+    fn ray_cast_with_cache(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        cache: &mut RayCastCache,
+        geometry: &Geometry,
+    ) -> Vec<RayHit> {
+        let tolerance = tolerance.into();
+
+        let mut hits = self
+            .shells()
+            .iter()
+            .flat_map(|shell| {
+                shell.ray_cast_with_cache(ray, tolerance, cache, geometry)
+            })
+            .collect::<Vec<_>>();
+
+        sort_hits(&mut hits);
+        hits
+    }
+}
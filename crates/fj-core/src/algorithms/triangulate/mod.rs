@@ -1,7 +1,7 @@
 //! Shape triangulation
 
 mod delaunay;
-mod polygon;
+pub(crate) mod polygon;
 
 use fj_interop::Mesh;
 use fj_math::Point;
@@ -10,7 +10,7 @@ use crate::{geometry::Tolerance, operations::presentation::GetColor, Core};
 
 use self::polygon::Polygon;
 
-use super::approx::{face::FaceApprox, Approx};
+use super::approx::{face::FaceApprox, Approx, ApproxPoint};
 
 /// Triangulate a shape
 pub trait Triangulate: Sized {
@@ -46,34 +46,75 @@ where
 
 impl Triangulate for FaceApprox {
     fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core) {
-        let face_as_polygon = Polygon::new()
-            .with_exterior(
-                self.exterior
-                    .points()
-                    .into_iter()
-                    .map(|point| point.local_form),
-            )
-            .with_interiors(self.interiors.iter().map(|interior| {
-                interior.points().into_iter().map(|point| point.local_form)
-            }));
-
-        let cycles = [self.exterior].into_iter().chain(self.interiors);
-        let mut triangles =
-            delaunay::triangulate(cycles, self.coord_handedness);
-        triangles.retain(|triangle| {
-            face_as_polygon
-                .contains_triangle(triangle.map(|point| point.point_surface))
-        });
-
         let color = self.face.region().get_color(core).unwrap_or_default();
 
-        for triangle in triangles {
-            let points = triangle.map(|point| point.point_global);
+        for triangle in triangulate_face(self) {
+            let points = triangle.map(|point| point.global_form);
             mesh.push_triangle(points, color);
         }
     }
 }
 
+/// # Triangulate the approximation of a face
+///
+/// Returns the triangles in both surface and global coordinates, for use by
+/// algorithms that need to relate a face's triangles to its surface.
+pub(crate) fn triangulate_face(approx: FaceApprox) -> Vec<[ApproxPoint<2>; 3]> {
+    let face_as_polygon = Polygon::new()
+        .with_exterior(
+            approx
+                .exterior
+                .points()
+                .into_iter()
+                .map(|point| point.local_form),
+        )
+        .with_interiors(approx.interiors.iter().map(|interior| {
+            interior.points().into_iter().map(|point| point.local_form)
+        }));
+
+    let cycles = [approx.exterior].into_iter().chain(approx.interiors);
+    let mut triangles = delaunay::triangulate(cycles, approx.coord_handedness);
+    triangles.retain(|triangle| {
+        face_as_polygon
+            .contains_triangle(triangle.map(|point| point.point_surface))
+    });
+
+    triangles
+        .into_iter()
+        .map(|triangle| {
+            triangle.map(|point| {
+                ApproxPoint::new(point.point_surface, point.point_global)
+            })
+        })
+        .collect()
+}
+
+/// # Convert a global point on a triangle into surface coordinates
+///
+/// The surface coordinates are interpolated from the triangle's points, using
+/// the barycentric coordinates of the global point.
+pub(crate) fn surface_point_on_triangle(
+    triangle: &[ApproxPoint<2>; 3],
+    point: Point<3>,
+) -> Point<2> {
+    let [a, b, c] = triangle;
+
+    let v0 = b.global_form - a.global_form;
+    let v1 = c.global_form - a.global_form;
+    let v2 = point - a.global_form;
+
+    let [d00, d01, d11] = [v0.dot(&v0), v0.dot(&v1), v1.dot(&v1)];
+    let [d20, d21] = [v2.dot(&v0), v2.dot(&v1)];
+
+    let denominator = d00 * d11 - d01 * d01;
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+
+    a.local_form
+        + (b.local_form - a.local_form) * v
+        + (c.local_form - a.local_form) * w
+}
+
 #[cfg(test)]
 mod tests {
     use fj_interop::Mesh;
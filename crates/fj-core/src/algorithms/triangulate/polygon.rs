@@ -108,12 +108,8 @@ impl Polygon {
 
     /// Check whether the polygon contains a point
     ///
-    /// # Implementation Note
-    ///
-    /// This code is being duplicated by the `Contains<Point<2>>` implementation
-    /// for `Face`. It would be nice to be able to consolidate the duplication,
-    /// but this has turned out to be difficult.
-    fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
+    /// Points on the boundary of the polygon are considered to be contained.
+    pub fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
         let ray = HorizontalRayToTheRight {
             origin: point.into(),
         };
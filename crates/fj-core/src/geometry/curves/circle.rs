@@ -61,7 +61,7 @@ impl<const D: usize> GenPolyline<D> for Circle<D> {
 
 /// Path approximation parameters for a circle
 #[derive(Debug)]
-struct CircleApproxParams {
+pub(super) struct CircleApproxParams {
     increment: Scalar,
 }
 
@@ -71,8 +71,14 @@ impl CircleApproxParams {
         circle: &Circle<D>,
         tolerance: impl Into<Tolerance>,
     ) -> Self {
-        let radius = circle.a().magnitude();
+        Self::from_radius(circle.a().magnitude(), tolerance)
+    }
 
+    /// Compute path approximation parameters for a circle of the given radius
+    pub fn from_radius(
+        radius: Scalar,
+        tolerance: impl Into<Tolerance>,
+    ) -> Self {
         let num_vertices_to_approx_full_circle = Scalar::max(
             Scalar::PI
                 / (Scalar::ONE - (tolerance.into().inner() / radius)).acos(),
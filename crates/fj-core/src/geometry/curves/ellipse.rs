@@ -0,0 +1,85 @@
+//! # Geometry code specific to ellipses
+
+use fj_math::{Ellipse, LineSegment, Point};
+
+use crate::geometry::{traits::GenPolyline, CurveBoundary, Tolerance};
+
+use super::circle::CircleApproxParams;
+
+impl<const D: usize> GenPolyline<D> for Ellipse<D> {
+    fn origin(&self) -> Point<D> {
+        self.center() + self.a()
+    }
+
+    fn line_segment_at(
+        &self,
+        point_curve: Point<1>,
+        tolerance: Tolerance,
+    ) -> LineSegment<D> {
+        let params = approx_params(self, tolerance);
+
+        // Like for a circle, the closest approximation points to the provided
+        // point are at integer multiples of the increment.
+        let t = point_curve.t / params.increment();
+        let points_curve = [t.floor(), t.ceil()]
+            .map(|t| [t * params.increment()])
+            .map(Point::from);
+
+        let points = points_curve
+            .map(|point_curve| self.point_from_ellipse_coords(point_curve));
+
+        LineSegment {
+            points,
+            points_line: points_curve,
+        }
+    }
+
+    fn generate_polyline(
+        &self,
+        boundary: CurveBoundary<Point<1>>,
+        tolerance: Tolerance,
+    ) -> Vec<Point<1>> {
+        approx_params(self, tolerance)
+            .approx_circle(boundary)
+            .collect()
+    }
+}
+
+/// # Compute the approximation parameters for an ellipse
+///
+/// An ellipse is the image of a circle under an affine transformation. For the
+/// same increment in curve coordinates, the distance between the curve and its
+/// approximation is at most that of the circle that has the major radius of
+/// the ellipse.
+fn approx_params<const D: usize>(
+    ellipse: &Ellipse<D>,
+    tolerance: Tolerance,
+) -> CircleApproxParams {
+    CircleApproxParams::from_radius(ellipse.major_radius(), tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::TAU;
+
+    use fj_math::Ellipse;
+
+    use crate::geometry::{
+        curves::tests::assert_polyline_within_tolerance, Tolerance,
+    };
+
+    #[test]
+    fn polyline_within_tolerance() -> anyhow::Result<()> {
+        let ellipse = Ellipse::new([0., 0.], [3., 0.], [0., 1.]);
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        assert_polyline_within_tolerance(
+            &ellipse,
+            |point| ellipse.point_from_ellipse_coords(point),
+            [[0.], [TAU]],
+            tolerance,
+        );
+
+        Ok(())
+    }
+}
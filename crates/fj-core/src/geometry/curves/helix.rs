@@ -0,0 +1,230 @@
+//! # Geometry code specific to helices
+
+use fj_math::{LineSegment, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{traits::GenPolyline, CurveBoundary, Tolerance},
+    topology::Handedness,
+};
+
+use super::circle::CircleApproxParams;
+
+/// # A helix
+///
+/// The curve coordinate of a point on the helix is the angle (in radians) that
+/// the helix has turned around its axis to get there, starting at `a`. With
+/// each full turn, the helix advances by `pitch` along the axis.
+///
+/// Unlike the other curves, a helix is only defined in 3D space. It can't be
+/// represented by a [`Path`], but it can be approximated, and regions can be
+/// swept along it.
+///
+/// [`Path`]: crate::geometry::Path
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Helix {
+    /// # The point on the axis, where the helix starts
+    pub center: Point<3>,
+
+    /// # The direction of the axis
+    ///
+    /// The helix advances in this direction, as it turns.
+    pub axis: Vector<3>,
+
+    /// # The vector from `center` to the start of the helix
+    ///
+    /// Must be perpendicular to `axis`. Its length is the radius of the helix.
+    pub a: Vector<3>,
+
+    /// # The distance that the helix advances along the axis, per turn
+    pub pitch: Scalar,
+
+    /// # The direction that the helix turns in
+    ///
+    /// A right-handed helix turns counter-clockwise, when looking at it against
+    /// the direction of the axis (right-hand rule). A left-handed one turns the
+    /// other way.
+    pub handedness: Handedness,
+}
+
+impl Helix {
+    /// # Access the radius of the helix
+    pub fn radius(&self) -> Scalar {
+        self.a.magnitude()
+    }
+
+    /// # Compute the boundary of the provided number of turns
+    ///
+    /// The boundary starts at the start of the helix.
+    pub fn boundary_of_turns(
+        &self,
+        turns: impl Into<Scalar>,
+    ) -> CurveBoundary<Point<1>> {
+        let end = turns.into() * Scalar::TAU;
+        CurveBoundary::from([[Scalar::ZERO], [end]])
+    }
+
+    /// # Convert a point in curve coordinates into a 3D point
+    pub fn point_from_helix_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<3> {
+        self.screw_point(self.center + self.a, point.into().t)
+    }
+
+    /// # Compute the tangent at a point on the helix
+    ///
+    /// The length of the tangent is the distance that the helix covers, per
+    /// radian.
+    pub fn tangent_at(&self, point: impl Into<Point<1>>) -> Vector<3> {
+        let axis = self.axis.normalize();
+        let radial = self.screw_vector(self.a, point.into().t);
+
+        axis * self.pitch / Scalar::TAU
+            + axis.cross(&radial) * self.direction_of_rotation()
+    }
+
+    /// # Move a point along with the helix
+    ///
+    /// Applies the screw motion that moves a point on the helix by `angle` (in
+    /// curve coordinates) along it. The point is rotated around the axis, while
+    /// being moved along it.
+    pub fn screw_point(&self, point: Point<3>, angle: Scalar) -> Point<3> {
+        let axis = self.axis.normalize();
+
+        self.center
+            + self.screw_vector(point - self.center, angle)
+            + axis * self.pitch * angle / Scalar::TAU
+    }
+
+    /// # Rotate a vector along with the helix
+    ///
+    /// Like [`Helix::screw_point`], but vectors only have a direction, and are
+    /// therefore only rotated.
+    pub fn screw_vector(&self, vector: Vector<3>, angle: Scalar) -> Vector<3> {
+        let axis = self.axis.normalize();
+
+        let along_axis = axis * vector.dot(&axis);
+        let radial = vector - along_axis;
+
+        let (sin, cos) = (angle * self.direction_of_rotation()).sin_cos();
+        along_axis + radial * cos + axis.cross(&radial) * sin
+    }
+
+    fn direction_of_rotation(&self) -> Scalar {
+        match self.handedness {
+            Handedness::RightHanded => Scalar::ONE,
+            Handedness::LeftHanded => -Scalar::ONE,
+        }
+    }
+}
+
+impl GenPolyline<3> for Helix {
+    fn origin(&self) -> Point<3> {
+        self.center + self.a
+    }
+
+    fn line_segment_at(
+        &self,
+        point_curve: Point<1>,
+        tolerance: Tolerance,
+    ) -> LineSegment<3> {
+        let params = approx_params(self, tolerance);
+
+        // Like for a circle, the closest approximation points to the provided
+        // point are at integer multiples of the increment.
+        let t = point_curve.t / params.increment();
+        let points_curve = [t.floor(), t.ceil()]
+            .map(|t| [t * params.increment()])
+            .map(Point::from);
+
+        let points = points_curve
+            .map(|point_curve| self.point_from_helix_coords(point_curve));
+
+        LineSegment {
+            points,
+            points_line: points_curve,
+        }
+    }
+
+    fn generate_polyline(
+        &self,
+        boundary: CurveBoundary<Point<1>>,
+        tolerance: Tolerance,
+    ) -> Vec<Point<1>> {
+        approx_params(self, tolerance)
+            .approx_circle(boundary)
+            .collect()
+    }
+}
+
+/// # Compute the approximation parameters for a helix
+///
+/// Viewed along its axis, a helix is a circle. Its advance along the axis is
+/// proportional to the curve coordinate, so it doesn't contribute to the
+/// distance between the helix and the chords of its approximation. This means
+/// the approximation of a circle with the same radius can be used.
+fn approx_params(helix: &Helix, tolerance: Tolerance) -> CircleApproxParams {
+    CircleApproxParams::from_radius(helix.radius(), tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        geometry::{
+            curves::tests::assert_polyline_within_tolerance, Tolerance,
+        },
+        topology::Handedness,
+    };
+
+    use super::Helix;
+
+    #[test]
+    fn point_from_helix_coords() {
+        let helix = |handedness| Helix {
+            center: Point::from([0., 0., 1.]),
+            axis: Vector::unit_z(),
+            a: Vector::from([2., 0., 0.]),
+            pitch: Scalar::from(4.),
+            handedness,
+        };
+        let quarter_turn = Point::from([Scalar::TAU / 4.]);
+
+        let right = helix(Handedness::RightHanded);
+        let left = helix(Handedness::LeftHanded);
+
+        for (helix, point_curve, expected) in [
+            (right, Point::from([0.]), [2., 0., 1.]),
+            (right, quarter_turn, [0., 2., 2.]),
+            (left, quarter_turn, [0., -2., 2.]),
+        ] {
+            let point = helix.point_from_helix_coords(point_curve);
+            assert!(
+                (point - Point::from(expected)).magnitude()
+                    < Scalar::from(1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn polyline_within_tolerance() -> anyhow::Result<()> {
+        let helix = Helix {
+            center: Point::origin(),
+            axis: Vector::unit_z(),
+            a: Vector::from([1., 0., 0.]),
+            pitch: Scalar::from(0.5),
+            handedness: Handedness::LeftHanded,
+        };
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        assert_polyline_within_tolerance(
+            &helix,
+            |point| helix.point_from_helix_coords(point),
+            helix.boundary_of_turns(2.),
+            tolerance,
+        );
+
+        Ok(())
+    }
+}
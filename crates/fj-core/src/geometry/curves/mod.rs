@@ -1,4 +1,60 @@
 //! # Geometry code specific to various types of curves
 
 pub mod circle;
+pub mod ellipse;
+pub mod helix;
 pub mod line;
+pub mod nurbs;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::geometry::{traits::GenPolyline, CurveBoundary, Tolerance};
+
+    /// # Assert that the polyline of a curve is within the tolerance
+    ///
+    /// The polyline is compared to the curve at several points within each of
+    /// its segments. The deviation is not necessarily largest in the middle of
+    /// a segment, so checking only there could miss where it is too large.
+    ///
+    /// The polyline must have points in between the boundaries, or this check
+    /// would pass trivially for a curve that isn't approximated at all.
+    pub fn assert_polyline_within_tolerance<const D: usize>(
+        curve: &impl GenPolyline<D>,
+        point_from_curve_coords: impl Fn(Point<1>) -> Point<D>,
+        boundary: impl Into<CurveBoundary<Point<1>>>,
+        tolerance: Tolerance,
+    ) {
+        let boundary = boundary.into();
+        let [start, end] = boundary.inner;
+
+        let points = [start]
+            .into_iter()
+            .chain(curve.generate_polyline(boundary, tolerance))
+            .chain([end])
+            .collect::<Vec<_>>();
+        assert!(points.len() > 2, "Expected curve to be approximated");
+
+        for segment in points.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            let [chord_start, chord_end] = [a, b].map(&point_from_curve_coords);
+            let chord = chord_end - chord_start;
+
+            for i in 1..8 {
+                let point =
+                    point_from_curve_coords(a + (b - a) * (i as f64 / 8.));
+
+                let t = ((point - chord_start).dot(&chord) / chord.dot(&chord))
+                    .max(Scalar::ZERO)
+                    .min(Scalar::ONE);
+                let deviation = (point - (chord_start + chord * t)).magnitude();
+
+                assert!(
+                    deviation <= tolerance.inner(),
+                    "Polyline deviates from curve by {deviation}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! # Geometry code specific to NURBS curves
+
+use fj_math::{LineSegment, NurbsCurve, Point, Scalar, Sign};
+
+use crate::geometry::{traits::GenPolyline, CurveBoundary, Tolerance};
+
+impl<const D: usize> GenPolyline<D> for NurbsCurve<D> {
+    fn origin(&self) -> Point<D> {
+        self.point_from_curve_coords([0.])
+    }
+
+    fn line_segment_at(
+        &self,
+        point_curve: Point<1>,
+        tolerance: Tolerance,
+    ) -> LineSegment<D> {
+        // Each knot span is subdivided into segments of equal size, separately
+        // from all others. Figure out which span the point is in, and where the
+        // closest points of its subdivision are, in units of its segments.
+        let span = self
+            .spans()
+            .filter(|[start, _]| *start <= point_curve.t)
+            .last()
+            .or_else(|| self.spans().next())
+            .expect("Curve domain is not empty; must have a span");
+
+        let [start, end] = span;
+        let increment = (end - start)
+            / Scalar::from(f64::from(num_segments(self, span, tolerance)));
+        let t = (point_curve.t - start) / increment;
+
+        let points_curve = [t.floor(), t.ceil()]
+            .map(|t| [start + t * increment])
+            .map(Point::from);
+        let points = points_curve
+            .map(|point_curve| self.point_from_curve_coords(point_curve));
+
+        LineSegment {
+            points,
+            points_line: points_curve,
+        }
+    }
+
+    fn generate_polyline(
+        &self,
+        boundary: CurveBoundary<Point<1>>,
+        tolerance: Tolerance,
+    ) -> Vec<Point<1>> {
+        let [a, b] = boundary.inner.map(|point| point.t);
+        let [min, max] = if a < b { [a, b] } else { [b, a] };
+
+        let mut points = Vec::new();
+        for span in self.spans() {
+            let [start, end] = span;
+            let num_segments = num_segments(self, span, tolerance);
+
+            for i in 0..num_segments {
+                let t = start
+                    + (end - start) * Scalar::from(f64::from(i))
+                        / Scalar::from(f64::from(num_segments));
+
+                // We can't generate a point exactly at the boundaries of the
+                // range as part of the approximation. Make sure we stay inside
+                // the range.
+                if min < t && t < max {
+                    points.push(Point::from([t]));
+                }
+            }
+        }
+
+        // The end of the last span isn't covered by the loop above.
+        let [_, end] = self.domain();
+        if min < end && end < max {
+            points.push(Point::from([end]));
+        }
+
+        if (b - a).sign() == Sign::Negative {
+            points.reverse();
+        }
+
+        points
+    }
+}
+
+/// # Compute the number of segments to subdivide a knot span into
+///
+/// The span is divided into a number of segments of equal size, doubling that
+/// number until the curve, sampled within each segment, doesn't deviate from
+/// the line between the segment's boundaries by more than the tolerance.
+///
+/// This only depends on the span itself and the tolerance, which makes the
+/// resulting approximation deterministic.
+pub(crate) fn num_segments<const D: usize>(
+    curve: &NurbsCurve<D>,
+    [start, end]: [Scalar; 2],
+    tolerance: Tolerance,
+) -> u32 {
+    // Limit the subdivision, in case the tolerance is too small to be reached
+    // with the available floating point precision.
+    const MAX_SEGMENTS: u32 = 1 << 16;
+
+    // Where to sample the curve within each segment. A single sample in the
+    // middle of a segment would miss deviations of curves that are not
+    // symmetrical within the segment.
+    const SAMPLES: [f64; 3] = [0.25, 0.5, 0.75];
+
+    let mut num_segments = 1;
+    loop {
+        let increment = (end - start) / Scalar::from(f64::from(num_segments));
+
+        let is_within_tolerance = (0..num_segments).all(|i| {
+            let segment_start = start + increment * Scalar::from(f64::from(i));
+            let [a, b] = [segment_start, segment_start + increment]
+                .map(|t| curve.point_from_curve_coords([t]));
+
+            SAMPLES.into_iter().all(|sample| {
+                let point = curve.point_from_curve_coords([
+                    segment_start + increment * sample
+                ]);
+
+                let ab = b - a;
+                let ap = point - a;
+
+                let s = if ab.magnitude() == Scalar::ZERO {
+                    Scalar::ZERO
+                } else {
+                    (ap.dot(&ab) / ab.dot(&ab))
+                        .max(Scalar::ZERO)
+                        .min(Scalar::ONE)
+                };
+
+                (ap - ab * s).magnitude() <= tolerance.inner()
+            })
+        });
+
+        if is_within_tolerance || num_segments >= MAX_SEGMENTS {
+            return num_segments;
+        }
+
+        num_segments *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use fj_math::{NurbsCurve, Point};
+
+    use crate::geometry::{
+        curves::tests::assert_polyline_within_tolerance, traits::GenPolyline,
+        CurveBoundary, Tolerance,
+    };
+
+    #[test]
+    fn polyline_within_tolerance() -> anyhow::Result<()> {
+        // Quarter circle with a radius of `1`.
+        let curve = NurbsCurve::clamped_with_weights(
+            2,
+            [[1., 0.], [1., 1.], [0., 1.]],
+            [1., FRAC_1_SQRT_2, 1.],
+        );
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        assert_polyline_within_tolerance(
+            &curve,
+            |point| curve.point_from_curve_coords(point),
+            [[0.], [1.]],
+            tolerance,
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn polyline_of_linear_curve() -> anyhow::Result<()> {
+        let curve =
+            NurbsCurve::clamped(1, [[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // A piecewise linear curve is approximated by its knots alone. In
+        // reverse, the points are generated in reverse order.
+        let [a, b] = [1. / 3., 2. / 3.].map(|t| Point::from([t]));
+        assert_eq!(
+            curve.generate_polyline(
+                CurveBoundary::from([[0.], [1.]]),
+                tolerance
+            ),
+            vec![a, b],
+        );
+        assert_eq!(
+            curve.generate_polyline(
+                CurveBoundary::from([[1.], [0.]]),
+                tolerance
+            ),
+            vec![b, a],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn curve_representation_must_be_deterministic() -> anyhow::Result<()> {
+        let curve = NurbsCurve::clamped(2, [[0., 0.], [1., 2.], [2., 0.]]);
+        let tolerance = Tolerance::from_scalar(0.1)?;
+
+        let a = curve.line_segment_at(Point::from([0.4]), tolerance);
+        let b = curve.line_segment_at(Point::from([0.45]), tolerance);
+
+        assert_eq!(
+            a, b,
+            "Expecting representation of the curve to be deterministic; it \
+            must not depend on the specific points that were sampled.",
+        );
+
+        Ok(())
+    }
+}
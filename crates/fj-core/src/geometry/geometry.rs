@@ -8,11 +8,14 @@ use crate::{
 };
 
 use super::{
-    repr::{polyline::Polyline, tri_mesh::TriMesh},
+    repr::{
+        polyline::Polyline,
+        tri_mesh::{convert_point_surface_to_global, TriMesh},
+    },
     surfaces::SweptCurve,
     traits::{GenPolyline, GenTriMesh},
     vertex::LocalVertexGeom,
-    Path, VertexGeom,
+    Path, Tolerance, VertexGeom,
 };
 
 /// Geometric data that is associated with topological objects
@@ -190,11 +193,25 @@ impl Geometry {
     ///
     /// Panics, if the geometry of the surface is not defined.
     pub fn of_surface(&self, surface: &Handle<Surface>) -> &SweptCurve {
-        self.surface
-            .get(surface)
+        self.try_of_surface(surface)
             .expect("Expected geometry of surface to be defined")
     }
 
+    /// # Access the geometry of the provided surface, if it is defined
+    ///
+    /// ## Implementation Note
+    ///
+    /// There currently is an ongoing transition to a new geometry system. Some
+    /// surfaces can only be represented by new-style geometry, meaning they
+    /// don't have any old-style geometry defined. This method can be used to
+    /// handle those surfaces gracefully.
+    pub fn try_of_surface(
+        &self,
+        surface: &Handle<Surface>,
+    ) -> Option<&SweptCurve> {
+        self.surface.get(surface)
+    }
+
     /// # Access the geometry of the provided vertex
     pub fn of_vertex(&self, vertex: &Handle<Vertex>) -> Option<&VertexGeom> {
         self.vertex.get(vertex)
@@ -252,16 +269,20 @@ pub struct CurveGeom {
     ///
     /// Having multiple redundant definitions is undesirable. However, we can't
     /// just use one global definition in 3D, as we need the local 2D
-    /// definitions to triangulate faces, and we currently don't have the tools
-    /// to project a global definition into a local context.
+    /// definitions to triangulate faces.
+    ///
+    /// Those local definitions no longer have to be written out by hand, for
+    /// every surface a curve is on. A definition on a plane can be projected
+    /// onto other planes (see [`UpdateCurveGeometry::project_from_surface`]),
+    /// and new-style global geometry can be projected onto any surface (see
+    /// [`CurveGeom2::project_onto_surface`]).
     ///
     /// Eventually, it should be possible to define the geometry of a curve
     /// once, either locally or globally, and then convert that single
-    /// definition into (other) local contexts, as needed. There currently is no
-    /// issue to track that specifically, but there is the following issue,
-    /// which is a prerequisite for making the required tooling practical:
+    /// definition into (other) local contexts, as needed, removing the need
+    /// to store the redundant definitions here.
     ///
-    /// <https://github.com/hannobraun/fornjot/issues/2118>
+    /// [`UpdateCurveGeometry::project_from_surface`]: crate::operations::geometry::UpdateCurveGeometry::project_from_surface
     pub definitions: BTreeMap<Handle<Surface>, LocalCurveGeom>,
 }
 
@@ -317,6 +338,64 @@ pub enum CurveGeom2 {
     },
 }
 
+impl CurveGeom2 {
+    /// # Project the curve onto a surface
+    ///
+    /// Derives the local representation of the curve on the provided surface.
+    /// Global curve geometry is projected directly. Curve geometry that is
+    /// local to another surface is first converted into global coordinates,
+    /// using the geometry of that surface.
+    ///
+    /// See [`Polyline::project_onto_surface`] for details.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the curve is defined on a surface whose geometry is not
+    /// defined.
+    pub fn project_onto_surface(
+        &self,
+        surface: &dyn GenTriMesh,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Polyline<2> {
+        let tolerance = tolerance.into();
+
+        match self {
+            Self::Surface {
+                geometry: polyline,
+                surface: surface_of_curve,
+                ..
+            } => {
+                let surface_of_curve = &geometry
+                    .of_surface_2(surface_of_curve)
+                    .expect("Expected geometry of surface to be defined")
+                    .generator;
+
+                let global = Polyline {
+                    points: polyline
+                        .points
+                        .iter()
+                        .map(|&point| {
+                            convert_point_surface_to_global(
+                                surface_of_curve,
+                                point,
+                                tolerance,
+                                geometry,
+                            )
+                        })
+                        .collect(),
+                    points_curve: polyline.points_curve.clone(),
+                };
+
+                global.project_onto_surface(surface, tolerance, geometry)
+            }
+            Self::Global {
+                geometry: polyline, ..
+            } => polyline.project_onto_surface(surface, tolerance, geometry),
+        }
+    }
+}
+
 /// # The representation of a surface's geometry
 ///
 /// Surface are represented by triangle meshes, their uniform intermediate
@@ -2,16 +2,24 @@
 //!
 //! See [`Path`].
 
-use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use fj_math::{
+    Circle, Ellipse, Line, NurbsCurve, Point, Scalar, Transform, Vector,
+};
 
 /// A path through surface (2D) or global (3D) space
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Path<const D: usize> {
     /// A circle
     Circle(Circle<D>),
 
+    /// An ellipse
+    Ellipse(Ellipse<D>),
+
     /// A line
     Line(Line<D>),
+
+    /// A NURBS curve
+    Nurbs(NurbsCurve<D>),
 }
 
 impl Path<2> {
@@ -64,7 +72,9 @@ impl Path<3> {
     pub fn transform(self, transform: &Transform) -> Self {
         match self {
             Self::Circle(curve) => Self::Circle(curve.transform(transform)),
+            Self::Ellipse(curve) => Self::Ellipse(curve.transform(transform)),
             Self::Line(curve) => Self::Line(curve.transform(transform)),
+            Self::Nurbs(curve) => Self::Nurbs(curve.transform(transform)),
         }
     }
 }
@@ -78,6 +88,40 @@ impl<const D: usize> Path<D> {
         Self::Circle(Circle::from_center_and_radius(center, radius))
     }
 
+    /// Build an ellipse from its center and semi-axes
+    ///
+    /// See [`Ellipse::new`].
+    pub fn ellipse(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        Self::Ellipse(Ellipse::new(center, a, b))
+    }
+
+    /// Build a circle from its center and semi-axes, or an ellipse
+    ///
+    /// Semi-axes that are computed, rather than provided exactly, are often
+    /// not precisely of the same length. [`Circle::new`] rejects those, so this
+    /// falls back to an ellipse, which represents the same shape.
+    pub fn circle_or_ellipse(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let [a, b] = [a.into(), b.into()];
+
+        // These are the same conditions that `Circle::new` checks.
+        let is_circle = a.magnitude() == b.magnitude()
+            && a.dot(&b).abs() < Scalar::from(f64::EPSILON);
+
+        if is_circle {
+            Self::Circle(Circle::new(center, a, b))
+        } else {
+            Self::Ellipse(Ellipse::new(center, a, b))
+        }
+    }
+
     /// Build a circle from the given radius
     pub fn circle_from_radius(radius: impl Into<Scalar>) -> Self {
         let radius = radius.into();
@@ -109,7 +153,9 @@ impl<const D: usize> Path<D> {
     ) -> Point<D> {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
+            Self::Ellipse(ellipse) => ellipse.point_from_ellipse_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
+            Self::Nurbs(curve) => curve.point_from_curve_coords(point),
         }
     }
 
@@ -120,7 +166,11 @@ impl<const D: usize> Path<D> {
     ) -> Vector<D> {
         match self {
             Self::Circle(circle) => circle.vector_from_circle_coords(vector),
+            Self::Ellipse(ellipse) => {
+                ellipse.vector_from_ellipse_coords(vector)
+            }
             Self::Line(line) => line.vector_from_line_coords(vector),
+            Self::Nurbs(curve) => curve.vector_from_curve_coords(vector),
         }
     }
 
@@ -129,7 +179,9 @@ impl<const D: usize> Path<D> {
     pub fn reverse(self) -> Self {
         match self {
             Self::Circle(circle) => Self::Circle(circle.reverse()),
+            Self::Ellipse(ellipse) => Self::Ellipse(ellipse.reverse()),
             Self::Line(line) => Self::Line(line.reverse()),
+            Self::Nurbs(curve) => Self::Nurbs(curve.reverse()),
         }
     }
 }
@@ -4,7 +4,12 @@ use std::iter;
 
 use fj_math::{Aabb, LineSegment, Point};
 
-use crate::geometry::{traits::GenPolyline, CurveBoundary, Tolerance};
+use crate::geometry::{
+    traits::{GenPolyline, GenTriMesh},
+    CurveBoundary, Geometry, Tolerance,
+};
+
+use super::tri_mesh::convert_point_global_to_surface;
 
 /// # A polyline, the uniform representation of curve geometry
 ///
@@ -60,6 +65,42 @@ impl<const D: usize> Polyline<D> {
     }
 }
 
+impl Polyline<3> {
+    /// # Project the polyline onto a surface
+    ///
+    /// Converts every point of the polyline into surface coordinates, using
+    /// [`convert_point_global_to_surface`]. The search for each point starts at
+    /// the previous one, which keeps the projection continuous on periodic
+    /// surfaces. The search for the first point starts at the surface origin.
+    ///
+    /// The curve coordinates of the points are preserved.
+    pub fn project_onto_surface(
+        &self,
+        surface: &dyn GenTriMesh,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Polyline<2> {
+        let tolerance = tolerance.into();
+
+        let mut start = Point::origin();
+        let points = self
+            .points
+            .iter()
+            .map(|&point| {
+                start = convert_point_global_to_surface(
+                    surface, point, start, tolerance, geometry,
+                );
+                start
+            })
+            .collect();
+
+        Polyline {
+            points,
+            points_curve: self.points_curve.clone(),
+        }
+    }
+}
+
 /// # Convert a point on a curve from curve coordinates to surface coordinates
 pub fn convert_from_curve_point<const D: usize>(
     curve: &dyn GenPolyline<D>,
@@ -70,6 +111,15 @@ pub fn convert_from_curve_point<const D: usize>(
     let tolerance = tolerance.into();
 
     let line_segment = curve.line_segment_at(point_curve, tolerance);
+
+    // The segment is degenerate, if the point is exactly on one of the points
+    // of the polyline, or if the curve requires no approximation. Either way,
+    // the segment collapses to the point we're looking for.
+    let [a, b] = line_segment.points_line;
+    if a == b {
+        return line_segment.points[0];
+    }
+
     let line = line_segment.to_line();
 
     line.point_from_line_coords(point_curve)
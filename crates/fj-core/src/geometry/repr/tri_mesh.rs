@@ -1,6 +1,6 @@
 //! # Geometric utility code based on triangle meshes
 
-use fj_math::{Point, Vector};
+use fj_math::{Point, Scalar, Vector};
 
 use crate::geometry::{traits::GenTriMesh, Geometry, Tolerance};
 
@@ -47,3 +47,78 @@ pub fn convert_vector_surface_to_global(
     );
     point - surface.origin(geometry)
 }
+
+/// # Convert a point in global coordinates to surface coordinates
+///
+/// Finds the point on the surface's triangle mesh representation that is
+/// closest to the provided global point, and returns its surface coordinates.
+/// If the global point is not on the surface, this amounts to a projection
+/// onto the surface.
+///
+/// ## Implementation Note
+///
+/// This is done iteratively, starting at `start`, by linearizing the triangle
+/// mesh around the current estimate and solving for the closest point on that
+/// linearization (Gauss-Newton). Since the triangle mesh is piecewise linear,
+/// this converges quickly, but it can only find a local solution. For surfaces
+/// that are periodic or that fold back on themselves, `start` must be close to
+/// the desired result.
+pub fn convert_point_global_to_surface(
+    surface: &dyn GenTriMesh,
+    point: impl Into<Point<3>>,
+    start: impl Into<Point<2>>,
+    tolerance: impl Into<Tolerance>,
+    geometry: &Geometry,
+) -> Point<2> {
+    const MAX_ITERATIONS: usize = 32;
+
+    let point = point.into();
+    let tolerance = tolerance.into();
+
+    // The step used to compute the derivatives numerically. Within a triangle
+    // of the mesh, the result is exact regardless of its size.
+    let h = tolerance.inner();
+
+    let mut point_surface = start.into();
+
+    for _ in 0..MAX_ITERATIONS {
+        let global = |point_surface: Point<2>| {
+            convert_point_surface_to_global(
+                surface,
+                point_surface,
+                tolerance,
+                geometry,
+            )
+        };
+
+        let current = global(point_surface);
+        let du = (global(point_surface + Vector::from([h, Scalar::ZERO]))
+            - current)
+            / h;
+        let dv = (global(point_surface + Vector::from([Scalar::ZERO, h]))
+            - current)
+            / h;
+
+        let [uu, uv, vv] = [du.dot(&du), du.dot(&dv), dv.dot(&dv)];
+        let det = uu * vv - uv * uv;
+        if det == Scalar::ZERO {
+            break;
+        }
+
+        let residual = point - current;
+        let [ru, rv] = [residual.dot(&du), residual.dot(&dv)];
+
+        let step = Vector::from([
+            (ru * vv - rv * uv) / det,
+            (rv * uu - ru * uv) / det,
+        ]);
+        point_surface += step;
+
+        let step_global = du * step.u + dv * step.v;
+        if step_global.magnitude() < tolerance.inner() * Scalar::from(1e-6) {
+            break;
+        }
+    }
+
+    point_surface
+}
@@ -0,0 +1,99 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::geometry::{traits::GenTriMesh, Geometry, Tolerance};
+
+use super::{angle_increment, grid_mesh, radial_axes, triangle_in_grid};
+
+/// # A conical surface
+///
+/// The u-coordinate of a surface point is its angle (in radians) around the
+/// axis, starting at `a`. The angle increases counter-clockwise, when looking
+/// at the surface against the direction of the axis (right-hand rule). The
+/// v-coordinate is the distance of the surface point from `center`, along the
+/// axis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Cone {
+    /// # The center of the circle at `v = 0`
+    pub center: Point<3>,
+
+    /// # The direction of the axis
+    pub axis: Vector<3>,
+
+    /// # The vector from `center` to the surface point at `u = 0`, `v = 0`
+    ///
+    /// Must be perpendicular to `axis`. Its length is the radius of the cone
+    /// at `v = 0`.
+    pub a: Vector<3>,
+
+    /// # The angle (in radians) between the axis and the surface
+    ///
+    /// If the angle is positive, the radius of the cone increases in the
+    /// direction of the axis. If it's negative, the radius decreases.
+    pub half_angle: Scalar,
+}
+
+impl Cone {
+    /// # Convert a point in surface coordinates into a 3D point
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `a` is not perpendicular to `axis`.
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let point = point.into();
+        let [a, b] = radial_axes(self.axis, self.a);
+        let (sin, cos) = point.u.sin_cos();
+
+        self.center
+            + self.axis.normalize() * point.v
+            + (a * cos + b * sin) * self.radius_at(point.v)
+    }
+
+    /// # Compute the radius of the cone at the provided v-coordinate
+    ///
+    /// Beyond the apex, the radius is negative.
+    pub fn radius_at(&self, v: Scalar) -> Scalar {
+        let (sin, cos) = self.half_angle.sin_cos();
+        self.a.magnitude() + v * sin / cos
+    }
+}
+
+impl GenTriMesh for Cone {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.center + self.a
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        // The cone is straight along the v-axis, so the triangle collapses
+        // along it. That means the approximation of the u-axis can follow the
+        // radius at this point exactly.
+        let radius = self.radius_at(point_surface.v).abs();
+
+        triangle_in_grid(
+            point_surface,
+            [angle_increment(radius, tolerance, 1.), None],
+            |point| self.point_from_surface_coords(point),
+        )
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        let radius = [boundary.min.v, boundary.max.v]
+            .map(|v| self.radius_at(v).abs())
+            .into_iter()
+            .fold(Scalar::ZERO, Scalar::max);
+
+        grid_mesh(boundary, [angle_increment(radius, tolerance, 1.), None])
+    }
+}
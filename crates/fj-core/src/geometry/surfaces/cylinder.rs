@@ -0,0 +1,83 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::geometry::{traits::GenTriMesh, Geometry, Tolerance};
+
+use super::{angle_increment, grid_mesh, radial_axes, triangle_in_grid};
+
+/// # A cylindrical surface
+///
+/// The u-coordinate of a surface point is its angle (in radians) around the
+/// axis, starting at `a`. The angle increases counter-clockwise, when looking
+/// at the surface against the direction of the axis (right-hand rule). The
+/// v-coordinate is the distance of the surface point from `center`, along the
+/// axis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Cylinder {
+    /// # The center of the circle at `v = 0`
+    pub center: Point<3>,
+
+    /// # The direction of the axis
+    pub axis: Vector<3>,
+
+    /// # The vector from `center` to the surface point at `u = 0`, `v = 0`
+    ///
+    /// Must be perpendicular to `axis`. Its length is the radius of the
+    /// cylinder.
+    pub a: Vector<3>,
+}
+
+impl Cylinder {
+    /// # Convert a point in surface coordinates into a 3D point
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `a` is not perpendicular to `axis`.
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let point = point.into();
+        let [a, b] = radial_axes(self.axis, self.a);
+        let (sin, cos) = point.u.sin_cos();
+
+        self.center
+            + self.axis.normalize() * point.v
+            + (a * cos + b * sin) * self.radius()
+    }
+
+    /// # Access the radius of the cylinder
+    pub fn radius(&self) -> Scalar {
+        self.a.magnitude()
+    }
+}
+
+impl GenTriMesh for Cylinder {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.center + self.a
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        triangle_in_grid(
+            point_surface,
+            [angle_increment(self.radius(), tolerance, 1.), None],
+            |point| self.point_from_surface_coords(point),
+        )
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        grid_mesh(
+            boundary,
+            [angle_increment(self.radius(), tolerance, 1.), None],
+        )
+    }
+}
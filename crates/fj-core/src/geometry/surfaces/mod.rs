@@ -1,8 +1,531 @@
 //! # Geometry code specific to various types of surfaces
 
+mod cone;
+mod cylinder;
+mod nurbs;
+mod revolved_curve;
+mod ruled_surface;
+mod sphere;
+mod swept_along_helix;
+mod swept_along_path;
 mod swept_curve;
+mod torus;
 mod transformed_surface;
 
 pub use self::{
-    swept_curve::SweptCurve, transformed_surface::TransformedSurface,
+    cone::Cone,
+    cylinder::Cylinder,
+    nurbs::NurbsSurface,
+    revolved_curve::RevolvedCurve,
+    ruled_surface::RuledSurface,
+    sphere::Sphere,
+    swept_along_helix::SweptAlongHelix,
+    swept_along_path::{SweepFrame, SweptAlongPath},
+    swept_curve::SweptCurve,
+    torus::Torus,
+    transformed_surface::TransformedSurface,
 };
+
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use super::Tolerance;
+
+/// # Select the triangle of a quadrilateral that contains a point
+///
+/// Surfaces that are curved along both axes can be approximated by a grid of
+/// quadrilaterals, each of which is made up of two triangles. `corners` are the
+/// corners of such a quadrilateral, in the order `[p00, p10, p01, p11]`. `s`
+/// and `t` are the coordinates of a point within the quadrilateral, each in the
+/// range `0` to `1`.
+///
+/// Returns the triangle that contains the point, as well as the barycentric
+/// coordinates of the point on that triangle.
+fn triangle_in_quad(
+    [p00, p10, p01, p11]: [Point<3>; 4],
+    [s, t]: [Scalar; 2],
+) -> (Triangle<3>, [Scalar; 3]) {
+    if s + t <= Scalar::ONE {
+        let triangle = Triangle::from([p00, p10, p01]);
+        (triangle, [Scalar::ONE - s - t, s, t])
+    } else {
+        let triangle = Triangle::from([p11, p01, p10]);
+        (
+            triangle,
+            [s + t - Scalar::ONE, Scalar::ONE - s, Scalar::ONE - t],
+        )
+    }
+}
+
+/// # Compute where a value is located within a range
+///
+/// Returns `0` for the start of the range, `1` for its end. If the range is
+/// empty, returns `0`.
+fn fraction(value: Scalar, [min, max]: [Scalar; 2]) -> Scalar {
+    if min == max {
+        return Scalar::ZERO;
+    }
+
+    (value - min) / (max - min)
+}
+
+/// # Find the cell of a grid that contains a value
+///
+/// The grid has lines at all multiples of `increment`. The cell is never empty,
+/// even if the value lies on a grid line.
+fn grid_cell(value: Scalar, increment: Scalar) -> [Scalar; 2] {
+    let min = (value / increment).floor();
+    [min, min + Scalar::ONE].map(|value| value * increment)
+}
+
+/// # Compute the lines of a grid within a range
+///
+/// The grid has lines at all multiples of `increment`. The boundaries of the
+/// range are always included.
+fn grid_points([min, max]: [Scalar; 2], increment: Scalar) -> Vec<Scalar> {
+    let mut points = vec![min];
+
+    let mut value = (min / increment).floor() * increment;
+    loop {
+        value += increment;
+        if value >= max {
+            break;
+        }
+
+        points.push(value);
+    }
+
+    points.push(max);
+    points
+}
+
+/// # The angle between points that approximate a circle of the given radius
+///
+/// For the full tolerance, this matches the approximation of circles, which
+/// means that the grid of a surface lines up with the approximation of circular
+/// edges on it, where they have the same radius.
+///
+/// Surfaces that are curved along both axes deviate from their approximation
+/// along both axes at once, so each axis only gets a share of the tolerance.
+/// `share` is that share.
+///
+/// Returns `None`, if the radius is so small that the circle requires no
+/// approximation.
+fn angle_increment(
+    radius: Scalar,
+    tolerance: Tolerance,
+    share: f64,
+) -> Option<Scalar> {
+    let tolerance = tolerance.inner() * share;
+    if radius <= tolerance {
+        return None;
+    }
+
+    let num_vertices_to_approx_full_circle = Scalar::max(
+        Scalar::PI / (Scalar::ONE - (tolerance / radius)).acos(),
+        3.,
+    )
+    .ceil();
+
+    Some(Scalar::TAU / num_vertices_to_approx_full_circle)
+}
+
+/// # The unit vectors that span the plane perpendicular to an axis
+///
+/// The first one points in the direction of `a`, the second one is rotated by a
+/// quarter turn from there, counter-clockwise around the axis.
+///
+/// ## Panics
+///
+/// Panics, if `a` is not perpendicular to `axis`.
+fn radial_axes(axis: Vector<3>, a: Vector<3>) -> [Vector<3>; 2] {
+    let axis = axis.normalize();
+    assert!(
+        a.dot(&axis).abs() <= a.magnitude() * Scalar::from(f64::EPSILON * 16.),
+        "`a` must be perpendicular to `axis`"
+    );
+    let a = (a - axis * a.dot(&axis)).normalize();
+
+    [a, axis.cross(&a)]
+}
+
+/// # Select the triangle of a grid that contains a point
+///
+/// For surfaces, whose surface coordinates are both angles or distances, and
+/// whose approximation therefore is a regular grid. The grid has lines at all
+/// multiples of the increment along either axis. If there is no increment for
+/// an axis, the surface requires no approximation along it, and a cell of unit
+/// size starting at the point is used instead. Either way, the returned triangle
+/// is never degenerate.
+fn triangle_in_grid(
+    point_surface: Point<2>,
+    increments: [Option<Scalar>; 2],
+    point_at: impl Fn([Scalar; 2]) -> Point<3>,
+) -> (Triangle<3>, [Scalar; 3]) {
+    let [[a, b], [c, d]] = [0, 1].map(|axis| {
+        let value = point_surface.coords.components[axis];
+        match increments[axis] {
+            Some(increment) => grid_cell(value, increment),
+            None => [value, value + Scalar::ONE],
+        }
+    });
+
+    let s = fraction(point_surface.u, [a, b]);
+    let t = fraction(point_surface.v, [c, d]);
+
+    let corners = [[a, c], [b, c], [a, d], [b, d]].map(point_at);
+
+    triangle_in_quad(corners, [s, t])
+}
+
+/// # Generate the points of a grid within a boundary
+///
+/// See [`triangle_in_grid`].
+fn grid_mesh(
+    boundary: Aabb<2>,
+    increments: [Option<Scalar>; 2],
+) -> Vec<Point<2>> {
+    let [points_u, points_v] = [0, 1].map(|axis| {
+        let range = [boundary.min, boundary.max]
+            .map(|point| point.coords.components[axis]);
+        match increments[axis] {
+            Some(increment) => grid_points(range, increment),
+            None => range.to_vec(),
+        }
+    });
+
+    points_u
+        .iter()
+        .flat_map(|&u| points_v.iter().map(move |&v| Point::from([u, v])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_math::{Aabb, Circle, Line, Point, Scalar, Transform, Vector};
+
+    use crate::{
+        algorithms::mass_properties::MassProperties,
+        geometry::{
+            curves::helix::Helix,
+            repr::{
+                polyline::Polyline, tri_mesh::convert_point_surface_to_global,
+            },
+            traits::GenTriMesh,
+            CurveGeom2, Path, Tolerance,
+        },
+        operations::{
+            build::{BuildFace, BuildSurface},
+            insert::Insert,
+        },
+        topology::{Face, Handedness, Surface},
+        Core,
+    };
+
+    use super::{Cone, Cylinder, NurbsSurface, Sphere, SweptCurve, Torus};
+
+    #[test]
+    fn approximation_within_tolerance() -> anyhow::Result<()> {
+        let center = Point::from([1., 2., 3.]);
+        let axis = Vector::from([0., 1., 1.]);
+        let a = Vector::from([2., 0., 0.]);
+
+        let cylinder = Cylinder { center, axis, a };
+        let cone = Cone {
+            center,
+            axis,
+            a,
+            half_angle: Scalar::from(0.3),
+        };
+        let sphere = Sphere { center, axis, a };
+        let torus = Torus {
+            center,
+            axis,
+            a,
+            minor_radius: Scalar::from(0.5),
+        };
+
+        let boundary = Aabb {
+            min: Point::from([-1., -1.]),
+            max: Point::from([2., 1.]),
+        };
+
+        check(&cylinder, boundary, |point| {
+            cylinder.point_from_surface_coords(point)
+        })?;
+        check(&cone, boundary, |point| {
+            cone.point_from_surface_coords(point)
+        })?;
+        check(&sphere, boundary, |point| {
+            sphere.point_from_surface_coords(point)
+        })?;
+        check(&torus, boundary, |point| {
+            torus.point_from_surface_coords(point)
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn nurbs_approximation_within_tolerance() -> anyhow::Result<()> {
+        // A saddle, to make sure the twist between the two axes is covered.
+        let saddle = NurbsSurface::clamped(
+            [1, 1],
+            [[[0., 0., 0.], [0., 2., 0.]], [[2., 0., 0.], [2., 2., 2.]]],
+        );
+
+        // A surface that is curved along both axes, with multiple knot spans
+        // along one of them.
+        let bump = NurbsSurface::new(
+            [2, 2],
+            [
+                [[0., 0., 0.], [0., 1., 0.], [0., 2., 0.]],
+                [[1., 0., 0.], [1., 1., 3.], [1., 2., 0.]],
+                [[2., 0., 0.], [2., 1., -1.], [2., 2., 0.]],
+                [[3., 0., 0.], [3., 1., 0.], [3., 2., 0.]],
+            ],
+            [[1., 1., 1.], [1., 2., 1.], [1., 0.5, 1.], [1., 1., 1.]],
+            [
+                [0., 0., 0., 0.5, 1., 1., 1.].map(Scalar::from).to_vec(),
+                [0., 0., 0., 1., 1., 1.].map(Scalar::from).to_vec(),
+            ],
+        );
+
+        let boundary = Aabb {
+            min: Point::from([0., 0.]),
+            max: Point::from([1., 1.]),
+        };
+
+        check(&saddle, boundary, |point| {
+            saddle.point_from_surface_coords(point)
+        })?;
+        check(&bump, boundary, |point| {
+            bump.point_from_surface_coords(point)
+        })?;
+
+        Ok(())
+    }
+
+    fn check(
+        surface: &dyn GenTriMesh,
+        boundary: Aabb<2>,
+        point_at: impl Fn(Point<2>) -> Point<3>,
+    ) -> anyhow::Result<()> {
+        let core = Core::new();
+        let geometry = &core.layers.geometry;
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let mesh = surface.generate_tri_mesh(boundary, tolerance, geometry);
+        for corner in [boundary.min, boundary.max] {
+            assert!(mesh.contains(&corner));
+        }
+
+        for i in 0..10 {
+            for j in 0..10 {
+                let [min, max] = [boundary.min, boundary.max];
+                let point = Point::from([
+                    min.u + (max.u - min.u) * f64::from(i) / 9.,
+                    min.v + (max.v - min.v) * f64::from(j) / 9.,
+                ]);
+
+                let (triangle, _) =
+                    surface.triangle_at(point, tolerance, geometry);
+                assert!(
+                    triangle.is_valid(),
+                    "Degenerate triangle at {point:?}"
+                );
+
+                let approx = convert_point_surface_to_global(
+                    surface, point, tolerance, geometry,
+                );
+                let distance = (approx - point_at(point)).magnitude();
+                assert!(
+                    distance <= tolerance.inner(),
+                    "Approximation deviates by {distance} at {point:?}"
+                );
+            }
+        }
+
+        // Points on grid lines must not end up in an empty grid cell.
+        let (triangle, _) =
+            surface.triangle_at(Point::origin(), tolerance, geometry);
+        assert!(triangle.is_valid());
+
+        Ok(())
+    }
+
+    #[test]
+    fn valid_face_on_each_surface() -> anyhow::Result<()> {
+        let center = Point::from([1., 2., 3.]);
+        let axis = Vector::from([0., 0., 2.]);
+        let a = Vector::from([0., 2., 0.]);
+        let radius = 2.;
+
+        let half_angle = 0.3_f64;
+        let minor_radius = 0.5;
+
+        // The faces span a quarter turn along `u`, and from `0` to `v` along
+        // `v`.
+        let u = PI / 2.;
+        let v = PI / 4.;
+
+        let mut core = Core::new();
+
+        let cylinder = Cylinder { center, axis, a };
+        let cone = Cone {
+            center,
+            axis,
+            a,
+            half_angle: Scalar::from(half_angle),
+        };
+        let sphere = Sphere { center, axis, a };
+        let torus = Torus {
+            center,
+            axis,
+            a,
+            minor_radius: Scalar::from(minor_radius),
+        };
+
+        let surfaces = [
+            (Surface::from_generator(cylinder, &mut core), radius * u * v),
+            (
+                Surface::from_generator(cone, &mut core),
+                u * (radius * v + half_angle.tan() * v * v / 2.)
+                    / half_angle.cos(),
+            ),
+            (
+                Surface::from_generator(sphere, &mut core),
+                radius * radius * u * v.sin(),
+            ),
+            (
+                Surface::from_generator(torus, &mut core),
+                minor_radius * u * (radius * v + minor_radius * v.sin()),
+            ),
+        ];
+
+        for (surface, expected) in surfaces {
+            let face = Face::polygon(
+                surface,
+                [[0., 0.], [u, 0.], [u, v], [0., v]],
+                &mut core,
+            )
+            .insert(&mut core);
+            core.layers.validation.take_errors()?;
+
+            let properties =
+                (&face).mass_properties(0.001, &core.layers.geometry);
+            assert!(
+                (properties.area - expected).abs() <= properties.area_error,
+                "Expected area {expected}, got {}",
+                properties.area,
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn define_surface() {
+        let mut core = Core::new();
+
+        let sphere = Sphere {
+            center: Point::origin(),
+            axis: Vector::unit_z(),
+            a: Vector::unit_x(),
+        };
+        let surface = Surface::from_generator(sphere, &mut core);
+
+        let generator = &core
+            .layers
+            .geometry
+            .of_surface_2(&surface)
+            .unwrap()
+            .generator;
+        let point = convert_point_surface_to_global(
+            generator,
+            [Scalar::ZERO, Scalar::PI / 2.],
+            core.tolerance(),
+            &core.layers.geometry,
+        );
+        assert!(
+            (point - Point::from([0., 0., 1.])).magnitude()
+                < Scalar::from(1e-9)
+        );
+    }
+
+    #[test]
+    fn project_path_onto_plane() {
+        // A plane with axes that are neither orthogonal nor normalized.
+        let plane = SweptCurve {
+            u: Path::Line(Line::from_origin_and_direction(
+                Point::from([1., 2., 3.]),
+                Vector::from([2., 0., 0.]),
+            )),
+            v: Vector::from([1., 1., 1.]),
+        };
+
+        // A circle that is offset from the plane along its normal. Projecting
+        // it must remove the offset and nothing else.
+        let normal = Vector::from([0., -1., 1.]);
+        let circle =
+            Path::<2>::Circle(Circle::from_center_and_radius([0.5, 1.], 1.));
+        let global = plane.path_to_global(&circle).unwrap();
+        let offset = global.transform(&Transform::translation(normal));
+
+        let projected = plane.project_path(&offset).unwrap();
+
+        for t in [0., 1., 2., 3.] {
+            let expected = circle.point_from_path_coords([t]);
+            let actual = projected.point_from_path_coords([t]);
+            assert!((actual - expected).magnitude() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn project_global_curve_onto_surface() -> anyhow::Result<()> {
+        let core = Core::new();
+        let geometry = &core.layers.geometry;
+        let tolerance = Tolerance::from_scalar(0.01)?;
+
+        let cylinder = Cylinder {
+            center: Point::origin(),
+            axis: Vector::unit_z(),
+            a: Vector::from([2., 0., 0.]),
+        };
+
+        // A helix on the cylinder, making more than one turn, to cover the
+        // projection across the seam of the periodic surface.
+        let helix = Helix {
+            center: Point::origin(),
+            axis: Vector::unit_z(),
+            a: Vector::from([2., 0., 0.]),
+            pitch: Scalar::ONE,
+            handedness: Handedness::RightHanded,
+        };
+        let curve = CurveGeom2::Global {
+            geometry: Polyline::from_curve(
+                &helix,
+                helix.boundary_of_turns(1.5),
+                tolerance,
+            ),
+            generator: Box::new(helix),
+        };
+
+        let projected =
+            curve.project_onto_surface(&cylinder, tolerance, geometry);
+
+        for (point, point_curve) in
+            projected.points.iter().zip(&projected.points_curve)
+        {
+            let expected =
+                Point::from([point_curve.t, point_curve.t / Scalar::TAU]);
+            assert!(
+                (*point - expected).magnitude() <= tolerance.inner(),
+                "Projected point {point:?} deviates from {expected:?}"
+            );
+        }
+
+        Ok(())
+    }
+}
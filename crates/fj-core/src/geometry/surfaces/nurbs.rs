@@ -0,0 +1,482 @@
+use fj_math::{Aabb, NurbsCurve, Point, Scalar, Triangle};
+
+use crate::geometry::{
+    curves::nurbs::num_segments, traits::GenTriMesh, Geometry, Tolerance,
+};
+
+use super::{fraction, triangle_in_quad};
+
+/// # A NURBS surface
+///
+/// A tensor-product non-uniform rational B-spline surface, defined by its
+/// degree along either axis, a grid of control points with associated weights,
+/// and a knot vector per axis.
+///
+/// The surface coordinates of a point on the surface are the parameter values
+/// of the B-spline along the u- and v-axes. The range of valid coordinates (the
+/// domain of the surface) along either axis is defined by the respective knot
+/// vector. See [`NurbsSurface::domain`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NurbsSurface {
+    /// # The curves along either axis, one per line of control points
+    ///
+    /// The control points of these curves are in homogeneous coordinates, with
+    /// the weight as the fourth component, which makes the curves themselves
+    /// non-rational. This allows evaluating the surface as a curve of curves.
+    curves: [Vec<NurbsCurve<4>>; 2],
+}
+
+impl NurbsSurface {
+    /// # Construct a NURBS surface
+    ///
+    /// `control_points` and `weights` are indexed by the position of the
+    /// control point along the u-axis first, and along the v-axis second.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - The control points must form a grid.
+    /// - There must be one weight per control point.
+    /// - All weights must be positive.
+    /// - Along either axis, the degree, number of control points, and knots
+    ///   must meet the requirements of [`NurbsCurve::new`].
+    pub fn new(
+        degree: [usize; 2],
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+        weights: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Scalar>>,
+        >,
+        knots: [Vec<Scalar>; 2],
+    ) -> Self {
+        let control_points = control_points
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let weights = weights
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let num_v = control_points.first().map_or(0, Vec::len);
+        assert!(
+            control_points.iter().all(|row| row.len() == num_v),
+            "control points must form a grid"
+        );
+        assert!(
+            weights.len() == control_points.len()
+                && weights.iter().all(|row| row.len() == num_v),
+            "must have one weight per control point"
+        );
+        assert!(
+            weights
+                .iter()
+                .flatten()
+                .all(|&weight| weight > Scalar::ZERO),
+            "weights must be positive"
+        );
+
+        let homogeneous = control_points
+            .iter()
+            .zip(&weights)
+            .map(|(points, weights)| {
+                points
+                    .iter()
+                    .zip(weights)
+                    .map(|(point, &weight)| {
+                        let [x, y, z] = (point.coords * weight).components;
+                        Point::from([x, y, z, weight])
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let curve = |axis: usize, control_points: Vec<Point<4>>| {
+            let weights = control_points
+                .iter()
+                .map(|_| Scalar::ONE)
+                .collect::<Vec<_>>();
+
+            NurbsCurve::new(
+                degree[axis],
+                control_points,
+                weights,
+                knots[axis].iter().copied(),
+            )
+        };
+
+        let along_u = (0..num_v)
+            .map(|j| curve(0, homogeneous.iter().map(|row| row[j]).collect()))
+            .collect();
+        let along_v =
+            homogeneous.into_iter().map(|row| curve(1, row)).collect();
+
+        Self {
+            curves: [along_u, along_v],
+        }
+    }
+
+    /// # Construct a non-rational NURBS surface with clamped knot vectors
+    ///
+    /// The knot vectors are uniform, except for the first and last knot, which
+    /// are repeated, so the surface starts and ends at the corner control
+    /// points. The domain of the surface is `0` to `1` along either axis.
+    pub fn clamped(
+        degree: [usize; 2],
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+    ) -> Self {
+        let control_points = control_points
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect::<Vec<_>>())
+            .collect::<Vec<Vec<Point<3>>>>();
+        let weights = control_points
+            .iter()
+            .map(|row| row.iter().map(|_| Scalar::ONE).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // A clamped curve with the same degree and number of control points
+        // has the knot vector we need along either axis.
+        let num_control_points = [
+            control_points.len(),
+            control_points.first().map_or(0, Vec::len),
+        ];
+        let knots = [0, 1].map(|axis| {
+            NurbsCurve::clamped(
+                degree[axis],
+                (0..num_control_points[axis]).map(|_| [0.]),
+            )
+            .knots()
+            .to_vec()
+        });
+
+        Self::new(degree, control_points, weights, knots)
+    }
+
+    /// # Access the degree of the surface along the u- and v-axes
+    pub fn degree(&self) -> [usize; 2] {
+        self.curves.each_ref().map(|curves| curves[0].degree())
+    }
+
+    /// # Access the domain of the surface along the provided axis
+    ///
+    /// Returns the first and last coordinate along the axis (`0` for u, `1`
+    /// for v), for which the surface is defined.
+    pub fn domain(&self, axis: usize) -> [Scalar; 2] {
+        self.curves[axis][0].domain()
+    }
+
+    /// # Access the knot spans of the surface along the provided axis
+    ///
+    /// See [`NurbsCurve::spans`].
+    pub fn spans(&self, axis: usize) -> impl Iterator<Item = [Scalar; 2]> + '_ {
+        self.curves[axis][0].spans()
+    }
+
+    /// # Convert a point in surface coordinates into a 3D point
+    ///
+    /// Points outside of the domain are computed by extending the first or
+    /// last knot span along the respective axis.
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let point = point.into();
+
+        let [x, y, z, w] = self
+            .homogeneous_iso_curve(0, point.v)
+            .point_from_curve_coords([point.u])
+            .coords
+            .components;
+
+        Point::from([x / w, y / w, z / w])
+    }
+
+    /// # Compute the curve along an axis, at a value along the other axis
+    ///
+    /// The value must be within the domain of the surface along the other
+    /// axis.
+    fn iso_curve(&self, axis: usize, value: Scalar) -> NurbsCurve<3> {
+        let curve = self.homogeneous_iso_curve(axis, value);
+
+        let (control_points, weights): (Vec<_>, Vec<_>) = curve
+            .control_points()
+            .iter()
+            .map(|point| {
+                let [x, y, z, w] = point.coords.components;
+                (Point::from([x / w, y / w, z / w]), w)
+            })
+            .unzip();
+
+        NurbsCurve::new(
+            curve.degree(),
+            control_points,
+            weights,
+            curve.knots().iter().copied(),
+        )
+    }
+
+    fn homogeneous_iso_curve(
+        &self,
+        axis: usize,
+        value: Scalar,
+    ) -> NurbsCurve<4> {
+        let [curve, ..] = self.curves[axis].as_slice() else {
+            unreachable!("Surface has control points along either axis");
+        };
+
+        let control_points = self.curves[1 - axis]
+            .iter()
+            .map(|curve| curve.point_from_curve_coords([value]))
+            .collect::<Vec<_>>();
+        let weights = control_points
+            .iter()
+            .map(|_| Scalar::ONE)
+            .collect::<Vec<_>>();
+
+        NurbsCurve::new(
+            curve.degree(),
+            control_points,
+            weights,
+            curve.knots().iter().copied(),
+        )
+    }
+}
+
+impl GenTriMesh for NurbsSurface {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.point_from_surface_coords([0., 0.])
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        let grid = Grid::new(self, tolerance);
+
+        let [[a, b], [c, d]] = [0, 1]
+            .map(|axis| grid.cell(axis, point_surface.coords.components[axis]));
+
+        let s = fraction(point_surface.u, [a, b]);
+        let t = fraction(point_surface.v, [c, d]);
+
+        let corners = [[a, c], [b, c], [a, d], [b, d]]
+            .map(|point| self.point_from_surface_coords(point));
+
+        triangle_in_quad(corners, [s, t])
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        let grid = Grid::new(self, tolerance);
+
+        let [points_u, points_v] = [0, 1].map(|axis| {
+            let [min, max] = [boundary.min, boundary.max]
+                .map(|point| point.coords.components[axis]);
+
+            let mut points = vec![min];
+            points.extend(
+                grid.lines[axis]
+                    .iter()
+                    .copied()
+                    .filter(|&line| min < line && line < max),
+            );
+            points.push(max);
+
+            points
+        });
+
+        points_u
+            .iter()
+            .flat_map(|&u| points_v.iter().map(move |&v| Point::from([u, v])))
+            .collect()
+    }
+}
+
+/// # The grid that approximates a NURBS surface
+///
+/// Each knot span along either axis is divided into a number of segments of
+/// equal size. Each cell of the resulting grid is approximated by two
+/// triangles, as per [`triangle_in_quad`].
+///
+/// Since the subdivision of a knot span applies to the whole surface along the
+/// other axis, the grid has no T-junctions, and the triangles fit together.
+struct Grid {
+    lines: [Vec<Scalar>; 2],
+}
+
+impl Grid {
+    /// # Compute the grid for the provided surface and tolerance
+    ///
+    /// The triangles deviate from the surface, due to its curvature along
+    /// either axis, and due to its twist. Each of those is allowed to take up a
+    /// third of the tolerance.
+    ///
+    /// This only depends on the surface and the tolerance, which makes the
+    /// resulting approximation deterministic.
+    fn new(surface: &NurbsSurface, tolerance: Tolerance) -> Self {
+        // Limit the subdivision, in case the tolerance is too small to be
+        // reached with the available floating point precision.
+        const MAX_SEGMENTS: u32 = 1 << 16;
+
+        let tolerance = Tolerance::from_scalar(tolerance.inner() / 3.)
+            .expect("Dividing a valid tolerance results in a valid one");
+
+        let spans = [0, 1].map(|axis| surface.spans(axis).collect::<Vec<_>>());
+
+        // Subdivide the knot spans along either axis, until the curves along
+        // that axis, at a number of positions along the other one, are within
+        // the tolerance.
+        let num_segments = [0, 1].map(|axis| {
+            let curves = samples_of_spans(&spans[1 - axis])
+                .map(|value| surface.iso_curve(axis, value))
+                .collect::<Vec<_>>();
+
+            spans[axis]
+                .iter()
+                .map(|&span| {
+                    curves
+                        .iter()
+                        .map(|curve| num_segments(curve, span, tolerance))
+                        .max()
+                        .unwrap_or(1)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        // The curves along either axis don't capture the twist of the surface.
+        // Where the corners of a cell don't lie in a plane, the center of the
+        // cell deviates from the diagonal that splits it into triangles. For a
+        // bilinear cell, that deviation is a quarter of the twist vector, and
+        // subdividing the cell along both axes by a factor of `f` reduces it by
+        // `f²`.
+        //
+        // Subdividing both axes of a cell by at least the required factor is
+        // enough. So a span needs the highest factor of all cells within it.
+        let cells = [0, 1]
+            .map(|axis| cells_of_spans(&spans[axis], &num_segments[axis]));
+        let lines = cells.each_ref().map(|cells| lines_of_cells(cells));
+        let corners = lines[0]
+            .iter()
+            .map(|&u| {
+                let curve = surface.iso_curve(1, u);
+                lines[1]
+                    .iter()
+                    .map(|&v| curve.point_from_curve_coords([v]))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut factors = spans.each_ref().map(|spans| vec![1; spans.len()]);
+        for (i, &(span_u, _)) in cells[0].iter().enumerate() {
+            for (j, &(span_v, _)) in cells[1].iter().enumerate() {
+                let twist = (corners[i][j] - corners[i + 1][j])
+                    - (corners[i][j + 1] - corners[i + 1][j + 1]);
+                let deviation = twist.magnitude() / 4.;
+
+                let mut factor = 1;
+                while deviation / Scalar::from(f64::from(factor * factor))
+                    > tolerance.inner()
+                    && factor < MAX_SEGMENTS
+                {
+                    factor *= 2;
+                }
+
+                factors[0][span_u] = factors[0][span_u].max(factor);
+                factors[1][span_v] = factors[1][span_v].max(factor);
+            }
+        }
+
+        let lines = [0, 1].map(|axis| {
+            let num_segments = num_segments[axis]
+                .iter()
+                .zip(&factors[axis])
+                .map(|(&num_segments, &factor)| {
+                    num_segments.saturating_mul(factor).min(MAX_SEGMENTS)
+                })
+                .collect::<Vec<_>>();
+
+            lines_of_cells(&cells_of_spans(&spans[axis], &num_segments))
+        });
+
+        Self { lines }
+    }
+
+    /// # Find the cell of the grid that contains a value along an axis
+    ///
+    /// Values outside of the domain of the surface are assigned to the first
+    /// or last cell.
+    fn cell(&self, axis: usize, value: Scalar) -> [Scalar; 2] {
+        let lines = &self.lines[axis];
+
+        let index = lines
+            .partition_point(|&line| line <= value)
+            .clamp(1, lines.len() - 1);
+
+        [lines[index - 1], lines[index]]
+    }
+}
+
+/// # Sample positions within knot spans
+///
+/// Returns the boundaries of the knot spans, as well as some positions between
+/// them.
+fn samples_of_spans(
+    spans: &[[Scalar; 2]],
+) -> impl Iterator<Item = Scalar> + '_ {
+    const SAMPLES: [f64; 4] = [0., 0.25, 0.5, 0.75];
+
+    spans
+        .iter()
+        .flat_map(|&[start, end]| {
+            SAMPLES
+                .into_iter()
+                .map(move |sample| start + (end - start) * sample)
+        })
+        .chain(spans.last().map(|&[_, end]| end))
+}
+
+/// # Divide knot spans into cells
+///
+/// Returns the cells, each with the index of the span that contains it.
+fn cells_of_spans(
+    spans: &[[Scalar; 2]],
+    num_segments: &[u32],
+) -> Vec<(usize, [Scalar; 2])> {
+    spans
+        .iter()
+        .zip(num_segments)
+        .enumerate()
+        .flat_map(|(span_index, (&[start, end], &num_segments))| {
+            let at = move |i: u32| {
+                start
+                    + (end - start) * Scalar::from(f64::from(i))
+                        / Scalar::from(f64::from(num_segments))
+            };
+
+            (0..num_segments).map(move |i| (span_index, [at(i), at(i + 1)]))
+        })
+        .collect()
+}
+
+/// # Collect the boundaries of a sequence of cells
+fn lines_of_cells(cells: &[(usize, [Scalar; 2])]) -> Vec<Scalar> {
+    let mut lines = cells
+        .iter()
+        .map(|&(_, [start, _])| start)
+        .collect::<Vec<_>>();
+    if let Some(&(_, [_, end])) = cells.last() {
+        lines.push(end);
+    }
+
+    lines
+}
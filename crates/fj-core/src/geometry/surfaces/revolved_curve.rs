@@ -0,0 +1,258 @@
+use fj_math::{Aabb, Line, Point, Scalar, Triangle};
+
+use crate::geometry::{
+    traits::{GenPolyline, GenTriMesh},
+    Geometry, Path, Tolerance,
+};
+
+use super::{fraction, triangle_in_quad};
+
+/// # A surface that is a curve, revolved around an axis
+///
+/// The surface uses polar coordinates: The distance of a surface point from the
+/// origin of the surface coordinate system is the curve coordinate of the point
+/// on `curve` that is being revolved. The angle of a surface point, relative to
+/// the u-axis, determines the angle by which that point is revolved around
+/// `axis`.
+///
+/// This means that a point on the curve that is located on the axis needs to
+/// have the curve coordinate zero, or the surface has a singularity there. If
+/// the curve doesn't touch the axis, this isn't a concern.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct RevolvedCurve {
+    /// The curve that is revolved
+    pub curve: Path<3>,
+
+    /// The axis that the curve is revolved around
+    ///
+    /// The curve is revolved counter-clockwise, when looking at it against the
+    /// direction of the axis (right-hand rule).
+    pub axis: Line<3>,
+
+    /// The range of angles (in radians) that the curve is revolved by
+    ///
+    /// A quarter turn in surface coordinates, from the u-axis to the v-axis,
+    /// maps to this range. Surface points outside of that quarter turn are
+    /// revolved accordingly.
+    ///
+    /// This means that the boundaries of a face that covers the range are
+    /// located exactly on the axes of the surface coordinate system, regardless
+    /// of the actual angles.
+    pub angles: [Scalar; 2],
+}
+
+impl RevolvedCurve {
+    fn point_at(&self, point_curve: Point<1>, angle: Scalar) -> Point<3> {
+        let angle = self.rotation_at(angle);
+
+        let point = self.curve.point_from_path_coords(point_curve);
+
+        let center = self
+            .axis
+            .point_from_line_coords(self.axis.point_to_line_coords(point));
+        let radius = point - center;
+        let tangent = self.axis.direction().normalize().cross(&radius);
+
+        let (sin, cos) = angle.sin_cos();
+        center + radius * cos + tangent * sin
+    }
+
+    /// The angle of the rotation around the axis at the given surface angle
+    fn rotation_at(&self, angle: Scalar) -> Scalar {
+        let [start, end] = self.angles;
+        start + (end - start) * angle / (Scalar::TAU / 4.)
+    }
+
+    fn distance_from_axis(&self, point_curve: Point<1>) -> Scalar {
+        let point = self.curve.point_from_path_coords(point_curve);
+        let center = self
+            .axis
+            .point_from_line_coords(self.axis.point_to_line_coords(point));
+
+        (point - center).magnitude()
+    }
+
+    fn angles_at(
+        &self,
+        points_curve: [Point<1>; 2],
+        angle: Scalar,
+        tolerance: Tolerance,
+    ) -> [Scalar; 2] {
+        let distance_from_axis = points_curve
+            .map(|point_curve| self.distance_from_axis(point_curve))
+            .into_iter()
+            .fold(Scalar::ZERO, Scalar::max);
+
+        let Some(increment) =
+            self.angle_increment(distance_from_axis, tolerance)
+        else {
+            return [angle; 2];
+        };
+
+        let angle = angle / increment;
+        [angle.floor(), angle.ceil()].map(|angle| angle * increment)
+    }
+
+    /// # The increment between the angles of the approximating grid
+    ///
+    /// Returns `None`, if points at the given distance from the axis don't move
+    /// far enough, when being revolved, to require approximation.
+    ///
+    /// The increment, which is in surface coordinates, always divides a quarter
+    /// turn into a power of two. This means that the grids used for different
+    /// distances from the axis line up with each other, which keeps the
+    /// approximation continuous. It also means that there are grid lines at the
+    /// boundaries of the range of `angles`.
+    fn angle_increment(
+        &self,
+        distance_from_axis: Scalar,
+        tolerance: Tolerance,
+    ) -> Option<Scalar> {
+        if distance_from_axis <= tolerance.inner() {
+            return None;
+        }
+
+        let mut increment = Scalar::TAU / 4.;
+        loop {
+            let rotation =
+                self.rotation_at(increment) - self.rotation_at(Scalar::ZERO);
+            let max_deviation =
+                distance_from_axis * (Scalar::ONE - (rotation / 2.).cos());
+
+            if max_deviation <= tolerance.inner() {
+                break;
+            }
+
+            increment /= 2.;
+        }
+
+        Some(increment)
+    }
+}
+
+impl GenTriMesh for RevolvedCurve {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.curve.origin()
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        let point_curve = Point::from([point_surface.coords.magnitude()]);
+        let angle = point_surface.v.atan2(point_surface.u);
+
+        // The surface is approximated by a grid of quadrilaterals, each of
+        // which is made up of two triangles. Let's figure out the corners of
+        // the quadrilateral that the point is in.
+        let points_curve = self
+            .curve
+            .line_segment_at(point_curve, tolerance)
+            .points_line;
+        let angles = self.angles_at(points_curve, angle, tolerance);
+
+        let [a, b] = points_curve.map(|point| point.t);
+        let [c, d] = angles;
+        let s = fraction(point_curve.t, [a, b]);
+        let t = fraction(angle, [c, d]);
+
+        let corners = [
+            (points_curve[0], c),
+            (points_curve[1], c),
+            (points_curve[0], d),
+            (points_curve[1], d),
+        ]
+        .map(|(point_curve, angle)| self.point_at(point_curve, angle));
+
+        triangle_in_quad(corners, [s, t])
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        let corners = [
+            [boundary.min.u, boundary.min.v],
+            [boundary.max.u, boundary.min.v],
+            [boundary.min.u, boundary.max.v],
+            [boundary.max.u, boundary.max.v],
+        ]
+        .map(Point::from);
+
+        let min_distance = Point::<2>::from([
+            Scalar::ZERO.max(boundary.min.u).min(boundary.max.u),
+            Scalar::ZERO.max(boundary.min.v).min(boundary.max.v),
+        ])
+        .coords
+        .magnitude();
+        let max_distance = corners
+            .map(|corner| corner.coords.magnitude())
+            .into_iter()
+            .fold(Scalar::ZERO, Scalar::max);
+
+        let contains_origin = min_distance == Scalar::ZERO;
+        let crosses_negative_u_axis = boundary.min.u < Scalar::ZERO
+            && boundary.min.v < Scalar::ZERO
+            && boundary.max.v > Scalar::ZERO;
+        let [min_angle, max_angle] =
+            if contains_origin || crosses_negative_u_axis {
+                [-Scalar::PI, Scalar::PI]
+            } else {
+                let angles = corners.map(|corner| corner.v.atan2(corner.u));
+                [
+                    angles.into_iter().fold(Scalar::PI, Scalar::min),
+                    angles.into_iter().fold(-Scalar::PI, Scalar::max),
+                ]
+            };
+
+        let points_curve = {
+            let boundary = [[min_distance], [max_distance]];
+
+            let mut points = vec![Point::from([min_distance])];
+            points.extend(
+                self.curve.generate_polyline(boundary.into(), tolerance),
+            );
+            points.push(Point::from([max_distance]));
+
+            points
+        };
+        let angles = {
+            let distance_from_axis = points_curve
+                .iter()
+                .map(|&point_curve| self.distance_from_axis(point_curve))
+                .fold(Scalar::ZERO, Scalar::max);
+
+            let mut angles = vec![min_angle];
+            if let Some(increment) =
+                self.angle_increment(distance_from_axis, tolerance)
+            {
+                let mut angle = (min_angle / increment).floor() * increment;
+                loop {
+                    angle += increment;
+                    if angle >= max_angle {
+                        break;
+                    }
+
+                    angles.push(angle);
+                }
+            }
+            angles.push(max_angle);
+
+            angles
+        };
+
+        points_curve
+            .iter()
+            .flat_map(|point_curve| {
+                angles.iter().map(|&angle| {
+                    let (sin, cos) = angle.sin_cos();
+                    Point::from([point_curve.t * cos, point_curve.t * sin])
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,171 @@
+use fj_math::{Aabb, Point, Scalar, Triangle};
+
+use crate::{
+    geometry::{
+        repr::tri_mesh::convert_point_surface_to_global, traits::GenTriMesh,
+        CurveBoundary, Geometry, Path, Tolerance,
+    },
+    storage::Handle,
+    topology::Surface,
+};
+
+use super::{fraction, grid_cell, grid_points, triangle_in_quad};
+
+/// # A surface that consists of straight lines between two curves
+///
+/// Each curve is defined by a path on another surface, and a boundary on that
+/// path. The u-coordinate of a surface point is the relative position between
+/// the start (`0`) and the end (`1`) of both boundaries. The v-coordinate is
+/// the relative position between the first (`0`) and the second (`1`) curve.
+pub struct RuledSurface {
+    /// # The surfaces that the curves are defined on
+    pub surfaces: [Handle<Surface>; 2],
+
+    /// # The paths of the curves, on their respective surfaces
+    pub paths: [Path<2>; 2],
+
+    /// # The boundaries of the curves
+    pub boundaries: [CurveBoundary<Point<1>>; 2],
+}
+
+impl RuledSurface {
+    fn point_at(
+        &self,
+        [u, v]: [Scalar; 2],
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Point<3> {
+        let [a, b] = [0, 1].map(|index| {
+            let [start, end] = self.boundaries[index].inner;
+            let point_curve = start + (end - start) * u;
+            let point_surface =
+                self.paths[index].point_from_path_coords(point_curve);
+
+            let surface = &geometry
+                .of_surface_2(&self.surfaces[index])
+                .unwrap()
+                .generator;
+            convert_point_surface_to_global(
+                surface,
+                point_surface,
+                tolerance,
+                geometry,
+            )
+        });
+
+        a + (b - a) * v
+    }
+
+    /// # The increment between the u-coordinates of the approximating grid
+    ///
+    /// Each increment divides the whole range into a power of two, which means
+    /// that the grid is the same, regardless of which part of the surface is
+    /// being approximated.
+    fn increment_u(&self, tolerance: Tolerance) -> Scalar {
+        let mut increment = Scalar::ONE;
+
+        for (path, boundary) in self.paths.iter().zip(&self.boundaries) {
+            let Path::Circle(circle) = path else {
+                continue;
+            };
+
+            let [start, end] = boundary.inner;
+            let angle = (end - start).t.abs();
+
+            while circle.radius()
+                * (Scalar::ONE - (angle * increment / 2.).cos())
+                > tolerance.inner()
+            {
+                increment /= 2.;
+            }
+        }
+
+        increment
+    }
+
+    /// # The increment between the v-coordinates of the approximating grid
+    ///
+    /// Along the v-axis, the surface is straight. But if the curves are twisted
+    /// relative to each other, so is the surface in between them. Then the two
+    /// triangles that make up a quadrilateral of the grid deviate from the
+    /// surface, and the quadrilaterals need to be smaller.
+    fn increment_v(
+        &self,
+        [a, b]: [Scalar; 2],
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Scalar {
+        let [p00, p10, p01, p11] = [
+            [a, Scalar::ZERO],
+            [b, Scalar::ZERO],
+            [a, Scalar::ONE],
+            [b, Scalar::ONE],
+        ]
+        .map(|[u, v]| self.point_at([u, v], tolerance, geometry));
+        let twist = ((p00 - p10) - (p01 - p11)).magnitude() / 4.;
+
+        let mut increment = Scalar::ONE;
+        while twist * increment > tolerance.inner() {
+            increment /= 2.;
+        }
+
+        increment
+    }
+}
+
+impl GenTriMesh for RuledSurface {
+    fn origin(&self, geometry: &Geometry) -> Point<3> {
+        let surface =
+            &geometry.of_surface_2(&self.surfaces[0]).unwrap().generator;
+        surface.origin(geometry)
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        // The surface is approximated by a grid of quadrilaterals, each of
+        // which is made up of two triangles. Let's figure out the corners of
+        // the quadrilateral that the point is in.
+        let [a, b] = grid_cell(point_surface.u, self.increment_u(tolerance));
+        let [c, d] = grid_cell(
+            point_surface.v,
+            self.increment_v([a, b], tolerance, geometry),
+        );
+
+        let s = fraction(point_surface.u, [a, b]);
+        let t = fraction(point_surface.v, [c, d]);
+
+        let corners = [[a, c], [b, c], [a, d], [b, d]]
+            .map(|point| self.point_at(point, tolerance, geometry));
+
+        triangle_in_quad(corners, [s, t])
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<Point<2>> {
+        let points_u = grid_points(
+            [boundary.min.u, boundary.max.u],
+            self.increment_u(tolerance),
+        );
+        let increment_v = points_u
+            .windows(2)
+            .map(|cell| {
+                self.increment_v([cell[0], cell[1]], tolerance, geometry)
+            })
+            .fold(Scalar::ONE, Scalar::min);
+        let points_v =
+            grid_points([boundary.min.v, boundary.max.v], increment_v);
+
+        points_u
+            .iter()
+            .flat_map(|&u| points_v.iter().map(move |&v| Point::from([u, v])))
+            .collect()
+    }
+}
@@ -0,0 +1,88 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::geometry::{traits::GenTriMesh, Geometry, Tolerance};
+
+use super::{angle_increment, grid_mesh, radial_axes, triangle_in_grid};
+
+/// # A spherical surface
+///
+/// The u-coordinate of a surface point is its longitude, the angle (in
+/// radians) around the axis, starting at `a`. The angle increases
+/// counter-clockwise, when looking at the surface against the direction of the
+/// axis (right-hand rule).
+///
+/// The v-coordinate is its latitude, the angle (in radians) between the
+/// surface point and the equator. It ranges from `-PI / 2` at the pole opposite
+/// to the axis to `PI / 2` at the pole the axis points to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Sphere {
+    /// # The center of the sphere
+    pub center: Point<3>,
+
+    /// # The direction of the axis that goes through both poles
+    pub axis: Vector<3>,
+
+    /// # The vector from `center` to the surface point at `u = 0`, `v = 0`
+    ///
+    /// Must be perpendicular to `axis`. Its length is the radius of the
+    /// sphere.
+    pub a: Vector<3>,
+}
+
+impl Sphere {
+    /// # Convert a point in surface coordinates into a 3D point
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `a` is not perpendicular to `axis`.
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let point = point.into();
+        let [a, b] = radial_axes(self.axis, self.a);
+        let (sin_u, cos_u) = point.u.sin_cos();
+        let (sin_v, cos_v) = point.v.sin_cos();
+
+        self.center
+            + ((a * cos_u + b * sin_u) * cos_v + self.axis.normalize() * sin_v)
+                * self.radius()
+    }
+
+    /// # Access the radius of the sphere
+    pub fn radius(&self) -> Scalar {
+        self.a.magnitude()
+    }
+
+    fn increments(&self, tolerance: Tolerance) -> [Option<Scalar>; 2] {
+        // Meridians and the equator are both great circles, so both axes are
+        // approximated with the same increment.
+        [angle_increment(self.radius(), tolerance, 0.5); 2]
+    }
+}
+
+impl GenTriMesh for Sphere {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.center + self.a
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        triangle_in_grid(point_surface, self.increments(tolerance), |point| {
+            self.point_from_surface_coords(point)
+        })
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        grid_mesh(boundary, self.increments(tolerance))
+    }
+}
@@ -0,0 +1,206 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::geometry::{
+    curves::helix::Helix,
+    traits::{GenPolyline, GenTriMesh},
+    CurveBoundary, Geometry, Path, Tolerance,
+};
+
+use super::{fraction, triangle_in_quad};
+
+/// # A surface that is a curve, swept along a helix
+///
+/// The u-coordinate of a surface point is the coordinate on `curve`, the
+/// v-coordinate is the coordinate on `helix`.
+///
+/// The curve rotates around the axis of the helix, while being swept, so it
+/// keeps its orientation relative to the helix. This is the motion of a thread
+/// profile that is cut along a screw.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SweptAlongHelix {
+    /// The curve that is swept, in the coordinates of a plane
+    pub curve: Path<2>,
+
+    /// The origin of the plane that `curve` is defined in
+    ///
+    /// This is where the plane is located at the start of the sweep.
+    pub origin: Point<3>,
+
+    /// The axes of the plane that `curve` is defined in
+    ///
+    /// This is how the plane is oriented at the start of the sweep.
+    pub axes: [Vector<3>; 2],
+
+    /// The helix that the curve is swept along
+    pub helix: Helix,
+
+    /// The coordinate on `helix`, where the sweep starts
+    pub start: Point<1>,
+}
+
+impl SweptAlongHelix {
+    /// # Compute the plane that the curve is located in
+    ///
+    /// Returns the origin and the axes of the plane, at the provided point on
+    /// the helix.
+    pub fn plane_at(
+        &self,
+        point_helix: impl Into<Point<1>>,
+    ) -> (Point<3>, [Vector<3>; 2]) {
+        let angle = point_helix.into().t - self.start.t;
+
+        let origin = self.helix.screw_point(self.origin, angle);
+        let axes = self.axes.map(|axis| self.helix.screw_vector(axis, angle));
+
+        (origin, axes)
+    }
+
+    fn point_at(
+        &self,
+        point_curve: Point<1>,
+        point_helix: Point<1>,
+    ) -> Point<3> {
+        let (origin, [u, v]) = self.plane_at(point_helix);
+        let point_plane = self.curve.point_from_path_coords(point_curve);
+
+        origin + u * point_plane.u + v * point_plane.v
+    }
+
+    /// # Compute the increment between points on the helix
+    ///
+    /// Returns `None`, if the curve is located on the axis of the helix, and
+    /// therefore only moves along a straight line.
+    ///
+    /// Like for [`SweptAlongPath`], the increment always divides a quarter turn
+    /// into a power of two, and the approximation starts at `start`.
+    ///
+    /// [`SweptAlongPath`]: super::SweptAlongPath
+    fn helix_increment(
+        &self,
+        points_curve: impl IntoIterator<Item = Point<1>>,
+        tolerance: Tolerance,
+    ) -> Option<Scalar> {
+        // Each point of the curve moves along a helix of its own. Its distance
+        // from the axis is the radius of the circle that this helix follows,
+        // when viewed along the axis.
+        let axis = self.helix.axis.normalize();
+        let radius = points_curve
+            .into_iter()
+            .map(|point_curve| {
+                let point = self.point_at(point_curve, self.start);
+                let radial = point - self.helix.center;
+                (radial - axis * radial.dot(&axis)).magnitude()
+            })
+            .fold(Scalar::ZERO, Scalar::max);
+
+        if radius <= tolerance.inner() {
+            return None;
+        }
+
+        let mut increment = Scalar::TAU / 4.;
+        while radius * (Scalar::ONE - (increment / 2.).cos())
+            > tolerance.inner()
+        {
+            increment /= 2.;
+        }
+
+        Some(increment)
+    }
+}
+
+impl GenTriMesh for SweptAlongHelix {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.point_at(Point::origin(), self.start)
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        let point_curve = Point::from([point_surface.u]);
+        let point_helix = Point::from([point_surface.v]);
+
+        // This works like the approximation of `SweptAlongPath`. See there
+        // for more information.
+        let [a, b] = {
+            let [a, b] = self
+                .curve
+                .line_segment_at(point_curve, tolerance)
+                .points_line
+                .map(|point| point.t);
+
+            if a == b {
+                [a, a + Scalar::ONE]
+            } else {
+                [a, b]
+            }
+        };
+        let [c, d] = match self
+            .helix_increment([a, b].map(|t| Point::from([t])), tolerance)
+        {
+            Some(increment) => {
+                let t = (point_helix.t - self.start.t) / increment;
+                [t.floor(), t.ceil()].map(|t| self.start.t + t * increment)
+            }
+            None => [point_helix.t, point_helix.t + Scalar::ONE],
+        };
+
+        let s = fraction(point_curve.t, [a, b]);
+        let t = fraction(point_helix.t, [c, d]);
+
+        let corners = [(a, c), (b, c), (a, d), (b, d)]
+            .map(|(u, v)| self.point_at(Point::from([u]), Point::from([v])));
+
+        triangle_in_quad(corners, [s, t])
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        let points_curve = {
+            let mut points = vec![Point::from([boundary.min.u])];
+            points.extend(self.curve.generate_polyline(
+                CurveBoundary::from([[boundary.min.u], [boundary.max.u]]),
+                tolerance,
+            ));
+            points.push(Point::from([boundary.max.u]));
+
+            points
+        };
+        let points_helix = {
+            let mut points = vec![boundary.min.v];
+            if let Some(increment) =
+                self.helix_increment(points_curve.iter().copied(), tolerance)
+            {
+                let mut t = self.start.t
+                    + ((boundary.min.v - self.start.t) / increment).floor()
+                        * increment;
+                loop {
+                    t += increment;
+                    if t >= boundary.max.v {
+                        break;
+                    }
+
+                    points.push(t);
+                }
+            }
+            points.push(boundary.max.v);
+
+            points
+        };
+
+        points_curve
+            .iter()
+            .flat_map(|point_curve| {
+                points_helix
+                    .iter()
+                    .map(|&t| Point::from([point_curve.t, t]))
+            })
+            .collect()
+    }
+}
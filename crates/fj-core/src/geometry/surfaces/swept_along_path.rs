@@ -0,0 +1,288 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::geometry::{
+    traits::{GenPolyline, GenTriMesh},
+    CurveBoundary, Geometry, Path, Tolerance,
+};
+
+use super::{fraction, triangle_in_quad};
+
+/// # A surface that is a curve, swept along a path that may be curved
+///
+/// The u-coordinate of a surface point is the coordinate on `curve`, the
+/// v-coordinate is the coordinate on `path`.
+///
+/// Unlike [`SweptCurve`], which only supports straight paths, the curve can be
+/// swept along a circular path. Depending on `frame`, the curve might rotate
+/// while being swept.
+///
+/// [`SweptCurve`]: super::SweptCurve
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SweptAlongPath {
+    /// The curve that is swept, in the coordinates of a plane
+    pub curve: Path<2>,
+
+    /// The origin of the plane that `curve` is defined in
+    ///
+    /// This is where the plane is located at the start of the sweep.
+    pub origin: Point<3>,
+
+    /// The axes of the plane that `curve` is defined in
+    ///
+    /// This is how the plane is oriented at the start of the sweep.
+    pub axes: [Vector<3>; 2],
+
+    /// The path that the curve is swept along
+    pub path: Path<3>,
+
+    /// The coordinate on `path`, where the sweep starts
+    pub start: Point<1>,
+
+    /// How the orientation of the curve changes along the path
+    pub frame: SweepFrame,
+}
+
+impl SweptAlongPath {
+    /// # Compute the plane that the curve is located in
+    ///
+    /// Returns the origin and the axes of the plane, at the provided point on
+    /// the path.
+    pub fn plane_at(
+        &self,
+        point_path: impl Into<Point<1>>,
+    ) -> (Point<3>, [Vector<3>; 2]) {
+        let point_path = point_path.into();
+
+        let origin = self.path.point_from_path_coords(point_path)
+            + self.rotate(self.origin - self.start_of_path(), point_path);
+        let axes = self.axes.map(|axis| self.rotate(axis, point_path));
+
+        (origin, axes)
+    }
+
+    fn point_at(
+        &self,
+        point_curve: Point<1>,
+        point_path: Point<1>,
+    ) -> Point<3> {
+        let (origin, [u, v]) = self.plane_at(point_path);
+        let point_plane = self.curve.point_from_path_coords(point_curve);
+
+        origin + u * point_plane.u + v * point_plane.v
+    }
+
+    fn start_of_path(&self) -> Point<3> {
+        self.path.point_from_path_coords(self.start)
+    }
+
+    /// # Rotate a vector from the start of the sweep to a point on the path
+    fn rotate(&self, vector: Vector<3>, point_path: Point<1>) -> Vector<3> {
+        let (Some(from), Some(to)) =
+            (self.frame_at(self.start), self.frame_at(point_path))
+        else {
+            return vector;
+        };
+
+        let [a, b, c] = from.map(|axis| vector.dot(&axis));
+        let [x, y, z] = to;
+
+        x * a + y * b + z * c
+    }
+
+    /// # Compute the frame at a point on the path
+    ///
+    /// Returns `None`, if the curve doesn't rotate while being swept.
+    fn frame_at(&self, point_path: Point<1>) -> Option<[Vector<3>; 3]> {
+        let Path::Circle(circle) = self.path else {
+            // The tangent of a line doesn't change, so neither does the frame,
+            // regardless of what kind it is.
+            return None;
+        };
+
+        let tangent = circle.vector_from_circle_coords(Vector::from([
+            point_path.t + Scalar::TAU / 4.,
+        ]));
+        let normal = match self.frame {
+            SweepFrame::Fixed => return None,
+            SweepFrame::Frenet => {
+                circle.center() - circle.point_from_circle_coords(point_path)
+            }
+            SweepFrame::Guided { normal } => normal,
+        };
+
+        let tangent = tangent.normalize();
+        let normal = (normal - tangent * normal.dot(&tangent)).normalize();
+
+        Some([tangent, normal, tangent.cross(&normal)])
+    }
+
+    /// # Compute the increment between points on the path
+    ///
+    /// Returns `None`, if the path is straight and needs no approximation.
+    ///
+    /// The increment always divides a quarter turn into a power of two, and the
+    /// approximation starts at `start`. This means that the grids used for
+    /// different parts of the curve line up with each other, which keeps the
+    /// approximation continuous.
+    fn path_increment(
+        &self,
+        points_curve: impl IntoIterator<Item = Point<1>>,
+        tolerance: Tolerance,
+    ) -> Option<Scalar> {
+        let Path::Circle(circle) = self.path else {
+            return None;
+        };
+
+        // While being swept, each point of the curve moves along the path, and
+        // might also rotate around its current position on the path. That
+        // rotation is never faster than the rotation along the path, so this is
+        // an upper bound for the radius of the circle that a point follows.
+        let start_of_path = self.start_of_path();
+        let radius = circle.radius()
+            + points_curve
+                .into_iter()
+                .map(|point_curve| {
+                    (self.point_at(point_curve, self.start) - start_of_path)
+                        .magnitude()
+                })
+                .fold(Scalar::ZERO, Scalar::max);
+
+        let mut increment = Scalar::TAU / 4.;
+        while radius * (Scalar::ONE - (increment / 2.).cos())
+            > tolerance.inner()
+        {
+            increment /= 2.;
+        }
+
+        Some(increment)
+    }
+}
+
+impl GenTriMesh for SweptAlongPath {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.point_at(Point::origin(), self.start)
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        let point_curve = Point::from([point_surface.u]);
+        let point_path = Point::from([point_surface.v]);
+
+        // The surface is approximated by a grid of quadrilaterals, each of
+        // which is made up of two triangles. Let's figure out the corners of
+        // the quadrilateral that the point is in.
+        //
+        // If the curve or path is a line, it doesn't need to be approximated.
+        // But we still need a non-degenerate quadrilateral.
+        let [a, b] = {
+            let [a, b] = self
+                .curve
+                .line_segment_at(point_curve, tolerance)
+                .points_line
+                .map(|point| point.t);
+
+            if a == b {
+                [a, a + Scalar::ONE]
+            } else {
+                [a, b]
+            }
+        };
+        let [c, d] = match self
+            .path_increment([a, b].map(|t| Point::from([t])), tolerance)
+        {
+            Some(increment) => {
+                let t = (point_path.t - self.start.t) / increment;
+                [t.floor(), t.ceil()].map(|t| self.start.t + t * increment)
+            }
+            None => [point_path.t, point_path.t + Scalar::ONE],
+        };
+
+        let s = fraction(point_curve.t, [a, b]);
+        let t = fraction(point_path.t, [c, d]);
+
+        let corners = [(a, c), (b, c), (a, d), (b, d)]
+            .map(|(u, v)| self.point_at(Point::from([u]), Point::from([v])));
+
+        triangle_in_quad(corners, [s, t])
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        let points_curve = {
+            let mut points = vec![Point::from([boundary.min.u])];
+            points.extend(self.curve.generate_polyline(
+                CurveBoundary::from([[boundary.min.u], [boundary.max.u]]),
+                tolerance,
+            ));
+            points.push(Point::from([boundary.max.u]));
+
+            points
+        };
+        let points_path = {
+            let mut points = vec![boundary.min.v];
+            if let Some(increment) =
+                self.path_increment(points_curve.iter().copied(), tolerance)
+            {
+                let mut t = self.start.t
+                    + ((boundary.min.v - self.start.t) / increment).floor()
+                        * increment;
+                loop {
+                    t += increment;
+                    if t >= boundary.max.v {
+                        break;
+                    }
+
+                    points.push(t);
+                }
+            }
+            points.push(boundary.max.v);
+
+            points
+        };
+
+        points_curve
+            .iter()
+            .flat_map(|point_curve| {
+                points_path.iter().map(|&t| Point::from([point_curve.t, t]))
+            })
+            .collect()
+    }
+}
+
+/// # How the orientation of a curve changes, while it is swept along a path
+///
+/// Along straight sections of a path, the orientation never changes. The frame
+/// only makes a difference along curved sections.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SweepFrame {
+    /// # The orientation of the curve doesn't change
+    ///
+    /// The curve is only moved along the path, but never rotated.
+    Fixed,
+
+    /// # The curve rotates with the path
+    ///
+    /// The curve's orientation relative to the tangent and the normal of the
+    /// path (the Frenet frame) stays the same.
+    Frenet,
+
+    /// # The curve rotates with the path, but stays aligned to a normal
+    ///
+    /// The curve's orientation relative to the tangent of the path stays the
+    /// same, while it is kept at the same orientation relative to the provided
+    /// normal vector.
+    ///
+    /// The normal must never be parallel to the tangent of the path.
+    Guided {
+        /// The normal that the curve stays aligned to
+        normal: Vector<3>,
+    },
+}
@@ -1,12 +1,14 @@
-use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+use fj_math::{Aabb, Ellipse, Line, Point, Scalar, Triangle, Vector};
 
 use crate::geometry::{
     traits::{GenPolyline, GenTriMesh},
     Geometry, Path, Tolerance,
 };
 
+use super::fraction;
+
 /// # A surface that is a curve, swept along a path
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct SweptCurve {
     /// The u-axis of the surface
     pub u: Path<3>,
@@ -15,6 +17,157 @@ pub struct SweptCurve {
     pub v: Vector<3>,
 }
 
+impl SweptCurve {
+    /// # Convert a path in surface coordinates into global coordinates
+    ///
+    /// If the surface's axes are not orthonormal, a circle in surface
+    /// coordinates becomes an ellipse in global coordinates.
+    ///
+    /// On curved surfaces, the image of a path is not generally a path itself.
+    /// Only lines along the axes of the surface are supported there. A line
+    /// along the v-axis is a line in global coordinates. A line along the
+    /// u-axis is a copy of the curve that the surface sweeps, if the line's
+    /// direction has unit length, so its coordinates match those of the curve.
+    ///
+    /// Returns `None`, if the path can't be converted.
+    pub fn path_to_global(&self, path: &Path<2>) -> Option<Path<3>> {
+        let Some((origin, [u, v])) = self.plane() else {
+            return self.axis_line_to_global(path);
+        };
+
+        let vector = |vector: Vector<2>| u * vector.u + v * vector.v;
+        let point = |point: Point<2>| origin + vector(point.coords);
+
+        let path = match path {
+            Path::Circle(circle) => Path::circle_or_ellipse(
+                point(circle.center()),
+                vector(circle.a()),
+                vector(circle.b()),
+            ),
+            Path::Ellipse(ellipse) => Path::Ellipse(Ellipse::new(
+                point(ellipse.center()),
+                vector(ellipse.a()),
+                vector(ellipse.b()),
+            )),
+            Path::Line(line) => Path::Line(Line::from_origin_and_direction(
+                point(line.origin()),
+                vector(line.direction()),
+            )),
+            Path::Nurbs(curve) => Path::Nurbs(curve.map_control_points(point)),
+        };
+
+        Some(path)
+    }
+
+    /// # Project a point in global coordinates into the surface
+    ///
+    /// The point is projected along the normal of the surface, which means
+    /// points that are not on the surface are projected onto the closest point
+    /// that is.
+    ///
+    /// Returns `None`, if the surface is not a plane.
+    pub fn project_point(
+        &self,
+        point: impl Into<Point<3>>,
+    ) -> Option<Point<2>> {
+        let (origin, axes) = self.plane()?;
+        let coords = project_vector(point.into() - origin, axes);
+        Some(Point { coords })
+    }
+
+    /// # Project a path in global coordinates into the surface
+    ///
+    /// The path is projected along the normal of the surface. The resulting
+    /// path has the same path coordinates as the original one.
+    ///
+    /// If the surface's axes are not orthonormal, or the circle is not parallel
+    /// to the surface, a projected circle is no longer a circle in surface
+    /// coordinates. It becomes an ellipse in that case.
+    ///
+    /// Returns `None`, if the surface is not a plane.
+    pub fn project_path(&self, path: &Path<3>) -> Option<Path<2>> {
+        let (origin, axes) = self.plane()?;
+
+        let vector = |vector: Vector<3>| project_vector(vector, axes);
+        let point = |point: Point<3>| Point {
+            coords: vector(point - origin),
+        };
+
+        let path = match path {
+            Path::Circle(circle) => Path::circle_or_ellipse(
+                point(circle.center()),
+                vector(circle.a()),
+                vector(circle.b()),
+            ),
+            Path::Ellipse(ellipse) => Path::Ellipse(Ellipse::new(
+                point(ellipse.center()),
+                vector(ellipse.a()),
+                vector(ellipse.b()),
+            )),
+            Path::Line(line) => Path::Line(Line::from_origin_and_direction(
+                point(line.origin()),
+                vector(line.direction()),
+            )),
+            Path::Nurbs(curve) => Path::Nurbs(curve.map_control_points(point)),
+        };
+
+        Some(path)
+    }
+
+    fn axis_line_to_global(&self, path: &Path<2>) -> Option<Path<3>> {
+        let Path::Line(line) = path else {
+            return None;
+        };
+        let [origin, direction] = [line.origin().coords, line.direction()];
+
+        let offset = self.v * origin.v;
+
+        if direction.u == Scalar::ZERO {
+            let origin = self.u.point_from_path_coords([origin.u]) + offset;
+            return Some(Path::Line(Line::from_origin_and_direction(
+                origin,
+                self.v * direction.v,
+            )));
+        }
+
+        if direction.v != Scalar::ZERO || direction.u.abs() != Scalar::ONE {
+            return None;
+        }
+
+        // The curve needs to start where the line does, and run in the same
+        // direction. Rotating the curve's axes by the line's origin takes care
+        // of the former, flipping the second axis takes care of the latter.
+        let (sin, cos) = origin.u.sin_cos();
+        let rotate = |[a, b]: [Vector<3>; 2]| {
+            [a * cos + b * sin, (b * cos - a * sin) * direction.u]
+        };
+
+        match &self.u {
+            Path::Circle(circle) => {
+                let [a, b] = rotate([circle.a(), circle.b()]);
+                Some(Path::circle_or_ellipse(circle.center() + offset, a, b))
+            }
+            Path::Ellipse(ellipse) => {
+                let [a, b] = rotate([ellipse.a(), ellipse.b()]);
+                Some(Path::Ellipse(Ellipse::new(
+                    ellipse.center() + offset,
+                    a,
+                    b,
+                )))
+            }
+            Path::Line(_) | Path::Nurbs(_) => None,
+        }
+    }
+
+    fn plane(&self) -> Option<(Point<3>, [Vector<3>; 2])> {
+        let Path::Line(line) = &self.u else {
+            return None;
+        };
+
+        Some((line.origin(), [line.direction(), self.v]))
+    }
+}
+
 impl GenTriMesh for SweptCurve {
     fn origin(&self, _: &Geometry) -> Point<3> {
         self.u.origin()
@@ -26,16 +179,21 @@ impl GenTriMesh for SweptCurve {
         tolerance: Tolerance,
         _: &Geometry,
     ) -> (Triangle<3>, [Scalar; 3]) {
-        let [a, b] = self
+        let segment = self
             .u
-            .line_segment_at(Point::from([point_surface.u]), tolerance)
+            .line_segment_at(Point::from([point_surface.u]), tolerance);
+
+        let [a, b] = segment
             .points
             .map(|point_global| point_global + self.v * point_surface.v);
 
         let c = a + (b - a) / 2.;
         let triangle = Triangle::from([a, b, c]);
 
-        let barycentric_coords = [1. / 3.; 3].map(Into::into);
+        // The point is on the line segment that approximates the curve. Where
+        // exactly, depends on where it is between the segment's boundaries.
+        let s = fraction(point_surface.u, segment.points_line.map(|p| p.t));
+        let barycentric_coords = [Scalar::ONE - s, s, Scalar::ZERO];
         (triangle, barycentric_coords)
     }
 
@@ -63,3 +221,16 @@ impl GenTriMesh for SweptCurve {
             .collect()
     }
 }
+
+/// # Project a vector onto the plane spanned by two axes
+///
+/// The axes don't need to be orthogonal or normalized. The projection is found
+/// by solving the normal equations of the least-squares problem.
+fn project_vector(vector: Vector<3>, [u, v]: [Vector<3>; 2]) -> Vector<2> {
+    let [uu, uv, vv] = [u.dot(&u), u.dot(&v), v.dot(&v)];
+    let [wu, wv] = [vector.dot(&u), vector.dot(&v)];
+
+    let det = uu * vv - uv * uv;
+
+    Vector::from([(wu * vv - wv * uv) / det, (wv * uu - wu * uv) / det])
+}
@@ -0,0 +1,95 @@
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::geometry::{traits::GenTriMesh, Geometry, Tolerance};
+
+use super::{angle_increment, grid_mesh, radial_axes, triangle_in_grid};
+
+/// # A toroidal surface
+///
+/// The u-coordinate of a surface point is the angle (in radians) around the
+/// axis, starting at `a`. The angle increases counter-clockwise, when looking
+/// at the surface against the direction of the axis (right-hand rule).
+///
+/// The v-coordinate is the angle (in radians) around the tube, starting at the
+/// outside of the torus, and increasing towards the direction of the axis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Torus {
+    /// # The center of the torus
+    pub center: Point<3>,
+
+    /// # The direction of the axis that goes through the hole of the torus
+    pub axis: Vector<3>,
+
+    /// # The vector from `center` to the center of the tube at `u = 0`
+    ///
+    /// Must be perpendicular to `axis`. Its length is the major radius of the
+    /// torus.
+    pub a: Vector<3>,
+
+    /// # The radius of the tube
+    pub minor_radius: Scalar,
+}
+
+impl Torus {
+    /// # Convert a point in surface coordinates into a 3D point
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `a` is not perpendicular to `axis`.
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let point = point.into();
+        let [a, b] = radial_axes(self.axis, self.a);
+        let (sin_u, cos_u) = point.u.sin_cos();
+        let (sin_v, cos_v) = point.v.sin_cos();
+
+        self.center
+            + (a * cos_u + b * sin_u)
+                * (self.major_radius() + self.minor_radius * cos_v)
+            + self.axis.normalize() * self.minor_radius * sin_v
+    }
+
+    /// # Access the major radius of the torus
+    pub fn major_radius(&self) -> Scalar {
+        self.a.magnitude()
+    }
+
+    fn increments(&self, tolerance: Tolerance) -> [Option<Scalar>; 2] {
+        [
+            angle_increment(
+                self.major_radius() + self.minor_radius,
+                tolerance,
+                0.5,
+            ),
+            angle_increment(self.minor_radius, tolerance, 0.5),
+        ]
+    }
+}
+
+impl GenTriMesh for Torus {
+    fn origin(&self, _: &Geometry) -> Point<3> {
+        self.point_from_surface_coords([0., 0.])
+    }
+
+    fn triangle_at(
+        &self,
+        point_surface: Point<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> (Triangle<3>, [Scalar; 3]) {
+        triangle_in_grid(point_surface, self.increments(tolerance), |point| {
+            self.point_from_surface_coords(point)
+        })
+    }
+
+    fn generate_tri_mesh(
+        &self,
+        boundary: Aabb<2>,
+        tolerance: Tolerance,
+        _: &Geometry,
+    ) -> Vec<Point<2>> {
+        grid_mesh(boundary, self.increments(tolerance))
+    }
+}
@@ -64,7 +64,9 @@ impl<const D: usize> GenPolyline<D> for Path<D> {
     fn origin(&self) -> Point<D> {
         match self {
             Self::Circle(circle) => circle.origin(),
+            Self::Ellipse(ellipse) => ellipse.origin(),
             Self::Line(line) => line.origin(),
+            Self::Nurbs(curve) => curve.origin(),
         }
     }
 
@@ -77,7 +79,11 @@ impl<const D: usize> GenPolyline<D> for Path<D> {
             Self::Circle(circle) => {
                 circle.line_segment_at(point_curve, tolerance)
             }
+            Self::Ellipse(ellipse) => {
+                ellipse.line_segment_at(point_curve, tolerance)
+            }
             Self::Line(line) => line.line_segment_at(point_curve, tolerance),
+            Self::Nurbs(curve) => curve.line_segment_at(point_curve, tolerance),
         }
     }
 
@@ -90,7 +96,11 @@ impl<const D: usize> GenPolyline<D> for Path<D> {
             Self::Circle(circle) => {
                 circle.generate_polyline(boundary, tolerance)
             }
+            Self::Ellipse(ellipse) => {
+                ellipse.generate_polyline(boundary, tolerance)
+            }
             Self::Line(line) => line.generate_polyline(boundary, tolerance),
+            Self::Nurbs(curve) => curve.generate_polyline(boundary, tolerance),
         }
     }
 }
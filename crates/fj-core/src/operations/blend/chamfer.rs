@@ -0,0 +1,568 @@
+use std::collections::BTreeMap;
+
+use fj_math::{Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{LocalVertexGeom, Path},
+    operations::{
+        build::BuildSurface,
+        derive::DeriveFrom,
+        geometry::UpdateCurveGeometry,
+        insert::Insert,
+        presentation::{GetColor, SetColor},
+        update::UpdateSolid,
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+use super::{
+    blend_edges, half_edges_by_shell, path_of, plane_of, position_of,
+    shell_containing, update_faces, Blend, Plane,
+};
+
+/// # Bevel edges and vertices with a chamfer
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Chamfer {
+    /// # Chamfer the provided edges
+    ///
+    /// Each edge is replaced by a planar face, which cuts off the material
+    /// between the two faces that meet at the edge. An edge can be identified by
+    /// either of its half-edges, but the choice makes a difference, if the size
+    /// of the chamfer is not symmetric. See [`ChamferSize`]. Where three
+    /// chamfered edges meet, the corner is cut off by a triangular face.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if any of the edges can't be found in the object, or if any of
+    /// them can't be chamfered. See [module documentation] for the limitations
+    /// of the current implementation.
+    ///
+    /// [module documentation]: super
+    #[must_use]
+    fn chamfer_edges(
+        &self,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        size: ChamferSize,
+        core: &mut Core,
+    ) -> Self;
+
+    /// # Chamfer the provided vertices
+    ///
+    /// Each vertex is replaced by a triangular face, which cuts through the
+    /// edges that meet at the vertex, at the provided distance from it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if any of the vertices can't be found in the object, if not
+    /// exactly three planar faces meet at any of them, or if any of the edges
+    /// that meet at a vertex are not straight or shorter than the distance.
+    #[must_use]
+    fn chamfer_vertices(
+        &self,
+        vertices: impl IntoIterator<Item = Handle<Vertex>>,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self;
+}
+
+impl Chamfer for Shell {
+    fn chamfer_edges(
+        &self,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        size: ChamferSize,
+        core: &mut Core,
+    ) -> Self {
+        blend_edges(self, half_edges, &size, core)
+    }
+
+    fn chamfer_vertices(
+        &self,
+        vertices: impl IntoIterator<Item = Handle<Vertex>>,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self {
+        let distance = distance.into();
+
+        let mut shell = self.clone();
+        for vertex in vertices {
+            shell = chamfer_vertex(&shell, &vertex, distance, core);
+        }
+
+        shell
+    }
+}
+
+impl Chamfer for Solid {
+    fn chamfer_edges(
+        &self,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        size: ChamferSize,
+        core: &mut Core,
+    ) -> Self {
+        let mut solid = self.clone();
+        for (shell, half_edges) in half_edges_by_shell(self, half_edges) {
+            solid = solid.update_shell(
+                &shell,
+                |shell, core| [shell.chamfer_edges(half_edges, size, core)],
+                core,
+            );
+        }
+
+        solid
+    }
+
+    fn chamfer_vertices(
+        &self,
+        vertices: impl IntoIterator<Item = Handle<Vertex>>,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self {
+        let distance = distance.into();
+
+        let mut solid = self.clone();
+        for vertex in vertices {
+            let shell =
+                shell_containing(&solid, |h| h.start_vertex() == &vertex);
+
+            solid = solid.update_shell(
+                &shell,
+                |shell, core| {
+                    [shell.chamfer_vertices([vertex], distance, core)]
+                },
+                core,
+            );
+        }
+
+        solid
+    }
+}
+
+/// # The size of a chamfer
+#[derive(Clone, Copy, Debug)]
+pub enum ChamferSize {
+    /// # The same distance from the edge, along either face
+    Distance(Scalar),
+
+    /// # Different distances from the edge, along each face
+    ///
+    /// The first distance is measured along the face that the provided
+    /// half-edge belongs to, the second along the face of its sibling.
+    Distances([Scalar; 2]),
+
+    /// # A distance from the edge, and the angle of the chamfer
+    ///
+    /// The distance is measured along the face that the provided half-edge
+    /// belongs to. The angle (in radians) is measured between that face and the
+    /// chamfer.
+    DistanceAndAngle {
+        /// The distance from the edge, along the face of the half-edge
+        distance: Scalar,
+
+        /// The angle between the face of the half-edge and the chamfer
+        angle: Scalar,
+    },
+}
+
+impl ChamferSize {
+    /// # Compute the distances from the edge, along either face
+    ///
+    /// Requires the angle between the two faces, as measured through the
+    /// material that is cut off.
+    fn distances(&self, angle_between_faces: Scalar) -> [Scalar; 2] {
+        match *self {
+            Self::Distance(distance) => [distance; 2],
+            Self::Distances(distances) => distances,
+            Self::DistanceAndAngle { distance, angle } => {
+                // The edge and the two ends of the chamfer form a triangle.
+                // The rest follows from the law of sines.
+                let remaining_angle = Scalar::PI - angle_between_faces - angle;
+                assert!(
+                    remaining_angle > Scalar::ZERO,
+                    "Chamfer angle is too large for the edge"
+                );
+
+                [distance, distance * angle.sin() / remaining_angle.sin()]
+            }
+        }
+    }
+}
+
+impl Blend for ChamferSize {
+    fn cross_section(
+        &self,
+        point: Point<3>,
+        [a, b]: [Vector<3>; 2],
+    ) -> (Path<3>, [Point<1>; 2]) {
+        let [distance_a, distance_b] = self.distances(a.angle_to(&b));
+        let [start, end] = [(a, distance_a), (b, distance_b)]
+            .map(|(direction, distance)| point + direction * distance);
+
+        Path::line_from_points([start, end])
+    }
+
+    fn corner(
+        &self,
+        points: [Point<3>; 3],
+        _: [Vector<3>; 3],
+        core: &mut Core,
+    ) -> (Handle<Surface>, [(Path<2>, [Point<1>; 2]); 3]) {
+        let (surface, points_surface) =
+            Surface::plane_from_points(points, core);
+        let coords = [0., 1.].map(|coord| Point::from([coord]));
+
+        let paths = [0, 1, 2].map(|index| {
+            let [start, end] =
+                [index, (index + 1) % 3].map(|index| points_surface[index]);
+
+            (
+                Path::line_from_points_with_coords([
+                    (coords[0], start),
+                    (coords[1], end),
+                ]),
+                coords,
+            )
+        });
+
+        (surface, paths)
+    }
+}
+
+fn chamfer_vertex(
+    shell: &Shell,
+    vertex: &Handle<Vertex>,
+    distance: Scalar,
+    core: &mut Core,
+) -> Shell {
+    // Each of the faces that meet at the vertex has a half-edge that starts
+    // there.
+    let mut corners = Vec::new();
+    for face in shell.faces() {
+        for cycle in face.region().all_cycles() {
+            for half_edge in cycle.half_edges() {
+                if half_edge.start_vertex() == vertex {
+                    let predecessor = cycle
+                        .half_edges()
+                        .before(half_edge)
+                        .expect("Half-edge must be part of its cycle");
+                    let end = cycle
+                        .half_edges()
+                        .after(half_edge)
+                        .expect("Half-edge must be part of its cycle")
+                        .start_vertex();
+
+                    corners.push(Corner {
+                        face: face.clone(),
+                        plane: plane_of(face.surface(), core),
+                        half_edge: half_edge.clone(),
+                        predecessor: predecessor.clone(),
+                        end: end.clone(),
+                    });
+                }
+            }
+        }
+    }
+    assert_eq!(
+        corners.len(),
+        3,
+        "Chamfering vertices is only supported, if exactly three faces meet \
+        there"
+    );
+
+    // Each edge that meets at the vertex gets a new vertex, where the chamfer
+    // cuts through it.
+    let mut cuts = BTreeMap::new();
+    for corner in &corners {
+        let curve = corner.half_edge.curve();
+        let Path::Line(line) = path_of(curve, corner.face.surface(), core)
+        else {
+            panic!("Only vertices between straight edges can be chamfered");
+        };
+
+        let [start, end] = [vertex, &corner.end]
+            .map(|vertex| position_of(vertex, curve, core));
+        let [start_point, end_point] = [start, end].map(|position| {
+            corner.plane.point(line.point_from_line_coords(position))
+        });
+
+        let length = (end_point - start_point).magnitude();
+        assert!(
+            distance < length,
+            "Chamfer doesn't fit on the edges next to the vertex"
+        );
+
+        let position = start + (end - start) * (distance / length);
+        let cut = Vertex::new().insert(core);
+        core.layers.geometry.define_vertex(
+            cut.clone(),
+            curve.clone(),
+            LocalVertexGeom { position },
+        );
+
+        cuts.insert(
+            curve.clone(),
+            (
+                cut,
+                position,
+                corner.plane.point(line.point_from_line_coords(position)),
+            ),
+        );
+    }
+
+    // The triangle that replaces the vertex has one edge in each face. Each of
+    // those edges starts where the face's half-edge is cut, and ends where its
+    // predecessor is cut. Let's sort the corners, so the triangle's edges
+    // connect.
+    let mut sorted = vec![corners.remove(0)];
+    while !corners.is_empty() {
+        let previous = &sorted[sorted.len() - 1];
+        let index = corners
+            .iter()
+            .position(|corner| {
+                corner.half_edge.curve() == previous.predecessor.curve()
+            })
+            .expect("Faces around chamfered vertex must be connected");
+        sorted.push(corners.remove(index));
+    }
+    let corners = sorted;
+
+    let triangle_surface = {
+        let [a, b, c] = [0, 1, 2].map(|index| {
+            let (_, _, point) = &cuts[corners[index].half_edge.curve()];
+            *point
+        });
+
+        Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(a, b - a)),
+            c - a,
+            core,
+        )
+    };
+    let points_triangle = [[0., 0.], [1., 0.], [0., 1.]].map(Point::from);
+
+    let mut replacements = BTreeMap::new();
+    let mut half_edges_triangle = Vec::new();
+
+    for (index, corner) in corners.iter().enumerate() {
+        let [(start, start_position, _), (end, end_position, _)] =
+            [corner.predecessor.curve(), corner.half_edge.curve()]
+                .map(|curve| &cuts[curve]);
+
+        let points_face = [
+            (corner.predecessor.curve(), start_position),
+            (corner.half_edge.curve(), end_position),
+        ]
+        .map(|(curve, position)| {
+            path_of(curve, corner.face.surface(), core)
+                .point_from_path_coords(*position)
+        });
+
+        let curve = Curve::new().insert(core);
+        for (vertex, position) in [(start, 0.), (end, 1.)] {
+            core.layers.geometry.define_vertex(
+                vertex.clone(),
+                curve.clone(),
+                LocalVertexGeom {
+                    position: Point::from([position]),
+                },
+            );
+        }
+
+        let curve = curve
+            .make_path_on_surface(
+                Path::line_from_points_with_coords([
+                    ([0.], points_face[0]),
+                    ([1.], points_face[1]),
+                ]),
+                corner.face.surface().clone(),
+                &mut core.layers.geometry,
+            )
+            .make_path_on_surface(
+                Path::line_from_points_with_coords([
+                    ([0.], points_triangle[(index + 1) % 3]),
+                    ([1.], points_triangle[index]),
+                ]),
+                triangle_surface.clone(),
+                &mut core.layers.geometry,
+            );
+
+        replacements.insert(
+            corner.half_edge.clone(),
+            vec![
+                HalfEdge::new(curve.clone(), start.clone()).insert(core),
+                HalfEdge::new(corner.half_edge.curve().clone(), end.clone())
+                    .insert(core)
+                    .derive_from(&corner.half_edge, core),
+            ],
+        );
+        half_edges_triangle
+            .push(HalfEdge::new(curve, end.clone()).insert(core));
+    }
+
+    let mut faces = update_faces(shell, &replacements, &BTreeMap::new(), core);
+
+    let triangle = {
+        let exterior = Cycle::new(half_edges_triangle).insert(core);
+        let region = Region::new(exterior, []).insert(core);
+        if let Some(color) = corners[0].face.region().get_color(core) {
+            region.set_color(color, core);
+        }
+
+        Face::new(triangle_surface, region).insert(core)
+    };
+    faces.push(triangle);
+
+    Shell::new(faces)
+}
+
+/// # One of the faces that meet at a chamfered vertex
+struct Corner {
+    face: Handle<Face>,
+    plane: Plane,
+
+    /// # The half-edge of the face that starts at the vertex
+    half_edge: Handle<HalfEdge>,
+
+    /// # The half-edge of the face that ends at the vertex
+    predecessor: Handle<HalfEdge>,
+
+    /// # The vertex where `half_edge` ends
+    end: Handle<Vertex>,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        operations::{
+            blend::tests::{all_edges, half_edge_between, vertex_at},
+            insert::Insert,
+            tests::{assert_volume, cuboid},
+        },
+        Core,
+    };
+
+    use super::{Chamfer, ChamferSize};
+
+    #[test]
+    fn chamfer_vertical_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let half_edges =
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]].map(|[x, y]| {
+                half_edge_between(&cuboid, [[x, y, 0.], [x, y, -1.]], &core)
+            });
+
+        let solid = cuboid
+            .chamfer_edges(
+                half_edges,
+                ChamferSize::Distance(Scalar::from(0.5)),
+                &mut core,
+            )
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 10);
+        core.layers.validation.take_errors()?;
+
+        // Each chamfer removes a triangle from the cross section.
+        assert_volume(&solid, Scalar::from(4. - 0.125 * 4.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_horizontal_edge_with_distance_and_angle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let half_edge =
+            half_edge_between(&cuboid, [[0., 2., 0.], [2., 2., 0.]], &core);
+
+        // With the faces at a right angle, the other distance is the tangent of
+        // the angle, times the provided distance.
+        let solid = cuboid
+            .chamfer_edges(
+                [half_edge],
+                ChamferSize::DistanceAndAngle {
+                    distance: Scalar::from(0.25),
+                    angle: Scalar::from(2_f64.atan()),
+                },
+                &mut core,
+            )
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 7);
+        core.layers.validation.take_errors()?;
+
+        assert_volume(&solid, Scalar::from(4. - 0.0625 * 2.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_vertices() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let vertices = [0., -1.]
+            .into_iter()
+            .flat_map(|z| {
+                [[0., 0.], [2., 0.], [2., 2.], [0., 2.]].map(|[x, y]| [x, y, z])
+            })
+            .map(|point| vertex_at(&cuboid, point, &core))
+            .collect::<Vec<_>>();
+
+        let solid = cuboid
+            .chamfer_vertices(vertices, 0.25, &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 14);
+        core.layers.validation.take_errors()?;
+
+        // Each chamfer removes a tetrahedron.
+        let removed = 0.25_f64.powi(3) / 6.;
+        assert_volume(&solid, Scalar::from(4. - removed * 8.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_all_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let half_edges =
+            all_edges().map(|points| half_edge_between(&cuboid, points, &core));
+
+        let distance: f64 = 0.25;
+        let solid = cuboid
+            .chamfer_edges(
+                half_edges,
+                ChamferSize::Distance(Scalar::from(distance)),
+                &mut core,
+            )
+            .insert(&mut core);
+
+        // The original faces, one per edge, and one per corner.
+        assert_eq!(solid.shells().only().faces().len(), 6 + 12 + 8);
+        core.layers.validation.take_errors()?;
+
+        // Along each edge, except where it reaches into a corner, the chamfer
+        // removes a triangle from the cross section. Of the cube at each
+        // corner, only a tetrahedron remains.
+        let length_of_edges = 4. * (2. + 2. + 1.) - distance * 24.;
+        let removed = length_of_edges * distance * distance / 2.
+            + distance.powi(3) * 5. / 6. * 8.;
+        assert_volume(&solid, Scalar::from(4. - removed), &mut core)?;
+
+        Ok(())
+    }
+}
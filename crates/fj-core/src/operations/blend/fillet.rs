@@ -0,0 +1,286 @@
+use fj_math::{Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{surfaces::RevolvedCurve, Path},
+    operations::{build::BuildSurface, update::UpdateSolid},
+    storage::Handle,
+    topology::{HalfEdge, Shell, Solid, Surface},
+    Core,
+};
+
+use super::{blend_edges, half_edges_by_shell, Blend};
+
+/// # Round edges with a fillet
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait FilletEdge {
+    /// # Fillet the provided edges with the provided radius
+    ///
+    /// Each edge is replaced by a cylindrical face, which is tangent to both
+    /// faces that meet at the edge. An edge can be identified by either of its
+    /// half-edges. Where three filleted edges meet, the corner is filled by a
+    /// spherical face.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if any of the edges can't be found in the object, or if any of
+    /// them can't be filleted. See [module documentation] for the limitations
+    /// of the current implementation.
+    ///
+    /// [module documentation]: super
+    #[must_use]
+    fn fillet_edges(
+        &self,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self;
+}
+
+impl FilletEdge for Shell {
+    fn fillet_edges(
+        &self,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self {
+        let fillet = Fillet {
+            radius: radius.into(),
+        };
+
+        blend_edges(self, half_edges, &fillet, core)
+    }
+}
+
+impl FilletEdge for Solid {
+    fn fillet_edges(
+        &self,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Self {
+        let radius = radius.into();
+
+        let mut solid = self.clone();
+        for (shell, half_edges) in half_edges_by_shell(self, half_edges) {
+            solid = solid.update_shell(
+                &shell,
+                |shell, core| [shell.fillet_edges(half_edges, radius, core)],
+                core,
+            );
+        }
+
+        solid
+    }
+}
+
+struct Fillet {
+    radius: Scalar,
+}
+
+impl Blend for Fillet {
+    fn cross_section(
+        &self,
+        point: Point<3>,
+        [a, b]: [Vector<3>; 2],
+    ) -> (Path<3>, [Point<1>; 2]) {
+        let radius = self.radius;
+
+        let angle = a.angle_to(&b);
+        assert!(
+            angle > Scalar::ZERO && angle < Scalar::PI,
+            "Can't fillet edge between parallel faces"
+        );
+
+        // The fillet is tangent to both faces, which means its center is on the
+        // bisector of the angle between them.
+        let half_angle = angle / 2.;
+        let distance = radius * half_angle.cos() / half_angle.sin();
+        let center = point + (a + b).normalize() * (radius / half_angle.sin());
+
+        let [start, end] = [a, b].map(|direction| point + direction * distance);
+        let a = (start - center).normalize() * radius;
+        let b = {
+            let b = end - center;
+            (b - a * (b.dot(&a) / a.dot(&a))).normalize() * radius
+        };
+
+        let coords = [Scalar::ZERO, Scalar::PI - angle]
+            .map(|coord| Point::from([coord]));
+
+        (Path::circle_or_ellipse(center, a, b), coords)
+    }
+
+    fn corner(
+        &self,
+        points: [Point<3>; 3],
+        normals: [Vector<3>; 3],
+        core: &mut Core,
+    ) -> (Handle<Surface>, [(Path<2>, [Point<1>; 2]); 3]) {
+        // The corner is filled by the part of a sphere, that is tangent to all
+        // three faces. Where it touches the faces, it also touches the fillets
+        // of the edges, but only if the faces are perpendicular to each other.
+        let center = points[0] - normals[0] * self.radius;
+        for (point, normal) in points.into_iter().zip(normals) {
+            let distance = (center + normal * self.radius - point).magnitude();
+            assert!(
+                distance < core.layers.validation.config.identical_max_distance,
+                "Only corners between perpendicular faces can be filleted"
+            );
+        }
+
+        // The sphere is a quarter circle, revolved by a quarter turn around
+        // the axis through its first point. Revolving counter-clockwise takes
+        // the second point to the third, as the points are ordered
+        // counter-clockwise around the outside of the sphere.
+        let [a, b, _] = points.map(|point| point - center);
+        let surface = Surface::from_generator(
+            RevolvedCurve {
+                curve: Path::circle_or_ellipse(center, a, b),
+                axis: Line::from_origin_and_direction(center, a),
+                angles: [Scalar::ZERO, Scalar::PI / 2.],
+            },
+            core,
+        );
+
+        // In the polar coordinates of the surface, the boundary runs out from
+        // the origin along the u-axis, along a circle to the v-axis, and back.
+        let quarter = Scalar::PI / 2.;
+        let [origin, on_u_axis, on_v_axis] = [[0., 0.], [1., 0.], [0., 1.]]
+            .map(|direction| {
+                Point::origin() + Vector::from(direction) * quarter
+            });
+        let [start, end] = [Scalar::ZERO, quarter].map(|t| Point::from([t]));
+
+        let paths = [
+            (
+                Path::line_from_points_with_coords([
+                    (start, origin),
+                    (end, on_u_axis),
+                ]),
+                [start, end],
+            ),
+            (Path::circle_from_radius(quarter), [start, end]),
+            (
+                Path::line_from_points_with_coords([
+                    (start, origin),
+                    (end, on_v_axis),
+                ]),
+                [end, start],
+            ),
+        ];
+
+        (surface, paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::mass_properties::MassProperties,
+        geometry::Tolerance,
+        operations::{
+            blend::tests::{all_edges, half_edge_between},
+            insert::Insert,
+            tests::{assert_volume, cuboid},
+        },
+        Core,
+    };
+
+    use super::FilletEdge;
+
+    #[test]
+    fn fillet_vertical_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let half_edges =
+            [[0., 0.], [2., 0.], [2., 2.], [0., 2.]].map(|[x, y]| {
+                half_edge_between(&cuboid, [[x, y, 0.], [x, y, -1.]], &core)
+            });
+
+        let solid = cuboid
+            .fillet_edges(half_edges, 0.5, &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 10);
+        core.layers.validation.take_errors()?;
+
+        // Each fillet removes a square, minus a quarter circle, from the cross
+        // section.
+        let removed = (Scalar::ONE - Scalar::PI / 4.) * 0.25;
+        assert_volume(&solid, Scalar::from(4.) - removed * 4., &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_horizontal_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let half_edge =
+            half_edge_between(&cuboid, [[0., 2., 0.], [2., 2., 0.]], &core);
+
+        let solid = cuboid
+            .fillet_edges([half_edge], 0.5, &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 7);
+        core.layers.validation.take_errors()?;
+
+        let removed = (Scalar::ONE - Scalar::PI / 4.) * 0.25 * 2.;
+        assert_volume(&solid, Scalar::from(4.) - removed, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_all_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let half_edges =
+            all_edges().map(|points| half_edge_between(&cuboid, points, &core));
+
+        let radius = 0.25;
+        let solid = cuboid
+            .fillet_edges(half_edges, radius, &mut core)
+            .insert(&mut core);
+
+        // The original faces, one per edge, and one per corner.
+        assert_eq!(solid.shells().only().faces().len(), 6 + 12 + 8);
+        core.layers.validation.take_errors()?;
+
+        // Along each edge, except where it reaches into a corner, the fillet
+        // removes a square, minus a quarter circle, from the cross section. At
+        // each corner, it removes a cube, minus an eighth of a sphere.
+        let radius = Scalar::from(radius);
+        let length_of_edges = Scalar::from(4. * (2. + 2. + 1.)) - radius * 24.;
+        let removed = length_of_edges
+            * (Scalar::ONE - Scalar::PI / 4.)
+            * radius
+            * radius
+            + (Scalar::ONE - Scalar::PI / 6.) * radius * radius * radius * 8.;
+        let expected = Scalar::from(4.) - removed;
+
+        // Faces are triangulated using only the points on their boundaries,
+        // which doesn't approximate the corners well. The error bound of the
+        // volume accounts for that.
+        let properties = solid.mass_properties(
+            Tolerance::from_scalar(0.001)?,
+            &core.layers.geometry,
+        );
+        assert!(
+            (properties.volume - expected).abs() <= properties.volume_error,
+            "Unexpected volume: {} (expected {expected})",
+            properties.volume,
+        );
+
+        Ok(())
+    }
+}
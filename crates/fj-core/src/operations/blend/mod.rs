@@ -0,0 +1,1103 @@
+//! # Blend edges and vertices of shapes
+//!
+//! Blending an edge replaces it with a new face, which connects the two faces
+//! that meet at the edge. Those two faces are trimmed back accordingly, and the
+//! faces at either end of the edge each gain a new edge, where they meet the
+//! new face. The new face is either rounded ([`FilletEdge`]) or flat
+//! ([`Chamfer`]).
+//!
+//! Where three blended edges meet, a new face fills the corner between them.
+//! This makes it possible to blend all edges of a box, for example.
+//!
+//! Vertices can be chamfered too, which replaces them with a triangular face.
+//!
+//! ## Limitations
+//!
+//! Only straight edges between planar faces can be blended. At either end of
+//! the edge, exactly three faces must meet. If the edge is the only blended
+//! edge there, the third face must be a plane that is perpendicular to the
+//! edge. Otherwise, all three edges that meet there must be blended, and for
+//! fillets, the three faces must be perpendicular to each other.
+
+mod chamfer;
+mod fillet;
+
+pub use self::{
+    chamfer::{Chamfer, ChamferSize},
+    fillet::FilletEdge,
+};
+
+use std::collections::BTreeMap;
+
+use fj_math::{Line, Point, Scalar, Transform, Vector, Winding};
+
+use crate::{
+    geometry::{LocalVertexGeom, Path},
+    queries::{BoundingVerticesOfHalfEdge, SiblingOfHalfEdge},
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+use super::{
+    build::BuildSurface,
+    derive::DeriveFrom,
+    geometry::UpdateCurveGeometry,
+    insert::Insert,
+    plane::Plane,
+    presentation::{GetColor, SetColor},
+};
+
+/// # The shape of a blend
+///
+/// Implemented by the different kinds of blends, to define the faces that
+/// replace the blended edges, and those that fill the corners between them.
+trait Blend {
+    /// # Compute the cross-section of the face that replaces an edge
+    ///
+    /// Called with the start point of the edge, and with the unit vectors that
+    /// point from there into either of the two faces that meet at the edge,
+    /// perpendicular to it. The first of those faces is the one that the
+    /// provided half-edge belongs to. Must return the path of the new face's
+    /// cross-section at that point, as well as the coordinates on that path
+    /// where it meets either face. The second coordinate must be larger than the
+    /// first.
+    fn cross_section(
+        &self,
+        point: Point<3>,
+        directions: [Vector<3>; 2],
+    ) -> (Path<3>, [Point<1>; 2]);
+
+    /// # Create the surface of the face that fills a corner
+    ///
+    /// A corner is a vertex, where three blended edges meet. The face that
+    /// fills it touches each of the three faces that meet there in one point.
+    /// `points` are those points, in the order in which the boundary of the new
+    /// face connects them, counter-clockwise when looking at its front.
+    /// `normals` are the unit normals of the faces that they are located on.
+    ///
+    /// Must return the surface, as well as, for each point, the path on that
+    /// surface that connects it to the next point, together with the
+    /// coordinates of both points on that path. The faces that replace the
+    /// edges end in a line in their surface coordinates, and each path must
+    /// follow the same curve in global coordinates, with its coordinates
+    /// changing proportionally.
+    fn corner(
+        &self,
+        points: [Point<3>; 3],
+        normals: [Vector<3>; 3],
+        core: &mut Core,
+    ) -> (Handle<Surface>, [(Path<2>, [Point<1>; 2]); 3]);
+}
+
+/// # Blend the edges that the provided half-edges belong to
+///
+/// The half-edges might have been replaced by an earlier operation, but as long
+/// as their curves and start vertices are still around, they can be found. If
+/// both half-edges of an edge are provided, the first one counts.
+///
+/// All edges are blended at once. At a vertex where only one of them ends, the
+/// third face that meets there gains a new edge. At a vertex where all of the
+/// edges that meet there are blended, a new face fills the corner.
+fn blend_edges(
+    shell: &Shell,
+    half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+    blend: &impl Blend,
+    core: &mut Core,
+) -> Shell {
+    let mut edges: Vec<Edge> = Vec::new();
+    for half_edge in half_edges {
+        let is_duplicate = edges
+            .iter()
+            .any(|edge| edge.sides[0].half_edge.curve() == half_edge.curve());
+        if !is_duplicate {
+            edges.push(Edge::new(shell, &half_edge, core));
+        }
+    }
+
+    let mut num_blended_edges = BTreeMap::new();
+    for edge in &edges {
+        for vertex in edge.vertices() {
+            *num_blended_edges.entry(vertex.clone()).or_insert(0) += 1;
+        }
+    }
+    let is_corner = |vertex: &Handle<Vertex>| match num_blended_edges[vertex] {
+        1 => false,
+        3 => true,
+        _ => panic!(
+            "Edges that share a vertex can only be blended, if all edges that \
+            meet there are blended"
+        ),
+    };
+
+    let cross_sections = edges
+        .iter()
+        .map(|edge| blend.cross_section(edge.points[0], edge.directions))
+        .collect::<Vec<_>>();
+
+    // The faces at the plain ends of the edges might be defined on surfaces
+    // with arbitrary coordinate systems, in which the cross-sections can't be
+    // represented. Those faces get new surfaces, with an orthonormal coordinate
+    // system.
+    let mut surfaces = BTreeMap::new();
+    for (edge, (path, coords)) in edges.iter().zip(&cross_sections) {
+        let offset = edge.points[1] - edge.points[0];
+
+        for (index, end) in edge.ends.iter().enumerate() {
+            if is_corner(&edge.vertices()[index]) {
+                continue;
+            }
+
+            let path = path
+                .clone()
+                .transform(&Transform::translation(offset * index as f64));
+            let normal = end.plane.normal();
+
+            for coord in coords {
+                let point = path.point_from_path_coords(*coord);
+                let distance = (point - end.point).dot(&normal).abs();
+                assert!(
+                    distance
+                        < core.layers.validation.config.identical_max_distance,
+                    "Faces at the ends of a blended edge must be perpendicular \
+                    to it"
+                );
+            }
+
+            if !surfaces.contains_key(&end.face) {
+                let surface = orthonormal_surface(&end.face, core);
+                surfaces.insert(end.face.clone(), surface);
+            }
+        }
+    }
+    let surface_of = |face: &Handle<Face>| {
+        surfaces.get(face).unwrap_or(face.surface()).clone()
+    };
+
+    // At a corner, the faces that replace two edges of the same face meet at
+    // the point where their boundaries within that face intersect.
+    let mut boundaries = BTreeMap::new();
+    for (edge, (path, coords)) in edges.iter().zip(&cross_sections) {
+        let offset = edge.points[1] - edge.points[0];
+
+        for vertex in edge.vertices().into_iter().filter(&is_corner) {
+            for (side, coord) in edge.sides.iter().zip(coords) {
+                boundaries
+                    .entry((vertex.clone(), side.face.clone()))
+                    .or_insert_with(Vec::new)
+                    .push(Line::from_origin_and_direction(
+                        path.point_from_path_coords(*coord),
+                        offset,
+                    ));
+            }
+        }
+    }
+    let mut corner_vertices = BTreeMap::new();
+    for (key, lines) in boundaries {
+        let [a, b] = lines.as_slice() else {
+            unreachable!("Two blended edges of a face meet at a corner");
+        };
+        let point = intersect_lines(*a, *b);
+
+        corner_vertices.insert(key, (Vertex::new().insert(core), point));
+    }
+
+    // The curves where the faces that fill the corners meet the faces that
+    // replace the edges, indexed by corner vertex and edge.
+    let mut corner_curves = BTreeMap::new();
+    let mut corner_faces = Vec::new();
+    for vertex in num_blended_edges.keys().filter(|v| is_corner(v)) {
+        // Each edge contributes one piece of the corner face's boundary. It
+        // runs opposite to the boundary of the face that replaces the edge,
+        // between the points where that face meets either side.
+        let mut pieces = Vec::new();
+        for (index, edge) in edges.iter().enumerate() {
+            if edge.vertices()[0] == *vertex {
+                pieces.push((index, [1, 0]));
+            }
+            if edge.vertices()[1] == *vertex {
+                pieces.push((index, [0, 1]));
+            }
+        }
+        let face =
+            |(index, side): (usize, usize)| &edges[index].sides[side].face;
+
+        let mut sorted = vec![pieces.remove(0)];
+        while !pieces.is_empty() {
+            let (previous, [_, to]) = sorted[sorted.len() - 1];
+            let index = pieces
+                .iter()
+                .position(|&(index, [from, _])| {
+                    face((index, from)) == face((previous, to))
+                })
+                .expect("Faces around a corner must be connected");
+            sorted.push(pieces.remove(index));
+        }
+        let pieces: [_; 3] = sorted
+            .try_into()
+            .expect("Three blended edges meet at a corner");
+
+        let corner_vertex = |(index, side): (usize, usize)| {
+            &corner_vertices[&(vertex.clone(), face((index, side)).clone())]
+        };
+        let points = pieces.map(|(index, [from, _])| {
+            let (_, point) = corner_vertex((index, from));
+            *point
+        });
+        let normals =
+            pieces.map(|(index, [from, _])| edges[index].normals[from]);
+
+        let (surface, paths) = blend.corner(points, normals, core);
+
+        let mut half_edges = Vec::new();
+        for ((index, sides), (path, coords)) in pieces.into_iter().zip(paths) {
+            let curve = Curve::new().insert(core);
+
+            for (side, position) in sides.into_iter().zip(coords) {
+                let (vertex, _) = corner_vertex((index, side));
+                core.layers.geometry.define_vertex(
+                    vertex.clone(),
+                    curve.clone(),
+                    LocalVertexGeom { position },
+                );
+            }
+
+            let curve = curve.make_path_on_surface(
+                path,
+                surface.clone(),
+                &mut core.layers.geometry,
+            );
+
+            let (start_vertex, _) = corner_vertex((index, sides[0]));
+            half_edges.push(
+                HalfEdge::new(curve.clone(), start_vertex.clone()).insert(core),
+            );
+            corner_curves.insert((vertex.clone(), index), (curve, coords));
+        }
+
+        let exterior = Cycle::new(half_edges).insert(core);
+        let region = Region::new(exterior, []).insert(core);
+        if let Some(color) = face((pieces[0].0, 0)).region().get_color(core) {
+            region.set_color(color, core);
+        }
+
+        corner_faces.push(Face::new(surface, region).insert(core));
+    }
+
+    // The replacements for the half-edges of the blended edges, and the
+    // vertices at which the trimmed edges next to them now start.
+    let mut blended = BTreeMap::new();
+    let mut trimmed = BTreeMap::new();
+
+    // The new half-edges of the faces at the plain ends of the blended edges,
+    // indexed by the half-edge that they precede.
+    let mut insertions = BTreeMap::new();
+
+    let mut blend_faces = Vec::new();
+
+    for (index, (edge, (path, coords))) in
+        edges.iter().zip(cross_sections).enumerate()
+    {
+        let [p, q] = edge.points;
+        let offset = q - p;
+        let paths = [
+            path.clone(),
+            path.clone().transform(&Transform::translation(offset)),
+        ];
+
+        // The new face sweeps the cross-section along the edge. Its surface
+        // coordinates are the coordinates on the path, and the relative
+        // position along the edge.
+        let blend_surface = Surface::from_uv(path, offset, core);
+
+        // Where the new face meets the two faces that are trimmed back,
+        // indexed by end of the edge, then by side.
+        let mut trims = Vec::new();
+        for (end_index, vertex) in edge.vertices().into_iter().enumerate() {
+            let mut trims_at_end = Vec::new();
+
+            for (side, coord) in edge.sides.iter().zip(coords) {
+                let surface = surface_of(&side.face);
+
+                let trim = if is_corner(&vertex) {
+                    let (vertex, point) =
+                        &corner_vertices[&(vertex.clone(), side.face.clone())];
+                    let v = (*point - p).dot(&offset) / offset.dot(&offset);
+
+                    Trim {
+                        vertex: vertex.clone(),
+                        point_face: plane_of(&surface, core)
+                            .project_point(*point),
+                        point_blend: Point::from([coord.t, v]),
+                    }
+                } else {
+                    let adjacent = &side.adjacent[end_index];
+                    let position = side.trim(
+                        adjacent,
+                        paths[end_index].point_from_path_coords(coord),
+                        &surface,
+                        shell,
+                        core,
+                    );
+
+                    let trim = Trim {
+                        vertex: Vertex::new().insert(core),
+                        point_face: path_of(adjacent.curve(), &surface, core)
+                            .point_from_path_coords(position),
+                        point_blend: Point::from([
+                            coord.t,
+                            Scalar::from(end_index as f64),
+                        ]),
+                    };
+
+                    core.layers.geometry.define_vertex(
+                        trim.vertex.clone(),
+                        adjacent.curve().clone(),
+                        LocalVertexGeom { position },
+                    );
+                    trimmed.insert(
+                        (vertex.clone(), adjacent.curve().clone()),
+                        trim.vertex.clone(),
+                    );
+
+                    trim
+                };
+
+                trims_at_end.push(trim);
+            }
+
+            // For a fillet, where the new face meets a corner is a circle,
+            // which requires both of its ends to be at precisely the same
+            // position along the edge. Computing them separately might not
+            // achieve that.
+            if let [a, b] = trims_at_end.as_mut_slice() {
+                let distance = (a.point_blend.v - b.point_blend.v).abs()
+                    * offset.magnitude();
+                if distance
+                    < core.layers.validation.config.identical_max_distance
+                {
+                    b.point_blend.v = a.point_blend.v;
+                }
+            }
+
+            trims.push(trims_at_end);
+        }
+
+        // The curves where the new face meets the two faces that are trimmed
+        // back.
+        let mut side_curves = Vec::new();
+        for (side_index, side) in edge.sides.iter().enumerate() {
+            let [start, end] = [&trims[0][side_index], &trims[1][side_index]];
+            assert!(
+                start.point_blend.v < end.point_blend.v,
+                "Blend doesn't fit on the blended edge"
+            );
+
+            let curve = Curve::new().insert(core);
+            for (trim, position) in [(start, 0.), (end, 1.)] {
+                core.layers.geometry.define_vertex(
+                    trim.vertex.clone(),
+                    curve.clone(),
+                    LocalVertexGeom {
+                        position: Point::from([position]),
+                    },
+                );
+            }
+
+            let curve = curve
+                .make_path_on_surface(
+                    Path::line_from_points_with_coords([
+                        ([0.], start.point_face),
+                        ([1.], end.point_face),
+                    ]),
+                    surface_of(&side.face),
+                    &mut core.layers.geometry,
+                )
+                .make_path_on_surface(
+                    Path::line_from_points_with_coords([
+                        ([0.], start.point_blend),
+                        ([1.], end.point_blend),
+                    ]),
+                    blend_surface.clone(),
+                    &mut core.layers.geometry,
+                );
+
+            // Each side's half-edge starts at the end with the same index.
+            blended.insert(
+                side.half_edge.clone(),
+                (curve.clone(), trims[side_index][side_index].vertex.clone()),
+            );
+            side_curves.push(curve);
+        }
+
+        // The curves where the new face meets the faces at the ends of the
+        // edge, or the faces that fill the corners there.
+        let mut end_curves = Vec::new();
+        for (end_index, (end, vertex)) in
+            edge.ends.iter().zip(edge.vertices()).enumerate()
+        {
+            let trims = &trims[end_index];
+
+            let curve = if is_corner(&vertex) {
+                let (curve, positions) = &corner_curves[&(vertex, index)];
+                let sides = if end_index == 0 { [1, 0] } else { [0, 1] };
+
+                curve.clone().make_path_on_surface(
+                    Path::line_from_points_with_coords([
+                        (positions[0], trims[sides[0]].point_blend),
+                        (positions[1], trims[sides[1]].point_blend),
+                    ]),
+                    blend_surface.clone(),
+                    &mut core.layers.geometry,
+                )
+            } else {
+                let surface = surface_of(&end.face);
+                let plane = plane_of(&surface, core);
+
+                let curve = Curve::new().insert(core);
+                for (trim, position) in trims.iter().zip(coords) {
+                    core.layers.geometry.define_vertex(
+                        trim.vertex.clone(),
+                        curve.clone(),
+                        LocalVertexGeom { position },
+                    );
+                }
+
+                let v = Scalar::from(end_index as f64);
+                let curve = curve
+                    .make_path_on_surface(
+                        plane.project_path(&paths[end_index]),
+                        surface,
+                        &mut core.layers.geometry,
+                    )
+                    .make_path_on_surface(
+                        Path::line_from_points_with_coords(
+                            coords.map(|coord| (coord, [coord.t, v])),
+                        ),
+                        blend_surface.clone(),
+                        &mut core.layers.geometry,
+                    );
+
+                // The half-edge of the end face, that starts at the edge, now
+                // starts at the new curve.
+                insertions.insert(
+                    end.half_edge.clone(),
+                    HalfEdge::new(
+                        curve.clone(),
+                        trims[1 - end_index].vertex.clone(),
+                    )
+                    .insert(core),
+                );
+
+                curve
+            };
+
+            end_curves.push(curve);
+        }
+
+        let half_edges = [
+            (&side_curves[0], &trims[1][0].vertex),
+            (&end_curves[0], &trims[0][0].vertex),
+            (&side_curves[1], &trims[0][1].vertex),
+            (&end_curves[1], &trims[1][1].vertex),
+        ]
+        .map(|(curve, start_vertex)| {
+            HalfEdge::new(curve.clone(), start_vertex.clone()).insert(core)
+        });
+
+        let exterior = Cycle::new(half_edges).insert(core);
+        let region = Region::new(exterior, []).insert(core);
+        if let Some(color) = edge.sides[0].face.region().get_color(core) {
+            region.set_color(color, core);
+        }
+
+        blend_faces.push(Face::new(blend_surface, region).insert(core));
+    }
+
+    let mut replacements = BTreeMap::new();
+    for face in shell.faces() {
+        for cycle in face.region().all_cycles() {
+            for half_edge in cycle.half_edges() {
+                let (curve, start_vertex) =
+                    if let Some(replacement) = blended.get(half_edge) {
+                        replacement.clone()
+                    } else if let Some(vertex) = trimmed.get(&(
+                        half_edge.start_vertex().clone(),
+                        half_edge.curve().clone(),
+                    )) {
+                        (half_edge.curve().clone(), vertex.clone())
+                    } else {
+                        continue;
+                    };
+
+                let mut half_edges = insertions
+                    .get(half_edge)
+                    .cloned()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                half_edges.push(
+                    HalfEdge::new(curve, start_vertex)
+                        .insert(core)
+                        .derive_from(half_edge, core),
+                );
+
+                replacements.insert(half_edge.clone(), half_edges);
+            }
+        }
+    }
+
+    let mut faces = update_faces(shell, &replacements, &surfaces, core);
+    faces.extend(blend_faces);
+    faces.extend(corner_faces);
+
+    Shell::new(faces)
+}
+
+/// # Where the face that replaces an edge meets one of the trimmed faces
+struct Trim {
+    vertex: Handle<Vertex>,
+
+    /// # The position of the vertex on the trimmed face's surface
+    point_face: Point<2>,
+
+    /// # The position of the vertex on the new face's surface
+    point_blend: Point<2>,
+}
+
+/// # Replace half-edges and surfaces of the faces of a shell
+///
+/// Each half-edge that is a key in `replacements` is replaced with the
+/// half-edges it maps to. Each face that is a key in `surfaces` gets the
+/// surface it maps to. Faces that are not affected by any replacement are kept
+/// as they are.
+fn update_faces(
+    shell: &Shell,
+    replacements: &BTreeMap<Handle<HalfEdge>, Vec<Handle<HalfEdge>>>,
+    surfaces: &BTreeMap<Handle<Face>, Handle<Surface>>,
+    core: &mut Core,
+) -> Vec<Handle<Face>> {
+    let mut faces = Vec::new();
+    for face in shell.faces() {
+        let surface = surfaces.get(face);
+        let is_affected = surface.is_some()
+            || face.region().all_cycles().any(|cycle| {
+                cycle
+                    .half_edges()
+                    .iter()
+                    .any(|half_edge| replacements.contains_key(half_edge))
+            });
+
+        if !is_affected {
+            faces.push(face.clone());
+            continue;
+        }
+
+        let mut cycles = Vec::new();
+        for cycle in face.region().all_cycles() {
+            let half_edges = cycle
+                .half_edges()
+                .iter()
+                .flat_map(|half_edge| {
+                    replacements
+                        .get(half_edge)
+                        .cloned()
+                        .unwrap_or_else(|| vec![half_edge.clone()])
+                })
+                .collect::<Vec<_>>();
+
+            cycles.push(
+                Cycle::new(half_edges).insert(core).derive_from(cycle, core),
+            );
+        }
+
+        let mut cycles = cycles.into_iter();
+        let exterior =
+            cycles.next().expect("Region must have an exterior cycle");
+        let region = Region::new(exterior, cycles)
+            .insert(core)
+            .derive_from(face.region(), core);
+        if let Some(color) = face.region().get_color(core) {
+            region.set_color(color, core);
+        }
+
+        let surface = surface.unwrap_or(face.surface()).clone();
+        faces.push(
+            Face::new(surface, region)
+                .insert(core)
+                .derive_from(face, core),
+        );
+    }
+
+    faces
+}
+
+/// # Group the provided half-edges by the shell of the solid they belong to
+///
+/// ## Panics
+///
+/// Panics, if any of the half-edges can't be found in the solid.
+fn half_edges_by_shell(
+    solid: &Solid,
+    half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+) -> BTreeMap<Handle<Shell>, Vec<Handle<HalfEdge>>> {
+    let mut by_shell = BTreeMap::new();
+    for half_edge in half_edges {
+        let shell = shell_containing(solid, |h| h.curve() == half_edge.curve());
+        by_shell
+            .entry(shell)
+            .or_insert_with(Vec::new)
+            .push(half_edge);
+    }
+
+    by_shell
+}
+
+/// # Find the shell of a solid that contains a matching half-edge
+///
+/// ## Panics
+///
+/// Panics, if no half-edge of the solid matches.
+fn shell_containing(
+    solid: &Solid,
+    is_match: impl Fn(&Handle<HalfEdge>) -> bool,
+) -> Handle<Shell> {
+    solid
+        .shells()
+        .iter()
+        .find(|shell| {
+            shell.faces().iter().any(|face| {
+                face.region()
+                    .all_cycles()
+                    .any(|cycle| cycle.half_edges().iter().any(&is_match))
+            })
+        })
+        .expect("Blended object must be part of the solid")
+        .clone()
+}
+
+/// # The edge that is blended, and its surroundings
+struct Edge {
+    /// # The start and end points of the edge
+    ///
+    /// This is the direction of the half-edge of the first side.
+    points: [Point<3>; 2],
+
+    /// # Unit vectors pointing from the edge into each side, perpendicular to it
+    directions: [Vector<3>; 2],
+
+    /// # The unit normals of the faces on each side
+    normals: [Vector<3>; 2],
+
+    /// # The faces that meet at the edge
+    sides: [Side; 2],
+
+    /// # The faces at either end of the edge
+    ends: [End; 2],
+}
+
+impl Edge {
+    fn new(shell: &Shell, half_edge: &Handle<HalfEdge>, core: &Core) -> Self {
+        // If the half-edge has been replaced, we can still find its curve.
+        // Unless its start vertex has been replaced too, that's enough to tell
+        // it apart from its sibling.
+        let mut candidates = Vec::new();
+        for face in shell.faces() {
+            for cycle in face.region().all_cycles() {
+                for h in cycle.half_edges() {
+                    if h.curve() == half_edge.curve() {
+                        candidates.push((
+                            face.clone(),
+                            cycle.clone(),
+                            h.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        let index = candidates
+            .iter()
+            .position(|(_, _, h)| h.start_vertex() == half_edge.start_vertex())
+            .unwrap_or_default();
+        assert!(
+            index < candidates.len(),
+            "Blended edge must be part of the shell"
+        );
+        let (face, cycle, half_edge) = candidates.swap_remove(index);
+
+        let sibling = shell
+            .get_sibling_of(&half_edge)
+            .expect("Blended edge must have a sibling");
+
+        let sides = [
+            Side::new(face, &cycle, half_edge, 0, core),
+            Side::new(sibling.face, &sibling.cycle, sibling.sibling, 1, core),
+        ];
+
+        let points = sides.each_ref().map(|side| {
+            let position = position_of(
+                side.half_edge.start_vertex(),
+                side.half_edge.curve(),
+                core,
+            );
+            let path =
+                path_of(side.half_edge.curve(), side.face.surface(), core);
+            let Path::Line(_) = path else {
+                panic!("Only straight edges can be blended");
+            };
+
+            side.plane.point(path.point_from_path_coords(position))
+        });
+
+        let [p, q] = points;
+        let direction = (q - p).normalize();
+        let normals = sides.each_ref().map(|side| {
+            // The exterior of a face winds counter-clockwise around its
+            // normal, so this is the normal of the face, not the plane.
+            let is_ccw = side.face.region().exterior().winding(
+                core.tolerance(),
+                &core.layers.geometry,
+                side.face.surface(),
+            ) == Winding::Ccw;
+            let normal = side.plane.normal();
+
+            if is_ccw {
+                normal
+            } else {
+                -normal
+            }
+        });
+        let directions = [
+            normals[0].cross(&direction).normalize(),
+            normals[1].cross(&-direction).normalize(),
+        ];
+
+        let ends = [0, 1].map(|index| {
+            // At this end, the predecessor of one side's half-edge and the
+            // successor of the other side's half-edge are both connected to
+            // the third face.
+            let [of_predecessor, of_successor] = [
+                &sides[index].adjacent[index],
+                &sides[1 - index].adjacent[index],
+            ]
+            .map(|half_edge| {
+                shell
+                    .get_sibling_of(half_edge)
+                    .expect("Edges next to blended edge must have siblings")
+            });
+
+            assert!(
+                of_predecessor.face.id() == of_successor.face.id()
+                    && of_predecessor
+                        .cycle
+                        .half_edges()
+                        .after(&of_successor.sibling)
+                        == Some(&of_predecessor.sibling),
+                "Blending edges is only supported, if exactly three faces meet \
+                at either end"
+            );
+
+            End {
+                plane: plane_of(of_predecessor.face.surface(), core),
+                point: points[index],
+                face: of_predecessor.face,
+                half_edge: of_predecessor.sibling,
+            }
+        });
+
+        Self {
+            points,
+            directions,
+            normals,
+            sides,
+            ends,
+        }
+    }
+
+    /// # The vertices at the start and end of the edge
+    fn vertices(&self) -> [Handle<Vertex>; 2] {
+        self.sides
+            .each_ref()
+            .map(|side| side.half_edge.start_vertex().clone())
+    }
+}
+
+/// # One of the faces that meet at the blended edge
+struct Side {
+    face: Handle<Face>,
+    plane: Plane,
+
+    /// # The half-edge of the blended edge that is part of this face
+    half_edge: Handle<HalfEdge>,
+
+    /// # The half-edges of this face that are adjacent to the blended edge
+    ///
+    /// Indexed by the end of the edge that they are adjacent to.
+    adjacent: [Handle<HalfEdge>; 2],
+}
+
+impl Side {
+    fn new(
+        face: Handle<Face>,
+        cycle: &Cycle,
+        half_edge: Handle<HalfEdge>,
+        index: usize,
+        core: &Core,
+    ) -> Self {
+        let plane = plane_of(face.surface(), core);
+
+        // The half-edge of the first side starts at the start of the edge, the
+        // half-edge of the second side at its end.
+        let [predecessor, successor] = [
+            cycle.half_edges().before(&half_edge),
+            cycle.half_edges().after(&half_edge),
+        ]
+        .map(|half_edge| {
+            half_edge
+                .expect("Half-edge must be part of its cycle")
+                .clone()
+        });
+        let adjacent = if index == 0 {
+            [predecessor, successor]
+        } else {
+            [successor, predecessor]
+        };
+
+        Self {
+            face,
+            plane,
+            half_edge,
+            adjacent,
+        }
+    }
+
+    /// # Compute the position of a trimmed end of an adjacent half-edge
+    ///
+    /// `surface` is the surface that the face is going to be defined on. It
+    /// must be the face's current surface, or one that replaces it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the position is not within the boundaries of the half-edge.
+    fn trim(
+        &self,
+        half_edge: &Handle<HalfEdge>,
+        point: Point<3>,
+        surface: &Handle<Surface>,
+        shell: &Shell,
+        core: &Core,
+    ) -> Point<1> {
+        let Path::Line(line) = path_of(half_edge.curve(), surface, core) else {
+            panic!("Edges next to blended edge must be straight");
+        };
+
+        let plane = plane_of(surface, core);
+        let origin = plane.point(line.origin());
+        let direction = plane.vector(line.direction());
+        let position = Point::from([
+            (point - origin).dot(&direction) / direction.dot(&direction)
+        ]);
+
+        let [a, b] = shell
+            .bounding_vertices_of_half_edge(half_edge)
+            .expect("Half-edge must be part of the shell")
+            .inner
+            .map(|vertex| position_of(&vertex, half_edge.curve(), core));
+        assert!(
+            position > a.min(b) && position < a.max(b),
+            "Blend doesn't fit on the edges next to the blended edge"
+        );
+
+        position
+    }
+}
+
+/// # One of the faces at the ends of the blended edge
+struct End {
+    face: Handle<Face>,
+    plane: Plane,
+
+    /// # The end point of the blended edge
+    point: Point<3>,
+
+    /// # The half-edge of this face that starts at the blended edge
+    half_edge: Handle<HalfEdge>,
+}
+
+/// # Create an orthonormal surface for a face, and define its curves on it
+fn orthonormal_surface(
+    face: &Handle<Face>,
+    core: &mut Core,
+) -> Handle<Surface> {
+    let plane = plane_of(face.surface(), core);
+    let orthonormal = plane.orthonormal();
+    let surface = orthonormal.surface(core);
+
+    for cycle in face.region().all_cycles() {
+        for half_edge in cycle.half_edges() {
+            let path = orthonormal.project_path(&plane.path_to_global(
+                &path_of(half_edge.curve(), face.surface(), core),
+            ));
+
+            half_edge.curve().clone().make_path_on_surface(
+                path,
+                surface.clone(),
+                &mut core.layers.geometry,
+            );
+        }
+    }
+
+    surface
+}
+
+fn plane_of(surface: &Handle<Surface>, core: &Core) -> Plane {
+    Plane::of(surface, core)
+        .expect("Only edges between planar faces can be blended")
+}
+
+/// # Compute the intersection of two lines in the same plane
+fn intersect_lines(a: Line<3>, b: Line<3>) -> Point<3> {
+    let [d_a, d_b] = [a.direction(), b.direction()];
+    let normal = d_a.cross(&d_b);
+
+    let s = (b.origin() - a.origin()).cross(&d_b).dot(&normal)
+        / normal.dot(&normal);
+
+    a.origin() + d_a * s
+}
+
+fn path_of(
+    curve: &Handle<Curve>,
+    surface: &Handle<Surface>,
+    core: &Core,
+) -> Path<2> {
+    core.layers
+        .geometry
+        .of_curve(curve)
+        .unwrap()
+        .local_on(surface)
+        .unwrap()
+        .path
+        .clone()
+}
+
+fn position_of(
+    vertex: &Handle<Vertex>,
+    curve: &Handle<Curve>,
+    core: &Core,
+) -> Point<1> {
+    core.layers
+        .geometry
+        .of_vertex(vertex)
+        .unwrap()
+        .local_on(curve)
+        .unwrap()
+        .position
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use fj_math::Point;
+
+    use crate::{
+        geometry::repr::tri_mesh::convert_point_surface_to_global,
+        storage::Handle,
+        topology::{HalfEdge, Solid, Vertex},
+        Core,
+    };
+
+    pub fn half_edge_between(
+        solid: &Solid,
+        points: [[f64; 3]; 2],
+        core: &Core,
+    ) -> Handle<HalfEdge> {
+        let points = points.map(Point::from);
+
+        half_edges_with_points(solid, core)
+            .into_iter()
+            .find(|(_, [a, b])| [*a, *b] == points || [*b, *a] == points)
+            .map(|(half_edge, _)| half_edge)
+            .expect("Expected to find half-edge")
+    }
+
+    /// # The points at either end of each edge of the cuboid
+    ///
+    /// See `crate::operations::tests::cuboid`.
+    pub fn all_edges() -> [[[f64; 3]; 2]; 12] {
+        let corners = [[0., 0.], [2., 0.], [2., 2.], [0., 2.]];
+
+        let mut edges = Vec::new();
+        for z in [0., -1.] {
+            for (i, [x, y]) in corners.into_iter().enumerate() {
+                let [x_next, y_next] = corners[(i + 1) % corners.len()];
+                edges.push([[x, y, z], [x_next, y_next, z]]);
+            }
+        }
+        for [x, y] in corners {
+            edges.push([[x, y, 0.], [x, y, -1.]]);
+        }
+
+        edges
+            .try_into()
+            .expect("Expected cuboid to have twelve edges")
+    }
+
+    pub fn vertex_at(
+        solid: &Solid,
+        point: [f64; 3],
+        core: &Core,
+    ) -> Handle<Vertex> {
+        let point = Point::from(point);
+
+        half_edges_with_points(solid, core)
+            .into_iter()
+            .find(|(_, [start, _])| *start == point)
+            .map(|(half_edge, _)| half_edge.start_vertex().clone())
+            .expect("Expected to find vertex")
+    }
+
+    fn half_edges_with_points(
+        solid: &Solid,
+        core: &Core,
+    ) -> Vec<(Handle<HalfEdge>, [Point<3>; 2])> {
+        let geometry = &core.layers.geometry;
+
+        let mut half_edges = Vec::new();
+        for face in solid.shells().only().faces() {
+            let surface =
+                &geometry.of_surface_2(face.surface()).unwrap().generator;
+
+            for cycle in face.region().all_cycles() {
+                for (half_edge, next) in cycle.half_edges().pairs() {
+                    let path = geometry
+                        .of_curve(half_edge.curve())
+                        .unwrap()
+                        .local_on(face.surface())
+                        .unwrap()
+                        .path
+                        .clone();
+                    let points = [half_edge, next].map(|h| {
+                        let position = geometry
+                            .of_vertex(h.start_vertex())
+                            .unwrap()
+                            .local_on(half_edge.curve())
+                            .unwrap()
+                            .position;
+
+                        convert_point_surface_to_global(
+                            surface,
+                            path.point_from_path_coords(position),
+                            core.tolerance(),
+                            geometry,
+                        )
+                    });
+
+                    half_edges.push((half_edge.clone(), points));
+                }
+            }
+        }
+
+        half_edges
+    }
+}
@@ -0,0 +1,388 @@
+//! # Boolean operations on solids
+//!
+//! See [`Boolean`], which is currently the only trait in this module, for more
+//! information.
+//!
+//! The operations follow the usual approach for boundary representations: The
+//! faces of each solid are split along the curves where they intersect the
+//! faces of the other. Each of the resulting pieces is classified as being
+//! inside of the other solid, outside of it, or on its boundary, and the
+//! pieces that make up the boundary of the result are combined into a new
+//! solid.
+
+mod polygon;
+mod rebuild;
+mod split;
+
+use fj_math::{Point, Scalar};
+
+use crate::{
+    algorithms::{
+        classify::{ClassifyPoint, PointClassification},
+        intersect::Intersect,
+    },
+    storage::Handle,
+    topology::{Face, Solid},
+    validation::ValidationConfig,
+    Core,
+};
+
+use self::{
+    polygon::Polygon, rebuild::polygons_to_solid, split::split_polygon,
+};
+
+/// Combine two [`Solid`]s using boolean operations
+///
+/// Where faces of both solids overlap, the result contains only one of them, or
+/// neither, if they face in opposite directions. Coplanar faces of the result
+/// that share edges are merged into a single face, where possible.
+///
+/// Results where more than two faces meet at an edge (for example, if two
+/// solids only touch along an edge) are not supported and lead to an invalid
+/// solid.
+///
+/// # Panics
+///
+/// The operations only support solids whose faces are planar and bounded by
+/// straight edges. They panic, if any face of either solid is not.
+pub trait Boolean {
+    /// Compute the union of this solid and another
+    ///
+    /// The result contains all space that is part of either solid.
+    #[must_use]
+    fn union(&self, other: &Self, core: &mut Core) -> Self;
+
+    /// Subtract another solid from this one
+    ///
+    /// The result contains all space that is part of this solid, but not of
+    /// the other.
+    #[must_use]
+    fn difference(&self, other: &Self, core: &mut Core) -> Self;
+
+    /// Compute the intersection of this solid and another
+    ///
+    /// The result contains all space that is part of both solids.
+    #[must_use]
+    fn intersection(&self, other: &Self, core: &mut Core) -> Self;
+}
+
+impl Boolean for Solid {
+    fn union(&self, other: &Self, core: &mut Core) -> Self {
+        combine(self, other, Operation::Union, core)
+    }
+
+    fn difference(&self, other: &Self, core: &mut Core) -> Self {
+        combine(self, other, Operation::Difference, core)
+    }
+
+    fn intersection(&self, other: &Self, core: &mut Core) -> Self {
+        combine(self, other, Operation::Intersection, core)
+    }
+}
+
+enum Operation {
+    Union,
+    Difference,
+    Intersection,
+}
+
+impl Operation {
+    /// Decide whether a piece of a face is part of the result
+    ///
+    /// Returns `None`, if it isn't. Otherwise, returns whether the piece needs
+    /// to be reversed.
+    fn select(&self, location: Location, is_first: bool) -> Option<bool> {
+        match (self, location, is_first) {
+            (Operation::Union, Location::Outside, _)
+            | (Operation::Union, Location::SameBoundary, true)
+            | (Operation::Intersection, Location::Inside, _)
+            | (Operation::Intersection, Location::SameBoundary, true)
+            | (Operation::Difference, Location::Outside, true)
+            | (Operation::Difference, Location::OppositeBoundary, true) => {
+                Some(false)
+            }
+            (Operation::Difference, Location::Inside, false) => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// The location of a piece of a face, relative to the other solid
+#[derive(Clone, Copy)]
+enum Location {
+    Inside,
+    Outside,
+
+    /// On a face of the other solid, that faces the same direction
+    SameBoundary,
+
+    /// On a face of the other solid, that faces the opposite direction
+    OppositeBoundary,
+}
+
+fn combine(
+    a: &Solid,
+    b: &Solid,
+    operation: Operation,
+    core: &mut Core,
+) -> Solid {
+    // Used to decide whether points are identical, or on a plane. This needs
+    // to be much smaller than the tolerance, or we'd merge geometry that is
+    // distinct.
+    let epsilon = core.tolerance().inner() * Scalar::from(1e-3);
+
+    let solids = [a, b];
+    let faces = solids.map(|solid| {
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+    let polygons = faces.each_ref().map(|faces| {
+        faces
+            .iter()
+            .map(|face| Polygon::from_face(face, core))
+            .collect::<Vec<_>>()
+    });
+
+    let mut result = Vec::new();
+
+    for (this, other) in [(0, 1), (1, 0)] {
+        for (face, polygon) in faces[this].iter().zip(&polygons[this]) {
+            let cuts = cuts(face, &faces[other], core);
+
+            for piece in split_polygon(polygon, &cuts, epsilon) {
+                let location = locate(
+                    &piece,
+                    solids[other],
+                    &polygons[other],
+                    epsilon,
+                    core,
+                );
+
+                match operation.select(location, this == 0) {
+                    Some(false) => result.push(piece),
+                    Some(true) => result.push(piece.reverse()),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    polygons_to_solid(result, epsilon, core)
+}
+
+/// Compute the segments along which a face intersects the faces of a solid
+fn cuts(
+    face: &Handle<Face>,
+    others: &[Handle<Face>],
+    core: &Core,
+) -> Vec<[Point<3>; 2]> {
+    others
+        .iter()
+        .filter_map(|other| {
+            (face, other, core.tolerance(), &*core.layers.geometry).intersect()
+        })
+        .flat_map(|intersection| intersection.curves)
+        .flat_map(|curve| {
+            curve
+                .global
+                .points
+                .windows(2)
+                .map(|window| [window[0], window[1]])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Locate a piece of a face, relative to another solid
+fn locate(
+    piece: &Polygon,
+    solid: &Solid,
+    polygons: &[Polygon],
+    epsilon: Scalar,
+    core: &Core,
+) -> Location {
+    let point = piece.interior_point();
+
+    // The piece is not split any further, so it's either completely on the
+    // boundary of the solid, or not at all. That means a point that is close
+    // to the boundary, but not on it, is still inside or outside.
+    let config = ValidationConfig {
+        identical_max_distance: epsilon,
+        ..core.layers.validation.config
+    };
+
+    match solid.classify_point(point, &config, &core.layers.geometry) {
+        PointClassification::Inside => Location::Inside,
+        PointClassification::Outside => Location::Outside,
+        PointClassification::Boundary => {
+            let same = piece.plane;
+            let opposite = piece.plane.flip();
+
+            // If the point is on the boundary, it must be on a face that is
+            // coplanar with the piece. Otherwise, that face would have split
+            // the piece. Should numerical inaccuracy prevent us from finding
+            // it, the piece only touches the solid.
+            polygons
+                .iter()
+                .find_map(|polygon| {
+                    let location = if polygon.plane.is_same_as(&same, epsilon) {
+                        Location::SameBoundary
+                    } else if polygon.plane.is_same_as(&opposite, epsilon) {
+                        Location::OppositeBoundary
+                    } else {
+                        return None;
+                    };
+
+                    polygon.contains(point, epsilon).then_some(location)
+                })
+                .unwrap_or(Location::Outside)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Scalar, Vector};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            tests::assert_volume,
+            transform::TransformObject,
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::Boolean;
+
+    #[test]
+    fn union_of_overlapping_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid(Vector::from([0., 0., 0.]), &mut core);
+        let b = cuboid(Vector::from([0.5, 0.5, 0.5]), &mut core);
+
+        let union = a.union(&b, &mut core).insert(&mut core);
+
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(union.shells().only().faces().len(), 12);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&union, Scalar::from(1.875), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_of_overlapping_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid(Vector::from([0., 0., 0.]), &mut core);
+        let b = cuboid(Vector::from([0.5, 0.5, 0.5]), &mut core);
+
+        let difference = a.difference(&b, &mut core).insert(&mut core);
+
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(difference.shells().only().faces().len(), 9);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&difference, Scalar::from(0.875), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_of_overlapping_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid(Vector::from([0., 0., 0.]), &mut core);
+        let b = cuboid(Vector::from([0.5, 0.5, 0.5]), &mut core);
+
+        let intersection = a.intersection(&b, &mut core).insert(&mut core);
+
+        assert_eq!(intersection.shells().len(), 1);
+        assert_eq!(intersection.shells().only().faces().len(), 6);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&intersection, Scalar::from(0.125), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_hole_through_cuboid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid(Vector::from([0., 0., 0.]), &mut core);
+        let b = Sketch::polygon(
+            [[0.25, 0.25], [0.75, 0.25], [0.75, 0.75], [0.25, 0.75]],
+            &mut core,
+        )
+        .sweep_sketch(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0., -2.],
+            &mut core,
+        )
+        .translate([0., 0., 0.5], &mut core);
+
+        let difference = a.difference(&b, &mut core).insert(&mut core);
+
+        // The top and bottom faces now have a hole each, and the hole is
+        // bounded by four additional faces.
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(difference.shells().only().faces().len(), 10);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&difference, Scalar::from(0.75), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn union_of_cuboids_that_share_a_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid(Vector::from([0., 0., 0.]), &mut core);
+        let b = cuboid(Vector::from([1., 0., 0.]), &mut core);
+
+        let union = a.union(&b, &mut core).insert(&mut core);
+
+        // The shared face is gone, and the faces on either side of it are
+        // merged.
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(union.shells().only().faces().len(), 6);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&union, Scalar::from(2.), &mut core)?;
+
+        Ok(())
+    }
+
+    fn cuboid(offset: Vector<3>, core: &mut Core) -> Solid {
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(
+                surface,
+                [Scalar::ZERO, Scalar::ZERO, -Scalar::ONE],
+                core,
+            )
+            .translate(offset, core)
+    }
+}
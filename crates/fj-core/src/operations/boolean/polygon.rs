@@ -0,0 +1,353 @@
+//! # Planar polygons
+//!
+//! The boolean operations split the faces of both solids into pieces, decide
+//! which of those pieces are part of the result, and rebuild a solid from
+//! them. In between, faces and pieces are represented as polygons.
+
+use std::{collections::BTreeMap, f64::consts::TAU};
+
+use fj_interop::Color;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::intersect::face_face::PlanarFace,
+    operations::presentation::GetColor, topology::Face, Core,
+};
+
+/// A plane in 3D space
+#[derive(Clone, Copy, Debug)]
+pub struct Plane {
+    /// The normal of the plane
+    ///
+    /// Points to the front side of the plane. Must be normalized.
+    pub normal: Vector<3>,
+
+    /// The distance of the plane from the origin, along the normal
+    pub distance: Scalar,
+}
+
+impl Plane {
+    /// Return the plane that faces the opposite direction
+    pub fn flip(&self) -> Self {
+        Self {
+            normal: -self.normal,
+            distance: -self.distance,
+        }
+    }
+
+    /// Determine whether both planes are the same, and face the same direction
+    pub fn is_same_as(&self, other: &Self, epsilon: Scalar) -> bool {
+        (self.normal - other.normal).magnitude() < epsilon
+            && (self.distance - other.distance).abs() < epsilon
+    }
+}
+
+/// A planar polygon, which might have holes
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    /// The cycles that bound the polygon
+    ///
+    /// The exterior comes first, followed by any interiors. The exterior is
+    /// ordered counter-clockwise, when looking at the front side, the interiors
+    /// clockwise.
+    pub cycles: Vec<Vec<Point<3>>>,
+
+    /// The plane that the polygon lies in
+    pub plane: Plane,
+
+    /// The color of the face that the polygon originates from
+    pub color: Option<Color>,
+}
+
+impl Polygon {
+    /// Construct a polygon from a face
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the face is not planar, or if it is bounded by edges that are
+    /// not straight lines.
+    pub fn from_face(face: &Face, core: &mut Core) -> Self {
+        let planar = PlanarFace::new(face, &core.layers.geometry).expect(
+            "Boolean operations only support planar faces with straight edges",
+        );
+
+        // The front side of the face is the side, where its exterior is wound
+        // counter-clockwise.
+        let mut normal = planar.normal().normalize();
+        if signed_area(&planar.cycles[0]) < Scalar::ZERO {
+            normal = -normal;
+        }
+
+        let cycles = planar
+            .cycles
+            .iter()
+            .map(|cycle| {
+                cycle
+                    .iter()
+                    .map(|&point| planar.point_to_global(point))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let distance = normal.dot(&cycles[0][0].coords);
+
+        Self {
+            cycles,
+            plane: Plane { normal, distance },
+            color: face.region().get_color(core),
+        }
+    }
+
+    /// Return the polygon that faces the opposite direction
+    pub fn reverse(mut self) -> Self {
+        for cycle in &mut self.cycles {
+            cycle.reverse();
+        }
+        self.plane = self.plane.flip();
+        self
+    }
+
+    /// Access the edges of all cycles of the polygon
+    pub fn edges(&self) -> impl Iterator<Item = [Point<3>; 2]> + '_ {
+        self.cycles.iter().flat_map(|cycle| {
+            (0..cycle.len()).map(|i| [cycle[i], cycle[(i + 1) % cycle.len()]])
+        })
+    }
+
+    /// Project the polygon into an orthonormal coordinate system in its plane
+    pub fn project(&self) -> (PlaneBasis, Vec<Vec<Point<2>>>) {
+        let basis = PlaneBasis::new(&self.plane, self.cycles[0][0]);
+        let cycles = self
+            .cycles
+            .iter()
+            .map(|cycle| {
+                cycle
+                    .iter()
+                    .map(|&point| basis.project(point))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        (basis, cycles)
+    }
+
+    /// Determine whether a point is within the polygon, or on its boundary
+    ///
+    /// The point is expected to be in the plane of the polygon. Points that are
+    /// within `epsilon` of an edge are considered to be on the boundary.
+    pub fn contains(&self, point: Point<3>, epsilon: Scalar) -> bool {
+        let (basis, cycles) = self.project();
+        let point = basis.project(point);
+
+        let is_on_boundary = cycles.iter().any(|cycle| {
+            (0..cycle.len()).any(|i| {
+                let [a, b] = [cycle[i], cycle[(i + 1) % cycle.len()]];
+                distance_to_segment(point, [a, b]) <= epsilon
+            })
+        });
+
+        is_on_boundary
+            || cycles
+                .iter()
+                .filter(|cycle| contains_point(cycle, point))
+                .count()
+                % 2
+                == 1
+    }
+
+    /// Compute a point that is within the polygon, and not on its boundary
+    ///
+    /// A horizontal line through the polygon is chosen, that doesn't pass
+    /// through any of its vertices, and is as far from them as possible. The
+    /// point is in the middle of the widest interval, in which that line
+    /// overlaps the polygon.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the polygon has no area.
+    pub fn interior_point(&self) -> Point<3> {
+        let (basis, cycles) = self.project();
+
+        let mut heights = cycles
+            .iter()
+            .flatten()
+            .map(|point| point.v)
+            .collect::<Vec<_>>();
+        heights.sort();
+        heights.dedup();
+
+        let v = heights
+            .windows(2)
+            .max_by_key(|window| window[1] - window[0])
+            .map(|window| (window[0] + window[1]) / 2.)
+            .expect("Expected polygon to have area");
+
+        let mut crossings = cycles
+            .iter()
+            .flat_map(|cycle| {
+                (0..cycle.len()).filter_map(|i| {
+                    let [a, b] = [cycle[i], cycle[(i + 1) % cycle.len()]];
+                    ((a.v > v) != (b.v > v))
+                        .then(|| a.u + (v - a.v) / (b.v - a.v) * (b.u - a.u))
+                })
+            })
+            .collect::<Vec<_>>();
+        crossings.sort();
+
+        let u = crossings
+            .chunks_exact(2)
+            .max_by_key(|interval| interval[1] - interval[0])
+            .map(|interval| (interval[0] + interval[1]) / 2.)
+            .expect("Expected line through polygon to cross its boundary");
+
+        basis.point_to_global(Point::from([u, v]))
+    }
+}
+
+/// An orthonormal coordinate system within a plane
+///
+/// Together with the normal of the plane, the axes form a right-handed
+/// coordinate system.
+pub struct PlaneBasis {
+    pub origin: Point<3>,
+    pub u: Vector<3>,
+    pub v: Vector<3>,
+}
+
+impl PlaneBasis {
+    pub fn new(plane: &Plane, origin: Point<3>) -> Self {
+        let normal = plane.normal;
+
+        let axis = if normal.x.abs() < Scalar::from(0.9) {
+            Vector::unit_x()
+        } else {
+            Vector::unit_y()
+        };
+        let u = (axis - normal * axis.dot(&normal)).normalize();
+        let v = normal.cross(&u);
+
+        Self { origin, u, v }
+    }
+
+    pub fn project(&self, point: Point<3>) -> Point<2> {
+        let offset = point - self.origin;
+        Point::from([offset.dot(&self.u), offset.dot(&self.v)])
+    }
+
+    pub fn point_to_global(&self, point: Point<2>) -> Point<3> {
+        self.origin + self.u * point.u + self.v * point.v
+    }
+}
+
+/// Connect directed edges between points into cycles
+///
+/// Where multiple edges leave a point, the cycle continues along the first one
+/// in clockwise direction, starting from the edge it arrived on. This keeps the
+/// area that a cycle encloses to its left, and separates cycles that only touch
+/// in a point.
+///
+/// Edges that can't be connected into a cycle are dropped.
+pub fn trace_cycles(
+    edges: impl IntoIterator<Item = [usize; 2]>,
+    points: &[Point<2>],
+) -> Vec<Vec<usize>> {
+    let mut outgoing: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for [a, b] in edges {
+        outgoing.entry(a).or_default().push(b);
+    }
+
+    let angle = |from: usize, to: usize| {
+        let direction = points[to] - points[from];
+        direction.v.atan2(direction.u)
+    };
+
+    let mut cycles = Vec::new();
+
+    while let Some((&start, targets)) = outgoing.first_key_value() {
+        let mut cycle = vec![start];
+        let [mut previous, mut current] = [start, targets[0]];
+        remove_edge(&mut outgoing, [previous, current]);
+
+        while current != start {
+            cycle.push(current);
+
+            let Some(targets) = outgoing.get(&current) else {
+                break;
+            };
+
+            let incoming = angle(current, previous);
+            let next = targets
+                .iter()
+                .copied()
+                .min_by_key(|&next| {
+                    let turn = (incoming - angle(current, next))
+                        .into_f64()
+                        .rem_euclid(TAU);
+                    Scalar::from(if turn == 0. { TAU } else { turn })
+                })
+                .expect("Expected list of targets to not be empty");
+
+            remove_edge(&mut outgoing, [current, next]);
+            [previous, current] = [current, next];
+        }
+
+        if current == start && cycle.len() >= 3 {
+            cycles.push(cycle);
+        }
+    }
+
+    cycles
+}
+
+fn remove_edge(outgoing: &mut BTreeMap<usize, Vec<usize>>, [a, b]: [usize; 2]) {
+    if let Some(targets) = outgoing.get_mut(&a) {
+        if let Some(index) = targets.iter().position(|&target| target == b) {
+            targets.remove(index);
+        }
+        if targets.is_empty() {
+            outgoing.remove(&a);
+        }
+    }
+}
+
+pub fn signed_area(points: &[Point<2>]) -> Scalar {
+    let mut area = Scalar::ZERO;
+
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area += a.u * b.v - b.u * a.v;
+    }
+
+    area / 2.
+}
+
+pub fn contains_point(polygon: &[Point<2>], point: Point<2>) -> bool {
+    let mut inside = false;
+
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+
+        if (a.v > point.v) != (b.v > point.v) {
+            let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+            if point.u < u {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+pub fn distance_to_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> Scalar {
+    let direction = b - a;
+    let length_squared = direction.dot(&direction);
+
+    let t = if length_squared == Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        ((point - a).dot(&direction) / length_squared)
+            .max(Scalar::ZERO)
+            .min(Scalar::ONE)
+    };
+
+    (point - (a + direction * t)).magnitude()
+}
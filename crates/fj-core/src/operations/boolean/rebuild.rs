@@ -0,0 +1,524 @@
+//! # Rebuild a solid from a set of polygons
+//!
+//! The pieces of faces that make up the result of a boolean operation are
+//! polygons, without any topological information. This module reconstructs the
+//! topology from those polygons, merging coplanar polygons back into faces
+//! where possible.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter,
+};
+
+use fj_interop::Color;
+use fj_math::{Line, Point, Scalar};
+
+use crate::{
+    geometry::{CurveBoundary, LocalVertexGeom, Path},
+    operations::{
+        build::BuildSurface, components::Components,
+        geometry::UpdateCurveGeometry, insert::Insert, presentation::SetColor,
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+use super::polygon::{
+    contains_point, signed_area, trace_cycles, Plane, PlaneBasis, Polygon,
+};
+
+/// Rebuild a solid from the provided polygons
+///
+/// The polygons must form one or more closed polyhedra. Points that are closer
+/// to each other than `epsilon` are considered identical.
+pub fn polygons_to_solid(
+    polygons: Vec<Polygon>,
+    epsilon: Scalar,
+    core: &mut Core,
+) -> Solid {
+    let mut points = Vec::new();
+    let mut polygons = polygons
+        .into_iter()
+        .filter_map(|polygon| {
+            let mut cycles = polygon
+                .cycles
+                .iter()
+                .map(|cycle| weld(cycle, &mut points, epsilon))
+                .collect::<Vec<_>>();
+            if is_degenerate(&cycles[0], &points, epsilon) {
+                return None;
+            }
+            cycles.retain(|cycle| !is_degenerate(cycle, &points, epsilon));
+
+            Some(IndexedPolygon {
+                cycles,
+                plane: polygon.plane,
+                color: polygon.color,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for polygon in &mut polygons {
+        for cycle in &mut polygon.cycles {
+            *cycle = insert_t_junctions(cycle, &points, epsilon);
+        }
+    }
+
+    let mut faces = group_coplanar(polygons, epsilon)
+        .into_iter()
+        .flat_map(|group| group.into_faces(&points))
+        .collect::<Vec<_>>();
+    remove_collinear_vertices(&mut faces, &points, epsilon);
+
+    build_solid(faces, &points, core)
+}
+
+struct IndexedPolygon {
+    cycles: Vec<Vec<usize>>,
+    plane: Plane,
+    color: Option<Color>,
+}
+
+/// Map the provided vertices to indices of unique points
+///
+/// Vertices that are closer to an existing point than `epsilon` are
+/// mapped to that point. Consecutive duplicate indices are removed.
+fn weld(
+    vertices: &[Point<3>],
+    points: &mut Vec<Point<3>>,
+    epsilon: Scalar,
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = Vec::new();
+
+    for vertex in vertices {
+        let index = points
+            .iter()
+            .position(|point| point.distance_to(vertex) < epsilon)
+            .unwrap_or_else(|| {
+                points.push(*vertex);
+                points.len() - 1
+            });
+
+        if indices.last() != Some(&index) {
+            indices.push(index);
+        }
+    }
+
+    while indices.len() > 1 && indices.first() == indices.last() {
+        indices.pop();
+    }
+
+    indices
+}
+
+/// Determine whether the polygon has collapsed into a line or point
+fn is_degenerate(
+    indices: &[usize],
+    points: &[Point<3>],
+    epsilon: Scalar,
+) -> bool {
+    if indices.len() < 3 {
+        return true;
+    }
+
+    let mut farthest = (Scalar::ZERO, [indices[0], indices[0]]);
+    for &a in indices {
+        for &b in indices {
+            let distance = points[a].distance_to(&points[b]);
+            if distance > farthest.0 {
+                farthest = (distance, [a, b]);
+            }
+        }
+    }
+
+    let (length, [a, b]) = farthest;
+    if length < epsilon {
+        return true;
+    }
+
+    indices.iter().all(|&index| {
+        distance_to_line(points[index], [points[a], points[b]]) < epsilon
+    })
+}
+
+/// Insert points that lie on the edges of the polygon as additional vertices
+///
+/// Neighboring polygons might have been split differently, which results in
+/// vertices of one polygon lying on the edge of another. Those need to be
+/// present in both polygons, for the edges to match up.
+fn insert_t_junctions(
+    indices: &[usize],
+    points: &[Point<3>],
+    epsilon: Scalar,
+) -> Vec<usize> {
+    let mut result = Vec::new();
+
+    for (i, &a) in indices.iter().enumerate() {
+        let b = indices[(i + 1) % indices.len()];
+        let [pa, pb] = [points[a], points[b]];
+
+        let direction = pb - pa;
+        let length_squared = direction.dot(&direction);
+
+        let mut on_edge = points
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !indices.contains(index))
+            .filter_map(|(index, point)| {
+                let t = (point - pa).dot(&direction) / length_squared;
+                if t <= Scalar::ZERO || t >= Scalar::ONE {
+                    return None;
+                }
+
+                let distance = point.distance_to(&(pa + direction * t));
+                (distance < epsilon).then_some((t, index))
+            })
+            .collect::<Vec<_>>();
+        on_edge.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        result.push(a);
+        result.extend(on_edge.into_iter().map(|(_, index)| index));
+    }
+
+    result
+}
+
+fn distance_to_line(point: Point<3>, [a, b]: [Point<3>; 2]) -> Scalar {
+    let direction = (b - a).normalize();
+    let offset = point - a;
+
+    (offset - direction * offset.dot(&direction)).magnitude()
+}
+
+/// A group of coplanar polygons of the same color
+struct Group {
+    plane: Plane,
+    color: Option<Color>,
+    polygons: Vec<Vec<Vec<usize>>>,
+}
+
+impl Group {
+    /// Merge the polygons of the group into faces
+    ///
+    /// Edges shared by two polygons of the group are removed, and the remaining
+    /// edges are connected into cycles. If polygons overlap, that doesn't lead
+    /// to a meaningful result, and each polygon becomes a face of its own.
+    fn into_faces(self, points: &[Point<3>]) -> Vec<FaceSpec> {
+        match self.merge(points) {
+            Some(faces) => faces,
+            None => self
+                .polygons
+                .into_iter()
+                .map(|mut cycles| {
+                    let exterior = cycles.remove(0);
+                    FaceSpec {
+                        plane: self.plane,
+                        color: self.color,
+                        exterior,
+                        interiors: cycles,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn merge(&self, points: &[Point<3>]) -> Option<Vec<FaceSpec>> {
+        let mut edges = BTreeMap::new();
+        for cycle in self.polygons.iter().flatten() {
+            for (i, &a) in cycle.iter().enumerate() {
+                let b = cycle[(i + 1) % cycle.len()];
+
+                if let Some(count) = edges.get_mut(&(b, a)) {
+                    *count -= 1;
+                    if *count == 0 {
+                        edges.remove(&(b, a));
+                    }
+                } else {
+                    *edges.entry((a, b)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        if edges.values().any(|&count| count > 1) {
+            return None;
+        }
+
+        let basis =
+            PlaneBasis::new(&self.plane, points[self.polygons[0][0][0]]);
+        let points_2d = points
+            .iter()
+            .map(|&point| basis.project(point))
+            .collect::<Vec<_>>();
+
+        let (exteriors, interiors): (Vec<_>, Vec<_>) =
+            trace_cycles(edges.into_keys().map(|(a, b)| [a, b]), &points_2d)
+                .into_iter()
+                .map(|cycle| {
+                    let cycle_2d = cycle
+                        .iter()
+                        .map(|&index| points_2d[index])
+                        .collect::<Vec<_>>();
+                    let area = signed_area(&cycle_2d);
+                    (cycle, cycle_2d, area)
+                })
+                .partition(|(_, _, area)| *area > Scalar::ZERO);
+
+        let mut faces = exteriors
+            .iter()
+            .map(|(exterior, _, _)| FaceSpec {
+                plane: self.plane,
+                color: self.color,
+                exterior: exterior.clone(),
+                interiors: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        for (interior, _, _) in interiors {
+            let (index, _) = exteriors
+                .iter()
+                .enumerate()
+                .filter(|(_, (exterior, exterior_2d, _))| {
+                    // An interior might touch the exterior it's in. Only its
+                    // other vertices tell, whether it is inside.
+                    interior
+                        .iter()
+                        .find(|index| !exterior.contains(index))
+                        .is_some_and(|&index| {
+                            contains_point(exterior_2d, points_2d[index])
+                        })
+                })
+                .min_by(|(_, (_, _, a)), (_, (_, _, b))| a.cmp(b))?;
+
+            faces[index].interiors.push(interior);
+        }
+
+        Some(faces)
+    }
+}
+
+fn group_coplanar(
+    polygons: Vec<IndexedPolygon>,
+    epsilon: Scalar,
+) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+
+    for polygon in polygons {
+        let group = groups.iter_mut().find(|group| {
+            group.color == polygon.color
+                && group.plane.is_same_as(&polygon.plane, epsilon)
+        });
+
+        match group {
+            Some(group) => group.polygons.push(polygon.cycles),
+            None => groups.push(Group {
+                plane: polygon.plane,
+                color: polygon.color,
+                polygons: vec![polygon.cycles],
+            }),
+        }
+    }
+
+    groups
+}
+
+/// Remove vertices that only subdivide a straight edge
+///
+/// Splitting polygons leaves vertices on the edges of the merged faces, that
+/// are no longer required. Such a vertex can be removed, if it lies between its
+/// neighbors on a straight line, in both of the cycles that it is part of.
+fn remove_collinear_vertices(
+    faces: &mut [FaceSpec],
+    points: &[Point<3>],
+    epsilon: Scalar,
+) {
+    let mut cycles = faces
+        .iter_mut()
+        .flat_map(FaceSpec::cycles_mut)
+        .collect::<Vec<_>>();
+
+    loop {
+        let mut occurrences: BTreeMap<usize, Vec<(usize, usize)>> =
+            BTreeMap::new();
+        for (i, cycle) in cycles.iter().enumerate() {
+            for (j, &vertex) in cycle.iter().enumerate() {
+                occurrences.entry(vertex).or_default().push((i, j));
+            }
+        }
+
+        let neighbors = |(i, j): (usize, usize)| {
+            let cycle: &Vec<usize> = cycles[i];
+            [
+                cycle[(j + cycle.len() - 1) % cycle.len()],
+                cycle[(j + 1) % cycle.len()],
+            ]
+        };
+
+        let mut removed = BTreeSet::new();
+        let mut num_removed = vec![0; cycles.len()];
+
+        for (vertex, occurrences) in occurrences {
+            let &[a, b] = occurrences.as_slice() else {
+                continue;
+            };
+            let [prev, next] = neighbors(a);
+
+            let is_removable = a.0 != b.0
+                && neighbors(b) == [next, prev]
+                && prev != next
+                && !removed.contains(&prev)
+                && !removed.contains(&next)
+                && [a.0, b.0]
+                    .iter()
+                    .all(|&i| cycles[i].len() - num_removed[i] > 3)
+                && (points[vertex] - points[prev])
+                    .dot(&(points[next] - points[vertex]))
+                    > Scalar::ZERO
+                && distance_to_line(
+                    points[vertex],
+                    [points[prev], points[next]],
+                ) < epsilon;
+
+            if is_removable {
+                removed.insert(vertex);
+                num_removed[a.0] += 1;
+                num_removed[b.0] += 1;
+            }
+        }
+
+        if removed.is_empty() {
+            break;
+        }
+
+        for cycle in &mut cycles {
+            cycle.retain(|vertex| !removed.contains(vertex));
+        }
+    }
+}
+
+/// The specification of a face that is to be built
+struct FaceSpec {
+    plane: Plane,
+    color: Option<Color>,
+    exterior: Vec<usize>,
+    interiors: Vec<Vec<usize>>,
+}
+
+impl FaceSpec {
+    fn cycles_mut(&mut self) -> impl Iterator<Item = &mut Vec<usize>> {
+        iter::once(&mut self.exterior).chain(self.interiors.iter_mut())
+    }
+}
+
+fn build_solid(
+    faces: Vec<FaceSpec>,
+    points: &[Point<3>],
+    core: &mut Core,
+) -> Solid {
+    let mut vertices = BTreeMap::new();
+    let mut curves = BTreeMap::new();
+
+    // Faces that share an edge belong to the same shell. Track which shell
+    // each face belongs to, using a simple union-find structure.
+    let mut shell_of_face = Components::new(faces.len());
+    let mut face_of_edge = BTreeMap::new();
+
+    let faces = faces
+        .into_iter()
+        .enumerate()
+        .map(|(face_index, face)| {
+            let basis = PlaneBasis::new(&face.plane, points[face.exterior[0]]);
+            let surface = Surface::from_uv(
+                Path::Line(Line::from_origin_and_direction(
+                    basis.origin,
+                    basis.u,
+                )),
+                basis.v,
+                core,
+            );
+
+            let mut build_cycle = |cycle: &[usize], core: &mut Core| {
+                let half_edges = cycle
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &a)| {
+                        let b = cycle[(i + 1) % cycle.len()];
+                        let edge = [a.min(b), a.max(b)];
+
+                        if let Some(other) =
+                            face_of_edge.insert(edge, face_index)
+                        {
+                            shell_of_face.connect(face_index, other);
+                        }
+
+                        let curve = curves
+                            .entry(edge)
+                            .or_insert_with(|| Curve::new().insert(core))
+                            .clone();
+                        let [vertex_a, vertex_b] = [a, b].map(|index| {
+                            vertices
+                                .entry(index)
+                                .or_insert_with(|| Vertex::new().insert(core))
+                                .clone()
+                        });
+
+                        let boundary = if a < b {
+                            CurveBoundary::from([[0.], [1.]])
+                        } else {
+                            CurveBoundary::from([[1.], [0.]])
+                        };
+                        let curve = curve.make_line_on_surface(
+                            [a, b].map(|index| basis.project(points[index])),
+                            boundary,
+                            surface.clone(),
+                            &mut core.layers.geometry,
+                        );
+
+                        for (vertex, position) in [
+                            (vertex_a.clone(), boundary.inner[0]),
+                            (vertex_b, boundary.inner[1]),
+                        ] {
+                            core.layers.geometry.define_vertex(
+                                vertex,
+                                curve.clone(),
+                                LocalVertexGeom { position },
+                            );
+                        }
+
+                        HalfEdge::new(curve, vertex_a).insert(core)
+                    })
+                    .collect::<Vec<_>>();
+
+                Cycle::new(half_edges).insert(core)
+            };
+
+            let exterior = build_cycle(&face.exterior, core);
+            let interiors = face
+                .interiors
+                .iter()
+                .map(|interior| build_cycle(interior, core))
+                .collect::<Vec<_>>();
+
+            let region = Region::new(exterior, interiors).insert(core);
+            if let Some(color) = face.color {
+                region.set_color(color, core);
+            }
+
+            Face::new(surface, region).insert(core)
+        })
+        .collect::<Vec<_>>();
+
+    let mut shells: BTreeMap<usize, Vec<Handle<Face>>> = BTreeMap::new();
+    for (face_index, face) in faces.into_iter().enumerate() {
+        let root = shell_of_face.find(face_index);
+        shells.entry(root).or_default().push(face);
+    }
+
+    Solid::new(
+        shells
+            .into_values()
+            .map(|faces| Shell::new(faces).insert(core)),
+    )
+}
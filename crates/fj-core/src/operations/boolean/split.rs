@@ -0,0 +1,203 @@
+//! # Split polygons along the curves where they intersect other faces
+//!
+//! Each face of one solid is split along the curves where it intersects the
+//! faces of the other. The resulting pieces are either completely inside of
+//! the other solid, completely outside of it, or on its boundary.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::operations::components::Components;
+
+use super::polygon::{
+    contains_point, distance_to_segment, signed_area, trace_cycles, Polygon,
+};
+
+/// Split a polygon along the provided cuts
+///
+/// The cuts are line segments within the polygon, or on its boundary. Parts of
+/// them that don't separate one piece from another are ignored. Points that are
+/// within `epsilon` of each other are considered identical.
+pub fn split_polygon(
+    polygon: &Polygon,
+    cuts: &[[Point<3>; 2]],
+    epsilon: Scalar,
+) -> Vec<Polygon> {
+    if cuts.is_empty() {
+        return vec![polygon.clone()];
+    }
+
+    let (basis, _) = polygon.project();
+
+    let segments = polygon
+        .edges()
+        .chain(cuts.iter().copied())
+        .map(|segment| segment.map(|point| basis.project(point)))
+        .collect::<Vec<_>>();
+
+    // Every point where segments start, end, or cross each other becomes a
+    // vertex of the arrangement of segments.
+    let mut points = Vec::new();
+    for &[a, b] in &segments {
+        weld(a, &mut points, epsilon);
+        weld(b, &mut points, epsilon);
+    }
+    for (i, &a) in segments.iter().enumerate() {
+        for &b in &segments[i + 1..] {
+            if let Some(point) = crossing(a, b, epsilon) {
+                weld(point, &mut points, epsilon);
+            }
+        }
+    }
+
+    // Each segment is split at all the vertices that lie on it.
+    let mut edges = BTreeSet::new();
+    for &[a, b] in &segments {
+        let direction = b - a;
+        let length_squared = direction.dot(&direction);
+
+        let mut on_segment = points
+            .iter()
+            .enumerate()
+            .filter(|&(_, &point)| {
+                distance_to_segment(point, [a, b]) <= epsilon
+            })
+            .map(|(index, point)| {
+                ((point - a).dot(&direction) / length_squared, index)
+            })
+            .collect::<Vec<_>>();
+        on_segment.sort();
+
+        for window in on_segment.windows(2) {
+            let [(_, a), (_, b)] = [window[0], window[1]];
+            if a != b {
+                edges.insert([a.min(b), a.max(b)]);
+            }
+        }
+    }
+
+    remove_dangling_edges(&mut edges);
+
+    let mut components = Components::new(points.len());
+    for &[a, b] in &edges {
+        components.connect(a, b);
+    }
+
+    // Every edge is traversed in both directions. Each piece is bounded by a
+    // counter-clockwise cycle, while the outer boundary of each connected set
+    // of edges results in a clockwise cycle.
+    let cycles = trace_cycles(
+        edges.iter().flat_map(|&[a, b]| [[a, b], [b, a]]),
+        &points,
+    )
+    .into_iter()
+    .map(|cycle| {
+        let points =
+            cycle.iter().map(|&index| points[index]).collect::<Vec<_>>();
+        let area = signed_area(&points);
+        let component = components.find(cycle[0]);
+        (points, area, component)
+    })
+    .collect::<Vec<_>>();
+
+    let (exteriors, interiors): (Vec<_>, Vec<_>) = cycles
+        .into_iter()
+        .filter(|(_, area, _)| area.abs() > epsilon * epsilon)
+        .partition(|(_, area, _)| *area > Scalar::ZERO);
+
+    let mut pieces = exteriors
+        .iter()
+        .map(|(exterior, _, _)| vec![exterior.clone()])
+        .collect::<Vec<_>>();
+
+    for (interior, _, component) in interiors {
+        // A clockwise cycle is a hole in the smallest piece that contains it.
+        // If there is no such piece, it bounds the area around the polygon.
+        let piece = exteriors
+            .iter()
+            .enumerate()
+            .filter(|(_, (exterior, _, other))| {
+                *other != component && contains_point(exterior, interior[0])
+            })
+            .min_by_key(|(_, (_, area, _))| *area)
+            .map(|(index, _)| index);
+
+        if let Some(index) = piece {
+            pieces[index].push(interior);
+        }
+    }
+
+    pieces
+        .into_iter()
+        .map(|cycles| Polygon {
+            cycles: cycles
+                .into_iter()
+                .map(|cycle| {
+                    cycle
+                        .into_iter()
+                        .map(|point| basis.point_to_global(point))
+                        .collect()
+                })
+                .collect(),
+            plane: polygon.plane,
+            color: polygon.color,
+        })
+        // Holes of the original polygon have become pieces too.
+        .filter(|piece| polygon.contains(piece.interior_point(), epsilon))
+        .collect()
+}
+
+/// Find the point, or add it, if it isn't already there
+fn weld(point: Point<2>, points: &mut Vec<Point<2>>, epsilon: Scalar) {
+    if !points
+        .iter()
+        .any(|other| other.distance_to(&point) <= epsilon)
+    {
+        points.push(point);
+    }
+}
+
+/// Compute the point where two segments cross
+///
+/// Returns `None`, if the segments don't cross, or if they are parallel.
+fn crossing(
+    [a, b]: [Point<2>; 2],
+    [c, d]: [Point<2>; 2],
+    epsilon: Scalar,
+) -> Option<Point<2>> {
+    let [ab, cd, ac] = [b - a, d - c, c - a];
+    let cross = |p: Vector<2>, q: Vector<2>| p.u * q.v - p.v * q.u;
+
+    let denominator = cross(ab, cd);
+    if denominator.abs() <= epsilon * ab.magnitude() * cd.magnitude() {
+        return None;
+    }
+
+    let s = cross(ac, cd) / denominator;
+    let t = cross(ac, ab) / denominator;
+
+    let range = Scalar::ZERO..=Scalar::ONE;
+    (range.contains(&s) && range.contains(&t)).then(|| a + ab * s)
+}
+
+/// Remove edges that have a vertex that no other edge is connected to
+///
+/// Such edges can't bound a piece. Removing them might result in other edges
+/// becoming dangling, so this repeats until there are none left.
+fn remove_dangling_edges(edges: &mut BTreeSet<[usize; 2]>) {
+    loop {
+        let mut degrees = BTreeMap::new();
+        for &[a, b] in edges.iter() {
+            *degrees.entry(a).or_insert(0) += 1;
+            *degrees.entry(b).or_insert(0) += 1;
+        }
+
+        let num_edges = edges.len();
+        edges.retain(|[a, b]| degrees[a] > 1 && degrees[b] > 1);
+
+        if edges.len() == num_edges {
+            break;
+        }
+    }
+}
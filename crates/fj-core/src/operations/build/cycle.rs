@@ -1,11 +1,11 @@
-use fj_math::{Point, Scalar, Vector};
+use fj_math::{Ellipse, Point, Scalar, Vector};
 use itertools::Itertools;
 
 use crate::{
     geometry::{CurveBoundary, LocalVertexGeom},
-    operations::build::BuildHalfEdge,
+    operations::{build::BuildHalfEdge, insert::Insert},
     storage::Handle,
-    topology::{Cycle, HalfEdge, Surface},
+    topology::{Curve, Cycle, HalfEdge, Surface, Vertex},
     Core,
 };
 
@@ -52,6 +52,34 @@ pub trait BuildCycle {
         Cycle::new(half_edges)
     }
 
+    /// # Build a cycle from curves and the vertices its half-edges start at
+    ///
+    /// If `reverse` is `true`, the half-edges are built in reverse order, each
+    /// starting at the vertex that the original one ends at.
+    fn from_curves_and_start_vertices(
+        half_edges: Vec<(Handle<Curve>, Handle<Vertex>)>,
+        reverse: bool,
+        core: &mut Core,
+    ) -> Cycle {
+        let half_edges = if reverse {
+            (0..half_edges.len())
+                .rev()
+                .map(|i| {
+                    let (curve, _) = &half_edges[i];
+                    let (_, end) = &half_edges[(i + 1) % half_edges.len()];
+
+                    (curve.clone(), end.clone())
+                })
+                .collect()
+        } else {
+            half_edges
+        };
+
+        Cycle::new(half_edges.into_iter().map(|(curve, start_vertex)| {
+            HalfEdge::new(curve, start_vertex).insert(core)
+        }))
+    }
+
     /// # Build a circle
     ///
     /// This circle is built out of 4 distinct arcs.
@@ -97,6 +125,33 @@ pub trait BuildCycle {
         Self::from_half_edges_and_boundaries(half_edges_and_boundaries, core)
     }
 
+    /// # Build an ellipse
+    ///
+    /// Like [`BuildCycle::circle`], and for the same reasons, the ellipse is
+    /// built out of 4 distinct arcs.
+    fn ellipse(
+        ellipse: Ellipse<2>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Cycle {
+        let quarter = Scalar::TAU / 4.;
+
+        let half_edges_and_boundaries = [0., 1., 2., 3.]
+            .into_iter()
+            .map(|i| {
+                let boundary = [i, i + 1.].map(|i| [quarter * i]);
+                HalfEdge::elliptical_arc(
+                    ellipse,
+                    boundary,
+                    surface.clone(),
+                    core,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_half_edges_and_boundaries(half_edges_and_boundaries, core)
+    }
+
     /// Build a polygon
     fn polygon<P, Ps>(
         points: Ps,
@@ -1,4 +1,4 @@
-use fj_math::{Arc, Point, Scalar};
+use fj_math::{Arc, Ellipse, NurbsCurve, Point, Scalar};
 
 use crate::{
     geometry::{CurveBoundary, LocalCurveGeom, Path},
@@ -67,6 +67,55 @@ pub trait BuildHalfEdge {
         (half_edge, boundary)
     }
 
+    /// Create an arc of an ellipse
+    ///
+    /// The boundary is given in ellipse coordinates. See [`Ellipse`].
+    fn elliptical_arc(
+        ellipse: Ellipse<2>,
+        boundary: impl Into<CurveBoundary<Point<1>>>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> (Handle<HalfEdge>, CurveBoundary<Point<1>>) {
+        let boundary = boundary.into();
+
+        let half_edge = HalfEdge::unjoined(core).insert(core);
+
+        core.layers.geometry.define_curve(
+            half_edge.curve().clone(),
+            surface,
+            LocalCurveGeom {
+                path: Path::Ellipse(ellipse),
+            },
+        );
+
+        (half_edge, boundary)
+    }
+
+    /// Create a half-edge on a NURBS curve
+    ///
+    /// The returned boundary covers the whole domain of the curve.
+    fn nurbs(
+        curve: NurbsCurve<2>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> (Handle<HalfEdge>, CurveBoundary<Point<1>>) {
+        let boundary = CurveBoundary {
+            inner: curve.domain().map(|coord| Point::from([coord])),
+        };
+
+        let half_edge = HalfEdge::unjoined(core).insert(core);
+
+        core.layers.geometry.define_curve(
+            half_edge.curve().clone(),
+            surface,
+            LocalCurveGeom {
+                path: Path::Nurbs(curve),
+            },
+        );
+
+        (half_edge, boundary)
+    }
+
     /// Create a line segment
     fn line_segment(
         points_surface: [impl Into<Point<2>>; 2],
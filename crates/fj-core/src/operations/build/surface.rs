@@ -2,7 +2,8 @@ use fj_math::{Point, Scalar, Vector};
 
 use crate::{
     geometry::{
-        repr::tri_mesh::TriMesh, surfaces::SweptCurve, Path, SurfaceGeom,
+        repr::tri_mesh::TriMesh, surfaces::SweptCurve, traits::GenTriMesh,
+        Path, SurfaceGeom,
     },
     operations::insert::Insert,
     storage::Handle,
@@ -25,7 +26,32 @@ pub trait BuildSurface {
 
         core.layers
             .geometry
-            .define_surface(surface.clone(), generator);
+            .define_surface(surface.clone(), generator.clone());
+        core.layers.geometry.define_surface_2(
+            surface.clone(),
+            SurfaceGeom {
+                generator: Box::new(generator),
+                geometry: TriMesh::empty(),
+            },
+        );
+
+        surface
+    }
+
+    /// Build a surface from the provided generator of surface geometry
+    ///
+    /// Unlike [`BuildSurface::from_geometry`], this only defines the new-style
+    /// surface geometry. This is how surfaces like [`Cylinder`] or [`Sphere`]
+    /// are attached to a surface.
+    ///
+    /// [`Cylinder`]: crate::geometry::surfaces::Cylinder
+    /// [`Sphere`]: crate::geometry::surfaces::Sphere
+    fn from_generator(
+        generator: impl GenTriMesh + 'static,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        let surface = Surface::new().insert(core);
+
         core.layers.geometry.define_surface_2(
             surface.clone(),
             SurfaceGeom {
@@ -0,0 +1,32 @@
+//! # Tracking which of a number of items are connected to each other
+
+/// # Sets of connected items, identified by their indices
+///
+/// This is a simple union-find structure, as used by operations that need to
+/// group faces into shells, or edges into connected sets.
+pub struct Components {
+    parents: Vec<usize>,
+}
+
+impl Components {
+    /// # Create a new instance, in which no items are connected yet
+    pub fn new(num_items: usize) -> Self {
+        Self {
+            parents: (0..num_items).collect(),
+        }
+    }
+
+    /// # Connect two items, merging the components they belong to
+    pub fn connect(&mut self, a: usize, b: usize) {
+        let [root_a, root_b] = [a, b].map(|index| self.find(index));
+        self.parents[root_a] = root_b;
+    }
+
+    /// # Find the item that identifies the component of the provided item
+    pub fn find(&self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            index = self.parents[index];
+        }
+        index
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
 };
 
 /// Update the geometry of a [`Curve`]
-pub trait UpdateCurveGeometry {
+pub trait UpdateCurveGeometry: Sized {
     /// Copy the geometry of the provided curve
     fn copy_geometry_from(
         self,
@@ -17,6 +17,57 @@ pub trait UpdateCurveGeometry {
         geometry: &mut Layer<Geometry>,
     ) -> Self;
 
+    /// Define the geometry on a surface, by projecting it from another one
+    ///
+    /// Takes the local definition of the curve on `from`, converts it into
+    /// global coordinates, and projects that onto `to`.
+    ///
+    /// If `from` has no 3D geometry (as is the case for the 2D space that
+    /// sketches are defined in), the local definition is placed on `to`
+    /// unchanged, as it is already in the coordinates it is meant to have
+    /// there.
+    ///
+    /// Local definitions of curves are paths, and the projection of a path is
+    /// only guaranteed to be a path, if `to` is a plane. Projecting onto other
+    /// surfaces is not supported.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error, if `to` is not a plane, or if the curve can't be
+    /// converted into global coordinates, which is only supported for some
+    /// curves on curved surfaces. See [`SweptCurve::path_to_global`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the curve is not defined on `from`, or if `to` has no 3D
+    /// geometry.
+    ///
+    /// [`SweptCurve::path_to_global`]: crate::geometry::surfaces::SweptCurve::path_to_global
+    fn project_from_surface(
+        self,
+        from: &Handle<Surface>,
+        to: Handle<Surface>,
+        geometry: &mut Layer<Geometry>,
+    ) -> Result<Self, ProjectCurveError>;
+
+    /// Define the geometry on a surface, by placing it there unchanged
+    ///
+    /// Takes the local definition of the curve on `from`, and defines the same
+    /// path on `to`, in the coordinates of that surface. Unlike
+    /// [`UpdateCurveGeometry::project_from_surface`], this doesn't care about
+    /// where either surface is located in 3D space. This is how sketches are
+    /// placed on the surfaces they are swept from.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the curve is not defined on `from`.
+    fn place_on_surface(
+        self,
+        from: &Handle<Surface>,
+        to: Handle<Surface>,
+        geometry: &mut Layer<Geometry>,
+    ) -> Self;
+
     /// Define the geometry as a path on a surface
     fn make_path_on_surface(
         self,
@@ -57,6 +108,56 @@ impl UpdateCurveGeometry for Handle<Curve> {
         self
     }
 
+    fn project_from_surface(
+        self,
+        from: &Handle<Surface>,
+        to: Handle<Surface>,
+        geometry: &mut Layer<Geometry>,
+    ) -> Result<Self, ProjectCurveError> {
+        let path = &geometry
+            .of_curve(&self)
+            .and_then(|curve_geom| curve_geom.local_on(from))
+            .expect("Expected curve to be defined on surface")
+            .path;
+
+        let path = if from == &to {
+            path.clone()
+        } else {
+            match (geometry.try_of_surface(from), geometry.try_of_surface(&to))
+            {
+                (Some(from), Some(to)) => {
+                    let path = from
+                        .path_to_global(path)
+                        .ok_or(ProjectCurveError::NotConvertibleToGlobal)?;
+                    to.project_path(&path)
+                        .ok_or(ProjectCurveError::NotProjectedOntoPlane)?
+                }
+                (None, _) => path.clone(),
+                (Some(_), None) => {
+                    panic!("Expected geometry of surface to be defined")
+                }
+            }
+        };
+
+        Ok(self.make_path_on_surface(path, to, geometry))
+    }
+
+    fn place_on_surface(
+        self,
+        from: &Handle<Surface>,
+        to: Handle<Surface>,
+        geometry: &mut Layer<Geometry>,
+    ) -> Self {
+        let path = geometry
+            .of_curve(&self)
+            .and_then(|curve_geom| curve_geom.local_on(from))
+            .expect("Expected curve to be defined on surface")
+            .path
+            .clone();
+
+        self.make_path_on_surface(path, to, geometry)
+    }
+
     fn make_path_on_surface(
         self,
         path: Path<2>,
@@ -82,3 +183,60 @@ impl UpdateCurveGeometry for Handle<Curve> {
         self.make_path_on_surface(path, surface, geometry)
     }
 }
+
+/// # Error projecting a curve from one surface onto another
+///
+/// See [`UpdateCurveGeometry::project_from_surface`].
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum ProjectCurveError {
+    /// # The curve can't be converted into global coordinates
+    #[error("Can't convert curve into global coordinates")]
+    NotConvertibleToGlobal,
+
+    /// # The surface that the curve is projected onto is not a plane
+    #[error("Can only project curves onto planes")]
+    NotProjectedOntoPlane,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Vector;
+
+    use crate::{
+        geometry::{CurveBoundary, Path},
+        operations::{build::BuildSurface, insert::Insert},
+        topology::{Curve, Surface},
+        Core,
+    };
+
+    use super::{ProjectCurveError, UpdateCurveGeometry};
+
+    #[test]
+    fn project_onto_curved_surface() {
+        let mut core = Core::new();
+
+        let plane = core.layers.topology.surfaces.xy_plane();
+        let cylinder = Surface::from_uv(
+            Path::circle_from_center_and_radius([0., 0., 0.], 1.),
+            Vector::unit_z(),
+            &mut core,
+        );
+
+        let curve = Curve::new().insert(&mut core).make_line_on_surface(
+            [[0., 0.], [1., 0.]],
+            CurveBoundary::from([[0.], [1.]]),
+            plane.clone(),
+            &mut core.layers.geometry,
+        );
+
+        let result = curve.project_from_surface(
+            &plane,
+            cylinder,
+            &mut core.layers.geometry,
+        );
+        assert!(matches!(
+            result,
+            Err(ProjectCurveError::NotProjectedOntoPlane)
+        ));
+    }
+}
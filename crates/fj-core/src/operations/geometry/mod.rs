@@ -2,4 +2,4 @@
 
 mod curve;
 
-pub use self::curve::UpdateCurveGeometry;
+pub use self::curve::{ProjectCurveError, UpdateCurveGeometry};
@@ -1,16 +1,25 @@
 //! Add holes to shapes
 
-use fj_math::{Point, Scalar, Vector};
+use fj_math::{Circle, Line, Point, Scalar, Vector};
 
 use crate::{
-    geometry::repr::tri_mesh::convert_point_surface_to_global,
+    algorithms::ray_cast::{Ray, RayCast},
+    geometry::{
+        repr::tri_mesh::convert_point_surface_to_global, surfaces::SweptCurve,
+        Path,
+    },
     storage::Handle,
-    topology::{Cycle, Face, Region, Shell},
+    topology::{Cycle, Face, HalfEdge, Region, Shell, Surface, Vertex},
     Core,
 };
 
 use super::{
-    build::{BuildCycle, BuildRegion},
+    build::{BuildCycle, BuildRegion, BuildSurface},
+    geometry::UpdateCurveGeometry,
+    insert::Insert,
+    presentation::GetColor,
+    reverse::Reverse,
+    revolve::RevolveRegion,
     sweep::{SweepCache, SweepRegion},
     update::{UpdateFace, UpdateRegion, UpdateShell},
 };
@@ -33,6 +42,31 @@ pub trait AddHole {
         radius: impl Into<Scalar>,
         core: &mut Core,
     ) -> Self;
+
+    /// Add a hole, as defined by `spec`, at the provided location
+    ///
+    /// The hole goes into the face along its normal. Unlike with the other
+    /// methods of this trait, all dimensions of the hole are distances in
+    /// global coordinates.
+    ///
+    /// A through hole goes up to the next face in the way, where it exits the
+    /// shell.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the face is not planar, or if its surface has axes that are
+    /// not orthogonal and of the same length. Through holes require the same
+    /// of the face they exit through, and also that this face is perpendicular
+    /// to the hole.
+    ///
+    /// Panics, if the dimensions of the hole are inconsistent. See
+    /// [`HoleSpec`] for details.
+    fn add_hole(
+        &self,
+        location: HoleLocation,
+        spec: HoleSpec,
+        core: &mut Core,
+    ) -> Self;
 }
 
 impl AddHole for Shell {
@@ -140,18 +174,18 @@ impl AddHole for Shell {
                 [face.update_region(
                     |region, core| {
                         for half_edge in exit.half_edges() {
-                            let geometry = core
-                                .layers
-                                .geometry
-                                .of_curve(half_edge.curve())
-                                .unwrap()
-                                .local_on(swept_region.top_face.surface())
-                                .unwrap();
-                            core.layers.geometry.define_curve(
-                                half_edge.curve().clone(),
-                                exit_location.face.surface().clone(),
-                                geometry.clone(),
-                            );
+                            half_edge
+                                .curve()
+                                .clone()
+                                .project_from_surface(
+                                    swept_region.top_face.surface(),
+                                    exit_location.face.surface().clone(),
+                                    &mut core.layers.geometry,
+                                )
+                                .expect(
+                                    "Face that hole exits through must be \
+                                    planar",
+                                );
                         }
                         region.add_interiors([exit.clone()], core)
                     },
@@ -162,6 +196,156 @@ impl AddHole for Shell {
         )
         .add_faces(hole, core)
     }
+
+    fn add_hole(
+        &self,
+        location: HoleLocation,
+        spec: HoleSpec,
+        core: &mut Core,
+    ) -> Self {
+        let entry_plane = HolePlane::of(location.face, core);
+        let entry_point = convert_point_surface_to_global(
+            &core
+                .layers
+                .geometry
+                .of_surface_2(location.face.surface())
+                .unwrap()
+                .generator,
+            location.position,
+            core.tolerance(),
+            &core.layers.geometry,
+        );
+
+        // The hole goes into the face, against its normal. The profile of the
+        // hole is revolved around this axis, starting from `radial`.
+        let axis = -entry_plane.normal;
+        let radial = entry_plane.axes[0].normalize();
+        let tangent = axis.cross(&radial);
+
+        let exit = match spec.end {
+            HoleEnd::Through => {
+                let hit = self
+                    .ray_cast(
+                        &Ray {
+                            origin: entry_point,
+                            direction: axis,
+                        },
+                        core.tolerance(),
+                        &core.layers.geometry,
+                    )
+                    .into_iter()
+                    .find(|hit| {
+                        &hit.face != location.face
+                            && hit.distance
+                                > core.tolerance().inner() * Scalar::from(1e-3)
+                    })
+                    .expect("No face for through hole to exit through");
+
+                let exit_plane = HolePlane::of(&hit.face, core);
+                assert!(
+                    exit_plane.normal.cross(&axis).magnitude()
+                        <= core.tolerance().inner(),
+                    "Through hole must exit through a perpendicular face"
+                );
+
+                Some((hit, exit_plane))
+            }
+            HoleEnd::Flat { .. } | HoleEnd::Tapered { .. } => None,
+        };
+
+        let profile = {
+            let depth = match spec.end {
+                HoleEnd::Flat { depth } | HoleEnd::Tapered { depth, .. } => {
+                    depth
+                }
+                HoleEnd::Through => {
+                    let (hit, _) =
+                        exit.as_ref().expect("Through hole has an exit");
+                    hit.distance
+                }
+            };
+
+            spec.profile(depth)
+        };
+        let radius_at_entry = profile[1].u;
+
+        // The profile is defined on a plane that contains the axis. Its
+        // u-coordinate is the distance from the axis, its v-coordinate the
+        // distance from the entry.
+        let surface = Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(entry_point, radial)),
+            axis,
+            core,
+        );
+        let color = location.face.region().get_color(core);
+        let shell = Region::polygon(profile, surface.clone(), core)
+            .revolve_region(
+                &surface,
+                color,
+                [[0., 0.], [0., 1.]],
+                Scalar::TAU,
+                core,
+            );
+
+        // That shell is the outside of the material that the hole removes. We
+        // need the inside.
+        let mut faces = shell
+            .faces()
+            .iter()
+            .map(|face| face.reverse(core))
+            .collect::<Vec<_>>();
+
+        // Revolving creates four faces per segment of the profile, one for
+        // each quarter turn, in the order of the segments. The first segment
+        // of the profile is located at the entry, and for a through hole, the
+        // last one is located at the exit. Those faces aren't part of the
+        // hole, but their boundaries are.
+        let entry = faces.drain(..4).collect::<Vec<_>>();
+        let entry = boundary_of_disk(
+            &entry,
+            location.face,
+            &entry_plane,
+            location.position,
+            [radial, tangent].map(|vector| vector * radius_at_entry),
+            core,
+        );
+
+        let mut shell = self.update_face(
+            location.face,
+            |face, core| {
+                [face.update_region(
+                    |region, core| region.add_interiors([entry], core),
+                    core,
+                )]
+            },
+            core,
+        );
+
+        if let Some((hit, exit_plane)) = exit {
+            let exit = faces.drain(faces.len() - 4..).collect::<Vec<_>>();
+            let exit = boundary_of_disk(
+                &exit,
+                &hit.face,
+                &exit_plane,
+                hit.point_surface,
+                [radial, tangent].map(|vector| vector * spec.radius),
+                core,
+            );
+
+            shell = shell.update_face(
+                &hit.face,
+                |face, core| {
+                    [face.update_region(
+                        |region, core| region.add_interiors([exit], core),
+                        core,
+                    )]
+                },
+                core,
+            );
+        }
+
+        shell.add_faces(faces, core)
+    }
 }
 
 /// Defines the location of a hole
@@ -172,3 +356,470 @@ pub struct HoleLocation<'r> {
     /// The position of the hole within the face, in surface coordinates
     pub position: Point<2>,
 }
+
+/// Defines a hole
+///
+/// See [`AddHole::add_hole`].
+#[derive(Clone, Copy, Debug)]
+pub struct HoleSpec {
+    /// The radius of the hole
+    pub radius: Scalar,
+
+    /// The shape of the hole at the face it enters through
+    pub entry: HoleEntry,
+
+    /// How far the hole goes, and the shape of its end
+    pub end: HoleEnd,
+}
+
+impl HoleSpec {
+    /// # Compute the profile of the hole
+    ///
+    /// The profile starts at the axis of the hole, at its entry, and goes
+    /// along its wall to its end. The u-coordinate of the profile is the
+    /// distance from the axis, its v-coordinate the distance from the entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the dimensions of the hole are inconsistent.
+    fn profile(&self, depth: Scalar) -> Vec<Point<2>> {
+        let radius = self.radius;
+        assert!(radius > Scalar::ZERO, "Hole must have a positive radius");
+
+        let mut profile = vec![Point::origin()];
+
+        let entry_depth = match self.entry {
+            HoleEntry::Plain => {
+                profile.push(Point::from([radius, Scalar::ZERO]));
+                Scalar::ZERO
+            }
+            HoleEntry::Counterbore {
+                radius: counterbore_radius,
+                depth: counterbore_depth,
+            } => {
+                assert!(
+                    counterbore_radius > radius,
+                    "Counterbore must be wider than the hole"
+                );
+                assert!(
+                    counterbore_depth > Scalar::ZERO,
+                    "Counterbore must have a positive depth"
+                );
+
+                profile.extend([
+                    Point::from([counterbore_radius, Scalar::ZERO]),
+                    Point::from([counterbore_radius, counterbore_depth]),
+                    Point::from([radius, counterbore_depth]),
+                ]);
+                counterbore_depth
+            }
+            HoleEntry::Countersink {
+                radius: countersink_radius,
+                angle,
+            } => {
+                assert!(
+                    countersink_radius > radius,
+                    "Countersink must be wider than the hole"
+                );
+
+                let countersink_depth =
+                    (countersink_radius - radius) / half_angle_tan(angle);
+
+                profile.extend([
+                    Point::from([countersink_radius, Scalar::ZERO]),
+                    Point::from([radius, countersink_depth]),
+                ]);
+                countersink_depth
+            }
+        };
+
+        assert!(
+            depth > entry_depth,
+            "Hole must be deeper than its counterbore or countersink"
+        );
+        profile.push(Point::from([radius, depth]));
+
+        let bottom = match self.end {
+            HoleEnd::Flat { .. } | HoleEnd::Through => depth,
+            HoleEnd::Tapered { angle, .. } => {
+                depth + radius / half_angle_tan(angle)
+            }
+        };
+        profile.push(Point::from([Scalar::ZERO, bottom]));
+
+        profile
+    }
+}
+
+/// The shape of a hole at the face it enters through
+///
+/// See [`HoleSpec`].
+#[derive(Clone, Copy, Debug)]
+pub enum HoleEntry {
+    /// The hole has the same radius at its entry, as everywhere else
+    Plain,
+
+    /// The hole is widened into a cylinder at its entry
+    ///
+    /// This leaves room for the head of a screw, so it doesn't stick out.
+    Counterbore {
+        /// The radius of the counterbore
+        ///
+        /// Must be larger than the radius of the hole.
+        radius: Scalar,
+
+        /// The depth of the counterbore
+        ///
+        /// Must be positive, and less than the depth of the hole.
+        depth: Scalar,
+    },
+
+    /// The hole is widened into a cone at its entry
+    ///
+    /// This fits the head of a countersunk screw, which ends up flush with
+    /// the face.
+    Countersink {
+        /// The radius of the countersink at the face
+        ///
+        /// Must be larger than the radius of the hole.
+        radius: Scalar,
+
+        /// The angle between opposite sides of the cone, in radians
+        ///
+        /// This is 90 degrees for most metric screws.
+        angle: Scalar,
+    },
+}
+
+/// How far a hole goes, and the shape of its end
+///
+/// See [`HoleSpec`].
+#[derive(Clone, Copy, Debug)]
+pub enum HoleEnd {
+    /// The hole ends at the given depth, with a flat bottom
+    Flat {
+        /// The depth of the hole
+        depth: Scalar,
+    },
+
+    /// The hole ends with a tapered bottom, as left by a drill
+    Tapered {
+        /// The depth of the hole, up to where its bottom starts to taper
+        depth: Scalar,
+
+        /// The angle between opposite sides of the bottom, in radians
+        ///
+        /// This is 118 degrees for most drills.
+        angle: Scalar,
+    },
+
+    /// The hole goes through the shell, up to the next face in its way
+    Through,
+}
+
+/// # A planar face that a hole enters or exits through
+struct HolePlane {
+    /// The axes of the surface coordinate system, in global coordinates
+    axes: [Vector<3>; 2],
+
+    /// The normal of the face, pointing away from the shell
+    normal: Vector<3>,
+}
+
+impl HolePlane {
+    fn of(face: &Handle<Face>, core: &Core) -> Self {
+        let Some(SweptCurve {
+            u: Path::Line(line),
+            v,
+        }) = core.layers.geometry.try_of_surface(face.surface())
+        else {
+            panic!("Holes can only be added to planar faces");
+        };
+
+        let axes = [line.direction(), *v];
+        let [u, v] = axes;
+
+        let normal = u.cross(&v).normalize();
+        let normal = if face
+            .region()
+            .exterior()
+            .winding(core.tolerance(), &core.layers.geometry, face.surface())
+            .is_ccw()
+        {
+            normal
+        } else {
+            -normal
+        };
+
+        Self { axes, normal }
+    }
+
+    /// # Convert a vector within the plane into surface coordinates
+    fn vector_to_surface(&self, vector: Vector<3>) -> Vector<2> {
+        let [u, v] = self.axes;
+        Vector::from([u, v].map(|axis| vector.dot(&axis) / axis.dot(&axis)))
+    }
+}
+
+/// # Build the boundary of a disk, on a face that the disk is located on
+///
+/// The disk consists of the faces that revolving the first or last segment of
+/// the profile of a hole created. The boundary is built from the half-edges
+/// of those faces, that are not connected to the axis. The disk is centered
+/// on `center`, and `radius` is the vector from there to where the revolution
+/// started, followed by the vector a quarter turn further.
+fn boundary_of_disk(
+    disk: &[Face],
+    face: &Handle<Face>,
+    plane: &HolePlane,
+    center: Point<2>,
+    radius: [Vector<3>; 2],
+    core: &mut Core,
+) -> Handle<Cycle> {
+    let start_vertices = |face: &Face| {
+        face.region()
+            .exterior()
+            .half_edges()
+            .iter()
+            .map(|half_edge| half_edge.start_vertex().clone())
+            .collect::<Vec<_>>()
+    };
+
+    // Opposite faces of the disk only share the vertex on the axis.
+    let on_axis = start_vertices(&disk[0])
+        .into_iter()
+        .find(|vertex| start_vertices(&disk[2]).contains(vertex))
+        .expect("Faces of disk must share a vertex on the axis");
+
+    let mut arcs: Vec<(Handle<HalfEdge>, Handle<Vertex>)> = Vec::new();
+    for (sector, disk_face) in disk.iter().enumerate() {
+        let (arc, next) = disk_face
+            .region()
+            .exterior()
+            .half_edges()
+            .pairs()
+            .find(|(half_edge, next)| {
+                half_edge.start_vertex() != &on_axis
+                    && next.start_vertex() != &on_axis
+            })
+            .expect("Faces of disk must have an arc");
+
+        // Each arc covers a quarter turn, starting where the revolution was at
+        // the start of its sector.
+        let angle = Scalar::TAU / 4. * Scalar::from_u64(sector as u64);
+        let (sin, cos) = angle.sin_cos();
+        let [a, b] = radius;
+        let [a, b] = [a * cos + b * sin, b * cos - a * sin]
+            .map(|vector| plane.vector_to_surface(vector));
+
+        arc.curve().clone().make_path_on_surface(
+            Path::Circle(Circle::new(center, a, b)),
+            face.surface().clone(),
+            &mut core.layers.geometry,
+        );
+
+        arcs.push((arc.clone(), next.start_vertex().clone()));
+    }
+
+    // The arcs are in the order of the sectors, which isn't necessarily the
+    // order they connect in.
+    let mut half_edges = vec![arcs.remove(0)];
+    while !arcs.is_empty() {
+        let (_, end) = half_edges.last().expect("Just added a half-edge");
+        let next = arcs
+            .iter()
+            .position(|(half_edge, _)| half_edge.start_vertex() == end)
+            .expect("Arcs of disk must connect");
+        half_edges.push(arcs.remove(next));
+    }
+
+    Cycle::new(half_edges.into_iter().map(|(half_edge, _)| half_edge))
+        .insert(core)
+}
+
+/// # Compute the tangent of half the provided angle
+fn half_angle_tan(angle: Scalar) -> Scalar {
+    Scalar::from((angle / 2.).into_f64().tan())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            tests::{assert_volume, volume},
+            update::{UpdateSketch, UpdateSolid},
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{AddHole, HoleEnd, HoleEntry, HoleLocation, HoleSpec};
+
+    #[test]
+    fn countersunk_through_hole() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // With a right angle, the countersink is as deep as it is wide.
+        let spec = HoleSpec {
+            radius: Scalar::from(0.25),
+            entry: HoleEntry::Countersink {
+                radius: Scalar::from(0.5),
+                angle: Scalar::from(FRAC_PI_2),
+            },
+            end: HoleEnd::Through,
+        };
+        let (plate, with_hole) = plate_with_hole(spec, &mut core)?;
+
+        let countersink = frustum_volume(0.5, 0.25, 0.25);
+        let bore = cylinder_volume(0.25, 0.75);
+        let expected = volume(&plate, &mut core)? - countersink - bore;
+        assert_volume(&with_hole, expected, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn counterbored_hole_with_flat_bottom() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let spec = HoleSpec {
+            radius: Scalar::from(0.25),
+            entry: HoleEntry::Counterbore {
+                radius: Scalar::from(0.5),
+                depth: Scalar::from(0.25),
+            },
+            end: HoleEnd::Flat {
+                depth: Scalar::from(0.75),
+            },
+        };
+        let (plate, with_hole) = plate_with_hole(spec, &mut core)?;
+
+        let counterbore = cylinder_volume(0.5, 0.25);
+        let bore = cylinder_volume(0.25, 0.5);
+        let expected = volume(&plate, &mut core)? - counterbore - bore;
+        assert_volume(&with_hole, expected, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn hole_with_tapered_bottom() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // With a right angle, the tip is as long as the hole is wide.
+        let spec = HoleSpec {
+            radius: Scalar::from(0.25),
+            entry: HoleEntry::Plain,
+            end: HoleEnd::Tapered {
+                depth: Scalar::from(0.5),
+                angle: Scalar::from(FRAC_PI_2),
+            },
+        };
+        let (plate, with_hole) = plate_with_hole(spec, &mut core)?;
+
+        let bore = cylinder_volume(0.25, 0.5);
+        let tip = frustum_volume(0.25, 0., 0.25);
+        let expected = volume(&plate, &mut core)? - bore - tip;
+        assert_volume(&with_hole, expected, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Counterbore must be wider than the hole")]
+    fn counterbore_narrower_than_hole() {
+        counterbored_hole(0.2, 0.25);
+    }
+
+    #[test]
+    #[should_panic(expected = "Counterbore must have a positive depth")]
+    fn counterbore_without_depth() {
+        counterbored_hole(0.5, 0.);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Hole must be deeper than its counterbore or countersink"
+    )]
+    fn counterbore_deeper_than_hole() {
+        counterbored_hole(0.5, 0.8);
+    }
+
+    /// # Add a hole with a counterbore of the provided dimensions to a plate
+    ///
+    /// The hole has a radius of `0.25` and a depth of `0.75`.
+    fn counterbored_hole(radius: f64, depth: f64) {
+        let mut core = Core::new();
+
+        let spec = HoleSpec {
+            radius: Scalar::from(0.25),
+            entry: HoleEntry::Counterbore {
+                radius: Scalar::from(radius),
+                depth: Scalar::from(depth),
+            },
+            end: HoleEnd::Flat {
+                depth: Scalar::from(0.75),
+            },
+        };
+        let _ = plate_with_hole(spec, &mut core);
+    }
+
+    /// # Build a plate, and a copy of it with a hole in the top face
+    fn plate_with_hole(
+        spec: HoleSpec,
+        core: &mut Core,
+    ) -> anyhow::Result<(Solid, Solid)> {
+        let plate = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                core,
+            )
+            .insert(core);
+
+        let with_hole = plate
+            .update_shell(
+                plate.shells().only(),
+                |shell, core| {
+                    [shell.add_hole(
+                        HoleLocation {
+                            face: shell.faces().first(),
+                            position: Point::origin(),
+                        },
+                        spec,
+                        core,
+                    )]
+                },
+                core,
+            )
+            .insert(core);
+
+        core.layers.validation.take_errors()?;
+
+        Ok((Solid::clone(&plate), Solid::clone(&with_hole)))
+    }
+
+    fn cylinder_volume(radius: f64, height: f64) -> Scalar {
+        Scalar::PI * radius * radius * height
+    }
+
+    fn frustum_volume(radius_a: f64, radius_b: f64, height: f64) -> Scalar {
+        Scalar::PI * height / 3.
+            * (radius_a * radius_a + radius_a * radius_b + radius_b * radius_b)
+    }
+}
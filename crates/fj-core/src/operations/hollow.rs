@@ -0,0 +1,458 @@
+//! # Hollow out solids
+//!
+//! See [`Hollow`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Point, Scalar, Vector, Winding};
+
+use crate::{
+    geometry::{LocalVertexGeom, Path},
+    queries::SiblingOfHalfEdge,
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+use super::{
+    derive::DeriveFrom,
+    geometry::UpdateCurveGeometry,
+    insert::Insert,
+    plane::Plane,
+    presentation::{GetColor, SetColor},
+};
+
+/// # Hollow out a solid, leaving walls of a given thickness
+pub trait Hollow {
+    /// # Hollow out the solid
+    ///
+    /// Each face of the solid is offset into the material by `thickness`, and
+    /// the offset faces are trimmed against each other, forming the inside of
+    /// the walls.
+    ///
+    /// If `open_faces` is empty, the result is a closed solid with an inner
+    /// cavity. Otherwise, the provided faces are removed, opening the cavity
+    /// to the outside. What remains of each removed face is the rim where the
+    /// walls end.
+    ///
+    /// ## Limitations
+    ///
+    /// All faces of the solid must be planar. The offset faces that meet at
+    /// each vertex must meet in a single point, which is always the case if
+    /// exactly three faces meet at each vertex. Faces that are removed must
+    /// not share an edge with each other.
+    ///
+    /// The thickness must be positive, and small enough for every edge of the
+    /// solid to still exist on the inside of the walls.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if any of the provided faces is not part of the solid, or if
+    /// the solid or the thickness don't meet the requirements listed above.
+    #[must_use]
+    fn hollow(
+        &self,
+        thickness: impl Into<Scalar>,
+        open_faces: impl IntoIterator<Item = Handle<Face>>,
+        core: &mut Core,
+    ) -> Self;
+}
+
+impl Hollow for Solid {
+    fn hollow(
+        &self,
+        thickness: impl Into<Scalar>,
+        open_faces: impl IntoIterator<Item = Handle<Face>>,
+        core: &mut Core,
+    ) -> Self {
+        let thickness = thickness.into();
+        let open_faces = open_faces.into_iter().collect::<BTreeSet<_>>();
+
+        assert!(thickness > Scalar::ZERO, "Thickness must be positive");
+
+        for face in &open_faces {
+            assert!(
+                self.shells()
+                    .iter()
+                    .any(|shell| shell.faces().contains(face)),
+                "Faces to remove must be part of the solid"
+            );
+        }
+
+        let shells = self
+            .shells()
+            .iter()
+            .flat_map(|shell| hollow_shell(shell, thickness, &open_faces, core))
+            .collect::<Vec<_>>();
+
+        Solid::new(shells)
+    }
+}
+
+fn hollow_shell(
+    shell: &Handle<Shell>,
+    thickness: Scalar,
+    open_faces: &BTreeSet<Handle<Face>>,
+    core: &mut Core,
+) -> Vec<Handle<Shell>> {
+    // The plane that each face is offset to. Removed faces stay where they
+    // are, as that is where the walls end.
+    let mut planes = BTreeMap::new();
+    for face in shell.faces() {
+        let plane = Plane::of(face.surface(), core)
+            .expect("Only solids with planar faces can be hollowed");
+        let is_ccw = face.region().exterior().winding(
+            core.tolerance(),
+            &core.layers.geometry,
+            face.surface(),
+        ) == Winding::Ccw;
+        let outward = if is_ccw {
+            plane.normal()
+        } else {
+            -plane.normal()
+        };
+
+        let offset = if open_faces.contains(face) {
+            Scalar::ZERO
+        } else {
+            thickness
+        };
+
+        planes.insert(face.clone(), (plane, plane.offset(-outward * offset)));
+    }
+
+    // Each vertex has a counterpart on the inside of the walls, where the
+    // offset planes of the faces around it meet.
+    let mut planes_at_vertex = BTreeMap::new();
+    for face in shell.faces() {
+        if open_faces.contains(face) {
+            for half_edge in face.region().all_cycles().flat_map(|cycle| {
+                cycle.half_edges().iter().cloned().collect::<Vec<_>>()
+            }) {
+                let sibling = shell
+                    .get_sibling_of(&half_edge)
+                    .expect("Shell must be closed");
+                assert!(
+                    !open_faces.contains(&sibling.face),
+                    "Faces to remove must not share an edge"
+                );
+            }
+        }
+
+        for cycle in face.region().all_cycles() {
+            for half_edge in cycle.half_edges() {
+                planes_at_vertex
+                    .entry(half_edge.start_vertex().clone())
+                    .or_insert_with(Vec::new)
+                    .push(planes[face]);
+            }
+        }
+    }
+    let mut vertices = BTreeMap::new();
+    let mut original_points = BTreeMap::new();
+    for (vertex, planes) in planes_at_vertex {
+        let (original, offset): (Vec<_>, Vec<_>) = planes.into_iter().unzip();
+
+        let point = intersect_planes(&offset, core);
+        original_points
+            .insert(vertex.clone(), intersect_planes(&original, core));
+        vertices.insert(vertex, (Vertex::new().insert(core), point));
+    }
+
+    // Each curve gets a counterpart too, connecting the new vertices.
+    let mut curves = BTreeMap::new();
+    for face in shell.faces() {
+        for cycle in face.region().all_cycles() {
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                if curves.contains_key(half_edge.curve()) {
+                    continue;
+                }
+
+                // If the walls are too thick, the counterpart of an edge
+                // collapses or points the other way.
+                let [original, offset] = [
+                    [half_edge.start_vertex(), next.start_vertex()]
+                        .map(|vertex| original_points[vertex]),
+                    [half_edge.start_vertex(), next.start_vertex()]
+                        .map(|vertex| vertices[vertex].1),
+                ]
+                .map(|[start, end]| end - start);
+                assert!(
+                    offset.dot(&original.normalize())
+                        > core.tolerance().inner(),
+                    "Thickness is too large; edges of the solid would vanish \
+                    on the inside of the walls"
+                );
+
+                let curve = Curve::new().insert(core);
+                let points = [half_edge.start_vertex(), next.start_vertex()]
+                    .map(|vertex| vertices[vertex].clone());
+                for ((vertex, _), position) in points.iter().zip([0., 1.]) {
+                    core.layers.geometry.define_vertex(
+                        vertex.clone(),
+                        curve.clone(),
+                        LocalVertexGeom {
+                            position: Point::from([position]),
+                        },
+                    );
+                }
+
+                curves.insert(
+                    half_edge.curve().clone(),
+                    (curve, points.map(|(_, point)| point)),
+                );
+            }
+        }
+    }
+
+    let mut faces_outside = Vec::new();
+    let mut faces_inside = Vec::new();
+
+    for face in shell.faces() {
+        let (_, plane) = planes[face];
+        let surface = if open_faces.contains(face) {
+            face.surface().clone()
+        } else {
+            plane.surface(core)
+        };
+
+        for cycle in face.region().all_cycles() {
+            for half_edge in cycle.half_edges() {
+                let (curve, points) = &curves[half_edge.curve()];
+                let [start, end] =
+                    points.map(|point| plane.project_point(point));
+
+                curve.clone().make_path_on_surface(
+                    Path::line_from_points_with_coords([
+                        ([0.], start),
+                        ([1.], end),
+                    ]),
+                    surface.clone(),
+                    &mut core.layers.geometry,
+                );
+            }
+        }
+
+        let cycles = face
+            .region()
+            .all_cycles()
+            .map(|cycle| {
+                reversed_copy(cycle, &curves, &vertices, core)
+                    .derive_from(cycle, core)
+            })
+            .collect::<Vec<_>>();
+
+        if open_faces.contains(face) {
+            // The removed face leaves a rim between each of its cycles and its
+            // counterpart. The counterparts are reversed, so the exterior cycle
+            // stays the exterior of its rim, while the counterpart of each
+            // interior cycle becomes the exterior of the rim around it.
+            let mut cycles = cycles.into_iter();
+            let mut rims = vec![(
+                face.region().exterior().clone(),
+                cycles.next().expect("Region must have an exterior cycle"),
+            )];
+            for (interior, counterpart) in
+                face.region().interiors().iter().zip(cycles)
+            {
+                rims.push((counterpart, interior.clone()));
+            }
+
+            for (exterior, interior) in rims {
+                faces_outside.push(new_face(
+                    face,
+                    surface.clone(),
+                    exterior,
+                    [interior],
+                    core,
+                ));
+            }
+        } else {
+            let mut cycles = cycles.into_iter();
+            let exterior =
+                cycles.next().expect("Region must have an exterior cycle");
+            faces_inside.push(new_face(face, surface, exterior, cycles, core));
+
+            faces_outside.push(face.clone());
+        }
+    }
+
+    if open_faces.is_empty() {
+        vec![shell.clone(), Shell::new(faces_inside).insert(core)]
+    } else {
+        faces_outside.extend(faces_inside);
+        vec![Shell::new(faces_outside)
+            .insert(core)
+            .derive_from(shell, core)]
+    }
+}
+
+/// # Copy a cycle onto the new curves and vertices, reversing it
+fn reversed_copy(
+    cycle: &Handle<Cycle>,
+    curves: &BTreeMap<Handle<Curve>, (Handle<Curve>, [Point<3>; 2])>,
+    vertices: &BTreeMap<Handle<Vertex>, (Handle<Vertex>, Point<3>)>,
+    core: &mut Core,
+) -> Handle<Cycle> {
+    let half_edges = cycle
+        .half_edges()
+        .pairs()
+        .map(|(half_edge, next)| {
+            let (start, _) = &vertices[next.start_vertex()];
+            let (curve, _) = &curves[half_edge.curve()];
+            HalfEdge::new(curve.clone(), start.clone()).insert(core)
+        })
+        .collect::<Vec<_>>();
+
+    Cycle::new(half_edges.into_iter().rev()).insert(core)
+}
+
+fn new_face(
+    original: &Handle<Face>,
+    surface: Handle<Surface>,
+    exterior: Handle<Cycle>,
+    interiors: impl IntoIterator<Item = Handle<Cycle>>,
+    core: &mut Core,
+) -> Handle<Face> {
+    let region = Region::new(exterior, interiors)
+        .insert(core)
+        .derive_from(original.region(), core);
+    if let Some(color) = original.region().get_color(core) {
+        region.set_color(color, core);
+    }
+
+    Face::new(surface, region)
+        .insert(core)
+        .derive_from(original, core)
+}
+
+/// # Find the point where the provided planes meet
+///
+/// Computes the least-squares solution, then checks that it is actually on all
+/// of the planes.
+fn intersect_planes(planes: &[Plane], core: &Core) -> Point<3> {
+    let normals = planes
+        .iter()
+        .map(|plane| plane.normal().normalize())
+        .collect::<Vec<_>>();
+
+    let column = |index: usize| {
+        normals
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, normal| {
+                sum + *normal * normal.components[index]
+            })
+    };
+    let [a, b, c] = [0, 1, 2].map(column);
+    let d = planes.iter().zip(&normals).fold(
+        Vector::from([0., 0., 0.]),
+        |sum, (plane, normal)| {
+            sum + *normal * normal.dot(&plane.origin().coords)
+        },
+    );
+
+    // Cramer's rule
+    let determinant = a.dot(&b.cross(&c));
+    assert!(
+        determinant != Scalar::ZERO,
+        "Faces around a vertex must not be parallel"
+    );
+    let point = Point {
+        coords: Vector::from([
+            d.dot(&b.cross(&c)),
+            a.dot(&d.cross(&c)),
+            a.dot(&b.cross(&d)),
+        ]) / determinant,
+    };
+
+    let tolerance = core.tolerance();
+    for (plane, normal) in planes.iter().zip(&normals) {
+        assert!(
+            normal.dot(&(point - plane.origin())).abs()
+                < tolerance.inner() / 2.,
+            "Offset faces around a vertex don't meet in a single point"
+        );
+    }
+
+    point
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        operations::{
+            insert::Insert,
+            tests::{assert_volume, cuboid},
+        },
+        Core,
+    };
+
+    use super::Hollow;
+
+    #[test]
+    fn hollow_closed() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = cuboid(&mut core)
+            .hollow(0.1, [], &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().len(), 2);
+        core.layers.validation.take_errors()?;
+
+        let cavity = Scalar::from(1.8 * 1.8 * 0.8);
+        assert_volume(&solid, Scalar::from(4.) - cavity, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn hollow_with_open_top() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let top = cuboid
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                *face.surface() == core.layers.topology.surfaces.xy_plane()
+            })
+            .cloned()
+            .unwrap();
+
+        let solid = cuboid.hollow(0.1, [top], &mut core).insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 11);
+        core.layers.validation.take_errors()?;
+
+        let cavity = Scalar::from(1.8 * 1.8 * 0.9);
+        assert_volume(&solid, Scalar::from(4.) - cavity, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Thickness must be positive")]
+    fn hollow_with_negative_thickness() {
+        let mut core = Core::new();
+
+        let cuboid = cuboid(&mut core);
+        let _ = cuboid.hollow(-0.1, [], &mut core);
+    }
+
+    #[test]
+    #[should_panic(expected = "Thickness is too large")]
+    fn hollow_with_too_large_thickness() {
+        let mut core = Core::new();
+
+        // The cuboid is only 1 high, so walls of this thickness overlap.
+        let cuboid = cuboid(&mut core);
+        let _ = cuboid.hollow(0.6, [], &mut core);
+    }
+}
@@ -0,0 +1,442 @@
+//! Loft between regions
+
+use std::collections::BTreeMap;
+
+use fj_math::{Line, Point, Scalar, Vector, Winding};
+
+use crate::{
+    geometry::{
+        repr::tri_mesh::{
+            convert_point_surface_to_global, convert_vector_surface_to_global,
+            TriMesh,
+        },
+        surfaces::RuledSurface,
+        CurveBoundary, LocalVertexGeom, Path, SurfaceGeom,
+    },
+    storage::Handle,
+    topology::{Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface},
+    Core,
+};
+
+use super::{
+    geometry::UpdateCurveGeometry,
+    insert::Insert,
+    presentation::{GetColor, SetColor},
+    reverse::Reverse,
+};
+
+/// # Loft between [`Region`]s
+///
+/// A loft connects a series of regions, the sections, with side faces. The
+/// side faces are ruled surfaces, consisting of straight lines between
+/// corresponding points of neighboring sections.
+pub trait Loft {
+    /// # Create a [`Solid`] by lofting between the provided sections
+    ///
+    /// Each section is a region, together with the surface that it is defined
+    /// on. The first and last section become the faces at the start and the
+    /// end of the loft.
+    ///
+    /// Sections correspond to each other by the order of their cycles and
+    /// half-edges: The first half-edge of a section's exterior is connected to
+    /// the first half-edge of the next section's exterior, and so on. All
+    /// sections should be oriented the same way, which means their exterior
+    /// cycles should have the same winding, when looking at them along the
+    /// direction of the loft.
+    ///
+    /// The side faces get the color of the first section, if it has one.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if fewer than two sections are provided, or if the sections
+    /// don't have the same number of cycles and half-edges.
+    fn loft(
+        sections: impl IntoIterator<Item = (Handle<Region>, Handle<Surface>)>,
+        core: &mut Core,
+    ) -> Solid;
+}
+
+impl Loft for Solid {
+    fn loft(
+        sections: impl IntoIterator<Item = (Handle<Region>, Handle<Surface>)>,
+        core: &mut Core,
+    ) -> Solid {
+        let sections = sections.into_iter().collect::<Vec<_>>();
+        assert!(sections.len() >= 2, "Loft requires at least two sections");
+
+        let (first_region, first_surface) = &sections[0];
+        for (region, _) in &sections[1..] {
+            assert_eq!(
+                region.all_cycles().count(),
+                first_region.all_cycles().count(),
+                "Sections of loft must have the same number of cycles"
+            );
+
+            for (a, b) in first_region.all_cycles().zip(region.all_cycles()) {
+                assert_eq!(
+                    a.half_edges().len(),
+                    b.half_edges().len(),
+                    "Corresponding cycles of loft sections must have the same \
+                    number of half-edges"
+                );
+            }
+        }
+
+        // The orientation of the side faces depends on the winding of the
+        // sections and on the direction that the loft goes in. If we don't
+        // adapt to that, we'd end up with faces that point inwards.
+        let reverse_side_faces = {
+            let normal = {
+                let surface = &core
+                    .layers
+                    .geometry
+                    .of_surface_2(first_surface)
+                    .unwrap()
+                    .generator;
+                let [u, v] = [Vector::unit_u(), Vector::unit_v()].map(|axis| {
+                    convert_vector_surface_to_global(
+                        surface,
+                        axis,
+                        core.tolerance(),
+                        &core.layers.geometry,
+                    )
+                });
+
+                u.cross(&v)
+            };
+            let direction = {
+                let [a, b] = [&sections[0], &sections[1]]
+                    .map(|(region, surface)| center_of(region, surface, core));
+                b - a
+            };
+
+            let is_ccw = first_region.exterior().winding(
+                core.tolerance(),
+                &core.layers.geometry,
+                first_surface,
+            ) == Winding::Ccw;
+            let is_lofted_forward = normal.dot(&direction) > Scalar::ZERO;
+
+            is_ccw != is_lofted_forward
+        };
+
+        let color = first_region.get_color(core);
+
+        let mut faces = Vec::new();
+
+        // The curves that connect the vertices of neighboring sections are
+        // shared between neighboring side faces, so we need to keep track of
+        // them.
+        let mut rails = BTreeMap::new();
+
+        for (index, pair) in sections.windows(2).enumerate() {
+            let [(region_a, surface_a), (region_b, surface_b)] =
+                [&pair[0], &pair[1]];
+
+            for (cycle_a, cycle_b) in
+                region_a.all_cycles().zip(region_b.all_cycles())
+            {
+                for ((a, next_a), (b, next_b)) in cycle_a
+                    .half_edges()
+                    .pairs()
+                    .zip(cycle_b.half_edges().pairs())
+                {
+                    let [(path_a, boundary_a), (path_b, boundary_b)] =
+                        [(a, next_a, surface_a), (b, next_b, surface_b)].map(
+                            |(half_edge, next, surface)| {
+                                let geometry = &core.layers.geometry;
+
+                                let path = geometry
+                                    .of_curve(half_edge.curve())
+                                    .unwrap()
+                                    .local_on(surface)
+                                    .unwrap()
+                                    .path
+                                    .clone();
+                                let boundary = [
+                                    half_edge.start_vertex(),
+                                    next.start_vertex(),
+                                ]
+                                .map(|vertex| {
+                                    geometry
+                                        .of_vertex(vertex)
+                                        .unwrap()
+                                        .local_on(half_edge.curve())
+                                        .unwrap()
+                                        .position
+                                });
+
+                                (path, CurveBoundary::from(boundary))
+                            },
+                        );
+
+                    let side_surface = Surface::new().insert(core);
+                    core.layers.geometry.define_surface_2(
+                        side_surface.clone(),
+                        SurfaceGeom {
+                            generator: Box::new(RuledSurface {
+                                surfaces: [
+                                    surface_a.clone(),
+                                    surface_b.clone(),
+                                ],
+                                paths: [path_a, path_b],
+                                boundaries: [boundary_a, boundary_b],
+                            }),
+                            geometry: TriMesh::empty(),
+                        },
+                    );
+
+                    for (half_edge, boundary, v) in
+                        [(a, boundary_a, 0.), (b, boundary_b, 1.)]
+                    {
+                        let [start, end] = boundary.inner;
+
+                        half_edge.curve().clone().make_path_on_surface(
+                            Path::line_from_points_with_coords([
+                                (start, [0., v]),
+                                (end, [1., v]),
+                            ]),
+                            side_surface.clone(),
+                            &mut core.layers.geometry,
+                        );
+                    }
+
+                    let mut rail =
+                        |[from, to]: [&HalfEdge; 2],
+                         u: f64,
+                         core: &mut Core| {
+                            rails
+                                .entry((index, from.start_vertex().clone()))
+                                .or_insert_with(|| {
+                                    let rail = Curve::new().insert(core);
+
+                                    for (vertex, position) in [
+                                        (from.start_vertex(), 0.),
+                                        (to.start_vertex(), 1.),
+                                    ] {
+                                        core.layers.geometry.define_vertex(
+                                            vertex.clone(),
+                                            rail.clone(),
+                                            LocalVertexGeom {
+                                                position: Point::from([
+                                                    position,
+                                                ]),
+                                            },
+                                        );
+                                    }
+
+                                    rail
+                                })
+                                .clone()
+                                .make_path_on_surface(
+                                    Path::Line(
+                                        Line::from_origin_and_direction(
+                                            Point::from([u, 0.]),
+                                            Vector::unit_v(),
+                                        ),
+                                    ),
+                                    side_surface.clone(),
+                                    &mut core.layers.geometry,
+                                )
+                        };
+
+                    let half_edges = [
+                        (a.curve().clone(), a.start_vertex().clone()),
+                        (
+                            rail([next_a, next_b], 1., core),
+                            next_a.start_vertex().clone(),
+                        ),
+                        (b.curve().clone(), next_b.start_vertex().clone()),
+                        (rail([a, b], 0., core), b.start_vertex().clone()),
+                    ]
+                    .map(|(curve, start_vertex)| {
+                        HalfEdge::new(curve, start_vertex).insert(core)
+                    });
+
+                    let exterior = {
+                        let cycle = Cycle::new(half_edges);
+                        if reverse_side_faces {
+                            cycle.reverse(core)
+                        } else {
+                            cycle
+                        }
+                    };
+                    let region =
+                        Region::new(exterior.insert(core), []).insert(core);
+                    if let Some(color) = color {
+                        region.set_color(color, core);
+                    }
+
+                    faces.push(Face::new(side_surface, region).insert(core));
+                }
+            }
+        }
+
+        let (last_region, last_surface) = &sections[sections.len() - 1];
+        for (region, surface, reverse) in [
+            (first_region, first_surface, !reverse_side_faces),
+            (last_region, last_surface, reverse_side_faces),
+        ] {
+            let region = if reverse {
+                let reversed = region.reverse(core).insert(core);
+                if let Some(color) = region.get_color(core) {
+                    reversed.set_color(color, core);
+                }
+
+                reversed
+            } else {
+                region.clone()
+            };
+
+            faces.push(Face::new(surface.clone(), region).insert(core));
+        }
+
+        Solid::new([Shell::new(faces).insert(core)])
+    }
+}
+
+/// # Compute the center of the vertices of a region's exterior
+fn center_of(
+    region: &Handle<Region>,
+    surface: &Handle<Surface>,
+    core: &Core,
+) -> Point<3> {
+    let geometry = &core.layers.geometry;
+    let generator = &geometry.of_surface_2(surface).unwrap().generator;
+
+    let half_edges = region.exterior().half_edges();
+    let sum = half_edges
+        .iter()
+        .map(|half_edge| {
+            let position = geometry
+                .of_vertex(half_edge.start_vertex())
+                .unwrap()
+                .local_on(half_edge.curve())
+                .unwrap()
+                .position;
+            let point_surface = geometry
+                .of_curve(half_edge.curve())
+                .unwrap()
+                .local_on(surface)
+                .unwrap()
+                .path
+                .point_from_path_coords(position);
+
+            convert_point_surface_to_global(
+                generator,
+                point_surface,
+                core.tolerance(),
+                geometry,
+            )
+        })
+        .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point.coords);
+
+    Point::origin() + sum / half_edges.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::mass_properties::MassProperties,
+        geometry::{Path, Tolerance},
+        operations::{
+            build::{BuildRegion, BuildSurface},
+            insert::Insert,
+            tests::assert_volume,
+        },
+        storage::Handle,
+        topology::{Region, Solid, Surface},
+        Core,
+    };
+
+    use super::Loft;
+
+    #[test]
+    fn loft_between_squares() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let bottom = core.layers.topology.surfaces.xy_plane();
+        let top = plane_at_height(2., &mut core);
+
+        let sections = [(bottom, 1.), (top, 0.5)].map(|(surface, size)| {
+            let region = Region::polygon(
+                [[-size, -size], [size, -size], [size, size], [-size, size]],
+                surface.clone(),
+                &mut core,
+            )
+            .insert(&mut core);
+
+            (region, surface)
+        });
+        let solid = Solid::loft(sections, &mut core).insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 6);
+        core.layers.validation.take_errors()?;
+
+        // A frustum of a pyramid.
+        assert_volume(&solid, Scalar::from(14. / 3.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn loft_from_square_to_circle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let bottom = core.layers.topology.surfaces.xy_plane();
+        let top = plane_at_height(1., &mut core);
+
+        // The corners of the square correspond to the start and end points of
+        // the arcs that make up the circle.
+        let square = Region::polygon(
+            [[1., 0.], [0., 1.], [-1., 0.], [0., -1.]],
+            bottom.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+        let circle = Region::circle([0., 0.], 1., top.clone(), &mut core)
+            .insert(&mut core);
+
+        let solid = Solid::loft([(square, bottom), (circle, top)], &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 6);
+        core.layers.validation.take_errors()?;
+
+        // The cross-section at each height is a blend of the square and the
+        // circle. Its area is a quadratic function of the height, whose
+        // integral is the average of the areas of the square and the circle,
+        // and the mixed area of both, which is `8 / π`.
+        let expected =
+            (Scalar::from(2.) + Scalar::PI + Scalar::from(8.) / Scalar::PI)
+                / 3.;
+
+        // The side faces are twisted, and their approximation only has points
+        // on their boundaries. So the approximated volume can't get as close
+        // to the actual one as usual, but it must be within its error bound.
+        let properties = solid.mass_properties(
+            Tolerance::from_scalar(0.001)?,
+            &core.layers.geometry,
+        );
+        assert!(
+            (properties.volume - expected).abs() <= properties.volume_error,
+            "Unexpected volume: {} (expected {expected})",
+            properties.volume,
+        );
+
+        Ok(())
+    }
+
+    fn plane_at_height(height: f64, core: &mut Core) -> Handle<Surface> {
+        Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(
+                Point::from([0., 0., height]),
+                Vector::unit_x(),
+            )),
+            Vector::unit_y(),
+            core,
+        )
+    }
+}
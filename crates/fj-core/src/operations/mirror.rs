@@ -0,0 +1,180 @@
+//! # Mirror objects
+//!
+//! See [`Mirror`].
+
+use fj_math::{Point, Transform, Vector};
+
+use crate::{
+    topology::{Face, Shell, Sketch, Solid},
+    Core,
+};
+
+use super::{
+    derive::DeriveFrom,
+    insert::Insert,
+    reverse::Reverse,
+    transform::{transform_regions_in_surface, TransformObject},
+};
+
+/// # Mirror an object
+///
+/// A mirror image has the opposite handedness of the original. Applying the
+/// reflection to an object's geometry alone would leave all faces pointing
+/// inwards, and the cycles of all regions winding the wrong way. This operation
+/// reverses them accordingly, so the mirrored object is as valid as the
+/// original.
+///
+/// `D` is the dimensionality of the space that the object is mirrored in.
+/// Solids, shells, and faces are mirrored in 3D, across a plane. Sketches are
+/// mirrored within their surface, across a line.
+pub trait Mirror<const D: usize> {
+    /// # Mirror the object
+    ///
+    /// The mirror plane (in 3D) or line (in 2D) goes through `origin` and is
+    /// perpendicular to `normal`.
+    #[must_use]
+    fn mirror(
+        &self,
+        origin: impl Into<Point<D>>,
+        normal: impl Into<Vector<D>>,
+        core: &mut Core,
+    ) -> Self;
+}
+
+impl Mirror<3> for Solid {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self {
+        let reflected =
+            self.clone().transform(&reflection(origin, normal), core);
+
+        let shells = reflected.shells().iter().map(|shell| {
+            reverse_faces(shell, core)
+                .insert(core)
+                .derive_from(shell, core)
+        });
+        Solid::new(shells.collect::<Vec<_>>())
+    }
+}
+
+impl Mirror<3> for Shell {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self {
+        let reflected =
+            self.clone().transform(&reflection(origin, normal), core);
+        reverse_faces(&reflected, core)
+    }
+}
+
+impl Mirror<3> for Face {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Self {
+        self.clone()
+            .transform(&reflection(origin, normal), core)
+            .reverse(core)
+    }
+}
+
+impl Mirror<2> for Sketch {
+    fn mirror(
+        &self,
+        origin: impl Into<Point<2>>,
+        normal: impl Into<Vector<2>>,
+        core: &mut Core,
+    ) -> Self {
+        let reflection =
+            reflection(origin.into().to_xyz(), normal.into().to_xyz());
+
+        let regions = transform_regions_in_surface(
+            self.regions(),
+            self.surface(),
+            &reflection,
+            core,
+        )
+        .into_iter()
+        .map(|region| {
+            region.reverse(core).insert(core).derive_from(&region, core)
+        })
+        .collect::<Vec<_>>();
+
+        Sketch::new(self.surface().clone(), regions)
+    }
+}
+
+/// # Reflection across a plane that doesn't necessarily go through the origin
+fn reflection(
+    origin: impl Into<Point<3>>,
+    normal: impl Into<Vector<3>>,
+) -> Transform {
+    let origin = origin.into();
+
+    Transform::translation(origin.coords)
+        * Transform::reflection(normal)
+        * Transform::translation(-origin.coords)
+}
+
+fn reverse_faces(shell: &Shell, core: &mut Core) -> Shell {
+    let faces = shell
+        .faces()
+        .iter()
+        .map(|face| face.reverse(core).insert(core).derive_from(face, core))
+        .collect::<Vec<_>>();
+
+    Shell::new(faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        operations::{
+            build::BuildSketch,
+            insert::Insert,
+            tests::{assert_volume, cuboid},
+        },
+        topology::Sketch,
+        Core,
+    };
+
+    use super::Mirror;
+
+    #[test]
+    fn mirror_solid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = cuboid(&mut core)
+            .mirror([0., 0., 0.5], [0., 1., 1.], &mut core)
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(4.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn mirror_sketch() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = Sketch::polygon([[0., 0.], [2., 0.], [1., 1.]], &mut core)
+            .mirror([3., 0.], [1., 1.], &mut core)
+            .insert(&mut core);
+
+        assert_eq!(sketch.regions().len(), 1);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
@@ -38,17 +38,78 @@
 //! assume that the code in question is outdated. Feel free to open an issue or
 //! send a pull request!
 
+pub mod blend;
+pub mod boolean;
 pub mod build;
 pub mod derive;
 pub mod geometry;
 pub mod holes;
+pub mod hollow;
 pub mod insert;
 pub mod join;
+pub mod loft;
 pub mod merge;
+pub mod mirror;
+pub mod pattern;
 pub mod presentation;
 pub mod replace;
 pub mod reverse;
+pub mod revolve;
+pub mod section;
 pub mod split;
 pub mod sweep;
+pub mod threads;
 pub mod transform;
 pub mod update;
+
+mod components;
+mod plane;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::mass_properties::MassProperties,
+        geometry::Tolerance,
+        operations::{build::BuildSketch, sweep::SweepSketch},
+        topology::{Sketch, Solid},
+        Core,
+    };
+
+    /// # Build a cuboid that is 2 by 2 by 1 units large
+    pub fn cuboid(core: &mut Core) -> Solid {
+        Sketch::polygon([[0., 0.], [2., 0.], [2., 2.], [0., 2.]], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                core,
+            )
+    }
+
+    pub fn assert_volume(
+        solid: &Solid,
+        expected: Scalar,
+        core: &mut Core,
+    ) -> anyhow::Result<()> {
+        let volume = volume(solid, core)?;
+
+        assert!(
+            (volume - expected).abs() < Scalar::from(0.005),
+            "Unexpected volume: {volume} (expected {expected})"
+        );
+
+        Ok(())
+    }
+
+    /// # Compute the volume of the approximation of a solid
+    ///
+    /// If any faces pointed inward, this would be off.
+    pub fn volume(solid: &Solid, core: &mut Core) -> anyhow::Result<Scalar> {
+        let tolerance = Tolerance::from_scalar(0.001)?;
+        let properties =
+            solid.mass_properties(tolerance, &core.layers.geometry);
+
+        Ok(properties.volume)
+    }
+}
@@ -0,0 +1,369 @@
+//! # Create patterns of copies
+//!
+//! See [`LinearPattern`] and [`CircularPattern`]. The copies of a solid are
+//! separate shells of the resulting solid. Use [`Fuse`] to combine them, where
+//! they overlap.
+
+use fj_math::{Line, Point, Scalar, Transform, Vector};
+
+use crate::{
+    storage::Handle,
+    topology::{Region, Sketch, Solid, Surface},
+    Core,
+};
+
+use super::{
+    boolean::Boolean,
+    transform::{transform_regions_in_surface, TransformObject},
+};
+
+/// # Create copies of an object, each offset from the previous one
+pub trait LinearPattern {
+    /// # The type of the offset between copies
+    type Offset;
+
+    /// # The result of the operation
+    type Patterned;
+
+    /// # Create a linear pattern of the object
+    ///
+    /// `count` is the total number of instances, including the original
+    /// object, which stays where it is. Each further instance is offset from
+    /// the previous one by `offset`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `count` is zero.
+    #[must_use]
+    fn linear_pattern(
+        &self,
+        offset: impl Into<Self::Offset>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned;
+}
+
+/// # Create copies of an object, each rotated from the previous one
+pub trait CircularPattern {
+    /// # The type of the axis that the copies are rotated around
+    type Axis;
+
+    /// # The result of the operation
+    type Patterned;
+
+    /// # Create a circular pattern of the object
+    ///
+    /// `count` is the total number of instances, including the original
+    /// object, which stays where it is. Each further instance is rotated from
+    /// the previous one around `axis` by `angle` (in radians). A positive angle
+    /// rotates counter-clockwise, as seen from where the axis points to. To
+    /// spread the instances evenly around a full circle, pass `TAU / count`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `count` is zero.
+    #[must_use]
+    fn circular_pattern(
+        &self,
+        axis: impl Into<Self::Axis>,
+        angle: impl Into<Scalar>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned;
+}
+
+/// # Fuse the shells of a solid with each other
+pub trait Fuse {
+    /// # Fuse all shells of the solid
+    ///
+    /// Shells that overlap are united into one. Shells that don't overlap stay
+    /// separate.
+    ///
+    /// This is a boolean operation. See [`Boolean`] for its limitations.
+    #[must_use]
+    fn fuse(&self, core: &mut Core) -> Self;
+}
+
+impl LinearPattern for Solid {
+    type Offset = Vector<3>;
+    type Patterned = Self;
+
+    fn linear_pattern(
+        &self,
+        offset: impl Into<Self::Offset>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned {
+        let offset = offset.into();
+        pattern_solid(self, count, |i| Transform::translation(offset * i), core)
+    }
+}
+
+impl CircularPattern for Solid {
+    type Axis = Line<3>;
+    type Patterned = Self;
+
+    fn circular_pattern(
+        &self,
+        axis: impl Into<Self::Axis>,
+        angle: impl Into<Scalar>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned {
+        let axis = axis.into();
+        let angle = angle.into();
+
+        pattern_solid(
+            self,
+            count,
+            |i| rotation(axis.origin(), axis.direction(), angle * i),
+            core,
+        )
+    }
+}
+
+impl Fuse for Solid {
+    fn fuse(&self, core: &mut Core) -> Self {
+        let mut shells = self.shells().iter();
+        let Some(first) = shells.next() else {
+            return self.clone();
+        };
+
+        shells.fold(Solid::new([first.clone()]), |fused, shell| {
+            fused.union(&Solid::new([shell.clone()]), core)
+        })
+    }
+}
+
+impl LinearPattern for Sketch {
+    type Offset = Vector<2>;
+    type Patterned = Self;
+
+    fn linear_pattern(
+        &self,
+        offset: impl Into<Self::Offset>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned {
+        let offset = offset.into();
+        pattern_sketch(
+            self,
+            count,
+            |i| Transform::translation((offset * i).to_xyz()),
+            core,
+        )
+    }
+}
+
+impl CircularPattern for Sketch {
+    type Axis = Point<2>;
+    type Patterned = Self;
+
+    fn circular_pattern(
+        &self,
+        axis: impl Into<Self::Axis>,
+        angle: impl Into<Scalar>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned {
+        let center = axis.into();
+        let angle = angle.into();
+
+        pattern_sketch(
+            self,
+            count,
+            |i| rotation(center.to_xyz(), Vector::unit_z(), angle * i),
+            core,
+        )
+    }
+}
+
+impl LinearPattern for (&Handle<Region>, &Handle<Surface>) {
+    type Offset = Vector<2>;
+    type Patterned = Vec<Handle<Region>>;
+
+    fn linear_pattern(
+        &self,
+        offset: impl Into<Self::Offset>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned {
+        let offset = offset.into();
+        pattern_regions(
+            [self.0],
+            self.1,
+            count,
+            |i| Transform::translation((offset * i).to_xyz()),
+            core,
+        )
+    }
+}
+
+impl CircularPattern for (&Handle<Region>, &Handle<Surface>) {
+    type Axis = Point<2>;
+    type Patterned = Vec<Handle<Region>>;
+
+    fn circular_pattern(
+        &self,
+        axis: impl Into<Self::Axis>,
+        angle: impl Into<Scalar>,
+        count: usize,
+        core: &mut Core,
+    ) -> Self::Patterned {
+        let center = axis.into();
+        let angle = angle.into();
+
+        pattern_regions(
+            [self.0],
+            self.1,
+            count,
+            |i| rotation(center.to_xyz(), Vector::unit_z(), angle * i),
+            core,
+        )
+    }
+}
+
+/// # Create the copies of a solid, using the transform for each instance
+fn pattern_solid(
+    solid: &Solid,
+    count: usize,
+    transform: impl Fn(f64) -> Transform,
+    core: &mut Core,
+) -> Solid {
+    assert!(count > 0, "Pattern must have at least one instance");
+
+    let mut shells = solid.shells().iter().cloned().collect::<Vec<_>>();
+    for i in 1..count {
+        // Using a new cache for each copy makes sure the copies don't share
+        // any objects.
+        let copy = solid.clone().transform(&transform(i as f64), core);
+        shells.extend(copy.shells().iter().cloned());
+    }
+
+    Solid::new(shells)
+}
+
+fn pattern_sketch(
+    sketch: &Sketch,
+    count: usize,
+    transform: impl Fn(f64) -> Transform,
+    core: &mut Core,
+) -> Sketch {
+    let regions = pattern_regions(
+        sketch.regions(),
+        sketch.surface(),
+        count,
+        transform,
+        core,
+    );
+
+    Sketch::new(sketch.surface().clone(), regions)
+}
+
+/// # Create the copies of some regions, within the surface they are on
+fn pattern_regions<'r>(
+    regions: impl IntoIterator<Item = &'r Handle<Region>> + Clone,
+    surface: &Handle<Surface>,
+    count: usize,
+    transform: impl Fn(f64) -> Transform,
+    core: &mut Core,
+) -> Vec<Handle<Region>> {
+    assert!(count > 0, "Pattern must have at least one instance");
+
+    let mut patterned =
+        regions.clone().into_iter().cloned().collect::<Vec<_>>();
+    for i in 1..count {
+        patterned.extend(transform_regions_in_surface(
+            regions.clone(),
+            surface,
+            &transform(i as f64),
+            core,
+        ));
+    }
+
+    patterned
+}
+
+/// # A rotation around an axis that doesn't necessarily go through the origin
+fn rotation(
+    origin: Point<3>,
+    direction: Vector<3>,
+    angle: Scalar,
+) -> Transform {
+    Transform::translation(origin.coords)
+        * Transform::rotation(direction.normalize() * angle)
+        * Transform::translation(-origin.coords)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::TAU;
+
+    use fj_math::{Line, Point, Scalar, Vector};
+
+    use crate::{
+        operations::{
+            build::BuildSketch,
+            insert::Insert,
+            tests::{assert_volume, cuboid},
+        },
+        topology::Sketch,
+        Core,
+    };
+
+    use super::{CircularPattern, Fuse, LinearPattern};
+
+    #[test]
+    fn linear_pattern_of_solid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = cuboid(&mut core)
+            .linear_pattern([3., 0., 0.], 3, &mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().len(), 3);
+        core.layers.validation.take_errors()?;
+
+        assert_volume(&solid, Scalar::from(12.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fused_circular_pattern_of_solid() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // Rotating the cuboid around one of its vertical edges results in a
+        // 4 by 4 by 1 cuboid.
+        let axis = Line::from_origin_and_direction(
+            Point::from([0., 0., 0.]),
+            Vector::unit_z(),
+        );
+        let solid = cuboid(&mut core)
+            .circular_pattern(axis, TAU / 4., 4, &mut core)
+            .fuse(&mut core)
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().len(), 1);
+        assert_eq!(solid.shells().only().faces().len(), 6);
+        core.layers.validation.take_errors()?;
+
+        assert_volume(&solid, Scalar::from(16.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn circular_pattern_of_sketch() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = Sketch::circle([3., 0.], 1., &mut core)
+            .circular_pattern([0., 0.], TAU / 6., 6, &mut core)
+            .insert(&mut core);
+
+        assert_eq!(sketch.regions().len(), 6);
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! # Planar surfaces, as used by operations that only support those
+
+use fj_math::{Line, Point, Vector};
+
+use crate::{
+    geometry::{surfaces::SweptCurve, Path},
+    storage::Handle,
+    topology::Surface,
+    Core,
+};
+
+use super::build::BuildSurface;
+
+/// # A plane, as defined by a [`SweptCurve`] with a straight u-axis
+///
+/// The axes of the plane don't need to be orthogonal or normalized.
+#[derive(Clone, Copy)]
+pub struct Plane {
+    u: Line<3>,
+    v: Vector<3>,
+}
+
+impl Plane {
+    /// # Access the plane that the provided surface is defined as
+    ///
+    /// Returns `None`, if the surface is not a plane.
+    pub fn of(surface: &Handle<Surface>, core: &Core) -> Option<Self> {
+        let SweptCurve {
+            u: Path::Line(u),
+            v,
+        } = core.layers.geometry.try_of_surface(surface)?
+        else {
+            return None;
+        };
+
+        Some(Self { u: *u, v: *v })
+    }
+
+    pub fn origin(&self) -> Point<3> {
+        self.u.origin()
+    }
+
+    pub fn axes(&self) -> [Vector<3>; 2] {
+        [self.u.direction(), self.v]
+    }
+
+    /// # The unit normal of the plane
+    pub fn normal(&self) -> Vector<3> {
+        let [u, v] = self.axes();
+        u.cross(&v).normalize()
+    }
+
+    /// # Convert a point in plane coordinates into global coordinates
+    pub fn point(&self, point: Point<2>) -> Point<3> {
+        self.origin() + self.vector(point.coords)
+    }
+
+    /// # Convert a vector in plane coordinates into global coordinates
+    pub fn vector(&self, vector: Vector<2>) -> Vector<3> {
+        let [u, v] = self.axes();
+        u * vector.u + v * vector.v
+    }
+
+    /// # Convert a path in plane coordinates into global coordinates
+    pub fn path_to_global(&self, path: &Path<2>) -> Path<3> {
+        self.swept_curve()
+            .path_to_global(path)
+            .expect("Any path can be converted from a plane")
+    }
+
+    /// # Project a point in global coordinates into the plane
+    pub fn project_point(&self, point: Point<3>) -> Point<2> {
+        self.swept_curve()
+            .project_point(point)
+            .expect("Any point can be projected into a plane")
+    }
+
+    /// # Project a path in global coordinates into the plane
+    pub fn project_path(&self, path: &Path<3>) -> Path<2> {
+        self.swept_curve()
+            .project_path(path)
+            .expect("Any path can be projected into a plane")
+    }
+
+    /// # Create a plane that is moved by the provided offset
+    pub fn offset(&self, offset: Vector<3>) -> Self {
+        Self {
+            u: Line::from_origin_and_direction(
+                self.origin() + offset,
+                self.u.direction(),
+            ),
+            v: self.v,
+        }
+    }
+
+    /// # Create a plane in the same place, with an orthonormal coordinate system
+    pub fn orthonormal(&self) -> Self {
+        let u = self.u.direction().normalize();
+
+        Self {
+            u: Line::from_origin_and_direction(self.origin(), u),
+            v: self.normal().cross(&u),
+        }
+    }
+
+    /// # Create a surface that is defined as this plane
+    pub fn surface(&self, core: &mut Core) -> Handle<Surface> {
+        Surface::from_geometry(self.swept_curve(), core)
+    }
+
+    fn swept_curve(&self) -> SweptCurve {
+        SweptCurve {
+            u: Path::Line(self.u),
+            v: self.v,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use fj_math::{Point, Scalar};
+
+use crate::{
+    operations::presentation::GetColor,
+    storage::Handle,
+    topology::{Face, Shell},
+    Core,
+};
+
+use super::RevolveRegion;
+
+/// # Revolve a [`Face`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait RevolveFace {
+    /// # Revolve the [`Face`] into a [`Shell`]
+    ///
+    /// See [`RevolveRegion::revolve_region`] for details on the arguments.
+    fn revolve_face(
+        &self,
+        axis: [impl Into<Point<2>>; 2],
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Shell;
+}
+
+impl RevolveFace for Handle<Face> {
+    fn revolve_face(
+        &self,
+        axis: [impl Into<Point<2>>; 2],
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Shell {
+        let color = self.region().get_color(core);
+
+        self.region()
+            .revolve_region(self.surface(), color, axis, angle, core)
+    }
+}
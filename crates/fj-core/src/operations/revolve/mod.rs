@@ -0,0 +1,13 @@
+//! Revolve objects around an axis to create new objects
+//!
+//! Revolves 2D objects around an axis that is located on the same surface,
+//! creating a 3D object. The resulting side faces are surfaces of revolution,
+//! bounded by circular edges.
+
+mod face;
+mod region;
+mod sketch;
+
+pub use self::{
+    face::RevolveFace, region::RevolveRegion, sketch::RevolveSketch,
+};
@@ -0,0 +1,675 @@
+use std::collections::BTreeMap;
+
+use fj_interop::{ext::ArrayExt, Color};
+use fj_math::{
+    Circle, Ellipse, Line, NurbsCurve, Point, Scalar, Transform, Vector,
+    Winding,
+};
+
+use crate::{
+    geometry::{
+        repr::tri_mesh::{convert_point_surface_to_global, TriMesh},
+        surfaces::RevolvedCurve,
+        LocalVertexGeom, Path, SurfaceGeom,
+    },
+    operations::{
+        build::BuildCycle, geometry::UpdateCurveGeometry, insert::Insert,
+        presentation::SetColor, transform::TransformObject,
+    },
+    storage::Handle,
+    topology::{Curve, Cycle, Face, Region, Shell, Surface, Vertex},
+    Core,
+};
+
+/// # Revolve a [`Region`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait RevolveRegion {
+    /// # Revolve the [`Region`]
+    ///
+    /// Revolve the region around an axis, creating a shell. Each half-edge of
+    /// the region is revolved into a set of side faces, one for each quarter
+    /// turn (or part thereof) of the revolution.
+    ///
+    /// Requires the surface that the region is defined on, and optionally the
+    /// color of the created faces. The axis is defined by two points on that
+    /// surface. The region is revolved by `angle` (in radians), counter-
+    /// clockwise when looking against the direction of the axis, which points
+    /// from the first point towards the second one (right-hand rule). Negative
+    /// angles revolve the region the other way.
+    ///
+    /// If the magnitude of the angle is a full turn or more, the region is
+    /// revolved into a closed ring. Otherwise, the resulting shell has faces at
+    /// the start and end of the revolution, in the shape of the region.
+    ///
+    /// The region may touch the axis, and half-edges that are located on the
+    /// axis are not revolved at all.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if the angle is zero, if the region crosses the axis, if the
+    /// region is located completely on the axis, or if `surface` is not a
+    /// plane.
+    fn revolve_region(
+        &self,
+        surface: &Handle<Surface>,
+        color: Option<Color>,
+        axis: [impl Into<Point<2>>; 2],
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Shell;
+}
+
+impl RevolveRegion for Region {
+    fn revolve_region(
+        &self,
+        surface: &Handle<Surface>,
+        color: Option<Color>,
+        axis: [impl Into<Point<2>>; 2],
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Shell {
+        let angle = angle.into();
+        assert!(!angle.is_zero(), "Can't revolve region by an angle of zero");
+
+        // Revolving around an axis by a negative angle is the same as revolving
+        // around the reversed axis by a positive angle. Let's normalize this, so
+        // we only have to deal with one case below.
+        let [a, b] = axis.map(Into::into);
+        let axis = if angle.is_negative() { [b, a] } else { [a, b] };
+
+        let is_full_turn = angle.abs() >= Scalar::TAU;
+        let angle = if is_full_turn {
+            Scalar::TAU
+        } else {
+            angle.abs()
+        };
+
+        // Arcs that span more than a quarter turn are problematic (see
+        // `BuildCycle::circle`, which builds circles from four arcs, for
+        // comparison). Let's split the revolution into sectors accordingly.
+        let num_sectors = (angle / (Scalar::TAU / 4.)).ceil().into_u64();
+        let angles = (0..=num_sectors)
+            .map(|i| {
+                angle * Scalar::from_u64(i) / Scalar::from_u64(num_sectors)
+            })
+            .collect::<Vec<_>>();
+        let num_sectors = num_sectors as usize;
+
+        let profile = Profile::new(self, surface, axis, core);
+
+        // The orientation of the side faces depends on the winding of the
+        // region and on which side of the axis it is located. If we don't
+        // adapt to that, we'd end up with faces that point inwards.
+        let reverse_side_faces = (self.exterior().winding(
+            core.tolerance(),
+            &core.layers.geometry,
+            surface,
+        ) == Winding::Ccw)
+            != profile.is_left_of_axis;
+
+        // Each vertex of the profile is revolved into a ring of vertices, one
+        // for each boundary between sectors. A full turn ends where it started,
+        // so it needs one ring less. And vertices on the axis don't move at
+        // all.
+        let num_rings = if is_full_turn {
+            num_sectors
+        } else {
+            num_sectors + 1
+        };
+        let vertices = profile
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let num_vertices =
+                    if vertex.is_on_axis { 1 } else { num_rings };
+
+                (0..num_vertices)
+                    .map(|_| Vertex::new().insert(core))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let vertex = |index: usize, ring: usize| {
+            let vertices = &vertices[index];
+            vertices[ring % vertices.len()].clone()
+        };
+
+        // Same for the curves of the profile.
+        let curves = profile
+            .cycles
+            .iter()
+            .map(|segments| {
+                segments
+                    .iter()
+                    .map(|segment| {
+                        let num_curves = if profile.is_on_axis(segment) {
+                            1
+                        } else {
+                            num_rings
+                        };
+
+                        (0..num_curves)
+                            .map(|ring| {
+                                let curve = Curve::new().insert(core);
+
+                                for (index, position) in segment
+                                    .vertices
+                                    .into_iter()
+                                    .zip(segment.boundary)
+                                {
+                                    core.layers.geometry.define_vertex(
+                                        vertex(index, ring),
+                                        curve.clone(),
+                                        LocalVertexGeom { position },
+                                    );
+                                }
+
+                                curve
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let curve = |cycle: usize, segment: usize, ring: usize| {
+            let curves = &curves[cycle][segment];
+            curves[ring % curves.len()].clone()
+        };
+
+        let mut faces = Vec::new();
+
+        // The circular curves that the vertices are revolved along are shared
+        // between neighboring side faces, so we need to keep track of them.
+        let mut circles = BTreeMap::new();
+
+        for (cycle_index, segments) in profile.cycles.iter().enumerate() {
+            for (segment_index, segment) in segments.iter().enumerate() {
+                if profile.is_on_axis(segment) {
+                    continue;
+                }
+
+                let revolution = Revolution::new(segment, &profile);
+                let curve_global = core
+                    .layers
+                    .geometry
+                    .of_surface(surface)
+                    .path_to_global(&revolution.path)
+                    .expect("Can only revolve regions on planes");
+
+                for (sector, sector_angles) in angles.windows(2).enumerate() {
+                    // Each side face covers a quarter turn in the coordinates
+                    // of its surface, regardless of the actual angle. That
+                    // places its boundaries exactly on the coordinate axes,
+                    // which the triangulation relies on.
+                    let boundaries = [
+                        (sector, Vector::from([1., 0.]), Scalar::ZERO),
+                        (sector + 1, Vector::from([0., 1.]), Scalar::TAU / 4.),
+                    ];
+
+                    let side_surface = Surface::new().insert(core);
+                    core.layers.geometry.define_surface_2(
+                        side_surface.clone(),
+                        SurfaceGeom {
+                            generator: Box::new(RevolvedCurve {
+                                curve: curve_global.clone(),
+                                axis: profile.axis,
+                                angles: [sector_angles[0], sector_angles[1]],
+                            }),
+                            geometry: TriMesh::empty(),
+                        },
+                    );
+
+                    for (ring, direction, _) in boundaries {
+                        curve(cycle_index, segment_index, ring)
+                            .make_path_on_surface(
+                                revolution.path_at(direction),
+                                side_surface.clone(),
+                                &mut core.layers.geometry,
+                            );
+                    }
+
+                    let mut circle =
+                        |index: usize, position: Point<1>, core: &mut Core| {
+                            if profile.vertices[index].is_on_axis {
+                                return None;
+                            }
+
+                            let circle = circles
+                                .entry((index, sector))
+                                .or_insert_with(|| {
+                                    let circle = Curve::new().insert(core);
+
+                                    for (ring, _, angle) in boundaries {
+                                        core.layers.geometry.define_vertex(
+                                            vertex(index, ring),
+                                            circle.clone(),
+                                            LocalVertexGeom {
+                                                position: Point::from([angle]),
+                                            },
+                                        );
+                                    }
+
+                                    circle
+                                })
+                                .clone()
+                                .make_path_on_surface(
+                                    Path::circle_from_radius(
+                                        revolution.distance_at(position),
+                                    ),
+                                    side_surface.clone(),
+                                    &mut core.layers.geometry,
+                                );
+
+                            Some(circle)
+                        };
+
+                    let [start, end] = segment.vertices;
+                    let [start_position, end_position] = segment.boundary;
+
+                    let mut half_edges = vec![(
+                        curve(cycle_index, segment_index, sector),
+                        vertex(start, sector),
+                    )];
+                    if let Some(circle) = circle(end, end_position, core) {
+                        half_edges.push((circle, vertex(end, sector)));
+                    }
+                    half_edges.push((
+                        curve(cycle_index, segment_index, sector + 1),
+                        vertex(end, sector + 1),
+                    ));
+                    if let Some(circle) = circle(start, start_position, core) {
+                        half_edges.push((circle, vertex(start, sector + 1)));
+                    }
+
+                    let exterior = Cycle::from_curves_and_start_vertices(
+                        half_edges,
+                        reverse_side_faces,
+                        core,
+                    )
+                    .insert(core);
+                    let region = Region::new(exterior, []).insert(core);
+                    if let Some(color) = color {
+                        region.set_color(color, core);
+                    }
+
+                    faces.push(Face::new(side_surface, region).insert(core));
+                }
+            }
+        }
+
+        if !is_full_turn {
+            let end_surface = {
+                let transform =
+                    Transform::translation(profile.axis.origin().coords)
+                        * Transform::rotation(
+                            profile.axis.direction().normalize() * angle,
+                        )
+                        * Transform::translation(-profile.axis.origin().coords);
+
+                surface.transform(&transform, core)
+            };
+
+            for (cap_surface, ring, reverse) in [
+                (surface.clone(), 0, !reverse_side_faces),
+                (end_surface, num_sectors, reverse_side_faces),
+            ] {
+                let mut cycles = Vec::new();
+
+                for (cycle_index, segments) in profile.cycles.iter().enumerate()
+                {
+                    let half_edges = segments
+                        .iter()
+                        .enumerate()
+                        .map(|(segment_index, segment)| {
+                            let [start, _] = segment.vertices;
+
+                            let curve = curve(cycle_index, segment_index, ring)
+                                .make_path_on_surface(
+                                    segment.path.clone(),
+                                    cap_surface.clone(),
+                                    &mut core.layers.geometry,
+                                );
+
+                            (curve, vertex(start, ring))
+                        })
+                        .collect();
+
+                    cycles.push(
+                        Cycle::from_curves_and_start_vertices(
+                            half_edges, reverse, core,
+                        )
+                        .insert(core),
+                    );
+                }
+
+                let mut cycles = cycles.into_iter();
+                let exterior =
+                    cycles.next().expect("Region must have an exterior cycle");
+                let region = Region::new(exterior, cycles).insert(core);
+                if let Some(color) = color {
+                    region.set_color(color, core);
+                }
+
+                faces.push(Face::new(cap_surface, region).insert(core));
+            }
+        }
+
+        Shell::new(faces)
+    }
+}
+
+/// The profile that is being revolved, prepared for the revolution
+struct Profile {
+    /// The axis of the revolution, in global coordinates
+    axis: Line<3>,
+
+    /// The vertices of the profile
+    vertices: Vec<ProfileVertex>,
+
+    /// The cycles of the profile, starting with the exterior
+    cycles: Vec<Vec<Segment>>,
+
+    /// Whether the profile is located on the left side of the axis
+    ///
+    /// "Left" refers to the axis being viewed from the front of the surface
+    /// that the profile is defined on.
+    is_left_of_axis: bool,
+}
+
+impl Profile {
+    fn new(
+        region: &Region,
+        surface: &Handle<Surface>,
+        axis: [Point<2>; 2],
+        core: &Core,
+    ) -> Self {
+        let geometry = &core.layers.geometry;
+        let surface_geom = &geometry.of_surface_2(surface).unwrap().generator;
+        let tolerance = core.tolerance();
+        let max_distance_from_axis =
+            core.layers.validation.config.identical_max_distance;
+
+        let (axis_global, _) = Line::from_points(axis.map(|point| {
+            convert_point_surface_to_global(
+                surface_geom,
+                point,
+                tolerance,
+                geometry,
+            )
+        }));
+
+        let add_vertex = |vertices: &mut Vec<ProfileVertex>,
+                          position: Point<2>| {
+            let position_global = convert_point_surface_to_global(
+                surface_geom,
+                position,
+                tolerance,
+                geometry,
+            );
+            let closest_on_axis = axis_global.point_from_line_coords(
+                axis_global.point_to_line_coords(position_global),
+            );
+            let distance_from_axis =
+                (position_global - closest_on_axis).magnitude();
+
+            vertices.push(ProfileVertex {
+                position,
+                distance_from_axis,
+                is_on_axis: distance_from_axis < max_distance_from_axis,
+            });
+
+            vertices.len() - 1
+        };
+
+        let mut vertices = Vec::new();
+        let mut vertex_indices = BTreeMap::new();
+        let mut cycles = Vec::new();
+
+        for cycle in region.all_cycles() {
+            let mut segments = Vec::new();
+
+            for (half_edge, next_half_edge) in cycle.half_edges().pairs() {
+                let path = geometry
+                    .of_curve(half_edge.curve())
+                    .unwrap()
+                    .local_on(surface)
+                    .unwrap()
+                    .path
+                    .clone();
+
+                let vertices_of_half_edge =
+                    [half_edge.start_vertex(), next_half_edge.start_vertex()];
+                let boundary = vertices_of_half_edge.map(|vertex| {
+                    geometry
+                        .of_vertex(vertex)
+                        .unwrap()
+                        .local_on(half_edge.curve())
+                        .unwrap()
+                        .position
+                });
+                let [start, end] = vertices_of_half_edge.zip_ext(boundary).map(
+                    |(vertex, position)| {
+                        *vertex_indices.entry(vertex.clone()).or_insert_with(
+                            || {
+                                add_vertex(
+                                    &mut vertices,
+                                    path.point_from_path_coords(position),
+                                )
+                            },
+                        )
+                    },
+                );
+
+                // An arc that touches the axis at both ends (like a half-circle
+                // that is revolved into a sphere) can't be revolved into a
+                // single surface without a singularity at one of the ends.
+                // Let's split it in the middle.
+                let is_arc_between_points_on_axis =
+                    matches!(path, Path::Circle(_))
+                        && vertices[start].is_on_axis
+                        && vertices[end].is_on_axis;
+
+                if is_arc_between_points_on_axis {
+                    let [a, b] = boundary;
+                    let middle = a + (b - a) / 2.;
+                    let middle_vertex = add_vertex(
+                        &mut vertices,
+                        path.point_from_path_coords(middle),
+                    );
+
+                    segments.push(Segment {
+                        path: path.clone(),
+                        boundary: [a, middle],
+                        vertices: [start, middle_vertex],
+                    });
+                    segments.push(Segment {
+                        path,
+                        boundary: [middle, b],
+                        vertices: [middle_vertex, end],
+                    });
+                } else {
+                    segments.push(Segment {
+                        path,
+                        boundary,
+                        vertices: [start, end],
+                    });
+                }
+            }
+
+            cycles.push(segments);
+        }
+
+        let is_left_of_axis = {
+            let [a, b] = axis;
+            let axis_direction = b - a;
+
+            let mut sides = vertices
+                .iter()
+                .filter(|vertex| !vertex.is_on_axis)
+                .map(|vertex| {
+                    axis_direction.cross2d(&(vertex.position - a)).is_positive()
+                });
+
+            let is_left_of_axis = sides
+                .next()
+                .expect("Can't revolve region that is located on the axis");
+            assert!(
+                sides.all(|side| side == is_left_of_axis),
+                "Can't revolve region that crosses the axis"
+            );
+
+            is_left_of_axis
+        };
+
+        Self {
+            axis: axis_global,
+            vertices,
+            cycles,
+            is_left_of_axis,
+        }
+    }
+
+    fn is_on_axis(&self, segment: &Segment) -> bool {
+        segment
+            .vertices
+            .iter()
+            .all(|&index| self.vertices[index].is_on_axis)
+    }
+}
+
+struct ProfileVertex {
+    position: Point<2>,
+    distance_from_axis: Scalar,
+    is_on_axis: bool,
+}
+
+/// A segment of a cycle of the profile, the equivalent of a half-edge
+#[derive(Clone)]
+struct Segment {
+    path: Path<2>,
+    boundary: [Point<1>; 2],
+    vertices: [usize; 2],
+}
+
+/// The information required to revolve a segment of the profile
+///
+/// See [`RevolvedCurve`] for the coordinate system of the surface that the
+/// segment is revolved into. The distance from the origin of that coordinate
+/// system is an affine function of the curve coordinate of the segment.
+struct Revolution {
+    /// The path of the segment, with coordinates adapted to the surface
+    path: Path<2>,
+
+    /// The curve coordinate of the segment that maps to `offset`
+    reference: Scalar,
+
+    /// The direction in which the distance increases along the segment
+    direction: Scalar,
+
+    /// The distance from the origin that `reference` maps to
+    offset: Scalar,
+}
+
+impl Revolution {
+    fn new(segment: &Segment, profile: &Profile) -> Self {
+        let [start, end] =
+            segment.vertices.map(|index| &profile.vertices[index]);
+        let [a, b] = segment.boundary.map(|position| position.t);
+
+        // If the segment touches the axis, the point that touches it must end
+        // up at the origin of the surface. Otherwise, there would be a
+        // singularity.
+        let (reference, other, offset) = if start.is_on_axis {
+            (a, b, Scalar::ZERO)
+        } else if end.is_on_axis {
+            (b, a, Scalar::ZERO)
+        } else {
+            (a, b, start.distance_from_axis)
+        };
+        let direction = if other > reference {
+            Scalar::ONE
+        } else {
+            -Scalar::ONE
+        };
+
+        let path = {
+            let origin = reference - direction * offset;
+
+            match &segment.path {
+                Path::Circle(circle) => {
+                    let a = circle.point_from_circle_coords([origin])
+                        - circle.center();
+
+                    // Constructing `b` like this, instead of computing it
+                    // the same way as `a`, makes sure that both vectors are
+                    // exactly perpendicular and of the same length.
+                    let orientation =
+                        if circle.a().cross2d(&circle.b()) > Scalar::ZERO {
+                            Scalar::ONE
+                        } else {
+                            -Scalar::ONE
+                        };
+                    let b = Vector::from([-a.v, a.u]) * orientation * direction;
+
+                    Path::Circle(Circle::new(circle.center(), a, b))
+                }
+                Path::Ellipse(ellipse) => {
+                    let [a, b] = [Scalar::ZERO, Scalar::PI / 2. * direction]
+                        .map(|t| {
+                            ellipse.vector_from_ellipse_coords([origin + t])
+                        });
+
+                    Path::Ellipse(Ellipse::new(ellipse.center(), a, b))
+                }
+                Path::Line(line) => {
+                    Path::Line(Line::from_origin_and_direction(
+                        line.point_from_line_coords([origin]),
+                        line.direction() * direction,
+                    ))
+                }
+                Path::Nurbs(curve) => {
+                    // Reversing the curve keeps its domain, but maps `origin`
+                    // to a different curve coordinate.
+                    let (curve, origin) = if direction.is_negative() {
+                        let [start, end] = curve.domain();
+                        (curve.clone().reverse(), start + end - origin)
+                    } else {
+                        (curve.clone(), origin)
+                    };
+
+                    Path::Nurbs(NurbsCurve::new(
+                        curve.degree(),
+                        curve.control_points().iter().copied(),
+                        curve.weights().iter().copied(),
+                        curve.knots().iter().map(|&knot| knot - origin),
+                    ))
+                }
+            }
+        };
+
+        Self {
+            path,
+            reference,
+            direction,
+            offset,
+        }
+    }
+
+    /// The distance from the origin of a point on the segment
+    fn distance_at(&self, position: Point<1>) -> Scalar {
+        self.offset + self.direction * (position.t - self.reference)
+    }
+
+    /// The local definition of the segment on a surface of revolution
+    ///
+    /// The direction points from the origin of the surface towards the segment.
+    fn path_at(&self, direction: Vector<2>) -> Path<2> {
+        Path::Line(Line::from_origin_and_direction(
+            Point::origin()
+                + direction * (self.offset - self.direction * self.reference),
+            direction * self.direction,
+        ))
+    }
+}
@@ -0,0 +1,266 @@
+use fj_math::{Point, Scalar};
+
+use crate::{
+    operations::{
+        geometry::UpdateCurveGeometry, insert::Insert, presentation::GetColor,
+    },
+    storage::Handle,
+    topology::{Sketch, Solid, Surface},
+    Core,
+};
+
+use super::RevolveRegion;
+
+/// # Revolve a [`Sketch`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait RevolveSketch {
+    /// # Revolve the [`Sketch`]
+    ///
+    /// Places the sketch on `surface`, then revolves each of its regions
+    /// around the axis, creating one shell per region. The axis is defined by
+    /// two points, in the coordinates of the sketch.
+    ///
+    /// See [`RevolveRegion::revolve_region`] for more details on the
+    /// arguments.
+    fn revolve_sketch(
+        &self,
+        surface: Handle<Surface>,
+        axis: [impl Into<Point<2>>; 2],
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid;
+}
+
+impl RevolveSketch for Sketch {
+    fn revolve_sketch(
+        &self,
+        surface: Handle<Surface>,
+        axis: [impl Into<Point<2>>; 2],
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let axis = axis.map(Into::into);
+        let angle = angle.into();
+
+        let mut shells = Vec::new();
+        for region in self.regions() {
+            for cycle in region.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    half_edge.curve().clone().place_on_surface(
+                        self.surface(),
+                        surface.clone(),
+                        &mut core.layers.geometry,
+                    );
+                }
+            }
+
+            let color = region.get_color(core);
+            let shell = region
+                .revolve_region(&surface, color, axis, angle, core)
+                .insert(core);
+            shells.push(shell);
+        }
+
+        Solid::new(shells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{NurbsCurve, Scalar};
+
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildSketch},
+            insert::Insert,
+            tests::{assert_volume, volume},
+            update::UpdateSketch,
+        },
+        storage::Handle,
+        topology::{Cycle, HalfEdge, Region, Sketch, Surface},
+        Core,
+    };
+
+    use super::RevolveSketch;
+
+    #[test]
+    fn full_turn_of_rectangle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = Sketch::polygon(
+            [[1., 0.], [2., 0.], [2., 1.], [1., 1.]],
+            &mut core,
+        );
+        let solid = sketch
+            .revolve_sketch(
+                core.layers.topology.surfaces.xz_plane(),
+                [[0., 0.], [0., 1.]],
+                Scalar::TAU,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 16);
+        core.layers.validation.take_errors()?;
+
+        let expected_volume = Scalar::PI * 3.;
+        assert_volume(&solid, expected_volume, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_turn_of_rectangle_touching_axis() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = Sketch::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            &mut core,
+        );
+        let solid = sketch
+            .revolve_sketch(
+                core.layers.topology.surfaces.xz_plane(),
+                [[0., 0.], [0., 1.]],
+                Scalar::TAU * 3. / 8.,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        // The half-edge on the axis isn't revolved. Each of the other three is
+        // revolved into two side faces. Plus the faces at start and end.
+        assert_eq!(solid.shells().only().faces().len(), 8);
+        core.layers.validation.take_errors()?;
+
+        let expected_volume = Scalar::PI * 3. / 8.;
+        assert_volume(&solid, expected_volume, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn negative_angle_on_other_side_of_axis() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = Sketch::polygon(
+            [[-1., 0.], [-1., 1.], [-2., 1.], [-2., 0.]],
+            &mut core,
+        );
+        let solid = sketch
+            .revolve_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [[0., 0.], [0., 1.]],
+                -Scalar::TAU / 4.,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 6);
+        core.layers.validation.take_errors()?;
+
+        let expected_volume = Scalar::PI * 3. / 4.;
+        assert_volume(&solid, expected_volume, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn half_circle_into_sphere() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = {
+            let sketch = Sketch::empty(&core.layers.topology);
+            let surface = sketch.surface().clone();
+            let exterior = half_circle(surface, &mut core).insert(&mut core);
+            sketch.add_regions([Region::new(exterior, [])], &mut core)
+        };
+        let solid = sketch
+            .revolve_sketch(
+                core.layers.topology.surfaces.xz_plane(),
+                [[0., -1.], [0., 1.]],
+                Scalar::TAU,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        // Two arcs, each revolved into four side faces. The line on the axis
+        // isn't revolved.
+        assert_eq!(solid.shells().only().faces().len(), 8);
+        core.layers.validation.take_errors()?;
+
+        // The triangulation only uses the points on the boundaries of faces,
+        // so it can't match the volume of the sphere. But it should come
+        // reasonably close.
+        let volume = volume(&solid, &mut core)?;
+        let expected_volume = Scalar::PI * 4. / 3.;
+        assert!(volume > expected_volume * 0.7 && volume < expected_volume);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nurbs_edge_into_cone() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let sketch = {
+            let sketch = Sketch::empty(&core.layers.topology);
+            let surface = sketch.surface().clone();
+
+            // The NURBS curve ends on the axis, so it needs to be reversed to
+            // revolve it.
+            let half_edges_and_boundaries = vec![
+                HalfEdge::line_segment(
+                    [[0., 0.], [2., 0.]],
+                    surface.clone(),
+                    &mut core,
+                ),
+                HalfEdge::nurbs(
+                    NurbsCurve::clamped(1, [[2., 0.], [0., 1.]]),
+                    surface.clone(),
+                    &mut core,
+                ),
+                HalfEdge::line_segment(
+                    [[0., 1.], [0., 0.]],
+                    surface,
+                    &mut core,
+                ),
+            ];
+            let exterior = Cycle::from_half_edges_and_boundaries(
+                half_edges_and_boundaries,
+                &mut core,
+            )
+            .insert(&mut core);
+
+            sketch.add_regions([Region::new(exterior, [])], &mut core)
+        };
+        let solid = sketch
+            .revolve_sketch(
+                core.layers.topology.surfaces.xz_plane(),
+                [[0., 0.], [0., 1.]],
+                Scalar::TAU,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 8);
+        core.layers.validation.take_errors()?;
+
+        let expected_volume = Scalar::PI * 4. / 3.;
+        assert_volume(&solid, expected_volume, &mut core)?;
+
+        Ok(())
+    }
+
+    fn half_circle(surface: Handle<Surface>, core: &mut Core) -> Cycle {
+        let quarter = Scalar::TAU / 4.;
+        let half_edges_and_boundaries = vec![
+            HalfEdge::arc([0., -1.], [1., 0.], quarter, surface.clone(), core),
+            HalfEdge::arc([1., 0.], [0., 1.], quarter, surface.clone(), core),
+            HalfEdge::line_segment([[0., 1.], [0., -1.]], surface, core),
+        ];
+
+        Cycle::from_half_edges_and_boundaries(half_edges_and_boundaries, core)
+    }
+}
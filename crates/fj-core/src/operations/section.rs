@@ -0,0 +1,526 @@
+//! # Cut solids with a plane
+//!
+//! See [`Section`].
+
+use fj_math::{Line, Point, PolyChain, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{face::approx_face, ApproxCache},
+        bounding_volume::BoundingVolume,
+        triangulate::{polygon::Polygon, triangulate_face},
+    },
+    geometry::Path,
+    topology::{Cycle, Region, Sketch, Solid, Surface},
+    Core,
+};
+
+use super::{
+    boolean::Boolean,
+    build::{BuildCycle, BuildRegion, BuildSketch, BuildSurface},
+    insert::Insert,
+    sweep::SweepSketch,
+    update::{UpdateRegion, UpdateSketch},
+};
+
+/// # Cut a solid with a plane
+///
+/// The plane goes through `origin` and is perpendicular to `normal`.
+///
+/// ## Implementation Note
+///
+/// The cross-section is computed from the triangulation of the solid, at the
+/// tolerance of the core. Curved faces therefore result in polygonal cycles.
+/// Parts of the solid that lie within the plane (like a face that is coplanar
+/// with it) don't contribute to the cross-section.
+pub trait Section {
+    /// # Compute the cross-section of the solid with the plane
+    ///
+    /// The returned sketch is on a new surface that represents the plane. Its
+    /// coordinates are orthonormal, with `normal` pointing to the front side
+    /// of the surface. Exteriors of the sketch's regions wind counter-clockwise
+    /// in those coordinates, interiors clockwise.
+    ///
+    /// If the plane doesn't cut through the solid, the sketch is empty.
+    #[must_use]
+    fn section(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Sketch;
+
+    /// # Compute the cross-section, and the halves on either side of the plane
+    ///
+    /// Returns the cross-section, as computed by [`Section::section`], and the
+    /// two halves of the solid. The first half is on the side of the plane
+    /// that `normal` points to, the second half on the other side.
+    ///
+    /// The halves are computed using [`Boolean::intersection`], and are
+    /// subject to its limitations.
+    #[must_use]
+    fn section_with_halves(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> (Sketch, [Solid; 2]);
+}
+
+impl Section for Solid {
+    fn section(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> Sketch {
+        let plane = SectionPlane::new(origin.into(), normal.into());
+
+        // Used to decide whether a point is on the plane, and whether points
+        // are identical. This needs to be much smaller than the tolerance, or
+        // we'd merge geometry that the approximation considers distinct.
+        let epsilon = core.tolerance().inner() * Scalar::from(1e-3);
+
+        let mut segments = Vec::new();
+        let mut cache = ApproxCache::default();
+        for face in self.shells().iter().flat_map(|shell| shell.faces()) {
+            let approx = approx_face(
+                face.clone(),
+                core.tolerance(),
+                &mut cache,
+                &core.layers.geometry,
+            );
+
+            segments.extend(triangulate_face(approx).into_iter().filter_map(
+                |triangle| {
+                    plane.cut_triangle(
+                        triangle.map(|point| point.global_form),
+                        epsilon,
+                    )
+                },
+            ));
+        }
+
+        let surface = Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(plane.origin, plane.u)),
+            plane.v,
+            core,
+        );
+
+        let loops = chain_segments(segments, epsilon)
+            .into_iter()
+            .map(|points| {
+                let points = points
+                    .into_iter()
+                    .map(|point| plane.project(point))
+                    .collect::<Vec<_>>();
+                remove_collinear_points(points, epsilon)
+            })
+            .filter(|points| points.len() >= 3)
+            .collect::<Vec<_>>();
+
+        // A loop is an exterior, if it's nested within an even number of other
+        // loops, and an interior of the innermost loop containing it
+        // otherwise.
+        let containers = loops
+            .iter()
+            .enumerate()
+            .map(|(i, points)| {
+                loops
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, other)| {
+                        j != i
+                            && Polygon::new()
+                                .with_exterior(
+                                    PolyChain::from(other.iter().copied())
+                                        .close(),
+                                )
+                                .contains_point(points[0])
+                    })
+                    .map(|(j, _)| j)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut regions = Vec::new();
+        for (i, exterior) in loops.iter().enumerate() {
+            if containers[i].len() % 2 != 0 {
+                continue;
+            }
+
+            let interiors = loops.iter().enumerate().filter(|&(j, _)| {
+                containers[j].len() == containers[i].len() + 1
+                    && containers[j].contains(&i)
+            });
+
+            let region = Region::polygon(
+                wound(exterior.clone(), Scalar::ONE),
+                surface.clone(),
+                core,
+            )
+            .add_interiors(
+                interiors
+                    .map(|(_, interior)| {
+                        Cycle::polygon(
+                            wound(interior.clone(), -Scalar::ONE),
+                            surface.clone(),
+                            core,
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                core,
+            );
+
+            regions.push(region.insert(core));
+        }
+
+        Sketch::new(surface, regions)
+    }
+
+    fn section_with_halves(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> (Sketch, [Solid; 2]) {
+        let origin = origin.into();
+        let normal = normal.into();
+
+        let section = self.section(origin, normal, core);
+
+        let plane = SectionPlane::new(origin, normal);
+        let size = match self.aabb(&core.layers.geometry) {
+            Some(aabb) => {
+                (aabb.center() - origin).magnitude()
+                    + (aabb.max - aabb.min).magnitude()
+            }
+            None => Scalar::ONE,
+        };
+
+        // Each half is the intersection of the solid with a box on the
+        // respective side of the plane, that is large enough to contain all of
+        // the solid that's on that side.
+        let halves = [Scalar::ONE, -Scalar::ONE].map(|side| {
+            // Sweeping a sketch away from the front side of its surface results
+            // in a correctly oriented solid. Pick the surface accordingly.
+            let surface = Surface::from_uv(
+                Path::Line(Line::from_origin_and_direction(
+                    plane.origin,
+                    plane.u,
+                )),
+                plane.v * -side,
+                core,
+            );
+
+            let surface_2d = core.layers.topology.surfaces.space_2d();
+            let half_space = Sketch::empty(&core.layers.topology)
+                .add_regions(
+                    [Region::polygon(
+                        [
+                            [-size, -size],
+                            [size, -size],
+                            [size, size],
+                            [-size, size],
+                        ],
+                        surface_2d,
+                        core,
+                    )],
+                    core,
+                )
+                .sweep_sketch(surface, plane.normal * size * side, core);
+
+            self.intersection(&half_space, core)
+        });
+
+        (section, halves)
+    }
+}
+
+/// An orthonormal coordinate system within the section plane
+struct SectionPlane {
+    origin: Point<3>,
+    normal: Vector<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+}
+
+impl SectionPlane {
+    fn new(origin: Point<3>, normal: Vector<3>) -> Self {
+        let normal = normal.normalize();
+
+        let axis = if normal.x.abs() < Scalar::from(0.9) {
+            Vector::unit_x()
+        } else {
+            Vector::unit_y()
+        };
+        let u = (axis - normal * axis.dot(&normal)).normalize();
+        let v = normal.cross(&u);
+
+        Self {
+            origin,
+            normal,
+            u,
+            v,
+        }
+    }
+
+    fn project(&self, point: Point<3>) -> Point<2> {
+        let offset = point - self.origin;
+        Point::from([offset.dot(&self.u), offset.dot(&self.v)])
+    }
+
+    fn cut_triangle(
+        &self,
+        triangle: [Point<3>; 3],
+        epsilon: Scalar,
+    ) -> Option<[Point<3>; 2]> {
+        let distances =
+            triangle.map(|point| self.normal.dot(&(point - self.origin)));
+
+        if distances.iter().all(|distance| distance.abs() <= epsilon) {
+            // The triangle is within the plane.
+            return None;
+        }
+
+        let mut points: Vec<Point<3>> = Vec::new();
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let [d_i, d_j] = [distances[i], distances[j]];
+
+            if d_i.abs() <= epsilon {
+                points.push(triangle[i]);
+            } else if d_j.abs() > epsilon && d_i.sign() != d_j.sign() {
+                let s = d_i / (d_i - d_j);
+                points.push(triangle[i] + (triangle[j] - triangle[i]) * s);
+            }
+        }
+
+        match points.as_slice() {
+            [p, q] => Some([*p, *q]),
+            _ => None,
+        }
+    }
+}
+
+/// Chain the segments of the cross-section into closed loops
+///
+/// Segments that don't form a closed loop are ignored. This can only happen,
+/// if the triangulation of the solid isn't watertight.
+fn chain_segments(
+    segments: Vec<[Point<3>; 2]>,
+    epsilon: Scalar,
+) -> Vec<Vec<Point<3>>> {
+    let mut points: Vec<Point<3>> = Vec::new();
+    let mut index_of = |point: Point<3>| {
+        let existing = points
+            .iter()
+            .position(|other| (*other - point).magnitude() <= epsilon);
+
+        existing.unwrap_or_else(|| {
+            points.push(point);
+            points.len() - 1
+        })
+    };
+
+    let mut edges = Vec::new();
+    for [start, end] in segments {
+        let [start, end] = [index_of(start), index_of(end)];
+        let edge = [start.min(end), start.max(end)];
+
+        // A segment along an edge of the triangulation results from both
+        // triangles that share that edge.
+        if start != end && !edges.contains(&edge) {
+            edges.push(edge);
+        }
+    }
+
+    let mut neighbors = vec![Vec::new(); points.len()];
+    for &[a, b] in &edges {
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+
+    let mut visited = vec![false; points.len()];
+    let mut loops = Vec::new();
+
+    for start in 0..points.len() {
+        if visited[start] || neighbors[start].len() != 2 {
+            continue;
+        }
+
+        let mut indices = vec![start];
+        visited[start] = true;
+
+        let mut previous = start;
+        let mut current = neighbors[start][0];
+        let is_closed = loop {
+            if current == start {
+                break true;
+            }
+            if visited[current] || neighbors[current].len() != 2 {
+                break false;
+            }
+
+            visited[current] = true;
+            indices.push(current);
+
+            let next = if neighbors[current][0] == previous {
+                neighbors[current][1]
+            } else {
+                neighbors[current][0]
+            };
+            previous = current;
+            current = next;
+        };
+
+        if is_closed {
+            loops.push(indices.into_iter().map(|i| points[i]).collect());
+        }
+    }
+
+    loops
+}
+
+fn remove_collinear_points(
+    mut points: Vec<Point<2>>,
+    epsilon: Scalar,
+) -> Vec<Point<2>> {
+    let mut i = 0;
+    while i < points.len() && points.len() >= 3 {
+        let previous = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        let point = points[i];
+
+        let ab = point - previous;
+        let bc = next - point;
+        let is_collinear = ab.cross2d(&bc).abs()
+            <= epsilon * (ab.magnitude() + bc.magnitude());
+
+        if is_collinear {
+            points.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+
+    points
+}
+
+/// Make sure that the points wind in the direction indicated by `sign`
+///
+/// A positive sign means counter-clockwise, a negative sign clockwise.
+fn wound(mut points: Vec<Point<2>>, sign: Scalar) -> Vec<Point<2>> {
+    let mut area = Scalar::ZERO;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area += a.u * b.v - b.u * a.v;
+    }
+
+    if area.sign() != sign.sign() {
+        points.reverse();
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::mass_properties::MassProperties,
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch},
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, Region, Sketch},
+        Core,
+    };
+
+    use super::Section;
+
+    #[test]
+    fn section_of_cube_with_hole() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+                    surface.clone(),
+                    &mut core,
+                )
+                .add_interiors(
+                    [Cycle::polygon(
+                        [[0.5, 0.5], [0.5, 1.5], [1.5, 1.5], [1.5, 0.5]],
+                        surface,
+                        &mut core,
+                    )],
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -2.],
+                &mut core,
+            );
+
+        let section = solid.section([0., 0., -1.], [0., 0., 1.], &mut core);
+
+        assert_eq!(section.regions().len(), 1);
+        let region = section.regions().only();
+        assert_eq!(region.exterior().half_edges().len(), 4);
+        assert_eq!(region.interiors().len(), 1);
+
+        let area = section.mass_properties(0.001, &core.layers.geometry).area;
+        assert!((area - 3.).abs() < Scalar::from(1e-9));
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_with_halves() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        let (section, [front, back]) =
+            solid.section_with_halves([0.25, 0., 0.], [1., 0., 0.], &mut core);
+
+        let area = section.mass_properties(0.001, &core.layers.geometry).area;
+        assert!((area - 1.).abs() < Scalar::from(1e-9));
+
+        let front = front.mass_properties(0.001, &core.layers.geometry);
+        let back = back.mass_properties(0.001, &core.layers.geometry);
+        assert!((front.volume - 0.75).abs() < Scalar::from(1e-9));
+        assert!((back.volume - 0.25).abs() < Scalar::from(1e-9));
+        assert!(
+            front.center_of_mass.unwrap().x > back.center_of_mass.unwrap().x
+        );
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
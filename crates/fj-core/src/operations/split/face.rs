@@ -1,12 +1,26 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter,
+};
+
 use fj_interop::ext::ArrayExt;
-use fj_math::Point;
+use fj_math::{Point, PolyChain, Scalar, Vector};
 use itertools::Itertools;
 
 use crate::{
-    geometry::LocalVertexGeom,
+    algorithms::{
+        approx::{cycle::approx_cycle, ApproxCache},
+        intersect::refine_path_intersection,
+        triangulate::polygon::Polygon,
+    },
+    geometry::{
+        traits::GenPolyline, CurveBoundary, Geometry, LocalCurveGeom,
+        LocalVertexGeom, Path, Tolerance,
+    },
     operations::{
         build::{BuildCycle, BuildHalfEdge},
         derive::DeriveFrom,
+        geometry::UpdateCurveGeometry,
         insert::Insert,
         split::SplitEdge,
         update::{
@@ -14,7 +28,7 @@ use crate::{
         },
     },
     storage::Handle,
-    topology::{Cycle, Face, HalfEdge, Shell},
+    topology::{Curve, Cycle, Face, HalfEdge, Region, Shell, Surface, Vertex},
     Core,
 };
 
@@ -33,9 +47,8 @@ pub trait SplitFace: Sized {
     /// # Implementation Note
     ///
     /// The way the split line is specified is rather inconvenient, and not very
-    /// flexible. This is an artifact of the current implementation, and more
-    /// flexible and convenient ways to split the face (like an arbitrary curve)
-    /// can be provided later.
+    /// flexible. See [`SplitFace::split_face_along_path`] for a more convenient
+    /// alternative.
     #[must_use]
     fn split_face(
         &self,
@@ -43,6 +56,35 @@ pub trait SplitFace: Sized {
         line: [(&Handle<HalfEdge>, impl Into<Point<1>>); 2],
         core: &mut Core,
     ) -> (Self, [Handle<Face>; 2]);
+
+    /// Split the face along a path
+    ///
+    /// The path is defined in the coordinates of the face's surface. Where it
+    /// crosses the boundary of the face, including its interior cycles, the
+    /// respective edges are split. Each part of the path that runs through the
+    /// face, between two such crossings, then divides the face. A closed path
+    /// that runs through the face without touching its boundary cuts out a new
+    /// face, leaving a hole in the original one.
+    ///
+    /// Returns the updated shell, as well as the faces that replaced the
+    /// original one. If the path doesn't run through the face, the shell is
+    /// returned unchanged, together with the original face.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the face is not part of the shell.
+    ///
+    /// Panics, if the path crosses the boundary of the face more than once at
+    /// the same vertex, or if a part of the path that runs through the face
+    /// doesn't divide it. The latter can happen, if an open path ends within
+    /// the face.
+    #[must_use]
+    fn split_face_along_path(
+        &self,
+        face: &Handle<Face>,
+        path: Path<2>,
+        core: &mut Core,
+    ) -> (Self, Vec<Handle<Face>>);
 }
 
 impl SplitFace for Shell {
@@ -101,159 +143,831 @@ impl SplitFace for Shell {
         let updated_face_after_split_edges = updated_face_after_split_edges
             .expect("Updated shell must contain updated face");
 
-        // Build the edge that's going to divide the new faces.
-        let dividing_half_edge_a_to_d = {
+        divide_face(&self_, updated_face_after_split_edges, [&b, &d], core)
+    }
+
+    fn split_face_along_path(
+        &self,
+        face: &Handle<Face>,
+        path: Path<2>,
+        core: &mut Core,
+    ) -> (Self, Vec<Handle<Face>>) {
+        assert!(
+            self.faces().contains(face),
+            "Expected face to be part of shell"
+        );
+
+        let tolerance = core.tolerance();
+        let epsilon = tolerance.inner() * Scalar::from(1e-3);
+
+        let boundary = face
+            .region()
+            .all_cycles()
+            .map(|cycle| {
+                cycle
+                    .half_edges()
+                    .pairs()
+                    .map(|(half_edge, next)| {
+                        let polyline = half_edge_polyline(
+                            half_edge,
+                            next,
+                            face.surface(),
+                            tolerance,
+                            &core.layers.geometry,
+                        );
+                        (half_edge.clone(), polyline)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let polygon = {
+            let mut chains = boundary.iter().map(|cycle| {
+                let points = cycle.iter().flat_map(|(_, polyline)| {
+                    // Leave out the end of the half-edge. It's the start of the
+                    // next one.
+                    polyline[..polyline.len() - 1]
+                        .iter()
+                        .map(|(_, point)| *point)
+                });
+
+                PolyChain::from(points.collect::<Vec<_>>()).close()
+            });
+
+            let exterior = chains.next().expect("Region has an exterior");
+            Polygon::new()
+                .with_exterior(exterior)
+                .with_interiors(chains)
+        };
+
+        let (domain, is_closed) = match &path {
+            Path::Circle(_) | Path::Ellipse(_) => {
+                ([Scalar::ZERO, Scalar::TAU], true)
+            }
+            Path::Line(line) => {
+                // A line is infinite, but we only care about the part of it
+                // that could possibly cross the face.
+                let coords = boundary
+                    .iter()
+                    .flatten()
+                    .flat_map(|(_, polyline)| polyline)
+                    .map(|(_, point)| line.point_to_line_coords(*point).t);
+                let min = coords.clone().fold(Scalar::MAX, Scalar::min);
+                let max = coords.fold(-Scalar::MAX, Scalar::max);
+
+                ([min - 1., max + 1.], false)
+            }
+            Path::Nurbs(curve) => {
+                let domain = curve.domain();
+                let [start, end] =
+                    domain.map(|t| curve.point_from_curve_coords([t]));
+
+                (domain, start.distance_to(&end) <= epsilon)
+            }
+        };
+        let period = domain[1] - domain[0];
+        let path_polyline =
+            polyline(&path, domain.map(|t| Point::from([t])), tolerance);
+
+        let mut crossings = find_crossings(
+            &boundary,
+            &path,
+            &path_polyline,
+            epsilon,
+            &core.layers.geometry,
+            face.surface(),
+        );
+
+        // The same crossing can be found twice, if it is located where two
+        // segments of an approximation meet.
+        crossings.sort_by_key(|crossing| crossing.t);
+        crossings.dedup_by(|b, a| {
+            path.point_from_path_coords([a.t])
+                .distance_to(&path.point_from_path_coords([b.t]))
+                <= epsilon
+        });
+        if is_closed && crossings.len() > 1 {
+            let [first, last] = [0, crossings.len() - 1]
+                .map(|i| path.point_from_path_coords([crossings[i].t]));
+            if first.distance_to(&last) <= epsilon {
+                crossings.pop();
+            }
+        }
+
+        // Figure out which parts of the path run through the face. For an open
+        // path, the parts before the first and after the last crossing don't
+        // divide the face, even if they run through it.
+        let num_intervals = if is_closed {
+            crossings.len()
+        } else {
+            crossings.len().saturating_sub(1)
+        };
+        let is_inside = (0..num_intervals)
+            .map(|i| {
+                let start = crossings[i].t;
+                let end = crossings
+                    .get(i + 1)
+                    .map(|crossing| crossing.t)
+                    .unwrap_or(crossings[0].t + period);
+
+                polygon.contains_point(
+                    path.point_from_path_coords([(start + end) / 2.]),
+                )
+            })
+            .collect::<Vec<_>>();
+        let interval_is_inside = |i: usize| {
+            if is_closed {
+                is_inside[i % num_intervals]
+            } else {
+                is_inside.get(i).copied().unwrap_or(false)
+            }
+        };
+
+        // Only crossings where the path enters or leaves the face are
+        // relevant. Where it just touches the boundary, it doesn't divide
+        // anything.
+        let num_crossings = crossings.len();
+        let crossings = crossings
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                let before = if *i == 0 {
+                    is_closed && interval_is_inside(num_crossings - 1)
+                } else {
+                    interval_is_inside(i - 1)
+                };
+                before != interval_is_inside(*i)
+            })
+            .map(|(i, crossing)| (crossing, interval_is_inside(i)))
+            .collect::<Vec<_>>();
+
+        let runs_through_without_crossing = is_closed
+            && crossings.is_empty()
+            && if num_crossings == 0 {
+                polygon.contains_point(path.point_from_path_coords([domain[0]]))
+            } else {
+                interval_is_inside(0)
+            };
+        if crossings.is_empty() && !runs_through_without_crossing {
+            return (self.clone(), vec![face.clone()]);
+        }
+
+        // Split the edges where the path crosses them. Where a half-edge is
+        // crossed multiple times, start splitting at the end, so the part that
+        // remains to be split is always the first one.
+        let mut shell = self.clone();
+        let mut updated_face = face.clone();
+        let mut vertices = crossings
+            .iter()
+            .map(|(crossing, _)| crossing.half_edge.start_vertex().clone())
+            .collect::<Vec<_>>();
+        let mut crossings_by_half_edge = BTreeMap::new();
+        for (i, (crossing, _)) in crossings.iter().enumerate() {
+            if let Some(position) = crossing.position {
+                crossings_by_half_edge
+                    .entry(crossing.half_edge.clone())
+                    .or_insert_with(Vec::new)
+                    .push((position, i));
+            }
+        }
+        for (half_edge, mut positions) in crossings_by_half_edge {
             let start = core
                 .layers
                 .geometry
-                .of_curve(b.curve())
+                .of_vertex(half_edge.start_vertex())
                 .unwrap()
-                .local_on(face.surface())
+                .local_on(half_edge.curve())
                 .unwrap()
-                .path
-                .point_from_path_coords(
-                    core.layers
-                        .geometry
-                        .of_vertex(b.start_vertex())
-                        .unwrap()
-                        .local_on(b.curve())
-                        .unwrap()
-                        .position,
+                .position;
+            positions.sort_by_key(|(position, _)| (*position - start).t.abs());
+
+            let mut half_edge = half_edge;
+            for (position, i) in positions.into_iter().rev() {
+                let (split_shell, [[a, b], _]) =
+                    shell.split_edge(&half_edge, position, core);
+
+                vertices[i] = b.start_vertex().clone();
+                updated_face = split_shell
+                    .faces()
+                    .iter()
+                    .find(|face| {
+                        face.region()
+                            .all_cycles()
+                            .any(|cycle| cycle.half_edges().contains(&a))
+                    })
+                    .expect("Updated shell must contain updated face")
+                    .clone();
+
+                shell = split_shell;
+                half_edge = a;
+            }
+        }
+
+        let unique_vertices = vertices.iter().collect::<BTreeSet<_>>();
+        assert_eq!(
+            unique_vertices.len(),
+            vertices.len(),
+            "Path crossing boundary of face multiple times at the same vertex \
+            is not supported"
+        );
+
+        // The half-edges of the new faces, each mapped to the half-edge that
+        // follows it within its cycle. To start with, that's just the original
+        // cycles.
+        let mut next_half_edges = BTreeMap::new();
+        let mut incoming = BTreeMap::new();
+        let mut outgoing = BTreeMap::new();
+        for cycle in updated_face.region().all_cycles() {
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                next_half_edges.insert(half_edge.clone(), next.clone());
+                incoming.insert(next.start_vertex().clone(), half_edge.clone());
+                outgoing.insert(
+                    half_edge.start_vertex().clone(),
+                    half_edge.clone(),
                 );
-            let end = core
-                .layers
-                .geometry
-                .of_curve(d.curve())
-                .unwrap()
-                .local_on(face.surface())
-                .unwrap()
-                .path
-                .point_from_path_coords(
-                    core.layers
-                        .geometry
-                        .of_vertex(d.start_vertex())
-                        .unwrap()
-                        .local_on(d.curve())
-                        .unwrap()
-                        .position,
+            }
+        }
+
+        // Build the half-edges along the parts of the path that run through
+        // the face, and connect them to the boundary. Where such a part
+        // starts, the face is divided into the area before it, and the area
+        // after it.
+        let mut chords = Vec::new();
+        let mut sections = Vec::new();
+        if crossings.is_empty() {
+            // This path runs through the face without crossing its boundary.
+            // Like in `BuildCycle::circle`, build it out of multiple
+            // half-edges.
+            let vertices = [0, 1, 2, 3].map(|_| Vertex::new().insert(core));
+            let quarter = period / 4.;
+
+            sections.push(PathSection {
+                points: (0..5)
+                    .map(|i| {
+                        (
+                            vertices[i % 4].clone(),
+                            domain[0] + quarter * Scalar::from(i as f64),
+                        )
+                    })
+                    .collect(),
+                is_loop: true,
+            });
+        } else {
+            for (i, ((crossing, is_inside), start_vertex)) in
+                crossings.iter().zip(&vertices).enumerate()
+            {
+                if !is_inside {
+                    continue;
+                }
+
+                let (end, end_vertex) = match crossings.get(i + 1) {
+                    Some((end, _)) => (end.t, vertices[i + 1].clone()),
+                    None => (crossings[0].0.t + period, vertices[0].clone()),
+                };
+
+                sections.push(PathSection {
+                    points: vec![
+                        (start_vertex.clone(), crossing.t),
+                        (end_vertex, end),
+                    ],
+                    is_loop: false,
+                });
+            }
+        }
+
+        for PathSection { points, is_loop } in sections {
+            let mut half_edges = Vec::new();
+
+            for [(start_vertex, start), (end_vertex, end)] in
+                points.iter().cloned().tuple_windows().map(|(a, b)| [a, b])
+            {
+                // Each half-edge gets its own curve. Otherwise, vertices that
+                // are shared between half-edges would need two positions on
+                // the same curve, where the path is closed.
+                let curve = Curve::new().insert(core);
+                core.layers.geometry.define_curve(
+                    curve.clone(),
+                    updated_face.surface().clone(),
+                    LocalCurveGeom { path: path.clone() },
                 );
+                for (vertex, position) in
+                    [(&start_vertex, start), (&end_vertex, end)]
+                {
+                    core.layers.geometry.define_vertex(
+                        vertex.clone(),
+                        curve.clone(),
+                        LocalVertexGeom {
+                            position: Point::from([position]),
+                        },
+                    );
+                }
 
-            let (half_edge, boundary) = HalfEdge::line_segment(
-                [start, end],
-                face.surface().clone(),
-                core,
-            );
+                let forward =
+                    HalfEdge::new(curve, start_vertex.clone()).insert(core);
+                let backward =
+                    HalfEdge::from_sibling(&forward, end_vertex.clone(), core);
+
+                half_edges.push([forward, backward]);
+            }
 
-            core.layers.geometry.define_vertex(
-                b.start_vertex().clone(),
-                half_edge.curve().clone(),
-                LocalVertexGeom {
-                    position: boundary.inner[0],
-                },
+            for ([forward_a, backward_a], [forward_b, backward_b]) in
+                half_edges.iter().tuple_windows()
+            {
+                next_half_edges.insert(forward_a.clone(), forward_b.clone());
+                next_half_edges.insert(backward_b.clone(), backward_a.clone());
+            }
+
+            let [first_forward, first_backward] =
+                half_edges.first().expect("Path section is not empty");
+            let [last_forward, last_backward] =
+                half_edges.last().expect("Path section is not empty");
+
+            if is_loop {
+                next_half_edges
+                    .insert(last_forward.clone(), first_forward.clone());
+                next_half_edges
+                    .insert(first_backward.clone(), last_backward.clone());
+            } else {
+                let start = first_forward.start_vertex();
+                let end = last_backward.start_vertex();
+
+                next_half_edges
+                    .insert(incoming[start].clone(), first_forward.clone());
+                next_half_edges
+                    .insert(first_backward.clone(), outgoing[start].clone());
+                next_half_edges
+                    .insert(last_forward.clone(), outgoing[end].clone());
+                next_half_edges
+                    .insert(incoming[end].clone(), last_backward.clone());
+            }
+
+            chords.extend(half_edges);
+        }
+
+        // Now we can follow the half-edges around, to get the cycles of the
+        // new faces.
+        let mut cycles = Vec::new();
+        let mut visited = BTreeSet::new();
+        for start in next_half_edges.keys() {
+            if visited.contains(start) {
+                continue;
+            }
+
+            let mut half_edges = Vec::new();
+            let mut half_edge = start;
+            while visited.insert(half_edge.clone()) {
+                half_edges.push(half_edge.clone());
+                half_edge = &next_half_edges[half_edge];
+            }
+
+            for [forward, backward] in &chords {
+                assert!(
+                    !(half_edges.contains(forward)
+                        && half_edges.contains(backward)),
+                    "Path must divide the face"
+                );
+            }
+
+            let cycle = Cycle::new(half_edges);
+            let points = approx_cycle(
+                &cycle,
+                updated_face.surface(),
+                tolerance,
+                &mut ApproxCache::default(),
+                &core.layers.geometry,
+            )
+            .points()
+            .into_iter()
+            .map(|point| point.local_form)
+            .collect::<Vec<_>>();
+
+            cycles.push((cycle.insert(core), points));
+        }
+
+        // Cycles that wind the same way as the exterior of the original face
+        // are the exteriors of the new faces. The other ones are interiors, of
+        // the smallest exterior that contains them.
+        let exterior_winding = {
+            let points = boundary[0]
+                .iter()
+                .flat_map(|(_, polyline)| {
+                    polyline[..polyline.len() - 1]
+                        .iter()
+                        .map(|(_, point)| *point)
+                })
+                .collect::<Vec<_>>();
+            signed_area(&points).sign()
+        };
+        let (exteriors, interiors): (Vec<_>, Vec<_>) =
+            cycles.into_iter().partition(|(_, points)| {
+                signed_area(points).sign() == exterior_winding
+            });
+
+        let mut interiors_by_exterior = vec![Vec::new(); exteriors.len()];
+        for (interior, points) in interiors {
+            // An interior can share its boundary with an exterior, so test
+            // against a point that is slightly off its boundary, on the side
+            // of the face that contains it.
+            let [a, b] = [points[0], points[1]];
+            let direction = b - a;
+            let point = a
+                + direction / 2.
+                + Vector::from([-direction.v, direction.u])
+                    * exterior_winding.to_scalar()
+                    * 1e-3;
+
+            let exterior = exteriors
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, exterior))| {
+                    Polygon::new()
+                        .with_exterior(exterior.iter().copied())
+                        .contains_point(point)
+                })
+                .min_by_key(|(_, (_, exterior))| signed_area(exterior).abs())
+                .map(|(i, _)| i)
+                .expect("Expected interior to be within an exterior");
+
+            interiors_by_exterior[exterior].push(interior);
+        }
+
+        let faces = exteriors
+            .into_iter()
+            .zip(interiors_by_exterior)
+            .map(|((exterior, _), interiors)| {
+                updated_face
+                    .update_region(
+                        |_, _| Region::new(exterior, interiors),
+                        core,
+                    )
+                    .insert(core)
+                    .derive_from(&updated_face, core)
+            })
+            .collect::<Vec<_>>();
+
+        let shell =
+            shell.update_face(&updated_face, |_, _| faces.clone(), core);
+
+        (shell, faces)
+    }
+}
+
+/// Divide a face into two, along a line between two of its vertices
+///
+/// The line connects the start vertices of the provided half-edges, which must
+/// be part of the face's exterior. It is a straight line in the coordinates of
+/// the face's surface.
+///
+/// Returns the updated shell, as well as the two faces that replaced the
+/// original one.
+pub(super) fn divide_face(
+    shell: &Shell,
+    face: &Handle<Face>,
+    [b, d]: [&Handle<HalfEdge>; 2],
+    core: &mut Core,
+) -> (Shell, [Handle<Face>; 2]) {
+    // Build the edge that's going to divide the new faces.
+    let dividing_half_edge_b_to_d = {
+        let start = core
+            .layers
+            .geometry
+            .of_curve(b.curve())
+            .unwrap()
+            .local_on(face.surface())
+            .unwrap()
+            .path
+            .point_from_path_coords(
+                core.layers
+                    .geometry
+                    .of_vertex(b.start_vertex())
+                    .unwrap()
+                    .local_on(b.curve())
+                    .unwrap()
+                    .position,
             );
-            core.layers.geometry.define_vertex(
-                d.start_vertex().clone(),
-                half_edge.curve().clone(),
-                LocalVertexGeom {
-                    position: boundary.inner[1],
-                },
+        let end = core
+            .layers
+            .geometry
+            .of_curve(d.curve())
+            .unwrap()
+            .local_on(face.surface())
+            .unwrap()
+            .path
+            .point_from_path_coords(
+                core.layers
+                    .geometry
+                    .of_vertex(d.start_vertex())
+                    .unwrap()
+                    .local_on(d.curve())
+                    .unwrap()
+                    .position,
             );
 
-            half_edge
-                .update_start_vertex(|_, _| b.start_vertex().clone(), core)
-                .insert(core)
+        // The line is parametrized by its length in surface coordinates. On a
+        // curved surface, this makes it possible to convert a line along one of
+        // the surface's axes into global coordinates.
+        let boundary = CurveBoundary {
+            inner: [
+                Point::from([0.]),
+                Point::from([(end - start).magnitude()]),
+            ],
         };
-        let dividing_half_edge_c_to_b = HalfEdge::from_sibling(
-            &dividing_half_edge_a_to_d,
+
+        let half_edge = HalfEdge::unjoined(core).insert(core);
+        half_edge.curve().clone().make_line_on_surface(
+            [start, end],
+            boundary,
+            face.surface().clone(),
+            &mut core.layers.geometry,
+        );
+
+        core.layers.geometry.define_vertex(
+            b.start_vertex().clone(),
+            half_edge.curve().clone(),
+            LocalVertexGeom {
+                position: boundary.inner[0],
+            },
+        );
+        core.layers.geometry.define_vertex(
             d.start_vertex().clone(),
-            core,
+            half_edge.curve().clone(),
+            LocalVertexGeom {
+                position: boundary.inner[1],
+            },
         );
 
-        let mut half_edges_of_face_starting_at_b =
-            updated_face_after_split_edges
-                .region()
-                .exterior()
-                .half_edges()
-                .iter()
-                .cloned()
-                .cycle()
-                .skip_while(|half_edge| half_edge != &b);
-
-        let half_edges_b_to_c_inclusive = half_edges_of_face_starting_at_b
-            .take_while_ref(|half_edge| half_edge != &d);
-        let split_face_a = updated_face_after_split_edges
-            .update_region(
-                |region, core| {
-                    region.update_exterior(
-                        |_, core| {
-                            Cycle::empty()
-                                .add_half_edges(
-                                    half_edges_b_to_c_inclusive,
-                                    core,
-                                )
-                                .add_half_edges(
-                                    [dividing_half_edge_c_to_b],
-                                    core,
-                                )
-                        },
-                        core,
-                    )
-                },
-                core,
-            )
+        half_edge
+            .update_start_vertex(|_, _| b.start_vertex().clone(), core)
             .insert(core)
-            .derive_from(updated_face_after_split_edges, core);
-
-        // The previous operation has moved the iterator along.
-        let half_edges_of_face_starting_at_d = half_edges_of_face_starting_at_b;
-
-        let half_edges_d_to_a_inclusive = half_edges_of_face_starting_at_d
-            .take_while(|half_edge| half_edge != &b);
-        let split_face_b = updated_face_after_split_edges
-            .update_region(
-                |region, core| {
-                    region.update_exterior(
-                        |_, core| {
-                            Cycle::empty()
-                                .add_half_edges(
-                                    half_edges_d_to_a_inclusive,
-                                    core,
-                                )
-                                .add_half_edges(
-                                    [dividing_half_edge_a_to_d],
-                                    core,
-                                )
-                        },
-                        core,
-                    )
-                },
-                core,
-            )
-            .insert(core)
-            .derive_from(updated_face_after_split_edges, core);
+    };
+    let dividing_half_edge_d_to_b = HalfEdge::from_sibling(
+        &dividing_half_edge_b_to_d,
+        d.start_vertex().clone(),
+        core,
+    );
 
-        let faces = [split_face_a, split_face_b];
-        let self_ = self_.update_face(
-            updated_face_after_split_edges,
-            |_, _| faces.clone(),
+    let mut half_edges_of_face_starting_at_b = face
+        .region()
+        .exterior()
+        .half_edges()
+        .iter()
+        .cloned()
+        .cycle()
+        .skip_while(|half_edge| half_edge != b);
+
+    let half_edges_b_to_d_exclusive = half_edges_of_face_starting_at_b
+        .take_while_ref(|half_edge| half_edge != d);
+    let split_face_a = face
+        .update_region(
+            |region, core| {
+                region.update_exterior(
+                    |_, core| {
+                        Cycle::empty()
+                            .add_half_edges(half_edges_b_to_d_exclusive, core)
+                            .add_half_edges([dividing_half_edge_d_to_b], core)
+                    },
+                    core,
+                )
+            },
             core,
-        );
+        )
+        .insert(core)
+        .derive_from(face, core);
+
+    // The previous operation has moved the iterator along.
+    let half_edges_of_face_starting_at_d = half_edges_of_face_starting_at_b;
+
+    let half_edges_d_to_b_exclusive =
+        half_edges_of_face_starting_at_d.take_while(|half_edge| half_edge != b);
+    let split_face_b = face
+        .update_region(
+            |region, core| {
+                region.update_exterior(
+                    |_, core| {
+                        Cycle::empty()
+                            .add_half_edges(half_edges_d_to_b_exclusive, core)
+                            .add_half_edges([dividing_half_edge_b_to_d], core)
+                    },
+                    core,
+                )
+            },
+            core,
+        )
+        .insert(core)
+        .derive_from(face, core);
+
+    let faces = [split_face_a, split_face_b];
+    let shell = shell.update_face(face, |_, _| faces.clone(), core);
+
+    (shell, faces)
+}
+
+/// An approximation of a curve, in curve and surface coordinates
+type Polyline = Vec<(Point<1>, Point<2>)>;
+
+/// A point where a path crosses the boundary of a face
+struct Crossing {
+    /// The position of the crossing on the path
+    t: Scalar,
+
+    /// The half-edge that the path crosses
+    half_edge: Handle<HalfEdge>,
+
+    /// The position of the crossing on the curve of the half-edge
+    ///
+    /// `None`, if the path crosses at the start vertex of the half-edge.
+    position: Option<Point<1>>,
+}
+
+/// A part of a path that runs through a face
+struct PathSection {
+    /// The vertices along the section, and their positions on the path
+    points: Vec<(Handle<Vertex>, Scalar)>,
+
+    /// Indicate whether the section is a closed loop
+    is_loop: bool,
+}
 
-        (self_, faces)
+fn signed_area(points: &[Point<2>]) -> Scalar {
+    let mut area = Scalar::ZERO;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area += a.u * b.v - b.u * a.v;
     }
+    area / 2.
+}
+
+fn find_crossings(
+    boundary: &[Vec<(Handle<HalfEdge>, Polyline)>],
+    path: &Path<2>,
+    path_polyline: &[(Point<1>, Point<2>)],
+    epsilon: Scalar,
+    geometry: &Geometry,
+    surface: &Handle<Surface>,
+) -> Vec<Crossing> {
+    let mut crossings = Vec::new();
+
+    for (half_edge, polyline) in boundary.iter().flatten() {
+        let half_edge_path = &geometry
+            .of_curve(half_edge.curve())
+            .unwrap()
+            .local_on(surface)
+            .unwrap()
+            .path;
+
+        for (i, [(s0, p0), (s1, p1)]) in polyline
+            .iter()
+            .copied()
+            .tuple_windows()
+            .map(|(a, b)| [a, b])
+            .enumerate()
+        {
+            for [(t0, q0), (t1, q1)] in path_polyline
+                .iter()
+                .copied()
+                .tuple_windows()
+                .map(|(a, b)| [a, b])
+            {
+                let Some([alpha, beta]) =
+                    intersect_segments([p0, p1], [q0, q1], epsilon)
+                else {
+                    continue;
+                };
+
+                // Crossings at a vertex are attributed to the half-edge that
+                // starts there.
+                let distance_to_start = (p1 - p0).magnitude() * alpha;
+                let distance_to_end =
+                    (p1 - p0).magnitude() * (Scalar::ONE - alpha);
+                let is_at_start = i == 0 && distance_to_start <= epsilon;
+                let is_at_end =
+                    i == polyline.len() - 2 && distance_to_end <= epsilon;
+                if is_at_end {
+                    continue;
+                }
+
+                // The crossing of the approximations is only close to the
+                // actual one. Refine it, to make sure the new vertices are
+                // exactly on both the half-edge and the path.
+                let s = if is_at_start {
+                    s0
+                } else {
+                    s0 + (s1 - s0) * alpha
+                };
+                let t = t0 + (t1 - t0) * beta;
+                let [s, t] = refine_path_intersection(
+                    half_edge_path,
+                    path,
+                    [s, t],
+                    is_at_start,
+                );
+
+                crossings.push(Crossing {
+                    t: t.t,
+                    half_edge: half_edge.clone(),
+                    position: (!is_at_start).then_some(s),
+                });
+            }
+        }
+    }
+
+    crossings
+}
+
+/// Compute where two line segments cross, in their respective coordinates
+///
+/// Returns `None`, if the segments don't cross or are parallel. Segments that
+/// miss each other by no more than `epsilon` at one of their ends, are still
+/// considered to cross there. Otherwise a crossing at a vertex could be missed
+/// due to rounding.
+fn intersect_segments(
+    [p0, p1]: [Point<2>; 2],
+    [q0, q1]: [Point<2>; 2],
+    epsilon: Scalar,
+) -> Option<[Scalar; 2]> {
+    let cross = |a: Vector<2>, b: Vector<2>| a.u * b.v - a.v * b.u;
+
+    let d1 = p1 - p0;
+    let d2 = q1 - q0;
+    let r = q0 - p0;
+
+    let det = cross(d1, d2);
+    if det == Scalar::ZERO {
+        return None;
+    }
+
+    let alpha = cross(r, d2) / det;
+    let beta = cross(r, d1) / det;
+
+    let is_within = |parameter: Scalar, segment: Vector<2>| {
+        let margin = epsilon / segment.magnitude();
+        parameter >= -margin && parameter <= Scalar::ONE + margin
+    };
+    (is_within(alpha, d1) && is_within(beta, d2)).then(|| {
+        [alpha, beta]
+            .map(|parameter| parameter.max(Scalar::ZERO).min(Scalar::ONE))
+    })
+}
+
+fn half_edge_polyline(
+    half_edge: &Handle<HalfEdge>,
+    next: &Handle<HalfEdge>,
+    surface: &Handle<Surface>,
+    tolerance: Tolerance,
+    geometry: &Geometry,
+) -> Polyline {
+    let boundary = [half_edge, next].map(|h| {
+        geometry
+            .of_vertex(h.start_vertex())
+            .unwrap()
+            .local_on(half_edge.curve())
+            .unwrap()
+            .position
+    });
+    let path = &geometry
+        .of_curve(half_edge.curve())
+        .unwrap()
+        .local_on(surface)
+        .unwrap()
+        .path;
+
+    polyline(path, boundary, tolerance)
+}
+
+fn polyline(
+    path: &Path<2>,
+    boundary: [Point<1>; 2],
+    tolerance: Tolerance,
+) -> Polyline {
+    let [start, end] = boundary;
+    let rest = path
+        .generate_polyline(CurveBoundary { inner: boundary }, tolerance)
+        .into_iter()
+        .filter(|&point| point != start && point != end);
+
+    iter::once(start)
+        .chain(rest)
+        .chain(iter::once(end))
+        .map(|point| (point, path.point_from_path_coords(point)))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use fj_interop::Color;
+    use fj_math::Scalar;
 
     use crate::{
+        algorithms::mass_properties::MassProperties,
+        geometry::Path,
         operations::{
-            build::BuildShell,
+            build::{BuildCycle, BuildRegion, BuildShell, BuildSketch},
             presentation::{GetColor, SetColor},
             split::SplitFace,
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
         },
-        topology::Shell,
+        storage::Handle,
+        topology::{Cycle, Face, Region, Shell, Sketch},
         Core,
     };
 
@@ -282,4 +996,98 @@ mod tests {
         assert_eq!(face_a.region().get_color(&mut core), Some(color));
         assert_eq!(face_b.region().get_color(&mut core), Some(color));
     }
+
+    #[test]
+    fn split_face_along_line_through_hole() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let (shell, face) = square_with_hole(&mut core);
+
+        let (path, _) = Path::line_from_points([[0., 1.5], [1., 1.5]]);
+        let (_shell, faces) =
+            shell.split_face_along_path(&face, path, &mut core);
+
+        // The line divides the face through the hole. The hole becomes part of
+        // the boundary of both new faces.
+        assert_eq!(faces.len(), 2);
+        for face in &faces {
+            assert!(face.region().interiors().is_empty());
+            assert_eq!(face.region().exterior().half_edges().len(), 8);
+
+            let area = face.mass_properties(0.001, &core.layers.geometry).area;
+            assert!((area - 4.).abs() < Scalar::from(1e-9));
+        }
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_face_along_circle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // The circle doesn't touch the boundary, but contains the hole. It
+        // cuts out a new face that contains the hole, leaving a hole in the
+        // original face.
+        let (shell, face) = square_with_hole(&mut core);
+        let path = Path::circle_from_center_and_radius([1.5, 1.5], 1.);
+        let (_shell, faces) =
+            shell.split_face_along_path(&face, path, &mut core);
+
+        assert_eq!(faces.len(), 2);
+        for face in &faces {
+            assert_eq!(face.region().interiors().len(), 1);
+        }
+
+        // A circle that crosses the boundary of the face cuts off a corner.
+        let (shell, face) = square_with_hole(&mut core);
+        let path = Path::circle_from_center_and_radius([0., 0.], 0.5);
+        let (_shell, faces) =
+            shell.split_face_along_path(&face, path, &mut core);
+
+        let mut areas = faces
+            .iter()
+            .map(|face| face.mass_properties(0.001, &core.layers.geometry).area)
+            .collect::<Vec<_>>();
+        areas.sort();
+        let corner = Scalar::PI * 0.25 / 4.;
+        assert!((areas[0] - corner).abs() < Scalar::from(0.01));
+        assert!((areas[1] + corner - 8.).abs() < Scalar::from(0.01));
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    fn square_with_hole(core: &mut Core) -> (Handle<Shell>, Handle<Face>) {
+        let surface = core.layers.topology.surfaces.space_2d();
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+
+        let hole = Cycle::polygon(
+            [[1., 1.], [1., 2.], [2., 2.], [2., 1.]],
+            surface.clone(),
+            core,
+        );
+        let region = Region::polygon(
+            [[0., 0.], [3., 0.], [3., 3.], [0., 3.]],
+            surface,
+            core,
+        )
+        .add_interiors([hole], core);
+
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(xy_plane.clone(), [0., 0., -1.], core);
+
+        let shell = solid.shells().only().clone();
+        let face = shell
+            .faces()
+            .iter()
+            .find(|face| face.surface() == &xy_plane)
+            .expect("Expected swept solid to contain face of sketch")
+            .clone();
+
+        (shell, face)
+    }
 }
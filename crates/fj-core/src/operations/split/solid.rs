@@ -0,0 +1,857 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Line, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{cycle::approx_cycle, face::approx_face, ApproxCache},
+        triangulate::polygon::Polygon,
+    },
+    geometry::{
+        repr::tri_mesh::convert_point_surface_to_global, surfaces::SweptCurve,
+        Path,
+    },
+    operations::{
+        build::{BuildHalfEdge, BuildSurface},
+        components::Components,
+        derive::DeriveFrom,
+        geometry::UpdateCurveGeometry,
+        insert::Insert,
+        split::{SplitEdge, SplitFace},
+    },
+    queries::SiblingOfHalfEdge,
+    storage::Handle,
+    topology::{
+        Cycle, Face, HalfEdge, Handedness, Region, Shell, Solid, Surface,
+        Vertex,
+    },
+    Core,
+};
+
+use super::face::divide_face;
+
+/// Split a solid into two
+pub trait SplitSolid: Sized {
+    /// Split the solid into two, along a plane
+    ///
+    /// The plane goes through `origin` and is perpendicular to `normal`.
+    /// Returns the two halves, the first on the side of the plane that
+    /// `normal` points to, the second on the other side. Both are closed by
+    /// new faces within the plane. If the plane doesn't cut through the solid,
+    /// one of the halves is empty.
+    ///
+    /// Edges that cross the plane are split using [`SplitEdge`], faces that
+    /// cross it are split along the plane, like [`SplitFace`] does.
+    ///
+    /// Faces that are within the plane end up in the half that they bound.
+    /// Where the plane cuts a shell into multiple disconnected pieces, each of
+    /// them becomes a separate shell of its half.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the plane crosses a face on a curved surface, unless the
+    /// intersection is a single straight line in the coordinates of that
+    /// surface, that doesn't cross any of the face's interior cycles. This is
+    /// the case, where the intersection runs along one of the surface's axes.
+    /// Faces on planes are supported, regardless of their shape.
+    ///
+    /// [`SplitFace`]: super::SplitFace
+    #[must_use]
+    fn split_solid(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> [Self; 2];
+}
+
+impl SplitSolid for Solid {
+    fn split_solid(
+        &self,
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+        core: &mut Core,
+    ) -> [Self; 2] {
+        let plane = SplitPlane {
+            origin: origin.into(),
+            normal: normal.into().normalize(),
+            epsilon: core.tolerance().inner() * Scalar::from(1e-3),
+        };
+
+        let mut shells_by_side: [Vec<Handle<Shell>>; 2] = Default::default();
+        let mut faces_by_side: [Vec<Handle<Face>>; 2] = Default::default();
+        let mut boundaries_by_side: [Vec<CapBoundary>; 2] = Default::default();
+
+        for shell in self.shells() {
+            let faces = shell
+                .faces()
+                .iter()
+                .map(|face| (face.clone(), plane.side_of_face(face, core)))
+                .collect::<Vec<_>>();
+            let is_on_one_side =
+                faces.iter().all(|(_, side)| *side == faces[0].1);
+
+            if is_on_one_side {
+                if let Some((_, side)) = faces.first() {
+                    shells_by_side[*side].push(shell.clone());
+                }
+                continue;
+            }
+
+            let split_shell = split_crossing_edges(shell, &plane, core);
+            let split_shell = divide_crossing_faces(split_shell, &plane, core);
+
+            let sides = split_shell
+                .faces()
+                .iter()
+                .map(|face| (face.clone(), plane.side_of_face(face, core)))
+                .collect::<Vec<_>>();
+
+            for (side, boundaries) in boundaries_by_side.iter_mut().enumerate()
+            {
+                let faces = sides
+                    .iter()
+                    .filter(|(_, s)| *s == side)
+                    .map(|(face, _)| face.clone())
+                    .collect::<BTreeSet<_>>();
+
+                boundaries.extend(find_cap_boundaries(&split_shell, &faces));
+                faces_by_side[side].extend(faces);
+            }
+        }
+
+        // The faces that close the halves have to face away from them. Which
+        // is towards the other side of the plane.
+        let [normal_front, normal_back] = [-plane.normal, plane.normal];
+
+        [(0, normal_front), (1, normal_back)].map(|(side, outward)| {
+            let mut faces = faces_by_side[side].clone();
+            faces.extend(build_caps(
+                &boundaries_by_side[side],
+                &plane,
+                outward,
+                core,
+            ));
+
+            let mut shells = shells_by_side[side].clone();
+            shells.extend(connected_shells(faces, core));
+
+            Solid::new(shells)
+        })
+    }
+}
+
+struct SplitPlane {
+    origin: Point<3>,
+    normal: Vector<3>,
+    epsilon: Scalar,
+}
+
+impl SplitPlane {
+    fn distance_to(&self, point: Point<3>) -> Scalar {
+        self.normal.dot(&(point - self.origin))
+    }
+
+    /// Determine which side of the plane a face is on
+    ///
+    /// Returns `0` for the side that the normal points to, `1` for the other.
+    /// Faces that cross the plane are considered to be on the side that is
+    /// furthest away from it. Faces within the plane are considered to be on
+    /// the side they are facing away from, as that is the side they bound.
+    fn side_of_face(&self, face: &Handle<Face>, core: &Core) -> usize {
+        let approx = approx_face(
+            face.clone(),
+            core.tolerance(),
+            &mut ApproxCache::default(),
+            &core.layers.geometry,
+        );
+
+        let distance = approx
+            .points()
+            .into_iter()
+            .map(|point| self.distance_to(point.global_form))
+            .max_by_key(|distance| distance.abs())
+            .unwrap_or_default();
+
+        if distance.abs() <= self.epsilon {
+            let (_, [u, v]) = planar_surface(face, core)
+                .expect("Expected face within splitting plane to be planar");
+            let mut normal = u.cross(&v);
+            if face.coord_handedness(core.tolerance(), &core.layers.geometry)
+                == Handedness::LeftHanded
+            {
+                normal = -normal;
+            }
+
+            return if self.normal.dot(&normal) > Scalar::ZERO {
+                1
+            } else {
+                0
+            };
+        }
+
+        if distance > Scalar::ZERO {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Determine whether a face has vertices on both sides of the plane
+    fn crosses_face(&self, face: &Handle<Face>, core: &Core) -> bool {
+        let mut sides = BTreeSet::new();
+
+        for cycle in face.region().all_cycles() {
+            for half_edge in cycle.half_edges() {
+                let distance = self.distance_to(start_vertex_position(
+                    half_edge,
+                    face.surface(),
+                    core,
+                ));
+
+                if distance.abs() > self.epsilon {
+                    sides.insert(distance.sign());
+                }
+            }
+        }
+
+        sides.len() > 1
+    }
+
+    /// Compute the intersection of the plane with the surface of a face
+    ///
+    /// Returns the intersection as a line in surface coordinates, or `None`,
+    /// if the face is not planar, or its surface is parallel to the plane.
+    fn intersection_with_planar_face(
+        &self,
+        face: &Handle<Face>,
+        core: &Core,
+    ) -> Option<Line<2>> {
+        let (origin, [u, v]) = planar_surface(face, core)?;
+
+        // Within the surface, the distance to the plane changes linearly. The
+        // intersection is where it is zero.
+        let gradient = Vector::from([self.normal.dot(&u), self.normal.dot(&v)]);
+        if gradient.magnitude() <= self.epsilon {
+            return None;
+        }
+
+        let distance_at_origin = self.distance_to(origin);
+        let point = Point::origin()
+            - gradient * distance_at_origin / gradient.dot(&gradient);
+        let direction = Vector::from([-gradient.v, gradient.u]);
+
+        Some(Line::from_origin_and_direction(point, direction))
+    }
+}
+
+/// A half-edge that needs a sibling in a face that closes one of the halves
+struct CapBoundary {
+    half_edge: Handle<HalfEdge>,
+    end_vertex: Handle<Vertex>,
+    surface: Handle<Surface>,
+}
+
+fn split_crossing_edges(
+    shell: &Shell,
+    plane: &SplitPlane,
+    core: &mut Core,
+) -> Shell {
+    let mut shell = shell.clone();
+
+    loop {
+        let mut crossing = None;
+
+        'faces: for face in shell.faces() {
+            for cycle in face.region().all_cycles() {
+                for (half_edge, next) in cycle.half_edges().pairs() {
+                    let boundary = [half_edge, next].map(|h| {
+                        core.layers
+                            .geometry
+                            .of_vertex(h.start_vertex())
+                            .unwrap()
+                            .local_on(half_edge.curve())
+                            .unwrap()
+                            .position
+                    });
+                    let distance = |point_curve: Point<1>| {
+                        plane.distance_to(point_on_curve(
+                            half_edge,
+                            face.surface(),
+                            point_curve,
+                            core,
+                        ))
+                    };
+
+                    let [d_start, d_end] = boundary.map(distance);
+                    let crosses = d_start.abs() > plane.epsilon
+                        && d_end.abs() > plane.epsilon
+                        && d_start.sign() != d_end.sign();
+
+                    if crosses {
+                        // The distance to the plane is continuous along the
+                        // curve, so we can find the crossing by bisection.
+                        let [mut a, mut b] = boundary;
+                        for _ in 0..64 {
+                            let center = Point::from([(a.t + b.t) / 2.]);
+                            if distance(center).sign() == d_start.sign() {
+                                a = center;
+                            } else {
+                                b = center;
+                            }
+                        }
+
+                        // Either end of the final interval could be closer to
+                        // the plane. If the crossing is exactly representable,
+                        // that's where it is.
+                        let point = if distance(a).abs() <= distance(b).abs() {
+                            a
+                        } else {
+                            b
+                        };
+
+                        crossing = Some((half_edge.clone(), point));
+                        break 'faces;
+                    }
+                }
+            }
+        }
+
+        let Some((half_edge, point)) = crossing else {
+            break shell;
+        };
+
+        (shell, _) = shell.split_edge(&half_edge, point, core);
+    }
+}
+
+fn divide_crossing_faces(
+    shell: Shell,
+    plane: &SplitPlane,
+    core: &mut Core,
+) -> Shell {
+    let mut shell = shell;
+
+    loop {
+        let crossing = shell
+            .faces()
+            .iter()
+            .find(|face| plane.crosses_face(face, core))
+            .cloned();
+
+        let Some(face) = crossing else {
+            break shell;
+        };
+
+        let faces;
+        (shell, faces) = match plane.intersection_with_planar_face(&face, core)
+        {
+            Some(line) => {
+                shell.split_face_along_path(&face, Path::Line(line), core)
+            }
+            None => divide_curved_face(&shell, &face, plane, core),
+        };
+
+        // If the face didn't get divided, we'd keep trying forever.
+        assert!(faces.len() > 1, "Failed to divide face along plane");
+    }
+}
+
+fn divide_curved_face(
+    shell: &Shell,
+    face: &Handle<Face>,
+    plane: &SplitPlane,
+    core: &mut Core,
+) -> (Shell, Vec<Handle<Face>>) {
+    let mut on_plane = Vec::new();
+    let mut sides = BTreeSet::new();
+
+    for (i, cycle) in face.region().all_cycles().enumerate() {
+        for half_edge in cycle.half_edges() {
+            let position =
+                start_vertex_position(half_edge, face.surface(), core);
+            let distance = plane.distance_to(position);
+
+            if distance.abs() <= plane.epsilon {
+                if i == 0 {
+                    on_plane.push(half_edge.clone());
+                }
+            } else {
+                sides.insert((i, distance.sign()));
+            }
+        }
+    }
+
+    assert!(
+        sides.iter().all(|(cycle, _)| *cycle == 0),
+        "Splitting faces on curved surfaces along interior cycles is not \
+        supported"
+    );
+    let [b, d] = on_plane.try_into().unwrap_or_else(|_| {
+        panic!("Plane must cross the exterior of a curved face exactly twice")
+    });
+
+    let [start, end] = [&b, &d].map(|half_edge| {
+        core.layers
+            .geometry
+            .of_curve(half_edge.curve())
+            .unwrap()
+            .local_on(face.surface())
+            .unwrap()
+            .path
+            .point_from_path_coords(
+                core.layers
+                    .geometry
+                    .of_vertex(half_edge.start_vertex())
+                    .unwrap()
+                    .local_on(half_edge.curve())
+                    .unwrap()
+                    .position,
+            )
+    });
+    let center = convert_point_surface_to_global(
+        &core
+            .layers
+            .geometry
+            .of_surface_2(face.surface())
+            .unwrap()
+            .generator,
+        start + (end - start) / 2.,
+        core.tolerance(),
+        &core.layers.geometry,
+    );
+    assert!(
+        plane.distance_to(center).abs() <= core.tolerance().inner(),
+        "Intersection of curved face and plane must be a straight line in \
+        surface coordinates"
+    );
+
+    let (shell, faces) = divide_face(shell, face, [&b, &d], core);
+    (shell, faces.to_vec())
+}
+
+fn find_cap_boundaries(
+    shell: &Shell,
+    faces: &BTreeSet<Handle<Face>>,
+) -> Vec<CapBoundary> {
+    let mut boundaries = Vec::new();
+
+    for face in faces {
+        for cycle in face.region().all_cycles() {
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                let sibling = shell.get_sibling_of(half_edge).expect(
+                    "Expected half-edge of valid shell to have sibling",
+                );
+
+                if !faces.contains(&sibling.face) {
+                    boundaries.push(CapBoundary {
+                        half_edge: half_edge.clone(),
+                        end_vertex: next.start_vertex().clone(),
+                        surface: face.surface().clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    boundaries
+}
+
+fn build_caps(
+    boundaries: &[CapBoundary],
+    plane: &SplitPlane,
+    outward: Vector<3>,
+    core: &mut Core,
+) -> Vec<Handle<Face>> {
+    if boundaries.is_empty() {
+        return Vec::new();
+    }
+
+    // Pick the axes of the surface such that the faces built on it are facing
+    // outward, if their exteriors are wound counter-clockwise.
+    let axis = if outward.x.abs() < Scalar::from(0.9) {
+        Vector::unit_x()
+    } else {
+        Vector::unit_y()
+    };
+    let u = (axis - outward * axis.dot(&outward)).normalize();
+    let v = outward.cross(&u);
+    let surface = Surface::from_uv(
+        Path::Line(Line::from_origin_and_direction(plane.origin, u)),
+        v,
+        core,
+    );
+
+    // Each half-edge of the caps is the sibling of a boundary half-edge, so it
+    // runs from the end of that to its start.
+    let mut half_edges_by_start_vertex = BTreeMap::new();
+    for boundary in boundaries {
+        boundary
+            .half_edge
+            .curve()
+            .clone()
+            .project_from_surface(
+                &boundary.surface,
+                surface.clone(),
+                &mut core.layers.geometry,
+            )
+            .expect(
+                "Plane can only cross curved faces along the axes of their \
+                surfaces",
+            );
+
+        let half_edge = HalfEdge::from_sibling(
+            &boundary.half_edge,
+            boundary.end_vertex.clone(),
+            core,
+        )
+        .derive_from(&boundary.half_edge, core);
+
+        half_edges_by_start_vertex.insert(
+            boundary.end_vertex.clone(),
+            (half_edge, boundary.half_edge.start_vertex().clone()),
+        );
+    }
+
+    let mut cycles: Vec<(Handle<Cycle>, Vec<Point<2>>)> = Vec::new();
+    while let Some((start, _)) = half_edges_by_start_vertex.first_key_value() {
+        let mut vertex = start.clone();
+        let mut half_edges = Vec::new();
+
+        while let Some((half_edge, end_vertex)) =
+            half_edges_by_start_vertex.remove(&vertex)
+        {
+            half_edges.push(half_edge);
+            vertex = end_vertex;
+        }
+
+        let cycle = Cycle::new(half_edges);
+        let points = approx_cycle(
+            &cycle,
+            &surface,
+            core.tolerance(),
+            &mut ApproxCache::default(),
+            &core.layers.geometry,
+        )
+        .points()
+        .into_iter()
+        .map(|point| point.local_form)
+        .collect::<Vec<_>>();
+
+        cycles.push((cycle.insert(core), points));
+    }
+
+    // Cycles that wind counter-clockwise are exteriors of the caps. The other
+    // ones are interiors, of the smallest exterior that contains them.
+    let signed_area = |points: &[Point<2>]| {
+        let mut area = Scalar::ZERO;
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            area += a.u * b.v - b.u * a.v;
+        }
+        area / 2.
+    };
+    let (exteriors, interiors): (Vec<_>, Vec<_>) = cycles
+        .into_iter()
+        .partition(|(_, points)| signed_area(points) > Scalar::ZERO);
+
+    let mut interiors_by_exterior = vec![Vec::new(); exteriors.len()];
+    for (interior, points) in interiors {
+        let exterior = exteriors
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, exterior))| {
+                Polygon::new()
+                    .with_exterior(exterior.iter().copied())
+                    .contains_point(points[0])
+            })
+            .min_by_key(|(_, (_, exterior))| signed_area(exterior))
+            .map(|(i, _)| i)
+            .expect("Expected interior of cap to be within an exterior");
+
+        interiors_by_exterior[exterior].push(interior);
+    }
+
+    exteriors
+        .into_iter()
+        .zip(interiors_by_exterior)
+        .map(|((exterior, _), interiors)| {
+            let region = Region::new(exterior, interiors).insert(core);
+            Face::new(surface.clone(), region).insert(core)
+        })
+        .collect()
+}
+
+/// Group faces into shells, such that faces that share an edge are in the same
+/// shell
+fn connected_shells(
+    faces: Vec<Handle<Face>>,
+    core: &mut Core,
+) -> Vec<Handle<Shell>> {
+    let mut components = Components::new(faces.len());
+    let mut faces_by_edge = BTreeMap::new();
+
+    for (i, face) in faces.iter().enumerate() {
+        for cycle in face.region().all_cycles() {
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                // Siblings share the curve, but run in opposite directions.
+                let mut vertices =
+                    [half_edge.start_vertex(), next.start_vertex()]
+                        .map(Clone::clone);
+                vertices.sort();
+
+                if let Some(j) = faces_by_edge
+                    .insert((half_edge.curve().clone(), vertices), i)
+                {
+                    components.connect(i, j);
+                }
+            }
+        }
+    }
+
+    let mut faces_by_component = BTreeMap::new();
+    for (i, face) in faces.into_iter().enumerate() {
+        faces_by_component
+            .entry(components.find(i))
+            .or_insert_with(Vec::new)
+            .push(face);
+    }
+
+    faces_by_component
+        .into_values()
+        .map(|faces| Shell::new(faces).insert(core))
+        .collect()
+}
+
+fn planar_surface(
+    face: &Face,
+    core: &Core,
+) -> Option<(Point<3>, [Vector<3>; 2])> {
+    let SweptCurve {
+        u: Path::Line(line),
+        v,
+    } = core.layers.geometry.try_of_surface(face.surface())?
+    else {
+        return None;
+    };
+
+    Some((line.origin(), [line.direction(), *v]))
+}
+
+fn start_vertex_position(
+    half_edge: &Handle<HalfEdge>,
+    surface: &Handle<Surface>,
+    core: &Core,
+) -> Point<3> {
+    let point_curve = core
+        .layers
+        .geometry
+        .of_vertex(half_edge.start_vertex())
+        .unwrap()
+        .local_on(half_edge.curve())
+        .unwrap()
+        .position;
+
+    point_on_curve(half_edge, surface, point_curve, core)
+}
+
+fn point_on_curve(
+    half_edge: &Handle<HalfEdge>,
+    surface: &Handle<Surface>,
+    point_curve: Point<1>,
+    core: &Core,
+) -> Point<3> {
+    let geometry = &core.layers.geometry;
+
+    let point_surface = geometry
+        .of_curve(half_edge.curve())
+        .unwrap()
+        .local_on(surface)
+        .unwrap()
+        .path
+        .point_from_path_coords(point_curve);
+
+    convert_point_surface_to_global(
+        &geometry.of_surface_2(surface).unwrap().generator,
+        point_surface,
+        core.tolerance(),
+        geometry,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::mass_properties::MassProperties,
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        topology::{Region, Shell, Sketch},
+        Core,
+    };
+
+    use super::SplitSolid;
+
+    fn assert_volume(shell: &Shell, expected_volume: f64, core: &Core) {
+        let volume = shell.mass_properties(0.001, &core.layers.geometry).volume;
+        assert!((volume - expected_volume).abs() < Scalar::from(1e-9));
+    }
+
+    #[test]
+    fn split_cube() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let cube = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        let [front, back] =
+            cube.split_solid([0.25, 0., 0.], [1., 0., 0.], &mut core);
+        let [front, back] = [front, back].map(|half| half.insert(&mut core));
+
+        for (half, expected_volume) in [(&front, 0.75), (&back, 0.25)] {
+            assert_eq!(half.shells().len(), 1);
+            assert_eq!(half.shells().only().faces().len(), 6);
+
+            let volume =
+                half.mass_properties(0.001, &core.layers.geometry).volume;
+            assert!((volume - expected_volume).abs() < Scalar::from(1e-9));
+        }
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_cylinder_through_axis() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let cylinder = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::circle([0., 0.], 1., surface, &mut core)],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        let [front, back] =
+            cylinder.split_solid([0., 0., 0.], [0., 1., 0.], &mut core);
+        let [front, back] = [front, back].map(|half| half.insert(&mut core));
+
+        let [front, back] = [&front, &back]
+            .map(|half| half.mass_properties(0.001, &core.layers.geometry));
+        assert!((front.volume - back.volume).abs() < Scalar::from(1e-9));
+        assert!(front.center_of_mass.unwrap().y > Scalar::ZERO);
+        assert!(back.center_of_mass.unwrap().y < Scalar::ZERO);
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_concave_solid_into_multiple_shells() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let u_shape = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [
+                        [0., 0.],
+                        [3., 0.],
+                        [3., 2.],
+                        [2., 2.],
+                        [2., 1.],
+                        [1., 1.],
+                        [1., 2.],
+                        [0., 2.],
+                    ],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        let [front, back] =
+            u_shape.split_solid([0., 1.5, 0.], [0., 1., 0.], &mut core);
+        let [front, back] = [front, back].map(|half| half.insert(&mut core));
+
+        assert_eq!(front.shells().len(), 2);
+        assert_eq!(back.shells().len(), 1);
+        for shell in front.shells() {
+            assert_volume(shell, 0.5, &core);
+        }
+        assert_volume(back.shells().only(), 4., &core);
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_solid_along_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let l_shape = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [
+                        [0., 0.],
+                        [2., 0.],
+                        [2., 1.],
+                        [1., 1.],
+                        [1., 2.],
+                        [0., 2.],
+                    ],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -1.],
+                &mut core,
+            );
+
+        let [front, back] =
+            l_shape.split_solid([0., 1., 0.], [0., 1., 0.], &mut core);
+        let [front, back] = [front, back].map(|half| half.insert(&mut core));
+
+        assert_volume(front.shells().only(), 1., &core);
+        assert_volume(back.shells().only(), 2., &core);
+
+        core.layers.validation.take_errors()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use fj_interop::Color;
+use fj_math::{Point, Scalar};
+
+use crate::{
+    geometry::{
+        curves::helix::Helix, surfaces::SweptAlongHelix, CurveBoundary, Path,
+    },
+    storage::Handle,
+    topology::{Region, Shell, Surface},
+    Core,
+};
+
+use super::profile::{plane_of_profile, sweep_profile, Section};
+
+/// # Sweep a [`Region`] along a [`Helix`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait SweepRegionAlongHelix {
+    /// # Sweep the [`Region`] along the helix
+    ///
+    /// Requires the surface that the region is defined on, which must be a
+    /// plane, and optionally the color of the created faces. The region is
+    /// swept within the provided boundary on the helix.
+    ///
+    /// The region keeps its position and orientation relative to the helix, as
+    /// it is swept along it, meaning it rotates around the axis of the helix.
+    /// Each half-edge of the region is swept into a set of side faces, one for
+    /// each quarter turn (or part thereof) of the helix.
+    ///
+    /// Returns a shell that consists of the side faces created by the sweep,
+    /// as well as the faces at its start and end, in the shape of the region.
+    /// If the region is wider than the pitch of the helix, along its axis, the
+    /// shell intersects itself.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `surface` isn't a plane, or if the boundary is empty.
+    fn sweep_region_along_helix(
+        &self,
+        surface: &Handle<Surface>,
+        color: Option<Color>,
+        helix: &Helix,
+        boundary: impl Into<CurveBoundary<Point<1>>>,
+        core: &mut Core,
+    ) -> Shell;
+}
+
+impl SweepRegionAlongHelix for Region {
+    fn sweep_region_along_helix(
+        &self,
+        surface: &Handle<Surface>,
+        color: Option<Color>,
+        helix: &Helix,
+        boundary: impl Into<CurveBoundary<Point<1>>>,
+        core: &mut Core,
+    ) -> Shell {
+        let boundary = boundary.into();
+        let [start, end] = boundary.inner;
+        assert_ne!(start, end, "Can't sweep region along empty boundary");
+
+        let (origin, axes) = plane_of_profile(surface, core);
+        let sweep = SweptAlongHelix {
+            curve: Path::u_axis(),
+            origin,
+            axes,
+            helix: *helix,
+            start,
+        };
+
+        // The curves that the vertices of the profile are swept along are
+        // approximated by subdividing them, until the approximation is close
+        // enough. This doesn't work for curves that end where they started,
+        // when viewed along the axis, like full turns of the helix. Let's
+        // split the sweep into sections accordingly.
+        let num_sections = ((end.t - start.t).abs() / (Scalar::TAU / 4.))
+            .ceil()
+            .into_u64();
+        let sections = (0..num_sections)
+            .map(|i| {
+                let [section_start, section_end] = [i, i + 1].map(|i| {
+                    start
+                        + (end - start) * Scalar::from_u64(i)
+                            / Scalar::from_u64(num_sections)
+                });
+
+                Section {
+                    surface: Box::new(move |curve| {
+                        Box::new(SweptAlongHelix { curve, ..sweep })
+                    }),
+                    boundary: CurveBoundary::from([section_start, section_end]),
+                }
+            })
+            .collect();
+
+        let direction = {
+            let tangent = helix.tangent_at(start);
+
+            if end > start {
+                tangent
+            } else {
+                -tangent
+            }
+        };
+
+        sweep_profile(
+            self,
+            surface,
+            color,
+            direction,
+            sections,
+            sweep.plane_at(end),
+            core,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        geometry::curves::helix::Helix,
+        operations::{
+            build::BuildRegion, insert::Insert, tests::assert_volume,
+        },
+        topology::{Handedness, Region, Solid},
+        Core,
+    };
+
+    use super::SweepRegionAlongHelix;
+
+    #[test]
+    fn sweep_along_helix() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let helix = Helix {
+            center: Point::origin(),
+            axis: Vector::unit_z(),
+            a: Vector::from([2., 0., 0.]),
+            pitch: Scalar::ONE,
+            handedness: Handedness::RightHanded,
+        };
+
+        // A square in a plane that contains the axis, centered on the start of
+        // the helix.
+        let surface = core.layers.topology.surfaces.xz_plane();
+        let region = Region::polygon(
+            [[1.75, -0.25], [2.25, -0.25], [2.25, 0.25], [1.75, 0.25]],
+            surface.clone(),
+            &mut core,
+        );
+
+        let shell = region.sweep_region_along_helix(
+            &surface,
+            None,
+            &helix,
+            helix.boundary_of_turns(1.5),
+            &mut core,
+        );
+        let solid = Solid::new([shell.insert(&mut core)]).insert(&mut core);
+
+        // Four side faces per quarter turn, plus the faces at either end.
+        assert_eq!(solid.shells().only().faces().len(), 4 * 6 + 2);
+        core.layers.validation.take_errors()?;
+
+        // The square moves within its own plane, as it advances along the
+        // axis. Only its rotation around the axis sweeps out any volume, which
+        // means the volume is the same as that of a revolution (per Pappus's
+        // centroid theorem).
+        let expected =
+            Scalar::from(0.25) * Scalar::from(2.) * Scalar::TAU * 1.5;
+
+        assert_volume(&solid, expected, &mut core)?;
+
+        Ok(())
+    }
+}
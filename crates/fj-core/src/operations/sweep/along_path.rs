@@ -0,0 +1,390 @@
+use fj_interop::Color;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{
+        surfaces::{SweepFrame, SweptAlongPath},
+        CurveBoundary, Path,
+    },
+    operations::{
+        geometry::UpdateCurveGeometry, insert::Insert, presentation::GetColor,
+    },
+    storage::Handle,
+    topology::{Region, Shell, Sketch, Solid, Surface},
+    Core,
+};
+
+use super::profile::{plane_of_profile, sweep_profile, Section};
+
+/// # A path that objects can be swept along
+///
+/// Consists of a sequence of segments, each of which is a section of a
+/// [`Path`]. Each segment must start where the previous one ends.
+#[derive(Clone, Debug)]
+pub struct SweepPath {
+    segments: Vec<(Path<3>, CurveBoundary<Point<1>>)>,
+}
+
+impl SweepPath {
+    /// # Create a path from its segments
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if no segments are provided, or if any of them is an ellipse or
+    /// a NURBS curve. Sweeping along those is not supported yet.
+    pub fn from_segments(
+        segments: impl IntoIterator<
+            Item = (Path<3>, impl Into<CurveBoundary<Point<1>>>),
+        >,
+    ) -> Self {
+        let segments = segments
+            .into_iter()
+            .map(|(path, boundary)| (path, boundary.into()))
+            .collect::<Vec<_>>();
+        assert!(!segments.is_empty(), "Sweep path must not be empty");
+        assert!(
+            segments.iter().all(|(path, _)| matches!(
+                path,
+                Path::Circle(_) | Path::Line(_)
+            )),
+            "Sweeping along ellipses or NURBS curves is not supported yet"
+        );
+
+        Self { segments }
+    }
+
+    /// # Access the segments of the path
+    pub fn segments(&self) -> &[(Path<3>, CurveBoundary<Point<1>>)] {
+        &self.segments
+    }
+}
+
+/// # Sweep a [`Region`] along a [`SweepPath`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait SweepRegionAlongPath {
+    /// # Sweep the [`Region`] along the path
+    ///
+    /// Requires the surface that the region is defined on, which must be a
+    /// plane, and optionally the color of the created faces.
+    ///
+    /// The region keeps its position relative to the path, as it is swept
+    /// along it. How its orientation changes along curved sections of the
+    /// path, depends on `frame`.
+    ///
+    /// Returns a shell that consists of the side faces created by the sweep,
+    /// as well as the faces at its start and end, in the shape of the region.
+    ///
+    /// ## Panics
+    ///
+    /// Panics, if `surface` isn't a plane, or if the segments of the path are
+    /// not connected.
+    ///
+    /// The region is never rotated where two segments of the path meet, which
+    /// would shear it, if they met at an angle. Panics, if the path has such
+    /// a corner. The path must also point out of the plane of the region
+    /// everywhere, or the side faces would degenerate. This means that, if
+    /// `frame` is [`SweepFrame::Fixed`], circular segments must be shorter
+    /// than half a turn. Panics, if that is not the case.
+    fn sweep_region_along_path(
+        &self,
+        surface: &Handle<Surface>,
+        color: Option<Color>,
+        path: &SweepPath,
+        frame: SweepFrame,
+        core: &mut Core,
+    ) -> Shell;
+}
+
+impl SweepRegionAlongPath for Region {
+    fn sweep_region_along_path(
+        &self,
+        surface: &Handle<Surface>,
+        color: Option<Color>,
+        path: &SweepPath,
+        frame: SweepFrame,
+        core: &mut Core,
+    ) -> Shell {
+        let (origin, axes) = plane_of_profile(surface, core);
+
+        let max_distance = core.layers.validation.config.identical_max_distance;
+
+        // The side of the region's plane that the path points towards. It must
+        // not change along the path.
+        let direction = {
+            let (path, boundary) = &path.segments()[0];
+            tangent_at(path, *boundary, boundary.inner[0])
+        };
+        let side = if direction.dot(&normal_of(axes)) > Scalar::ZERO {
+            Scalar::ONE
+        } else {
+            -Scalar::ONE
+        };
+
+        // Each segment of the path is swept along with the plane that the
+        // region is in at its start. The plane at the end of one segment, is
+        // the plane at the start of the next one.
+        let mut sections = Vec::new();
+        let mut plane = (origin, axes);
+        let mut previous_end: Option<(Point<3>, Vector<3>)> = None;
+        for (path, boundary) in path.segments() {
+            let (path, boundary) = (path.clone(), *boundary);
+            let [start, end] = boundary.inner;
+            let [tangent_start, tangent_end] =
+                [start, end].map(|point| tangent_at(&path, boundary, point));
+
+            if let Some((previous_end, previous_tangent)) = previous_end {
+                let distance = (path.point_from_path_coords(start)
+                    - previous_end)
+                    .magnitude();
+                assert!(
+                    distance < max_distance,
+                    "Segments of sweep path must be connected"
+                );
+
+                // Compare the points at unit distance along both tangents, so
+                // this uses the same limit as the check above.
+                assert!(
+                    (tangent_start - previous_tangent).magnitude()
+                        < max_distance,
+                    "Segments of sweep path must not meet at an angle"
+                );
+            }
+            previous_end =
+                Some((path.point_from_path_coords(end), tangent_end));
+
+            assert!(
+                !(matches!(frame, SweepFrame::Fixed)
+                    && matches!(path, Path::Circle(_))
+                    && (end.t - start.t).abs() >= Scalar::PI),
+                "Sweep path must point out of the plane of the region"
+            );
+
+            let (origin, axes) = plane;
+            let sweep = SweptAlongPath {
+                curve: Path::u_axis(),
+                origin,
+                axes,
+                path,
+                start,
+                frame,
+            };
+            plane = sweep.plane_at(end);
+
+            for (tangent, (_, axes)) in
+                [(tangent_start, (origin, axes)), (tangent_end, plane)]
+            {
+                // The distance that the path moves out of the plane, per unit
+                // of distance along the path.
+                let distance = tangent.dot(&normal_of(axes)) * side;
+                assert!(
+                    distance > max_distance,
+                    "Sweep path must point out of the plane of the region"
+                );
+            }
+
+            sections.push(Section {
+                surface: Box::new(move |curve| {
+                    Box::new(SweptAlongPath {
+                        curve,
+                        path: sweep.path.clone(),
+                        ..sweep
+                    })
+                }),
+                boundary,
+            });
+        }
+
+        sweep_profile(self, surface, color, direction, sections, plane, core)
+    }
+}
+
+/// # Compute the unit tangent of a segment, in the direction it is swept in
+fn tangent_at(
+    path: &Path<3>,
+    boundary: CurveBoundary<Point<1>>,
+    point: Point<1>,
+) -> Vector<3> {
+    let tangent = match path {
+        Path::Circle(circle) => {
+            circle.vector_from_circle_coords(Vector::from([
+                point.t + Scalar::TAU / 4.
+            ]))
+        }
+        Path::Line(line) => line.direction(),
+        Path::Ellipse(_) | Path::Nurbs(_) => {
+            unreachable!("`SweepPath` rejects ellipses and NURBS curves")
+        }
+    };
+
+    let [start, end] = boundary.inner;
+    if end > start {
+        tangent.normalize()
+    } else {
+        -tangent.normalize()
+    }
+}
+
+fn normal_of([u, v]: [Vector<3>; 2]) -> Vector<3> {
+    u.cross(&v).normalize()
+}
+
+/// # Sweep a [`Sketch`] along a [`SweepPath`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait SweepSketchAlongPath {
+    /// # Sweep the [`Sketch`] along the path
+    ///
+    /// Places the sketch on `surface`, then sweeps each of its regions along
+    /// the path, creating one shell per region.
+    ///
+    /// See [`SweepRegionAlongPath::sweep_region_along_path`] for more details.
+    fn sweep_sketch_along_path(
+        &self,
+        surface: Handle<Surface>,
+        path: &SweepPath,
+        frame: SweepFrame,
+        core: &mut Core,
+    ) -> Solid;
+}
+
+impl SweepSketchAlongPath for Sketch {
+    fn sweep_sketch_along_path(
+        &self,
+        surface: Handle<Surface>,
+        path: &SweepPath,
+        frame: SweepFrame,
+        core: &mut Core,
+    ) -> Solid {
+        let mut shells = Vec::new();
+        for region in self.regions() {
+            for cycle in region.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    half_edge.curve().clone().place_on_surface(
+                        self.surface(),
+                        surface.clone(),
+                        &mut core.layers.geometry,
+                    );
+                }
+            }
+
+            let color = region.get_color(core);
+            let shell = region
+                .sweep_region_along_path(&surface, color, path, frame, core)
+                .insert(core);
+            shells.push(shell);
+        }
+
+        Solid::new(shells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Point, Scalar, Vector};
+
+    use crate::{
+        geometry::{surfaces::SweepFrame, Path},
+        operations::{
+            build::BuildSketch, insert::Insert, tests::assert_volume,
+        },
+        topology::Sketch,
+        Core,
+    };
+
+    use super::{SweepPath, SweepSketchAlongPath};
+
+    #[test]
+    #[should_panic(
+        expected = "Segments of sweep path must not meet at an angle"
+    )]
+    fn sweep_along_path_with_corner() {
+        sweep_along_lines([[0., 0., 0.], [0., 0., 1.], [0., 1., 2.]]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Sweep path must point out of the plane of the region"
+    )]
+    fn sweep_along_path_in_plane_of_region() {
+        sweep_along_lines([[0., 0., 0.], [1., 0., 0.]]);
+    }
+
+    #[test]
+    fn sweep_along_bend_with_frenet_frame() -> anyhow::Result<()> {
+        sweep_along_bend(SweepFrame::Frenet)
+    }
+
+    #[test]
+    fn sweep_along_bend_with_guided_frame() -> anyhow::Result<()> {
+        sweep_along_bend(SweepFrame::Guided {
+            normal: Vector::unit_y(),
+        })
+    }
+
+    fn sweep_along_bend(frame: SweepFrame) -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let (line, line_boundary) =
+            Path::line_from_points([[0., 0., 0.], [0., 0., 1.]]);
+        let arc = Path::Circle(Circle::new(
+            [3., 0., 1.],
+            [-3., 0., 0.],
+            [0., 0., 3.],
+        ));
+        let path = SweepPath::from_segments([
+            (line, line_boundary),
+            (
+                arc,
+                [Scalar::ZERO, Scalar::TAU / 4.].map(|t| Point::from([t])),
+            ),
+        ]);
+
+        let solid = square(&mut core)
+            .sweep_sketch_along_path(
+                core.layers.topology.surfaces.xy_plane(),
+                &path,
+                frame,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        assert_eq!(solid.shells().only().faces().len(), 10);
+        core.layers.validation.take_errors()?;
+
+        // Straight section, plus the bend (per Pappus's centroid theorem).
+        let expected_volume = Scalar::ONE + Scalar::from(3.) * Scalar::PI / 2.;
+        assert_volume(&solid, expected_volume, &mut core)?;
+
+        Ok(())
+    }
+
+    fn sweep_along_lines(points: impl IntoIterator<Item = [f64; 3]>) {
+        let mut core = Core::new();
+
+        let points = points.into_iter().collect::<Vec<_>>();
+        let path = SweepPath::from_segments(
+            points
+                .windows(2)
+                .map(|points| Path::line_from_points([points[0], points[1]])),
+        );
+
+        square(&mut core).sweep_sketch_along_path(
+            core.layers.topology.surfaces.xy_plane(),
+            &path,
+            SweepFrame::Fixed,
+            &mut core,
+        );
+    }
+
+    fn square(core: &mut Core) -> Sketch {
+        Sketch::polygon(
+            [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]],
+            core,
+        )
+    }
+}
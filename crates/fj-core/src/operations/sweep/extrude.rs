@@ -0,0 +1,852 @@
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{cycle::approx_cycle, face::approx_face, ApproxCache},
+        intersect::refine_path_intersection,
+        ray_cast::{Ray, RayCast, RayCastCache},
+        triangulate::triangulate_face,
+    },
+    geometry::{surfaces::SweptCurve, CurveBoundary, LocalCurveGeom, Path},
+    operations::{
+        build::{BuildCycle, BuildHalfEdge, BuildSurface},
+        insert::Insert,
+        presentation::{GetColor, SetColor},
+    },
+    storage::Handle,
+    topology::{Cycle, Face, HalfEdge, Region, Shell, Surface},
+    Core,
+};
+
+/// # Options for an extrusion
+///
+/// Extruding is sweeping a planar face or sketch along the normal of its
+/// plane. See [`SweepFaceOfShell::extrude_face_of_shell`] and
+/// [`SweepSketch::extrude_sketch`].
+///
+/// [`SweepFaceOfShell::extrude_face_of_shell`]: super::SweepFaceOfShell::extrude_face_of_shell
+/// [`SweepSketch::extrude_sketch`]: super::SweepSketch::extrude_sketch
+#[derive(Clone, Debug)]
+pub struct Extrude {
+    /// # How far to extrude
+    pub extent: ExtrudeExtent,
+
+    /// # The draft angle of the side faces, in radians
+    ///
+    /// With a positive angle, the side faces lean inward, and the extruded
+    /// shape gets smaller with distance from where the extrusion starts. With
+    /// a negative angle, it gets larger.
+    ///
+    /// A draft angle other than zero is only supported for regions that are
+    /// bounded by lines and circles, and only if the extrusion ends within a
+    /// plane that is parallel to where it starts.
+    pub draft_angle: Scalar,
+
+    /// # Extrude in both directions
+    ///
+    /// If this is `true`, the extrusion extends by the full extent in both
+    /// directions from where it starts.
+    pub symmetric: bool,
+}
+
+impl Extrude {
+    /// # Extrude by the provided distance
+    pub fn distance(distance: impl Into<Scalar>) -> Self {
+        Self::from(ExtrudeExtent::Distance(distance.into()))
+    }
+
+    /// # Extrude up to the next face that is in the way
+    pub fn up_to_next_face() -> Self {
+        Self::from(ExtrudeExtent::UpToNextFace)
+    }
+
+    /// # Extrude up to the provided surface
+    pub fn up_to_surface(surface: Handle<Surface>) -> Self {
+        Self::from(ExtrudeExtent::UpToSurface(surface))
+    }
+
+    /// # Add a draft angle to the extrusion
+    ///
+    /// See [`Extrude::draft_angle`].
+    #[must_use]
+    pub fn with_draft_angle(mut self, angle: impl Into<Scalar>) -> Self {
+        self.draft_angle = angle.into();
+        self
+    }
+
+    /// # Make the extrusion symmetric
+    ///
+    /// See [`Extrude::symmetric`].
+    #[must_use]
+    pub fn symmetric(mut self) -> Self {
+        self.symmetric = true;
+        self
+    }
+
+    /// # Compute where the extrusion ends
+    ///
+    /// Extrusions that go up to the next face need to know the face they start
+    /// from, and the shell that it is part of.
+    pub(super) fn end(
+        &self,
+        plane: &ExtrudePlane,
+        direction: Vector<3>,
+        face_of_shell: Option<(&Handle<Face>, &Shell)>,
+        core: &Core,
+    ) -> ExtrudeEnd {
+        let end = match &self.extent {
+            ExtrudeExtent::Distance(distance) => {
+                ExtrudeEnd::Parallel(*distance)
+            }
+            ExtrudeExtent::UpToNextFace => {
+                let Some((face, shell)) = face_of_shell else {
+                    panic!(
+                        "Extruding up to the next face requires a face of a \
+                        shell"
+                    );
+                };
+
+                let next_face = next_face(face, shell, direction, core)
+                    .expect("No face to extrude up to");
+                plane.end_at(next_face.surface(), direction, core)
+            }
+            ExtrudeExtent::UpToSurface(surface) => {
+                plane.end_at(surface, direction, core)
+            }
+        };
+
+        match end {
+            ExtrudeEnd::Parallel(length) => {
+                assert!(
+                    length > Scalar::ZERO,
+                    "Extrusion must have a positive length"
+                );
+            }
+            ExtrudeEnd::Slanted(_) => {
+                assert!(
+                    self.draft_angle == Scalar::ZERO,
+                    "Extruding with a draft angle is only supported up to \
+                    surfaces that are parallel to the extruded face or sketch"
+                );
+            }
+        }
+
+        end
+    }
+
+    /// # Compute how far the draft angle moves the end of the extrusion inward
+    pub(super) fn inset(&self, end: &ExtrudeEnd) -> Scalar {
+        match end {
+            ExtrudeEnd::Parallel(length) => {
+                *length * self.draft_angle.into_f64().tan()
+            }
+            ExtrudeEnd::Slanted(_) => Scalar::ZERO,
+        }
+    }
+}
+
+impl From<ExtrudeExtent> for Extrude {
+    fn from(extent: ExtrudeExtent) -> Self {
+        Self {
+            extent,
+            draft_angle: Scalar::ZERO,
+            symmetric: false,
+        }
+    }
+}
+
+/// # How far to extrude
+///
+/// See [`Extrude`].
+#[derive(Clone, Debug)]
+pub enum ExtrudeExtent {
+    /// # Extrude by a fixed distance
+    Distance(Scalar),
+
+    /// # Extrude up to the next face of the shell that is in the way
+    ///
+    /// This face must be planar. See [`ExtrudeExtent::UpToSurface`] for
+    /// further requirements.
+    UpToNextFace,
+
+    /// # Extrude up to a surface
+    ///
+    /// This surface must be a plane. It doesn't need to be parallel to the
+    /// extruded face or sketch, but it must not be parallel to the direction of
+    /// the extrusion, and it must be in front of the whole face or sketch.
+    ///
+    /// If the plane is not parallel to the extruded face or sketch, the
+    /// extrusion can't have a draft angle.
+    UpToSurface(Handle<Surface>),
+}
+
+/// # Where an extrusion ends
+pub(super) enum ExtrudeEnd {
+    /// # The end is parallel to the start, at this distance along the normal
+    Parallel(Scalar),
+
+    /// # The end is within a plane that is not parallel to the start
+    ///
+    /// The coordinates of this plane are those of the start, projected onto it
+    /// along the direction of the extrusion. A region on the start plane can be
+    /// moved to the end plane without changing its surface coordinates.
+    Slanted(ExtrudePlane),
+}
+
+impl ExtrudeEnd {
+    /// # The plane of the end, in the coordinates of the start
+    ///
+    /// See [`ExtrudeEnd::Slanted`].
+    pub(super) fn plane(
+        &self,
+        start: &ExtrudePlane,
+        direction: Vector<3>,
+    ) -> ExtrudePlane {
+        match self {
+            Self::Parallel(length) => start.moved(direction * *length),
+            Self::Slanted(plane) => *plane,
+        }
+    }
+}
+
+/// # The plane that an extrusion starts from
+#[derive(Clone, Copy)]
+pub(super) struct ExtrudePlane {
+    origin: Point<3>,
+    axes: [Vector<3>; 2],
+}
+
+impl ExtrudePlane {
+    pub(super) fn of(surface: &Handle<Surface>, core: &Core) -> Self {
+        Self::try_of(surface, core)
+            .expect("Only planar faces and sketches can be extruded")
+    }
+
+    fn try_of(surface: &Handle<Surface>, core: &Core) -> Option<Self> {
+        match core.layers.geometry.try_of_surface(surface) {
+            Some(SweptCurve {
+                u: Path::Line(line),
+                v,
+            }) => Some(Self {
+                origin: line.origin(),
+                axes: [line.direction(), *v],
+            }),
+            _ => None,
+        }
+    }
+
+    pub(super) fn normal(&self) -> Vector<3> {
+        let [u, v] = self.axes;
+        u.cross(&v).normalize()
+    }
+
+    /// # Convert a point in surface coordinates into a 3D point
+    fn point(&self, point: Point<2>) -> Point<3> {
+        let [u, v] = self.axes;
+        self.origin + u * point.u + v * point.v
+    }
+
+    /// # Move the plane by the provided offset
+    pub(super) fn moved(&self, offset: Vector<3>) -> Self {
+        Self {
+            origin: self.origin + offset,
+            axes: self.axes,
+        }
+    }
+
+    /// # Mirror the plane on another one
+    pub(super) fn mirrored(&self, mirror: &ExtrudePlane) -> Self {
+        let normal = mirror.normal();
+        let reflect =
+            |vector: Vector<3>| vector - normal * vector.dot(&normal) * 2.;
+
+        Self {
+            origin: mirror.origin + reflect(self.origin - mirror.origin),
+            axes: self.axes.map(reflect),
+        }
+    }
+
+    /// # Build a surface for the plane
+    pub(super) fn surface(&self, core: &mut Core) -> Handle<Surface> {
+        let [u, v] = self.axes;
+        Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(self.origin, u)),
+            v,
+            core,
+        )
+    }
+
+    /// # Build a surface for the plane, moved by the provided offset
+    pub(super) fn surface_at(
+        &self,
+        offset: Vector<3>,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        self.moved(offset).surface(core)
+    }
+
+    /// # Find out where an extrusion up to the provided surface ends
+    fn end_at(
+        &self,
+        surface: &Handle<Surface>,
+        direction: Vector<3>,
+        core: &Core,
+    ) -> ExtrudeEnd {
+        let other = Self::try_of(surface, core)
+            .expect("Can only extrude up to planar surfaces");
+        let normal = other.normal();
+
+        let is_parallel = self.normal().cross(&normal).magnitude()
+            <= core.tolerance().inner() * Scalar::from(1e-3);
+        if is_parallel {
+            let length = (other.origin - self.origin).dot(&direction);
+            return ExtrudeEnd::Parallel(length);
+        }
+
+        let rate = direction.dot(&normal);
+        assert!(
+            rate.abs() > core.tolerance().inner() * Scalar::from(1e-3),
+            "Can't extrude up to a surface that is parallel to the direction \
+            of the extrusion"
+        );
+
+        // Project the start plane onto the other one, along the direction of
+        // the extrusion.
+        let [u, v] = self.axes;
+        let distance = (other.origin - self.origin).dot(&normal) / rate;
+        let [u, v] =
+            [u, v].map(|axis| axis - direction * axis.dot(&normal) / rate);
+
+        ExtrudeEnd::Slanted(Self {
+            origin: self.origin + direction * distance,
+            axes: [u, v],
+        })
+    }
+
+    /// # Convert a vector within the plane into surface coordinates
+    fn project_vector(&self, vector: Vector<3>) -> Vector<2> {
+        // The axes are not necessarily orthogonal, so we need to solve for the
+        // coordinates.
+        let [u, v] = self.axes;
+        let [uu, uv, vv] = [u.dot(&u), u.dot(&v), v.dot(&v)];
+        let [pu, pv] = [u, v].map(|axis| vector.dot(&axis));
+        let determinant = uu * vv - uv * uv;
+
+        Vector::from([
+            (pu * vv - pv * uv) / determinant,
+            (pv * uu - pu * uv) / determinant,
+        ])
+    }
+}
+
+/// # Check that the end of an extrusion is in front of a region
+///
+/// The region is defined on the surface of the start plane. Unless the planes
+/// are parallel, the distance between them varies across the region.
+///
+/// # Panics
+///
+/// Panics, if any point of the region's boundary is not in front of the end,
+/// along the direction of the extrusion.
+pub(super) fn assert_in_front(
+    start: &ExtrudePlane,
+    end: &ExtrudePlane,
+    direction: Vector<3>,
+    region: &Region,
+    surface: &Handle<Surface>,
+    core: &Core,
+) {
+    let approx = approx_cycle(
+        region.exterior(),
+        surface,
+        core.tolerance(),
+        &mut ApproxCache::default(),
+        &core.layers.geometry,
+    );
+
+    for point in approx.points() {
+        let [start, end] =
+            [start, end].map(|plane| plane.point(point.local_form));
+        assert!(
+            (end - start).dot(&direction) > Scalar::ZERO,
+            "Surface to extrude up to must be in front of the whole extruded \
+            face or sketch"
+        );
+    }
+}
+
+/// # Build a copy of a region on a parallel plane, with an inset boundary
+///
+/// The cycles of the copy are moved towards the inside of the region by
+/// `inset`, which is a distance in global coordinates. A negative inset moves
+/// them towards the outside.
+///
+/// # Panics
+///
+/// Panics, if the inset is not zero and the region is bounded by curves other
+/// than lines and circles, or if it is bounded by circles, but the axes of the
+/// plane are not orthogonal and of the same length.
+pub(super) fn inset_region(
+    region: &Handle<Region>,
+    surface: &Handle<Surface>,
+    target: Handle<Surface>,
+    inset: Scalar,
+    core: &mut Core,
+) -> Handle<Region> {
+    let plane = ExtrudePlane::of(surface, core);
+
+    // Check all curves before building anything, so we don't fail halfway
+    // through.
+    if inset != Scalar::ZERO {
+        for half_edge in
+            region.all_cycles().flat_map(|cycle| cycle.half_edges())
+        {
+            let path = core
+                .layers
+                .geometry
+                .of_curve(half_edge.curve())
+                .unwrap()
+                .local_on(surface)
+                .unwrap()
+                .path
+                .clone();
+            let curve = match path {
+                Path::Line(_) | Path::Circle(_) => continue,
+                Path::Ellipse(_) => "an ellipse",
+                Path::Nurbs(_) => "a NURBS curve",
+            };
+
+            panic!(
+                "Extruding with a draft angle is only supported for regions \
+                that are bounded by lines and circles, but this region is \
+                bounded by {curve}"
+            );
+        }
+    }
+
+    // The inside of the region is to the left of its exterior, if that winds
+    // counter-clockwise, and to the right otherwise.
+    let inset = if region
+        .exterior()
+        .winding(core.tolerance(), &core.layers.geometry, surface)
+        .is_ccw()
+    {
+        inset
+    } else {
+        -inset
+    };
+
+    let mut cycles = region.all_cycles().map(|cycle| {
+        let half_edges = cycle
+            .half_edges()
+            .pairs()
+            .map(|(half_edge, next)| {
+                let geometry = &core.layers.geometry;
+
+                let path = geometry
+                    .of_curve(half_edge.curve())
+                    .unwrap()
+                    .local_on(surface)
+                    .unwrap()
+                    .path
+                    .clone();
+                let boundary = [half_edge, next].map(|h| {
+                    geometry
+                        .of_vertex(h.start_vertex())
+                        .unwrap()
+                        .local_on(half_edge.curve())
+                        .unwrap()
+                        .position
+                });
+
+                let path = inset_path(path, boundary, inset, &plane);
+                (path, boundary)
+            })
+            .collect::<Vec<_>>();
+
+        // The inset curves of neighboring half-edges no longer meet where the
+        // original ones did. Find out where they meet now.
+        let vertices = (0..half_edges.len())
+            .map(|i| {
+                let (previous, [_, end]) =
+                    &half_edges[(i + half_edges.len() - 1) % half_edges.len()];
+                let (current, [start, _]) = &half_edges[i];
+
+                refine_path_intersection(
+                    previous,
+                    current,
+                    [*end, *start],
+                    false,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let half_edges_and_boundaries = half_edges
+            .into_iter()
+            .enumerate()
+            .map(|(i, (path, _))| {
+                let boundary = CurveBoundary {
+                    inner: [
+                        vertices[i][1],
+                        vertices[(i + 1) % vertices.len()][0],
+                    ],
+                };
+
+                let half_edge = HalfEdge::unjoined(core).insert(core);
+                core.layers.geometry.define_curve(
+                    half_edge.curve().clone(),
+                    target.clone(),
+                    LocalCurveGeom { path },
+                );
+
+                (half_edge, boundary)
+            })
+            .collect::<Vec<_>>();
+
+        Cycle::from_half_edges_and_boundaries(half_edges_and_boundaries, core)
+            .insert(core)
+    });
+
+    let exterior = cycles.next().expect("Region has an exterior");
+    let interiors = cycles.collect::<Vec<_>>();
+
+    let inset_region = Region::new(exterior, interiors).insert(core);
+    if let Some(color) = region.get_color(core) {
+        inset_region.set_color(color, core);
+    }
+
+    inset_region
+}
+
+/// # Move a path to the left of its direction of travel
+fn inset_path(
+    path: Path<2>,
+    [start, end]: [Point<1>; 2],
+    inset: Scalar,
+    plane: &ExtrudePlane,
+) -> Path<2> {
+    if inset == Scalar::ZERO {
+        return path;
+    }
+
+    let is_forward = end > start;
+
+    match path {
+        Path::Line(line) => {
+            let direction = if is_forward {
+                line.direction()
+            } else {
+                -line.direction()
+            };
+
+            let [u, v] = plane.axes;
+            let direction = u * direction.u + v * direction.v;
+            let offset = plane.normal().cross(&direction).normalize() * inset;
+
+            Path::Line(Line::from_origin_and_direction(
+                line.origin() + plane.project_vector(offset),
+                line.direction(),
+            ))
+        }
+        Path::Circle(circle) => {
+            let [u, v] = plane.axes;
+            let epsilon = u.magnitude() * v.magnitude() * Scalar::from(1e-9);
+            let is_conformal = u.dot(&v).abs() <= epsilon
+                && (u.dot(&u) - v.dot(&v)).abs() <= epsilon;
+            assert!(
+                is_conformal,
+                "Can only inset circles on planes with orthogonal axes of the \
+                same length"
+            );
+
+            // The center is to the left of the direction of travel, if that
+            // goes counter-clockwise around the circle.
+            let is_ccw = circle.a().u * circle.b().v
+                - circle.a().v * circle.b().u
+                > Scalar::ZERO;
+            let inset = if is_ccw == is_forward { inset } else { -inset };
+
+            let radius = circle.radius();
+            let inset_radius = radius - inset / u.magnitude();
+            assert!(
+                inset_radius > Scalar::ZERO,
+                "Inset is too large for circle"
+            );
+
+            let scale = inset_radius / radius;
+            Path::Circle(Circle::new(
+                circle.center(),
+                circle.a() * scale,
+                circle.b() * scale,
+            ))
+        }
+        Path::Ellipse(_) | Path::Nurbs(_) => {
+            unreachable!("Unsupported curves are rejected by `inset_region`")
+        }
+    }
+}
+
+/// # Find the next face of the shell, in front of the provided one
+fn next_face(
+    face: &Handle<Face>,
+    shell: &Shell,
+    direction: Vector<3>,
+    core: &Core,
+) -> Option<Handle<Face>> {
+    let tolerance = core.tolerance();
+    let geometry = &core.layers.geometry;
+
+    let approx = approx_face(
+        face.clone(),
+        tolerance,
+        &mut ApproxCache::default(),
+        geometry,
+    );
+
+    // Cast rays from the center of each triangle of the face. Those are
+    // within the face, so the rays won't hit its neighbors right away.
+    let mut cache = RayCastCache::default();
+    triangulate_face(approx)
+        .into_iter()
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.map(|point| point.global_form);
+            let ray = Ray {
+                origin: a + ((b - a) + (c - a)) / 3.,
+                direction,
+            };
+
+            shell
+                .ray_cast_with_cache(&ray, tolerance, &mut cache, geometry)
+                .into_iter()
+                .find(|hit| {
+                    &hit.face != face
+                        && hit.distance > tolerance.inner() * Scalar::from(1e-3)
+                })
+        })
+        .min_by_key(|hit| hit.distance)
+        .map(|hit| hit.face)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Ellipse, Line, Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::ray_cast::{Ray, RayCast},
+        geometry::Path,
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch, BuildSurface},
+            insert::Insert,
+            sweep::{SweepFaceOfShell, SweepSketch},
+            tests::assert_volume,
+            update::UpdateSketch,
+        },
+        topology::{Cycle, Region, Sketch, Solid, Surface},
+        Core,
+    };
+
+    use super::Extrude;
+
+    #[test]
+    fn extrude_sketch_with_draft_angle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // With this draft angle, the 2x2 square shrinks to 1x1 over a height
+        // of 1, making the result a frustum.
+        let draft_angle = Scalar::from(0.5_f64.atan());
+
+        let extrude = Extrude::distance(1.).with_draft_angle(draft_angle);
+        let solid = square(&mut core)
+            .extrude_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                &extrude,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(7. / 3.), &mut core)?;
+
+        let extrude = extrude.symmetric();
+        let solid = square(&mut core)
+            .extrude_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                &extrude,
+                &mut core,
+            )
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(14. / 3.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn extrude_sketch_up_to_surface() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+        let target = super::ExtrudePlane::of(&xy_plane, &core)
+            .surface_at(Vector::from([0., 0., -3.]), &mut core);
+
+        let solid = square(&mut core)
+            .extrude_sketch(
+                xy_plane.clone(),
+                &Extrude::up_to_surface(target.clone()),
+                &mut core,
+            )
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(12.), &mut core)?;
+
+        let solid = square(&mut core)
+            .extrude_sketch(
+                xy_plane,
+                &Extrude::up_to_surface(target).symmetric(),
+                &mut core,
+            )
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(24.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn extrude_sketch_up_to_slanted_surface() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // The extrusion gets longer from 2 to 3 along the x-axis, across the
+        // square. On average, it is 2.5 long.
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+        let target = Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(
+                Point::from([0., 0., -3.]),
+                Vector::from([1., 0., 0.5]),
+            )),
+            [0., 1., 0.],
+            &mut core,
+        );
+
+        let solid = square(&mut core)
+            .extrude_sketch(
+                xy_plane.clone(),
+                &Extrude::up_to_surface(target.clone()),
+                &mut core,
+            )
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(10.), &mut core)?;
+
+        let solid = square(&mut core)
+            .extrude_sketch(
+                xy_plane,
+                &Extrude::up_to_surface(target).symmetric(),
+                &mut core,
+            )
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(20.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "but this region is bounded by an ellipse")]
+    fn extrude_ellipse_with_draft_angle() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let exterior = Cycle::ellipse(
+            Ellipse::new([0., 0.], [2., 0.], [0., 1.]),
+            surface.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+        let region = Region::new(exterior, []).insert(&mut core);
+
+        Sketch::new(surface, [region]).extrude_sketch(
+            core.layers.topology.surfaces.xy_plane(),
+            &Extrude::distance(1.).with_draft_angle(0.1),
+            &mut core,
+        );
+    }
+
+    #[test]
+    fn extrude_face_of_shell_up_to_next_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        // A C-shaped solid. The slot is open towards positive x, and its upper
+        // side is longer than its lower one.
+        let surface = core.layers.topology.surfaces.space_2d();
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [
+                        [0., 0.],
+                        [3., 0.],
+                        [3., 1.],
+                        [1., 1.],
+                        [1., 3.],
+                        [4., 3.],
+                        [4., 4.],
+                        [0., 4.],
+                    ],
+                    surface,
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -2.],
+                &mut core,
+            );
+        let shell = solid.shells().only();
+
+        // Extrude the lower side of the slot up to the upper one. The draft
+        // angle keeps the new top face from touching any edges of the shell.
+        let face = shell
+            .ray_cast(
+                &Ray {
+                    origin: Point::from([2., 2., -1.]),
+                    direction: Vector::from([0., -1., 0.]),
+                },
+                core.tolerance(),
+                &core.layers.geometry,
+            )
+            .remove(0)
+            .face;
+        let extended = shell.extrude_face_of_shell(
+            face,
+            &Extrude::up_to_next_face()
+                .with_draft_angle(Scalar::from(0.25_f64.atan())),
+            &mut core,
+        );
+
+        let solid =
+            Solid::new([extended.shell.insert(&mut core)]).insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(18. + 14. / 3.), &mut core)?;
+
+        Ok(())
+    }
+
+    fn square(core: &mut Core) -> Sketch {
+        let surface = core.layers.topology.surfaces.space_2d();
+        Sketch::empty(&core.layers.topology).add_regions(
+            [Region::polygon(
+                [[0., 0.], [2., 0.], [2., 2.], [0., 2.]],
+                surface,
+                core,
+            )],
+            core,
+        )
+    }
+}
@@ -1,19 +1,31 @@
 //! Sweep objects along a path to create new objects
 //!
 //! Sweeps 1D or 2D objects along a straight path, creating a 2D or 3D object,
-//! respectively.
+//! respectively. Regions and sketches can also be swept along a [`SweepPath`],
+//! which may consist of multiple segments and be curved. Regions can also be
+//! swept along a [`Helix`]. Planar faces and sketches can be extruded with
+//! additional options, as described by [`Extrude`].
+//!
+//! [`Helix`]: crate::geometry::curves::helix::Helix
 
+mod along_helix;
+mod along_path;
 mod cycle;
+mod extrude;
 mod face;
 mod half_edge;
 mod path;
+mod profile;
 mod region;
 mod shell_face;
 mod sketch;
 mod vertex;
 
 pub use self::{
+    along_helix::SweepRegionAlongHelix,
+    along_path::{SweepPath, SweepRegionAlongPath, SweepSketchAlongPath},
     cycle::{SweepCycle, SweptCycle},
+    extrude::{Extrude, ExtrudeExtent},
     face::SweepFace,
     half_edge::{SweepHalfEdge, SweptHalfEdge},
     path::SweepSurfacePath,
@@ -1,4 +1,4 @@
-use fj_math::{Circle, Line, Vector};
+use fj_math::{Circle, Ellipse, Line, Vector};
 
 use crate::{
     geometry::{
@@ -47,7 +47,7 @@ impl SweepSurfacePath for Path<2> {
     ) -> Handle<Surface> {
         let SweptCurve { u, .. } = surface;
         match u {
-            Path::Circle(_) => {
+            Path::Circle(_) | Path::Ellipse(_) | Path::Nurbs(_) => {
                 // Sweeping a `Curve` creates a `Surface`. The u-axis of that
                 // `Surface` is a `Path<3>`, which we are computing below. That
                 // computation might or might not work with an arbitrary
@@ -95,6 +95,24 @@ impl SweepSurfacePath for Path<2> {
 
                 Path::Circle(circle)
             }
+            Path::Ellipse(ellipse) => {
+                let center = convert_point_surface_to_global(
+                    surface,
+                    ellipse.center(),
+                    core.tolerance(),
+                    &core.layers.geometry,
+                );
+                let [a, b] = [ellipse.a(), ellipse.b()].map(|vector| {
+                    convert_vector_surface_to_global(
+                        surface,
+                        vector,
+                        core.tolerance(),
+                        &core.layers.geometry,
+                    )
+                });
+
+                Path::Ellipse(Ellipse::new(center, a, b))
+            }
             Path::Line(line) => {
                 let origin = convert_point_surface_to_global(
                     surface,
@@ -113,6 +131,14 @@ impl SweepSurfacePath for Path<2> {
 
                 Path::Line(line)
             }
+            Path::Nurbs(_) => {
+                // Mapping the control points of a NURBS curve only results in
+                // the image of that curve, if the map is affine. This is the
+                // case for planes, which `path_to_global` makes sure of.
+                surface
+                    .path_to_global(self)
+                    .expect("Sweeping NURBS curves is only supported on planes")
+            }
         };
 
         Surface::from_uv(u, path, core)
@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use fj_interop::Color;
+use fj_math::{Line, Point, Scalar, Vector, Winding};
+
+use crate::{
+    geometry::{
+        repr::tri_mesh::TriMesh, surfaces::SweptCurve, traits::GenTriMesh,
+        CurveBoundary, LocalVertexGeom, Path, SurfaceGeom,
+    },
+    operations::{
+        build::{BuildCycle, BuildSurface},
+        geometry::UpdateCurveGeometry,
+        insert::Insert,
+        presentation::SetColor,
+    },
+    storage::Handle,
+    topology::{Curve, Cycle, Face, Region, Shell, Surface, Vertex},
+    Core,
+};
+
+/// # Determine the plane of a profile that is swept along a path or helix
+///
+/// ## Panics
+///
+/// Panics, if `surface` isn't a plane.
+pub(super) fn plane_of_profile(
+    surface: &Handle<Surface>,
+    core: &Core,
+) -> (Point<3>, [Vector<3>; 2]) {
+    match core.layers.geometry.of_surface(surface) {
+        SweptCurve {
+            u: Path::Line(line),
+            v,
+        } => (line.origin(), [line.direction(), *v]),
+        SweptCurve {
+            u: Path::Circle(_) | Path::Ellipse(_) | Path::Nurbs(_),
+            ..
+        } => {
+            panic!("Sweeping along a path requires a region on a plane")
+        }
+    }
+}
+
+/// # A section of a sweep along a path or helix
+///
+/// Each curve of the profile is swept into one side face per section.
+pub(super) struct Section {
+    /// # Create the surface that a curve of the profile is swept into
+    ///
+    /// The curve is provided in the coordinates of the plane of the profile.
+    pub surface: Box<dyn Fn(Path<2>) -> Box<dyn GenTriMesh>>,
+
+    /// # The start and end of the section
+    ///
+    /// These are v-coordinates on the surfaces that the curves are swept into.
+    pub boundary: CurveBoundary<Point<1>>,
+}
+
+/// # Sweep a profile through a sequence of sections
+///
+/// `direction` is the direction that the profile is swept in at the start.
+/// `end_plane` is the plane that the profile ends up in, after it has been
+/// swept through all sections.
+pub(super) fn sweep_profile(
+    region: &Region,
+    surface: &Handle<Surface>,
+    color: Option<Color>,
+    direction: Vector<3>,
+    sections: Vec<Section>,
+    end_plane: (Point<3>, [Vector<3>; 2]),
+    core: &mut Core,
+) -> Shell {
+    let profile = Profile::new(region, surface, core);
+
+    // The orientation of the side faces depends on the winding of the region
+    // and on the direction that it is swept in. If we don't adapt to that,
+    // we'd end up with faces that point inwards.
+    let reverse_side_faces = {
+        let (_, [u, v]) = plane_of_profile(surface, core);
+        let normal = u.cross(&v);
+
+        let is_ccw = region.exterior().winding(
+            core.tolerance(),
+            &core.layers.geometry,
+            surface,
+        ) == Winding::Ccw;
+        let is_swept_forward = normal.dot(&direction) > Scalar::ZERO;
+
+        is_ccw != is_swept_forward
+    };
+
+    // Each vertex of the profile is swept into a ring of vertices, one at the
+    // start of the sweep, and one at the end of each section.
+    let num_rings = sections.len() + 1;
+    let vertices = (0..profile.num_vertices)
+        .map(|_| {
+            (0..num_rings)
+                .map(|_| Vertex::new().insert(core))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Same for the curves of the profile.
+    let curves = profile
+        .cycles
+        .iter()
+        .map(|segments| {
+            segments
+                .iter()
+                .map(|segment| {
+                    (0..num_rings)
+                        .map(|ring| {
+                            let curve = Curve::new().insert(core);
+
+                            for (index, position) in segment
+                                .vertices
+                                .into_iter()
+                                .zip(segment.boundary)
+                            {
+                                core.layers.geometry.define_vertex(
+                                    vertices[index][ring].clone(),
+                                    curve.clone(),
+                                    LocalVertexGeom { position },
+                                );
+                            }
+
+                            curve
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut faces = Vec::new();
+
+    // The curves that the vertices are swept along are shared between
+    // neighboring side faces, so we need to keep track of them.
+    let mut rails = BTreeMap::new();
+
+    for (cycle_index, segments) in profile.cycles.iter().enumerate() {
+        for (segment_index, segment) in segments.iter().enumerate() {
+            for (section_index, section) in sections.iter().enumerate() {
+                let boundary = section.boundary;
+
+                let side_surface = Surface::new().insert(core);
+                core.layers.geometry.define_surface_2(
+                    side_surface.clone(),
+                    SurfaceGeom {
+                        generator: (section.surface)(segment.path.clone()),
+                        geometry: TriMesh::empty(),
+                    },
+                );
+
+                let rings = [section_index, section_index + 1];
+
+                for (ring, point_path) in rings.into_iter().zip(boundary.inner)
+                {
+                    curves[cycle_index][segment_index][ring]
+                        .clone()
+                        .make_path_on_surface(
+                            Path::Line(Line::from_origin_and_direction(
+                                Point::from([Scalar::ZERO, point_path.t]),
+                                Vector::unit_u(),
+                            )),
+                            side_surface.clone(),
+                            &mut core.layers.geometry,
+                        );
+                }
+
+                let mut rail =
+                    |index: usize, position: Point<1>, core: &mut Core| {
+                        rails
+                            .entry((index, section_index))
+                            .or_insert_with(|| {
+                                let rail = Curve::new().insert(core);
+
+                                for (ring, position) in
+                                    rings.into_iter().zip(boundary.inner)
+                                {
+                                    core.layers.geometry.define_vertex(
+                                        vertices[index][ring].clone(),
+                                        rail.clone(),
+                                        LocalVertexGeom { position },
+                                    );
+                                }
+
+                                rail
+                            })
+                            .clone()
+                            .make_path_on_surface(
+                                Path::Line(Line::from_origin_and_direction(
+                                    Point::from([position.t, Scalar::ZERO]),
+                                    Vector::unit_v(),
+                                )),
+                                side_surface.clone(),
+                                &mut core.layers.geometry,
+                            )
+                    };
+
+                let [start, end] = segment.vertices;
+                let [start_position, end_position] = segment.boundary;
+
+                let half_edges = vec![
+                    (
+                        curves[cycle_index][segment_index][section_index]
+                            .clone(),
+                        vertices[start][section_index].clone(),
+                    ),
+                    (
+                        rail(end, end_position, core),
+                        vertices[end][section_index].clone(),
+                    ),
+                    (
+                        curves[cycle_index][segment_index][section_index + 1]
+                            .clone(),
+                        vertices[end][section_index + 1].clone(),
+                    ),
+                    (
+                        rail(start, start_position, core),
+                        vertices[start][section_index + 1].clone(),
+                    ),
+                ];
+
+                let exterior = Cycle::from_curves_and_start_vertices(
+                    half_edges,
+                    reverse_side_faces,
+                    core,
+                )
+                .insert(core);
+                let region = Region::new(exterior, []).insert(core);
+                if let Some(color) = color {
+                    region.set_color(color, core);
+                }
+
+                faces.push(Face::new(side_surface, region).insert(core));
+            }
+        }
+    }
+
+    let end_surface = {
+        let (origin, [u, v]) = end_plane;
+        Surface::from_uv(
+            Path::Line(Line::from_origin_and_direction(origin, u)),
+            v,
+            core,
+        )
+    };
+
+    for (cap_surface, ring, reverse) in [
+        (surface.clone(), 0, !reverse_side_faces),
+        (end_surface, num_rings - 1, reverse_side_faces),
+    ] {
+        let mut cycles = Vec::new();
+
+        for (cycle_index, segments) in profile.cycles.iter().enumerate() {
+            let half_edges = segments
+                .iter()
+                .enumerate()
+                .map(|(segment_index, segment)| {
+                    let [start, _] = segment.vertices;
+
+                    let curve = curves[cycle_index][segment_index][ring]
+                        .clone()
+                        .make_path_on_surface(
+                            segment.path.clone(),
+                            cap_surface.clone(),
+                            &mut core.layers.geometry,
+                        );
+
+                    (curve, vertices[start][ring].clone())
+                })
+                .collect();
+
+            cycles.push(
+                Cycle::from_curves_and_start_vertices(
+                    half_edges, reverse, core,
+                )
+                .insert(core),
+            );
+        }
+
+        let mut cycles = cycles.into_iter();
+        let exterior =
+            cycles.next().expect("Region must have an exterior cycle");
+        let region = Region::new(exterior, cycles).insert(core);
+        if let Some(color) = color {
+            region.set_color(color, core);
+        }
+
+        faces.push(Face::new(cap_surface, region).insert(core));
+    }
+
+    Shell::new(faces)
+}
+
+/// The profile that is being swept, prepared for the sweep
+struct Profile {
+    /// The number of vertices of the profile
+    num_vertices: usize,
+
+    /// The cycles of the profile, starting with the exterior
+    cycles: Vec<Vec<Segment>>,
+}
+
+impl Profile {
+    fn new(region: &Region, surface: &Handle<Surface>, core: &Core) -> Self {
+        let geometry = &core.layers.geometry;
+
+        let mut vertex_indices = BTreeMap::new();
+        let mut cycles = Vec::new();
+
+        for cycle in region.all_cycles() {
+            let mut segments = Vec::new();
+
+            for (half_edge, next_half_edge) in cycle.half_edges().pairs() {
+                let path = geometry
+                    .of_curve(half_edge.curve())
+                    .unwrap()
+                    .local_on(surface)
+                    .unwrap()
+                    .path
+                    .clone();
+
+                let vertices_of_half_edge =
+                    [half_edge.start_vertex(), next_half_edge.start_vertex()];
+                let boundary = vertices_of_half_edge.map(|vertex| {
+                    geometry
+                        .of_vertex(vertex)
+                        .unwrap()
+                        .local_on(half_edge.curve())
+                        .unwrap()
+                        .position
+                });
+                let vertices = vertices_of_half_edge.map(|vertex| {
+                    let next_index = vertex_indices.len();
+                    *vertex_indices.entry(vertex.clone()).or_insert(next_index)
+                });
+
+                segments.push(Segment {
+                    path,
+                    boundary,
+                    vertices,
+                });
+            }
+
+            cycles.push(segments);
+        }
+
+        Self {
+            num_vertices: vertex_indices.len(),
+            cycles,
+        }
+    }
+}
+
+/// A segment of a cycle of the profile, the equivalent of a half-edge
+struct Segment {
+    path: Path<2>,
+    boundary: [Point<1>; 2],
+    vertices: [usize; 2],
+}
@@ -1,19 +1,25 @@
-use fj_math::Vector;
+use fj_math::{Scalar, Vector};
 
 use crate::{
     operations::{
         derive::DeriveFrom,
         insert::Insert,
+        loft::Loft,
         presentation::GetColor,
         reverse::Reverse,
         sweep::{SweepCache, SweepRegion},
         update::UpdateShell,
     },
     storage::Handle,
-    topology::{Face, Region, Shell},
+    topology::{Face, Region, Shell, Solid},
     Core,
 };
 
+use super::{
+    extrude::{assert_in_front, inset_region, ExtrudeEnd, ExtrudePlane},
+    Extrude,
+};
+
 /// # Sweep a [`Face`] that is part of a [`Shell`]
 ///
 /// See [module documentation] for more information.
@@ -38,6 +44,34 @@ pub trait SweepFaceOfShell {
         path: impl Into<Vector<3>>,
         core: &mut Core,
     ) -> ShellExtendedBySweep;
+
+    /// # Extrude the [`Face`] of the [`Shell`]
+    ///
+    /// Extends the shell, like [`SweepFaceOfShell::sweep_face_of_shell`].
+    /// Instead of sweeping the face along a fixed path, it is extruded along
+    /// its normal, away from the shell, as specified by `extrude`.
+    ///
+    /// The same limitation as for [`SweepFaceOfShell::sweep_face_of_shell`]
+    /// applies. When extruding up to the next face of the shell, the new top
+    /// face lies within the same plane as that face.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the face is not planar, or if the extrusion is symmetric.
+    /// A symmetric extrusion would extend into the shell.
+    ///
+    /// Panics, if the extrusion has no draft angle and the face has interior
+    /// cycles, like [`SweepFaceOfShell::sweep_face_of_shell`] does.
+    ///
+    /// See [`Extrude`] and [`ExtrudeExtent`] for further requirements.
+    ///
+    /// [`ExtrudeExtent`]: super::ExtrudeExtent
+    fn extrude_face_of_shell(
+        &self,
+        face: Handle<Face>,
+        extrude: &Extrude,
+        core: &mut Core,
+    ) -> ShellExtendedBySweep;
 }
 
 impl SweepFaceOfShell for Shell {
@@ -83,6 +117,95 @@ impl SweepFaceOfShell for Shell {
             top_face: swept_region.top_face,
         }
     }
+
+    fn extrude_face_of_shell(
+        &self,
+        face: Handle<Face>,
+        extrude: &Extrude,
+        core: &mut Core,
+    ) -> ShellExtendedBySweep {
+        assert!(
+            !extrude.symmetric,
+            "Faces of shells can't be extruded symmetrically"
+        );
+
+        // The face points away from the shell, and that's where we want to
+        // extrude it.
+        let plane = ExtrudePlane::of(face.surface(), core);
+        let direction = if face
+            .region()
+            .exterior()
+            .winding(core.tolerance(), &core.layers.geometry, face.surface())
+            .is_ccw()
+        {
+            plane.normal()
+        } else {
+            -plane.normal()
+        };
+
+        let end = extrude.end(&plane, direction, Some((&face, self)), core);
+
+        if let ExtrudeEnd::Parallel(length) = end {
+            if extrude.draft_angle == Scalar::ZERO {
+                return self.sweep_face_of_shell(
+                    face,
+                    direction * length,
+                    core,
+                );
+            }
+        }
+
+        let top = end.plane(&plane, direction);
+        assert_in_front(
+            &plane,
+            &top,
+            direction,
+            face.region(),
+            face.surface(),
+            core,
+        );
+
+        let top_surface = top.surface(core);
+        let top_region = inset_region(
+            face.region(),
+            face.surface(),
+            top_surface.clone(),
+            extrude.inset(&end),
+            core,
+        );
+
+        // The loft creates a face in place of the original one, which we don't
+        // need. The side faces connect to the rest of the shell, just like the
+        // original face did.
+        let lofted = Solid::loft(
+            [
+                (face.region().clone(), face.surface().clone()),
+                (top_region, top_surface.clone()),
+            ],
+            core,
+        );
+        let (top_faces, side_faces): (Vec<_>, Vec<_>) = lofted
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .filter(|f| f.surface() != face.surface())
+            .cloned()
+            .partition(|f| f.surface() == &top_surface);
+
+        let shell = self
+            .remove_face(&face)
+            .add_faces(side_faces.iter().chain(&top_faces).cloned(), core);
+
+        ShellExtendedBySweep {
+            shell,
+            side_faces: side_faces.iter().map(|f| Face::clone(f)).collect(),
+            top_face: top_faces
+                .first()
+                .map(|f| Face::clone(f))
+                .expect("Loft creates a top face"),
+        }
+    }
 }
 
 /// The result of sweeping a [`Face`] of a [`Shell`]
@@ -1,13 +1,17 @@
-use fj_math::Vector;
+use fj_math::{Scalar, Vector};
 
 use crate::{
-    operations::insert::Insert,
+    operations::{geometry::UpdateCurveGeometry, insert::Insert, loft::Loft},
     storage::Handle,
-    topology::{Face, Sketch, Solid, Surface},
+    topology::{Face, Region, Sketch, Solid, Surface},
     Core,
 };
 
-use super::{face::SweepFace, SweepCache};
+use super::{
+    extrude::{assert_in_front, inset_region, ExtrudeEnd, ExtrudePlane},
+    face::SweepFace,
+    Extrude, SweepCache,
+};
 
 /// # Sweep a [`Sketch`]
 ///
@@ -17,6 +21,10 @@ use super::{face::SweepFace, SweepCache};
 pub trait SweepSketch {
     /// # Sweep the [`Sketch`]
     ///
+    /// The sketch is placed on `surface`, meaning its curves are defined there
+    /// in the same coordinates they have in the sketch. Where the sketch's own
+    /// surface is located in 3D space doesn't matter.
+    ///
     /// Requires `path` to point towards the back of `surface`. If one of them
     /// is fixed, make sure to adapt the other one accordingly.
     ///
@@ -43,6 +51,27 @@ pub trait SweepSketch {
         path: impl Into<Vector<3>>,
         core: &mut Core,
     ) -> Solid;
+
+    /// # Extrude the [`Sketch`]
+    ///
+    /// Places the sketch on `surface`, like [`SweepSketch::sweep_sketch`], and
+    /// extrudes it along the normal of that surface, towards its back, as
+    /// specified by `extrude`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `surface` is not a plane, or if `extrude` extends up to the
+    /// next face. A sketch is not part of a shell, so there is no next face.
+    ///
+    /// See [`Extrude`] and [`ExtrudeExtent`] for further requirements.
+    ///
+    /// [`ExtrudeExtent`]: super::ExtrudeExtent
+    fn extrude_sketch(
+        &self,
+        surface: Handle<Surface>,
+        extrude: &Extrude,
+        core: &mut Core,
+    ) -> Solid;
 }
 
 impl SweepSketch for Sketch {
@@ -57,23 +86,7 @@ impl SweepSketch for Sketch {
 
         let mut shells = Vec::new();
         for region in self.regions() {
-            for cycle in region.all_cycles() {
-                for half_edge in cycle.half_edges() {
-                    let curve_geom = core
-                        .layers
-                        .geometry
-                        .of_curve(half_edge.curve())
-                        .unwrap()
-                        .local_on(self.surface())
-                        .unwrap();
-
-                    core.layers.geometry.define_curve(
-                        half_edge.curve().clone(),
-                        surface.clone(),
-                        curve_geom.clone(),
-                    );
-                }
-            }
+            place_region(region, self.surface(), &surface, core);
 
             let face = Face::new(surface.clone(), region.clone()).insert(core);
             let shell = face.sweep_face(path, &mut cache, core).insert(core);
@@ -82,4 +95,178 @@ impl SweepSketch for Sketch {
 
         Solid::new(shells)
     }
+
+    fn extrude_sketch(
+        &self,
+        surface: Handle<Surface>,
+        extrude: &Extrude,
+        core: &mut Core,
+    ) -> Solid {
+        let plane = ExtrudePlane::of(&surface, core);
+        let direction = -plane.normal();
+        let end = extrude.end(&plane, direction, None, core);
+
+        if let ExtrudeEnd::Parallel(length) = end {
+            if extrude.draft_angle == Scalar::ZERO {
+                // Without a draft angle, this is a regular sweep. If it's
+                // symmetric, that sweep just starts further in front.
+                let (surface, length) = if extrude.symmetric {
+                    (plane.surface_at(-direction * length, core), length * 2.)
+                } else {
+                    (surface, length)
+                };
+
+                return self.sweep_sketch(surface, direction * length, core);
+            }
+        }
+
+        let inset = extrude.inset(&end);
+        let front = end.plane(&plane, direction);
+        let back = extrude.symmetric.then(|| front.mirrored(&plane));
+
+        let mut shells = Vec::new();
+        for region in self.regions() {
+            place_region(region, self.surface(), &surface, core);
+            assert_in_front(&plane, &front, direction, region, &surface, core);
+
+            let section_on = |end: ExtrudePlane, core: &mut Core| {
+                let target = end.surface(core);
+                let region =
+                    inset_region(region, &surface, target.clone(), inset, core);
+                (region, target)
+            };
+
+            let mut sections = Vec::new();
+            if let Some(back) = back {
+                sections.push(section_on(back, core));
+            }
+            sections.push((region.clone(), surface.clone()));
+            sections.push(section_on(front, core));
+
+            let solid = Solid::loft(sections, core);
+            shells.push(solid.shells().only().clone());
+        }
+
+        Solid::new(shells)
+    }
+}
+
+/// # Place the curves of a region on another surface
+///
+/// See [`UpdateCurveGeometry::place_on_surface`].
+fn place_region(
+    region: &Region,
+    from: &Handle<Surface>,
+    to: &Handle<Surface>,
+    core: &mut Core,
+) {
+    for cycle in region.all_cycles() {
+        for half_edge in cycle.half_edges() {
+            half_edge.curve().clone().place_on_surface(
+                from,
+                to.clone(),
+                &mut core.layers.geometry,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Ellipse, NurbsCurve, Scalar};
+
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildRegion},
+            insert::Insert,
+            tests::assert_volume,
+        },
+        topology::{Cycle, HalfEdge, Region, Sketch},
+        Core,
+    };
+
+    use super::SweepSketch;
+
+    #[test]
+    fn sweep_sketch_with_nurbs_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        // A parabolic segment, closed by a line. Its area is two thirds of the
+        // bounding rectangle.
+        let line = HalfEdge::line_segment(
+            [[0., 0.], [2., 0.]],
+            surface.clone(),
+            &mut core,
+        );
+        let parabola = HalfEdge::nurbs(
+            NurbsCurve::clamped(2, [[2., 0.], [1., 2.], [0., 0.]]),
+            surface.clone(),
+            &mut core,
+        );
+        let exterior =
+            Cycle::from_half_edges_and_boundaries([line, parabola], &mut core)
+                .insert(&mut core);
+        let region = Region::new(exterior, []).insert(&mut core);
+
+        let solid = Sketch::new(surface.clone(), [region])
+            .sweep_sketch(surface, [0., 0., -1.], &mut core)
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::from(4. / 3.), &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_sketch_with_ellipse() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        // Define the ellipse through semi-diameters that are not its axes, to
+        // make sure those are supported. The area of the ellipse is the area of
+        // the parallelogram they span, times `PI`.
+        let ellipse = Ellipse::new([1., 1.], [2., 0.], [1., 1.]);
+        let exterior = Cycle::ellipse(ellipse, surface.clone(), &mut core)
+            .insert(&mut core);
+        let region = Region::new(exterior, []).insert(&mut core);
+
+        let solid = Sketch::new(surface.clone(), [region])
+            .sweep_sketch(surface, [0., 0., -1.], &mut core)
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::PI * 2., &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_sketch_onto_perpendicular_surface() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let xy_plane = core.layers.topology.surfaces.xy_plane();
+        let yz_plane = core.layers.topology.surfaces.yz_plane();
+
+        // The sketch is placed on the target surface, not projected onto it.
+        // Otherwise, the profile would be degenerate.
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            xy_plane.clone(),
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let solid = Sketch::new(xy_plane, [region])
+            .sweep_sketch(yz_plane, [-1., 0., 0.], &mut core)
+            .insert(&mut core);
+
+        core.layers.validation.take_errors()?;
+        assert_volume(&solid, Scalar::ONE, &mut core)?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,1361 @@
+//! Add threads to shapes
+
+use std::{collections::BTreeMap, f64::consts::FRAC_PI_6};
+
+use fj_interop::Color;
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{
+        curves::helix::Helix,
+        repr::tri_mesh::TriMesh,
+        surfaces::{SweptAlongHelix, SweptCurve},
+        CurveBoundary, LocalVertexGeom, Path, SurfaceGeom,
+    },
+    operations::{
+        build::{BuildCycle, BuildSurface},
+        geometry::UpdateCurveGeometry,
+        insert::Insert,
+        presentation::{GetColor, SetColor},
+        update::{UpdateShell, UpdateSolid},
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, Handedness, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+/// Add a thread to a [`Solid`]
+pub trait AddThread {
+    /// Add a thread to the provided cylindrical face
+    ///
+    /// The thread is cut into the face as a helical groove with a V-shaped
+    /// profile, whose flanks are at an angle of 60 degrees to each other. For
+    /// an external thread, the groove is cut towards the axis of the face; for
+    /// an internal one, away from it. This means the face should be located at
+    /// the major diameter of an external thread (like the shaft of a screw),
+    /// and at the minor diameter of an internal thread (like a hole that is
+    /// about to be tapped).
+    ///
+    /// A cylinder usually consists of multiple faces, like the four quarters
+    /// that are created by sweeping a circle. The thread goes around all faces
+    /// of the shell that are located on the same cylinder as the provided one.
+    /// Together, these faces must cover the full circumference of the cylinder,
+    /// and each of them must cover its full length.
+    ///
+    /// The groove starts and ends abruptly, at the boundaries between those
+    /// faces. It is centered on the length of the cylinder, and stays clear of
+    /// either end by at least half its width.
+    ///
+    /// The face must be part of the solid.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the face is not cylindrical, if the faces of the cylinder
+    /// don't meet the requirements above, if the groove is too deep for the
+    /// pitch of the thread, meaning subsequent turns would overlap, or if the
+    /// cylinder is too short to fit the groove.
+    #[must_use]
+    fn add_thread(
+        &self,
+        face: &Handle<Face>,
+        spec: ThreadSpec,
+        core: &mut Core,
+    ) -> Self;
+}
+
+impl AddThread for Solid {
+    fn add_thread(
+        &self,
+        face: &Handle<Face>,
+        spec: ThreadSpec,
+        core: &mut Core,
+    ) -> Self {
+        let shell = self
+            .shells()
+            .iter()
+            .find(|shell| shell.faces().contains(face))
+            .expect("Face must be part of the solid");
+
+        let cylinder = Cylinder::of(face, core);
+        let faces = cylinder.faces(shell.faces().iter(), face, core);
+
+        // The profile of the groove is a triangle. Its base is located on the
+        // cylinder, its tip at the depth of the groove.
+        let half_width = spec.depth * FRAC_PI_6.tan();
+        assert!(
+            half_width * 2. < spec.pitch,
+            "Thread is too deep for its pitch"
+        );
+
+        let thread = Thread::new(&cylinder, &faces, spec, half_width, core);
+        let color = face.region().get_color(core);
+
+        // The cylinder is rebuilt from the pieces that the groove leaves of
+        // it. Those pieces define which way the half-edges they share with the
+        // faces of the groove go, so they need to be built first.
+        let mut directions = BTreeMap::new();
+        let mut updated_shell = Shell::clone(shell);
+        for (index, cylinder_face) in faces.iter().enumerate() {
+            let pieces =
+                thread.pieces_of_face(index, &faces, &mut directions, core);
+
+            updated_shell = updated_shell.update_face(
+                &cylinder_face.face,
+                |_, _| pieces,
+                core,
+            );
+        }
+
+        let groove = thread.groove(&directions, color, core);
+        let updated_shell = updated_shell.add_faces(groove, core);
+
+        self.update_shell(shell, |_, _| [updated_shell], core)
+    }
+}
+
+/// Defines a thread
+#[derive(Clone, Copy, Debug)]
+pub struct ThreadSpec {
+    /// Whether the thread is external or internal
+    pub kind: ThreadKind,
+
+    /// The distance between subsequent turns of the thread
+    pub pitch: Scalar,
+
+    /// The depth of the groove, measured from the face
+    pub depth: Scalar,
+
+    /// The direction that the thread turns in
+    ///
+    /// Most threads are right-handed.
+    pub handedness: Handedness,
+}
+
+/// The kind of a thread
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ThreadKind {
+    /// An external thread, like on a screw
+    External,
+
+    /// An internal thread, like in a tapped hole or a nut
+    Internal,
+}
+
+/// # The cylinder that a thread is cut into
+///
+/// Points on the cylinder are described by an angle around its axis, and by a
+/// height along it.
+struct Cylinder {
+    origin: Point<3>,
+    axis: Vector<3>,
+    radial: [Vector<3>; 2],
+    radius: Scalar,
+}
+
+impl Cylinder {
+    fn of(face: &Handle<Face>, core: &Core) -> Self {
+        let Some(SweptCurve {
+            u: Path::Circle(circle),
+            v,
+        }) = core.layers.geometry.try_of_surface(face.surface())
+        else {
+            panic!("Threads can only be added to cylindrical faces");
+        };
+        assert!(
+            circle.a().cross(&circle.b()).cross(v).magnitude()
+                <= core.tolerance().inner() * v.magnitude(),
+            "Threads can only be added to cylindrical faces"
+        );
+
+        let axis = v.normalize();
+        let radial = circle.a().normalize();
+
+        Self {
+            origin: circle.center(),
+            axis,
+            radial: [radial, axis.cross(&radial)],
+            radius: circle.radius(),
+        }
+    }
+
+    fn angle_of(&self, vector: Vector<3>) -> Scalar {
+        let [x, y] = self.radial.map(|radial| vector.dot(&radial));
+        y.atan2(x)
+    }
+
+    fn radial_at(&self, angle: Scalar) -> Vector<3> {
+        let (sin, cos) = angle.sin_cos();
+        let [x, y] = self.radial;
+
+        x * cos + y * sin
+    }
+
+    fn is_on_cylinder(
+        &self,
+        circle: &Circle<3>,
+        v: &Vector<3>,
+        core: &Core,
+    ) -> bool {
+        let tolerance = core.tolerance().inner();
+
+        let offset = circle.center() - self.origin;
+        let distance_from_axis =
+            (offset - self.axis * offset.dot(&self.axis)).magnitude();
+
+        (circle.radius() - self.radius).abs() <= tolerance
+            && distance_from_axis <= tolerance
+            && circle.a().dot(&self.axis).abs() <= tolerance
+            && circle.b().dot(&self.axis).abs() <= tolerance
+            && v.cross(&self.axis).magnitude() <= tolerance * v.magnitude()
+    }
+
+    /// # Find the faces that the cylinder consists of
+    ///
+    /// Returns them ordered by angle, starting with `face`.
+    fn faces<'r>(
+        &self,
+        faces: impl IntoIterator<Item = &'r Handle<Face>>,
+        face: &Handle<Face>,
+        core: &Core,
+    ) -> Vec<CylinderFace> {
+        let mut faces = faces
+            .into_iter()
+            .filter_map(|face| CylinderFace::new(face, self, core))
+            .collect::<Vec<_>>();
+        let start = faces
+            .iter()
+            .find(|cylinder_face| &cylinder_face.face == face)
+            .map(|cylinder_face| cylinder_face.angles[0])
+            .expect("Face is located on its own cylinder");
+
+        let epsilon = core.tolerance().inner() / self.radius;
+
+        for cylinder_face in &mut faces {
+            let turns = ((cylinder_face.angles[0] - start + epsilon)
+                / Scalar::TAU)
+                .floor();
+            cylinder_face.shift_angles(-turns * Scalar::TAU);
+
+            // The angles end up in the coordinates of the pieces of the
+            // cylinder, after the thread is cut. See `snap`.
+            cylinder_face.angles = cylinder_face.angles.map(snap);
+        }
+        faces.sort_by_key(|cylinder_face| cylinder_face.angles[0]);
+
+        let mut angle = start;
+        let mut heights = None;
+        for (cylinder_face, next) in
+            faces.iter().zip(faces.iter().cycle().skip(1))
+        {
+            assert!(
+                (cylinder_face.angles[0] - angle).abs() <= epsilon,
+                "Faces must cover the circumference of the cylinder once"
+            );
+            assert_eq!(
+                cylinder_face.bottom.vertices[1], next.bottom.vertices[0],
+                "Faces of the cylinder must be connected"
+            );
+            assert_eq!(
+                cylinder_face.top.vertices[1], next.top.vertices[0],
+                "Faces of the cylinder must be connected"
+            );
+
+            let [min, max] = cylinder_face.heights;
+            let [expected_min, expected_max] =
+                *heights.get_or_insert(cylinder_face.heights);
+            assert!(
+                (min - expected_min).abs() <= core.tolerance().inner()
+                    && (max - expected_max).abs() <= core.tolerance().inner(),
+                "Faces must cover the full length of the cylinder"
+            );
+
+            angle = cylinder_face.angles[1];
+        }
+        assert!(
+            (angle - start - Scalar::TAU).abs() <= epsilon,
+            "Faces must cover the circumference of the cylinder once"
+        );
+
+        faces
+    }
+}
+
+/// # A face that is part of a [`Cylinder`]
+struct CylinderFace {
+    face: Handle<Face>,
+
+    /// # The angles of the sides of the face, in ascending order
+    angles: [Scalar; 2],
+
+    /// # The heights of the bottom and top of the face
+    heights: [Scalar; 2],
+
+    bottom: Boundary,
+    top: Boundary,
+
+    /// # Whether the bottom boundary goes towards the larger angle
+    ///
+    /// Then the face is oriented counter-clockwise, in terms of angle and
+    /// height.
+    is_ccw: bool,
+}
+
+impl CylinderFace {
+    fn new(
+        face: &Handle<Face>,
+        cylinder: &Cylinder,
+        core: &Core,
+    ) -> Option<Self> {
+        let geometry = &core.layers.geometry;
+        let tolerance = core.tolerance().inner();
+
+        let Some(SweptCurve {
+            u: Path::Circle(circle),
+            v,
+        }) = geometry.try_of_surface(face.surface())
+        else {
+            return None;
+        };
+        if !cylinder.is_on_cylinder(circle, v, core) {
+            return None;
+        }
+
+        let half_edges = face.region().exterior().half_edges();
+        assert!(
+            half_edges.len() == 4 && face.region().interiors().is_empty(),
+            "Faces of the cylinder must be bounded by four half-edges"
+        );
+
+        let angle_offset = cylinder.angle_of(circle.a());
+        let angle_direction =
+            if circle.a().cross(&circle.b()).dot(&cylinder.axis) > Scalar::ZERO
+            {
+                Scalar::ONE
+            } else {
+                -Scalar::ONE
+            };
+        let height_offset =
+            (circle.center() - cylinder.origin).dot(&cylinder.axis);
+        let height_scale = v.dot(&cylinder.axis);
+
+        let corners = half_edges
+            .iter()
+            .map(|half_edge| {
+                let path = &geometry
+                    .of_curve(half_edge.curve())
+                    .unwrap()
+                    .local_on(face.surface())
+                    .unwrap()
+                    .path;
+                let position = geometry
+                    .of_vertex(half_edge.start_vertex())
+                    .unwrap()
+                    .local_on(half_edge.curve())
+                    .unwrap()
+                    .position;
+                let point = path.point_from_path_coords(position);
+
+                (
+                    half_edge.start_vertex().clone(),
+                    angle_offset + angle_direction * point.u,
+                    height_offset + height_scale * point.v,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let [min, max] = corners
+            .iter()
+            .fold([Scalar::MAX, -Scalar::MAX], |[min, max], (_, _, height)| {
+                [min.min(*height), max.max(*height)]
+            });
+
+        let mut bottom = None;
+        let mut top = None;
+        for (half_edge, (start, next)) in half_edges
+            .iter()
+            .zip(corners.iter().zip(corners.iter().cycle().skip(1)))
+        {
+            let (start_vertex, start_angle, start_height) = start;
+            let (end_vertex, end_angle, end_height) = next;
+
+            let boundary = if (*start_height - min).abs() <= tolerance
+                && (*end_height - min).abs() <= tolerance
+            {
+                &mut bottom
+            } else if (*start_height - max).abs() <= tolerance
+                && (*end_height - max).abs() <= tolerance
+            {
+                &mut top
+            } else {
+                assert!(
+                    (*start_angle - *end_angle).abs() * cylinder.radius
+                        <= tolerance,
+                    "Faces of the cylinder must be bounded along its axis"
+                );
+                continue;
+            };
+
+            let is_ascending = start_angle < end_angle;
+            let (vertices, angles) = if is_ascending {
+                (
+                    [start_vertex.clone(), end_vertex.clone()],
+                    [*start_angle, *end_angle],
+                )
+            } else {
+                (
+                    [end_vertex.clone(), start_vertex.clone()],
+                    [*end_angle, *start_angle],
+                )
+            };
+
+            *boundary = Some((
+                Boundary {
+                    curve: half_edge.curve().clone(),
+                    vertices,
+                },
+                angles,
+                is_ascending,
+            ));
+        }
+
+        let (Some((bottom, angles, is_ccw)), Some((top, _, _))) = (bottom, top)
+        else {
+            panic!("Faces of the cylinder must be bounded at its ends");
+        };
+
+        Some(Self {
+            face: face.clone(),
+            angles,
+            heights: [min, max],
+            bottom,
+            top,
+            is_ccw,
+        })
+    }
+
+    fn shift_angles(&mut self, offset: Scalar) {
+        self.angles = self.angles.map(|angle| angle + offset);
+    }
+}
+
+/// # A boundary of a piece of a [`CylinderFace`]
+///
+/// Goes from one side of the face to the other. The vertices are ordered by
+/// angle.
+#[derive(Clone)]
+struct Boundary {
+    curve: Handle<Curve>,
+    vertices: [Handle<Vertex>; 2],
+}
+
+/// # The thread, as it is laid out on the cylinder
+struct Thread {
+    /// # The profile of the groove
+    ///
+    /// Its points are the tip, and the lower and upper end of its base, in
+    /// the coordinates of the plane that the profile starts in.
+    profile: [Point<2>; 3],
+
+    /// # The surface that a line through the origin of the profile sweeps
+    ///
+    /// Its `curve` is replaced for each flank of the groove.
+    sweep: SweptAlongHelix,
+
+    /// # The surface of the pieces that the groove leaves of the cylinder
+    ///
+    /// Its u-coordinate is the angle, relative to the start of the thread. Its
+    /// v-coordinate is the height, relative to the start of the thread, in
+    /// terms of the coordinate on the helix, scaled by `v_scale`.
+    cylinder: SweptCurve,
+
+    /// # The scale of the v-coordinate of `cylinder`
+    ///
+    /// This is a power of two, so scaling is exact. It is chosen such, that
+    /// the surface is not distorted too much, which would result in a poor
+    /// triangulation.
+    v_scale: Scalar,
+
+    /// # The angle at which the thread starts
+    start_angle: Scalar,
+
+    /// # The heights of the bottom and top of the cylinder
+    ///
+    /// Like all heights on the pieces of the cylinder, they are given in terms
+    /// of the coordinate on the helix, relative to the start of the thread.
+    ends: [Scalar; 2],
+
+    /// # Half the width of the groove, in terms of the helix coordinate
+    offset: Scalar,
+
+    /// # `1`, if the angle grows along the thread, `-1` otherwise
+    direction: Scalar,
+
+    rings: Vec<Ring>,
+    sections: Vec<Section>,
+
+    /// # The points, at which the groove meets the sides of the faces
+    ///
+    /// Indexed by the side. Side `i` is the first side of face `i`, in terms of
+    /// angle, and the second side of the face before it.
+    sides: Vec<Side>,
+}
+
+impl Thread {
+    fn new(
+        cylinder: &Cylinder,
+        faces: &[CylinderFace],
+        spec: ThreadSpec,
+        half_width: Scalar,
+        core: &mut Core,
+    ) -> Self {
+        let direction = match spec.handedness {
+            Handedness::RightHanded => Scalar::ONE,
+            Handedness::LeftHanded => -Scalar::ONE,
+        };
+
+        // The groove goes from one side of a face to the next, until it
+        // wouldn't fit on the cylinder anymore.
+        let [min, max] = faces[0].heights;
+        let length = max - min;
+        let advance = |t: Scalar| spec.pitch * t / Scalar::TAU;
+
+        let mut ring_sides = vec![0];
+        let mut section_faces = Vec::new();
+        let mut t = vec![Scalar::ZERO];
+        loop {
+            let side = *ring_sides.last().unwrap();
+            let (face, next_side) = if direction > Scalar::ZERO {
+                (side, (side + 1) % faces.len())
+            } else {
+                let next_side = (side + faces.len() - 1) % faces.len();
+                (next_side, next_side)
+            };
+            let [start, end] = faces[face].angles;
+            let next_t = *t.last().unwrap() + (end - start);
+
+            if advance(next_t) + half_width * 4. > length {
+                break;
+            }
+
+            ring_sides.push(next_side);
+            section_faces.push(face);
+            t.push(next_t);
+        }
+        assert!(
+            !section_faces.is_empty(),
+            "Cylinder is too short for the thread"
+        );
+
+        let groove_length = advance(*t.last().unwrap()) + half_width * 2.;
+        let start_height = min + (length - groove_length) / 2. + half_width;
+
+        let depth = match spec.kind {
+            ThreadKind::External => -spec.depth,
+            ThreadKind::Internal => spec.depth,
+        };
+        let radius = cylinder.radius;
+        let profile = [
+            Point::from([radius + depth, Scalar::ZERO]),
+            Point::from([radius, -half_width]),
+            Point::from([radius, half_width]),
+        ];
+
+        let origin = cylinder.origin + cylinder.axis * start_height;
+        let radial = cylinder.radial_at(faces[0].angles[0]);
+        let sweep = SweptAlongHelix {
+            curve: Path::u_axis(),
+            origin,
+            axes: [radial, cylinder.axis],
+            helix: Helix {
+                center: origin,
+                axis: cylinder.axis,
+                a: radial * radius,
+                pitch: spec.pitch,
+                handedness: spec.handedness,
+            },
+            start: Point::origin(),
+        };
+        let v_scale = {
+            let exponent = (radius * Scalar::TAU / spec.pitch)
+                .into_f64()
+                .log2()
+                .round();
+            Scalar::from(2f64.powf(-exponent))
+        };
+        let cylinder = SweptCurve {
+            u: Path::Circle(Circle::new(
+                origin,
+                radial * radius,
+                cylinder.axis.cross(&radial) * radius,
+            )),
+            v: cylinder.axis * spec.pitch / Scalar::TAU / v_scale,
+        };
+
+        let rings = ring_sides
+            .into_iter()
+            .zip(t)
+            .map(|(side, t)| Ring {
+                side,
+                t,
+                vertices: [(); 3].map(|_| Vertex::new().insert(core)),
+            })
+            .collect::<Vec<_>>();
+
+        let sections = section_faces
+            .into_iter()
+            .enumerate()
+            .map(|(index, face)| {
+                let rails = [(); 3].map(|_| Curve::new().insert(core));
+
+                for (rail, vertex_index) in rails.iter().zip(0..) {
+                    for ring in &rings[index..=index + 1] {
+                        core.layers.geometry.define_vertex(
+                            ring.vertices[vertex_index].clone(),
+                            rail.clone(),
+                            LocalVertexGeom {
+                                position: Point::from([ring.t]),
+                            },
+                        );
+                    }
+                }
+
+                Section { face, rails }
+            })
+            .collect::<Vec<_>>();
+
+        // The points on the surface of the pieces are snapped to a grid, like
+        // the angles of the faces. See `snap`.
+        let to_helix = |height: Scalar| {
+            snap((height - start_height) * Scalar::TAU / spec.pitch)
+        };
+        let ends = [min, max].map(to_helix);
+        let offset = snap(half_width * Scalar::TAU / spec.pitch);
+
+        let sides = (0..faces.len())
+            .map(|index| {
+                Side::new(index, &faces[index], &rings, offset, ends, core)
+            })
+            .collect();
+
+        Self {
+            profile,
+            sweep,
+            cylinder,
+            v_scale,
+            start_angle: faces[0].angles[0],
+            ends,
+            offset,
+            direction,
+            rings,
+            sections,
+            sides,
+        }
+    }
+
+    /// # Build the pieces that the groove leaves of a face of the cylinder
+    ///
+    /// Records the start vertex of each half-edge of the pieces in
+    /// `directions`.
+    fn pieces_of_face(
+        &self,
+        index: usize,
+        faces: &[CylinderFace],
+        directions: &mut BTreeMap<Handle<Curve>, Handle<Vertex>>,
+        core: &mut Core,
+    ) -> Vec<Handle<Face>> {
+        let face = &faces[index];
+        let sides =
+            [&self.sides[index], &self.sides[(index + 1) % faces.len()]];
+
+        // Each part of the groove that crosses the face splits off another
+        // piece.
+        let num_pieces = self
+            .sections
+            .iter()
+            .filter(|section| section.face == index)
+            .count()
+            + 1;
+        let surfaces = (0..num_pieces)
+            .map(|_| {
+                let surface = Surface::new().insert(core);
+                core.layers.geometry.define_surface_2(
+                    surface.clone(),
+                    SurfaceGeom {
+                        generator: Box::new(self.cylinder.clone()),
+                        geometry: TriMesh::empty(),
+                    },
+                );
+                surface
+            })
+            .collect::<Vec<_>>();
+
+        // Each piece is bounded by two of these boundaries, below and above
+        // it.
+        let mut boundaries = Vec::new();
+
+        self.define_end_on_surface(&face.bottom, face, &surfaces[0], core);
+        boundaries.push(face.bottom.clone());
+
+        for (section_index, section) in self.sections.iter().enumerate() {
+            if section.face != index {
+                continue;
+            }
+
+            let rings = if self.direction > Scalar::ZERO {
+                [section_index, section_index + 1]
+            } else {
+                [section_index + 1, section_index]
+            };
+            let entry_angle = if self.direction > Scalar::ZERO {
+                face.angles[0]
+            } else {
+                face.angles[1]
+            };
+            let start = &self.rings[section_index];
+
+            // The rails of the base of the profile. Each of them bounds the
+            // part of the groove that crosses the face, and the piece below or
+            // above it.
+            for (vertex_index, offset) in [(1, -self.offset), (2, self.offset)]
+            {
+                let rail = section.rails[vertex_index].clone();
+                let surface = &surfaces[boundaries.len() / 2];
+
+                // On the surface of the pieces, the rails are straight lines.
+                // All components of their direction are powers of two, so
+                // points on them are computed exactly, as long as their
+                // coordinate is snapped to the grid, or derived from snapped
+                // coordinates by subdivision.
+                let origin = self.point_on_cylinder(
+                    entry_angle - self.direction * start.t,
+                    offset,
+                );
+                rail.clone().make_path_on_surface(
+                    Path::Line(Line::from_origin_and_direction(
+                        origin,
+                        Vector::from([self.direction, self.v_scale]),
+                    )),
+                    surface.clone(),
+                    &mut core.layers.geometry,
+                );
+
+                boundaries.push(Boundary {
+                    curve: rail,
+                    vertices: rings.map(|ring| {
+                        self.rings[ring].vertices[vertex_index].clone()
+                    }),
+                });
+            }
+        }
+
+        self.define_end_on_surface(
+            &face.top,
+            face,
+            &surfaces[num_pieces - 1],
+            core,
+        );
+        boundaries.push(face.top.clone());
+
+        let color = face.face.region().get_color(core);
+
+        boundaries
+            .chunks(2)
+            .zip(surfaces)
+            .map(|(boundaries, surface)| {
+                let [lower, upper] = [&boundaries[0], &boundaries[1]];
+
+                let mut half_edges =
+                    vec![(lower.curve.clone(), lower.vertices[0].clone())];
+                half_edges.extend(
+                    sides[1]
+                        .half_edges_between(
+                            [&lower.vertices[1], &upper.vertices[1]],
+                            |height| {
+                                self.point_on_cylinder(face.angles[1], height)
+                            },
+                            &surface,
+                            core,
+                        )
+                        .into_iter()
+                        .map(|(curve, [start, _])| (curve, start)),
+                );
+                half_edges
+                    .push((upper.curve.clone(), upper.vertices[1].clone()));
+                half_edges.extend(
+                    sides[0]
+                        .half_edges_between(
+                            [&lower.vertices[0], &upper.vertices[0]],
+                            |height| {
+                                self.point_on_cylinder(face.angles[0], height)
+                            },
+                            &surface,
+                            core,
+                        )
+                        .into_iter()
+                        .rev()
+                        .map(|(curve, [_, end])| (curve, end)),
+                );
+
+                let exterior = Cycle::from_curves_and_start_vertices(
+                    half_edges,
+                    !face.is_ccw,
+                    core,
+                )
+                .insert(core);
+                for half_edge in exterior.half_edges() {
+                    directions.insert(
+                        half_edge.curve().clone(),
+                        half_edge.start_vertex().clone(),
+                    );
+                }
+
+                build_face(surface, exterior, color, core)
+            })
+            .collect()
+    }
+
+    /// # Define the bottom or top boundary of a face on a piece's surface
+    ///
+    /// The boundary is located at a constant height, so it is a straight line
+    /// along the u-axis of the surface.
+    fn define_end_on_surface(
+        &self,
+        boundary: &Boundary,
+        face: &CylinderFace,
+        surface: &Handle<Surface>,
+        core: &mut Core,
+    ) {
+        let [bottom, top] = self.ends;
+        let height = if boundary.curve == face.bottom.curve {
+            bottom
+        } else {
+            top
+        };
+
+        let points_surface = face
+            .angles
+            .map(|angle| self.point_on_cylinder(angle, height));
+        let points_curve = boundary.vertices.each_ref().map(|vertex| {
+            core.layers
+                .geometry
+                .of_vertex(vertex)
+                .unwrap()
+                .local_on(&boundary.curve)
+                .unwrap()
+                .position
+        });
+
+        boundary.curve.clone().make_line_on_surface(
+            points_surface,
+            CurveBoundary::from(points_curve),
+            surface.clone(),
+            &mut core.layers.geometry,
+        );
+    }
+
+    /// # Compute the point on the pieces' surface at an angle and height
+    ///
+    /// The height is given in terms of the coordinate on the helix, relative to
+    /// the start of the thread. If the angle and height are snapped to the
+    /// grid, the computation is exact.
+    fn point_on_cylinder(&self, angle: Scalar, height: Scalar) -> Point<2> {
+        Point::from([angle - self.start_angle, height * self.v_scale])
+    }
+
+    /// # Build the faces of the groove
+    ///
+    /// These are the flanks of the groove and the faces at its ends. Each of
+    /// them shares half-edges with the pieces of the cylinder, and is oriented
+    /// such, that its half-edges go the other way than those, as recorded in
+    /// `directions`.
+    fn groove(
+        &self,
+        directions: &BTreeMap<Handle<Curve>, Handle<Vertex>>,
+        color: Option<Color>,
+        core: &mut Core,
+    ) -> Vec<Handle<Face>> {
+        let [tip, ..] = self.profile;
+
+        // Each ring has a curve for each flank, from the tip of the profile to
+        // its base.
+        let flanks = self
+            .rings
+            .iter()
+            .map(|ring| {
+                [1, 2].map(|vertex_index| {
+                    let curve = Curve::new().insert(core);
+
+                    for (index, position) in [(0, 0.), (vertex_index, 1.)] {
+                        core.layers.geometry.define_vertex(
+                            ring.vertices[index].clone(),
+                            curve.clone(),
+                            LocalVertexGeom {
+                                position: Point::from([position]),
+                            },
+                        );
+                    }
+
+                    curve
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut faces = Vec::new();
+
+        for (section_index, section) in self.sections.iter().enumerate() {
+            let rings = [section_index, section_index + 1];
+
+            for (flank_index, vertex_index) in [(0, 1), (1, 2)] {
+                let surface = Surface::new().insert(core);
+                core.layers.geometry.define_surface_2(
+                    surface.clone(),
+                    SurfaceGeom {
+                        generator: Box::new(SweptAlongHelix {
+                            curve: Path::line_from_points([
+                                tip,
+                                self.profile[vertex_index],
+                            ])
+                            .0,
+                            ..self.sweep
+                        }),
+                        geometry: TriMesh::empty(),
+                    },
+                );
+
+                for ring in rings {
+                    flanks[ring][flank_index].clone().make_path_on_surface(
+                        Path::Line(Line::from_origin_and_direction(
+                            Point::from([Scalar::ZERO, self.rings[ring].t]),
+                            Vector::unit_u(),
+                        )),
+                        surface.clone(),
+                        &mut core.layers.geometry,
+                    );
+                }
+                for (rail_index, u) in [(0, 0.), (vertex_index, 1.)] {
+                    section.rails[rail_index].clone().make_path_on_surface(
+                        Path::Line(Line::from_origin_and_direction(
+                            Point::from([u, 0.]),
+                            Vector::unit_v(),
+                        )),
+                        surface.clone(),
+                        &mut core.layers.geometry,
+                    );
+                }
+
+                let [start, end] = rings.map(|ring| &self.rings[ring].vertices);
+                let half_edges = vec![
+                    (flanks[rings[0]][flank_index].clone(), start[0].clone()),
+                    (
+                        section.rails[vertex_index].clone(),
+                        start[vertex_index].clone(),
+                    ),
+                    (
+                        flanks[rings[1]][flank_index].clone(),
+                        end[vertex_index].clone(),
+                    ),
+                    (section.rails[0].clone(), end[0].clone()),
+                ];
+                let reverse = directions.get(&section.rails[vertex_index])
+                    == Some(&start[vertex_index]);
+
+                let exterior = Cycle::from_curves_and_start_vertices(
+                    half_edges, reverse, core,
+                )
+                .insert(core);
+                faces.push(build_face(surface, exterior, color, core));
+            }
+        }
+
+        for ring_index in [0, self.rings.len() - 1] {
+            let ring = &self.rings[ring_index];
+            let side = &self.sides[ring.side];
+
+            let (origin, [u, v]) = self.sweep.plane_at(Point::from([ring.t]));
+            let surface = Surface::from_uv(
+                Path::Line(Line::from_origin_and_direction(origin, u)),
+                v,
+                core,
+            );
+
+            let base = side.base_of(ring_index);
+            let [flank_lower, flank_upper] = &flanks[ring_index];
+            for (curve, points) in [
+                (&base, [self.profile[1], self.profile[2]]),
+                (flank_lower, [tip, self.profile[1]]),
+                (flank_upper, [tip, self.profile[2]]),
+            ] {
+                curve.clone().make_path_on_surface(
+                    Path::line_from_points(points).0,
+                    surface.clone(),
+                    &mut core.layers.geometry,
+                );
+            }
+
+            let [tip, lower, upper] = &ring.vertices;
+            let half_edges = vec![
+                (base.clone(), lower.clone()),
+                (flank_upper.clone(), upper.clone()),
+                (flank_lower.clone(), tip.clone()),
+            ];
+            let reverse = directions.get(&base) == Some(lower);
+
+            let exterior = Cycle::from_curves_and_start_vertices(
+                half_edges, reverse, core,
+            )
+            .insert(core);
+            faces.push(build_face(surface, exterior, color, core));
+        }
+
+        faces
+    }
+}
+
+/// # A point along the groove, where it crosses a side of a face
+struct Ring {
+    /// # The index of the side that the ring is located on
+    side: usize,
+
+    /// # The coordinate of the ring on the helix
+    t: Scalar,
+
+    /// # The vertices of the profile at the ring
+    ///
+    /// The tip, and the lower and upper end of the base.
+    vertices: [Handle<Vertex>; 3],
+}
+
+/// # A section of the groove, between two rings
+struct Section {
+    /// # The index of the face that the section crosses
+    face: usize,
+
+    /// # The curves that the vertices of the profile are swept along
+    rails: [Handle<Curve>; 3],
+}
+
+/// # A side of a face of the cylinder, along its axis
+///
+/// It is shared by two faces, and split into segments where the groove meets
+/// it.
+struct Side {
+    /// # The points on the side, ordered by height
+    ///
+    /// Each point is stored with its height, in terms of the coordinate on the
+    /// helix, relative to the start of the thread.
+    points: Vec<(Scalar, Handle<Vertex>)>,
+
+    /// # The segments between the points
+    ///
+    /// A segment is `None`, if it is located within the groove.
+    segments: Vec<Option<Handle<Curve>>>,
+
+    /// # The segments that the groove starts or ends at, by ring
+    bases: BTreeMap<usize, Handle<Curve>>,
+}
+
+impl Side {
+    fn new(
+        index: usize,
+        face: &CylinderFace,
+        rings: &[Ring],
+        offset: Scalar,
+        [bottom, top]: [Scalar; 2],
+        core: &mut Core,
+    ) -> Self {
+        let mut points = vec![
+            (bottom, face.bottom.vertices[0].clone()),
+            (top, face.top.vertices[0].clone()),
+        ];
+        let mut bases = BTreeMap::new();
+        for (ring_index, ring) in rings.iter().enumerate() {
+            if ring.side != index {
+                continue;
+            }
+
+            let [_, lower, upper] = &ring.vertices;
+            points.push((ring.t - offset, lower.clone()));
+            points.push((ring.t + offset, upper.clone()));
+
+            // Where the groove starts or ends, its base is a segment of the
+            // side, shared by the face at the end of the groove and a piece of
+            // the cylinder. Everywhere else, that segment is not part of any
+            // face.
+            if ring_index == 0 || ring_index == rings.len() - 1 {
+                bases.insert(ring_index, Curve::new().insert(core));
+            }
+        }
+        points.sort_by_key(|(height, _)| *height);
+
+        let segments = points
+            .windows(2)
+            .map(|points| {
+                let [(_, lower), (_, upper)] = [&points[0], &points[1]];
+
+                let ring = rings.iter().enumerate().find(|(_, ring)| {
+                    ring.side == index
+                        && &ring.vertices[1] == lower
+                        && &ring.vertices[2] == upper
+                });
+                let curve = match ring {
+                    Some((ring_index, _)) => bases.get(&ring_index)?.clone(),
+                    None => Curve::new().insert(core),
+                };
+
+                for (vertex, position) in [(lower, 0.), (upper, 1.)] {
+                    core.layers.geometry.define_vertex(
+                        vertex.clone(),
+                        curve.clone(),
+                        LocalVertexGeom {
+                            position: Point::from([position]),
+                        },
+                    );
+                }
+
+                Some(curve)
+            })
+            .collect();
+
+        Self {
+            points,
+            segments,
+            bases,
+        }
+    }
+
+    fn base_of(&self, ring_index: usize) -> Handle<Curve> {
+        self.bases
+            .get(&ring_index)
+            .expect("Groove starts or ends at ring")
+            .clone()
+    }
+
+    /// # Access the segments between two vertices on the side
+    ///
+    /// Defines the segments on `surface`, using `point_surface` to convert the
+    /// height of a point on the side into a point on it. Returns the segments from bottom
+    /// to top, along with their vertices.
+    fn half_edges_between(
+        &self,
+        vertices: [&Handle<Vertex>; 2],
+        point_surface: impl Fn(Scalar) -> Point<2>,
+        surface: &Handle<Surface>,
+        core: &mut Core,
+    ) -> Vec<(Handle<Curve>, [Handle<Vertex>; 2])> {
+        let [start, end] = vertices.map(|vertex| {
+            self.points
+                .iter()
+                .position(|(_, point)| point == vertex)
+                .expect("Vertex is located on side")
+        });
+
+        (start..end)
+            .map(|index| {
+                let curve = self.segments[index]
+                    .clone()
+                    .expect("Pieces of the cylinder don't overlap the groove");
+                let [(lower_height, lower), (upper_height, upper)] =
+                    [&self.points[index], &self.points[index + 1]];
+
+                curve.clone().make_line_on_surface(
+                    [lower_height, upper_height]
+                        .map(|height| point_surface(*height)),
+                    CurveBoundary::from([[0.], [1.]]),
+                    surface.clone(),
+                    &mut core.layers.geometry,
+                );
+
+                (curve, [lower.clone(), upper.clone()])
+            })
+            .collect()
+    }
+}
+
+/// # Snap a coordinate to a grid
+///
+/// The sides and rails of the pieces of the cylinder are straight lines on
+/// their surface, and many of them have more than two points on them. Unless
+/// those points are exactly collinear, the triangulation of the pieces ends up
+/// with sliver triangles between them.
+///
+/// The grid is much finer than any tolerance, but coarse enough, that sums and
+/// differences of snapped coordinates are computed exactly.
+fn snap(value: Scalar) -> Scalar {
+    const GRID: f64 = (1u64 << 32) as f64;
+    Scalar::from((value.into_f64() * GRID).round() / GRID)
+}
+
+fn build_face(
+    surface: Handle<Surface>,
+    exterior: Handle<Cycle>,
+    color: Option<Color>,
+    core: &mut Core,
+) -> Handle<Face> {
+    let region = Region::new(exterior, []).insert(core);
+    if let Some(color) = color {
+        region.set_color(color, core);
+    }
+
+    Face::new(surface, region).insert(core)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_6;
+
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        geometry::{surfaces::SweptCurve, Path},
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            holes::{AddHole, HoleLocation},
+            insert::Insert,
+            sweep::SweepSketch,
+            tests::{assert_volume, volume},
+            update::{UpdateSketch, UpdateSolid},
+        },
+        storage::Handle,
+        topology::{Face, Handedness, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{AddThread, ThreadKind, ThreadSpec};
+
+    #[test]
+    fn external_thread() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cylinder = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::circle(
+                    [0., 0.],
+                    1.,
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -2.],
+                &mut core,
+            )
+            .insert(&mut core);
+
+        let spec = ThreadSpec {
+            kind: ThreadKind::External,
+            pitch: Scalar::from(0.5),
+            depth: Scalar::from(0.25),
+            handedness: Handedness::RightHanded,
+        };
+        let face = cylindrical_face(&cylinder, &core);
+        let screw = cylinder
+            .add_thread(&face, spec, &mut core)
+            .insert(&mut core);
+
+        assert_eq!(screw.shells().len(), 1);
+        core.layers.validation.take_errors()?;
+
+        let expected = volume(&cylinder, &mut core)? - groove_volume(spec);
+        assert_volume(&screw, expected, &mut core)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn internal_thread() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., -2.],
+                &mut core,
+            )
+            .insert(&mut core);
+        let cuboid = cuboid.update_shell(
+            cuboid.shells().only(),
+            |shell, core| {
+                let top = shell.faces().first();
+                let bottom = shell.faces().nth(5).unwrap();
+
+                [shell.add_through_hole(
+                    [bottom, top].map(|face| HoleLocation {
+                        face,
+                        position: Point::origin(),
+                    }),
+                    1.,
+                    core,
+                )]
+            },
+            &mut core,
+        );
+
+        let spec = ThreadSpec {
+            kind: ThreadKind::Internal,
+            pitch: Scalar::from(0.5),
+            depth: Scalar::from(0.25),
+            handedness: Handedness::LeftHanded,
+        };
+        let face = cylindrical_face(&cuboid, &core);
+        let nut = cuboid.add_thread(&face, spec, &mut core).insert(&mut core);
+
+        assert_eq!(nut.shells().len(), 1);
+        core.layers.validation.take_errors()?;
+
+        let expected = volume(&cuboid, &mut core)? - groove_volume(spec);
+        assert_volume(&nut, expected, &mut core)?;
+
+        Ok(())
+    }
+
+    fn cylindrical_face(solid: &Solid, core: &Core) -> Handle<Face> {
+        solid
+            .shells()
+            .only()
+            .faces()
+            .iter()
+            .find(|face| {
+                matches!(
+                    core.layers.geometry.try_of_surface(face.surface()),
+                    Some(SweptCurve {
+                        u: Path::Circle(_),
+                        ..
+                    })
+                )
+            })
+            .expect("Expected solid to have a cylindrical face")
+            .clone()
+    }
+
+    /// # The volume of the groove
+    ///
+    /// The profile of the groove is a triangle of `depth^2 * tan(30°)`, whose
+    /// centroid is a third of the depth away from the face, which has a radius
+    /// of `1`.
+    ///
+    /// The cylinders in the tests are `2` long. Each quarter turn of the groove
+    /// advances it by an eighth, and it needs to stay clear of the ends of the
+    /// cylinder by half its width. That leaves room for eleven quarter turns.
+    fn groove_volume(spec: ThreadSpec) -> Scalar {
+        let area = spec.depth * spec.depth * FRAC_PI_6.tan();
+        let radius = match spec.kind {
+            ThreadKind::External => Scalar::ONE - spec.depth / 3.,
+            ThreadKind::Internal => Scalar::ONE + spec.depth / 3.,
+        };
+        let turns = Scalar::from(11. / 4.);
+
+        area * Scalar::TAU * radius * turns
+    }
+}
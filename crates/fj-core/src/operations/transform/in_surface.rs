@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use fj_math::{Circle, Ellipse, Line, Point, Transform, Vector};
+
+use crate::{
+    geometry::Path,
+    operations::{
+        derive::DeriveFrom,
+        geometry::UpdateCurveGeometry,
+        insert::Insert,
+        presentation::{GetColor, SetColor},
+    },
+    storage::Handle,
+    topology::{Curve, Cycle, HalfEdge, Region, Surface, Vertex},
+    Core,
+};
+
+/// # Transform regions within the surface they are on
+///
+/// Unlike [`TransformObject`], which moves the surface and leaves the curves
+/// as they are on it, this transforms the curves in surface coordinates. The
+/// transformed regions are new objects on the same surface.
+///
+/// The transform is applied to surface coordinates, as if they were the `x`
+/// and `y` coordinates of a 3D point.
+///
+/// [`TransformObject`]: super::TransformObject
+pub fn transform_regions_in_surface<'r>(
+    regions: impl IntoIterator<Item = &'r Handle<Region>>,
+    surface: &Handle<Surface>,
+    transform: &Transform,
+    core: &mut Core,
+) -> Vec<Handle<Region>> {
+    // Regions might share curves and vertices. Their transformed versions
+    // need to share the transformed versions of those.
+    let mut curves = BTreeMap::new();
+    let mut vertices = BTreeMap::new();
+
+    let mut transformed = Vec::new();
+    for region in regions {
+        let cycles = region
+            .all_cycles()
+            .map(|cycle| {
+                let half_edges = cycle
+                    .half_edges()
+                    .pairs()
+                    .map(|(half_edge, next)| {
+                        let curve = curves
+                            .entry(half_edge.curve().clone())
+                            .or_insert_with(|| {
+                                transform_curve(
+                                    half_edge.curve(),
+                                    surface,
+                                    transform,
+                                    core,
+                                )
+                            })
+                            .clone();
+
+                        for vertex in
+                            [half_edge.start_vertex(), next.start_vertex()]
+                        {
+                            let transformed_vertex = vertices
+                                .entry(vertex.clone())
+                                .or_insert_with(|| Vertex::new().insert(core))
+                                .clone();
+                            let geometry = core
+                                .layers
+                                .geometry
+                                .of_vertex(vertex)
+                                .unwrap()
+                                .local_on(half_edge.curve())
+                                .unwrap()
+                                .clone();
+                            core.layers.geometry.define_vertex(
+                                transformed_vertex,
+                                curve.clone(),
+                                geometry,
+                            );
+                        }
+
+                        HalfEdge::new(
+                            curve,
+                            vertices[half_edge.start_vertex()].clone(),
+                        )
+                        .insert(core)
+                        .derive_from(half_edge, core)
+                    })
+                    .collect::<Vec<_>>();
+
+                Cycle::new(half_edges).insert(core).derive_from(cycle, core)
+            })
+            .collect::<Vec<_>>();
+
+        let mut cycles = cycles.into_iter();
+        let exterior =
+            cycles.next().expect("Region must have an exterior cycle");
+        let transformed_region = Region::new(exterior, cycles)
+            .insert(core)
+            .derive_from(region, core);
+        if let Some(color) = region.get_color(core) {
+            transformed_region.set_color(color, core);
+        }
+
+        transformed.push(transformed_region);
+    }
+
+    transformed
+}
+
+fn transform_curve(
+    curve: &Handle<Curve>,
+    surface: &Handle<Surface>,
+    transform: &Transform,
+    core: &mut Core,
+) -> Handle<Curve> {
+    let path = core
+        .layers
+        .geometry
+        .of_curve(curve)
+        .unwrap()
+        .local_on(surface)
+        .unwrap()
+        .path
+        .clone();
+
+    let point = |point: Point<2>| {
+        let point = transform.transform_point(&point.to_xyz());
+        Point::from([point.x, point.y])
+    };
+    let vector =
+        |vector: Vector<2>| transform.transform_vector(&vector.to_xyz()).xy();
+
+    let path = match path {
+        Path::Circle(circle) => Path::Circle(Circle::new(
+            point(circle.center()),
+            vector(circle.a()),
+            vector(circle.b()),
+        )),
+        Path::Ellipse(ellipse) => Path::Ellipse(Ellipse::new(
+            point(ellipse.center()),
+            vector(ellipse.a()),
+            vector(ellipse.b()),
+        )),
+        Path::Line(line) => Path::Line(Line::from_origin_and_direction(
+            point(line.origin()),
+            vector(line.direction()),
+        )),
+        Path::Nurbs(curve) => Path::Nurbs(curve.map_control_points(point)),
+    };
+
+    Curve::new()
+        .insert(core)
+        .derive_from(curve, core)
+        .make_path_on_surface(path, surface.clone(), &mut core.layers.geometry)
+}
@@ -4,6 +4,7 @@ mod curve;
 mod cycle;
 mod face;
 mod half_edge;
+mod in_surface;
 mod region;
 mod shell;
 mod solid;
@@ -24,6 +25,8 @@ use crate::{
 
 use super::derive::DeriveFrom;
 
+pub(crate) use self::in_surface::transform_regions_in_surface;
+
 /// Transform an object
 ///
 /// # Implementation Note
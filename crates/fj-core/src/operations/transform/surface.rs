@@ -28,18 +28,20 @@ impl TransformObject for &Handle<Surface> {
             .or_insert_with(|| {
                 let surface = Surface::new().insert(core);
 
-                let geometry = {
-                    let SweptCurve { u, v } =
-                        core.layers.geometry.of_surface(self);
-
-                    SweptCurve {
-                        u: u.transform(transform),
+                // Not all surfaces have old-style geometry defined. If this one
+                // doesn't, the new-style geometry defined below is all there
+                // is.
+                if let Some(SweptCurve { u, v }) =
+                    core.layers.geometry.try_of_surface(self)
+                {
+                    let geometry = SweptCurve {
+                        u: u.clone().transform(transform),
                         v: transform.transform_vector(v),
-                    }
-                };
-                core.layers
-                    .geometry
-                    .define_surface(surface.clone(), geometry);
+                    };
+                    core.layers
+                        .geometry
+                        .define_surface(surface.clone(), geometry);
+                }
 
                 core.layers.geometry.define_surface_2(
                     surface.clone(),
@@ -1,7 +1,7 @@
 use fj_math::{Scalar, Winding};
 
 use crate::{
-    geometry::{Geometry, Path},
+    geometry::{traits::GenPolyline, CurveBoundary, Geometry, Path, Tolerance},
     storage::Handle,
     topology::{HalfEdge, ObjectSet},
 };
@@ -31,15 +31,31 @@ impl Cycle {
     /// Please note that this is not *the* winding of the cycle, only one of the
     /// two possible windings, depending on the direction you look at the
     /// surface that the cycle is defined on from.
+    ///
+    /// Curved edges are approximated within the provided tolerance, to figure
+    /// out which side of the cycle they bulge out to.
     pub fn winding(
         &self,
+        tolerance: impl Into<Tolerance>,
         geometry: &Geometry,
         surface: &Handle<Surface>,
     ) -> Winding {
-        // The cycle could be made up of one or two circles. If that is the
-        // case, the winding of the cycle is determined by the winding of the
-        // first circle.
-        if self.half_edges.len() < 3 {
+        // The cycle could be made up of one or two circles or ellipses. If
+        // that is the case, the winding of the cycle is determined by the
+        // winding of the first of them.
+        let first_is_circle_or_ellipse =
+            self.half_edges().iter().next().is_some_and(|half_edge| {
+                matches!(
+                    geometry
+                        .of_curve(half_edge.curve())
+                        .unwrap()
+                        .local_on(surface)
+                        .unwrap()
+                        .path,
+                    Path::Circle(_) | Path::Ellipse(_)
+                )
+            });
+        if self.half_edges.len() < 3 && first_is_circle_or_ellipse {
             let first = self
                 .half_edges()
                 .iter()
@@ -78,13 +94,16 @@ impl Cycle {
             ];
             let edge_direction_positive = a < b;
 
-            let circle = match curve_geom.path {
-                Path::Circle(circle) => circle,
-                Path::Line(_) => unreachable!(
-                    "Invalid cycle: less than 3 edges, but not all are circles"
-                ),
+            let cross = match curve_geom.path {
+                Path::Circle(circle) => circle.a().cross2d(&circle.b()),
+                Path::Ellipse(ellipse) => ellipse.a().cross2d(&ellipse.b()),
+                Path::Line(_) | Path::Nurbs(_) => {
+                    unreachable!(
+                        "Just checked that first edge is a circle or ellipse"
+                    );
+                }
             };
-            let cross_positive = circle.a().cross2d(&circle.b()) > Scalar::ZERO;
+            let cross_positive = cross > Scalar::ZERO;
 
             if edge_direction_positive == cross_positive {
                 return Winding::Ccw;
@@ -96,27 +115,48 @@ impl Cycle {
         // Now that we got the special case out of the way, we can treat the
         // cycle as a polygon:
         // https://stackoverflow.com/a/1165943
-
-        let mut sum = Scalar::ZERO;
-
-        for (a, b) in self.half_edges().pairs() {
-            let [a, b] = [a, b].map(|half_edge| {
+        //
+        // The vertices alone are not enough to describe that polygon, if the
+        // cycle contains curved edges. A NURBS curve, for example, can bulge
+        // out arbitrarily far between its vertices, so we need to approximate
+        // each edge.
+
+        let tolerance = tolerance.into();
+        let mut points = Vec::new();
+
+        for (half_edge, next) in self.half_edges().pairs() {
+            let path = &geometry
+                .of_curve(half_edge.curve())
+                .unwrap()
+                .local_on(surface)
+                .unwrap()
+                .path;
+            let [start, end] = [half_edge, next].map(|h| {
                 geometry
-                    .of_curve(half_edge.curve())
+                    .of_vertex(h.start_vertex())
                     .unwrap()
-                    .local_on(surface)
+                    .local_on(half_edge.curve())
                     .unwrap()
-                    .path
-                    .point_from_path_coords(
-                        geometry
-                            .of_vertex(half_edge.start_vertex())
-                            .unwrap()
-                            .local_on(half_edge.curve())
-                            .unwrap()
-                            .position,
-                    )
+                    .position
             });
 
+            points.push(path.point_from_path_coords(start));
+            points.extend(
+                path.generate_polyline(
+                    CurveBoundary {
+                        inner: [start, end],
+                    },
+                    tolerance,
+                )
+                .into_iter()
+                .map(|point| path.point_from_path_coords(point)),
+            );
+        }
+
+        let mut sum = Scalar::ZERO;
+
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
             sum += (b.u - a.u) * (b.v + a.v);
         }
 
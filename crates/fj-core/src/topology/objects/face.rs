@@ -1,7 +1,7 @@
 use fj_math::Winding;
 
 use crate::{
-    geometry::Geometry,
+    geometry::{Geometry, Tolerance},
     storage::Handle,
     topology::{Region, Surface},
 };
@@ -62,8 +62,16 @@ impl Face {
     /// Faces *do* have an orientation, meaning they have definite front and
     /// back sides. The front side is the side, where the face's exterior cycle
     /// is wound counter-clockwise.
-    pub fn coord_handedness(&self, geometry: &Geometry) -> Handedness {
-        match self.region.exterior().winding(geometry, self.surface()) {
+    pub fn coord_handedness(
+        &self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Handedness {
+        match self.region.exterior().winding(
+            tolerance,
+            geometry,
+            self.surface(),
+        ) {
             Winding::Ccw => Handedness::RightHanded,
             Winding::Cw => Handedness::LeftHanded,
         }
@@ -72,12 +72,14 @@ impl SketchValidationError {
     fn check_exterior_cycles(
         sketch: &Sketch,
         geometry: &Geometry,
-        _config: &ValidationConfig,
+        config: &ValidationConfig,
         errors: &mut Vec<ValidationError>,
     ) {
         sketch.regions().iter().for_each(|region| {
             let cycle = region.exterior();
-            if cycle.winding(geometry, sketch.surface()) == Winding::Cw {
+            if cycle.winding(config.tolerance, geometry, sketch.surface())
+                == Winding::Cw
+            {
                 errors.push(ValidationError::Sketch(
                     SketchValidationError::ClockwiseExteriorCycle {
                         cycle: cycle.clone(),
@@ -90,7 +92,7 @@ impl SketchValidationError {
     fn check_interior_cycles(
         sketch: &Sketch,
         geometry: &Geometry,
-        _config: &ValidationConfig,
+        config: &ValidationConfig,
         errors: &mut Vec<ValidationError>,
     ) {
         sketch.regions().iter().for_each(|region| {
@@ -98,7 +100,11 @@ impl SketchValidationError {
                 .interiors()
                 .iter()
                 .filter(|interior| {
-                    interior.winding(geometry, sketch.surface()) == Winding::Ccw
+                    interior.winding(
+                        config.tolerance,
+                        geometry,
+                        sketch.surface(),
+                    ) == Winding::Ccw
                 })
                 .for_each(|cycle| {
                     errors.push(ValidationError::Sketch(
@@ -226,7 +226,8 @@ fn distances(
         let path = geometry
             .of_curve(half_edge.curve())?
             .local_on(surface)?
-            .path;
+            .path
+            .clone();
         let surface_coords = path.point_from_path_coords(path_coords);
         Some(convert_point_surface_to_global(
             &geometry.of_surface_2(surface).unwrap().generator,
@@ -237,8 +238,8 @@ fn distances(
     }
 
     // Three samples (start, middle, end), are enough to detect weather lines
-    // and circles match. If we were to add more complicated curves, this might
-    // need to change.
+    // and circles match. For NURBS curves, this is only a heuristic, as two
+    // different curves could still match at those samples.
     let sample_count = 3;
     let step = 1.0 / (sample_count as f64 - 1.0);
 
@@ -40,7 +40,7 @@ impl ValidationCheck<Face> for InteriorCycleHasInvalidWinding {
     fn check<'r>(
         object: &'r Face,
         geometry: &'r Geometry,
-        _: &'r ValidationConfig,
+        config: &'r ValidationConfig,
     ) -> impl Iterator<Item = Self> + 'r {
         object.region().interiors().iter().filter_map(|interior| {
             let exterior = object.region().exterior();
@@ -53,8 +53,10 @@ impl ValidationCheck<Face> for InteriorCycleHasInvalidWinding {
                 return None;
             }
 
-            let exterior_winding = exterior.winding(geometry, object.surface());
-            let interior_winding = interior.winding(geometry, object.surface());
+            let exterior_winding =
+                exterior.winding(config.tolerance, geometry, object.surface());
+            let interior_winding =
+                interior.winding(config.tolerance, geometry, object.surface());
 
             if exterior_winding == interior_winding {
                 return Some(InteriorCycleHasInvalidWinding {
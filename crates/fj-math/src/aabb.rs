@@ -1,5 +1,6 @@
 use parry2d_f64::bounding_volume::BoundingVolume as _;
 use parry3d_f64::bounding_volume::BoundingVolume as _;
+use parry3d_f64::query::{Ray, RayCast as _};
 
 use super::{Point, Vector};
 
@@ -140,6 +141,21 @@ impl Aabb<3> {
     pub fn merged(&self, other: &Self) -> Self {
         self.to_parry().merged(&other.to_parry()).into()
     }
+
+    /// Check whether a ray hits the AABB
+    pub fn intersects_ray(
+        &self,
+        origin: Point<3>,
+        dir: Vector<3>,
+        max_toi: f64,
+    ) -> bool {
+        let ray = Ray {
+            origin: origin.to_na(),
+            dir: dir.to_na(),
+        };
+
+        self.to_parry().intersects_local_ray(&ray, max_toi)
+    }
 }
 
 impl From<parry2d_f64::bounding_volume::Aabb> for Aabb<2> {
@@ -156,6 +172,8 @@ impl From<parry3d_f64::bounding_volume::Aabb> for Aabb<3> {
 
 #[cfg(test)]
 mod tests {
+    use crate::{Point, Vector};
+
     use super::Aabb;
 
     #[test]
@@ -174,4 +192,16 @@ mod tests {
         assert!(!aabb.contains([0., 2.]));
         assert!(!aabb.contains([4., 2.]));
     }
+
+    #[test]
+    fn intersects_ray() {
+        let aabb = Aabb::<3>::from_points([[1., 1., 1.], [3., 3., 3.]]);
+        let origin = Point::from([0., 2., 2.]);
+
+        assert!(aabb.intersects_ray(origin, Vector::from([1., 0., 0.]), 2.));
+
+        assert!(!aabb.intersects_ray(origin, Vector::from([1., 0., 0.]), 0.5));
+        assert!(!aabb.intersects_ray(origin, Vector::from([-1., 0., 0.]), 2.));
+        assert!(!aabb.intersects_ray(origin, Vector::from([0., 1., 0.]), 2.));
+    }
 }
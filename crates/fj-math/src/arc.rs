@@ -67,13 +67,14 @@ impl Arc {
         };
         let end_angle = {
             let from_center = p1 - center;
-            let offset = if more_than_half_turn {
-                Scalar::TAU
-            } else {
-                Scalar::ZERO
-            };
-
-            from_center.v.atan2(from_center.u) + offset
+            let end_angle = from_center.v.atan2(from_center.u);
+
+            // The angle we just computed is within `(-PI, PI]`, which doesn't
+            // necessarily reflect the direction or size of the arc. Select the
+            // equivalent angle that does.
+            let turns =
+                ((start_angle + angle_rad - end_angle) / Scalar::TAU).round();
+            end_angle + Scalar::TAU * turns
         };
         Self {
             center,
@@ -126,6 +127,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn arc_construction_across_angle_wraparound() {
+        // The end angle is computed using `atan2`, which returns angles within
+        // `(-PI, PI]`. Here, that's less than the start angle, but the arc is
+        // counter-clockwise, so the end angle must be larger.
+        check_arc_calculation(
+            [0.5, 0.5],
+            0.3,
+            180_f64.to_radians(),
+            270_f64.to_radians(),
+        );
+
+        // The same, in the other direction.
+        check_arc_calculation(
+            [0.5, 0.5],
+            0.3,
+            -170_f64.to_radians(),
+            -280_f64.to_radians(),
+        );
+    }
+
     fn check_arc_calculation(
         center: impl Into<Point<2>>,
         radius: f64,
@@ -0,0 +1,299 @@
+use approx::AbsDiffEq;
+use num_traits::Float;
+
+use crate::{Aabb, Point, Scalar, Transform, Vector};
+
+/// An n-dimensional ellipse
+///
+/// The ellipse is defined by its center and two vectors, `a` and `b`, which
+/// are conjugate semi-diameters of the ellipse. They can be the semi-axes of
+/// the ellipse, but unlike with a [`Circle`], they don't need to be of equal
+/// length, nor perpendicular to each other. This makes ellipses closed under
+/// affine transformations, like those between the coordinate systems of a
+/// plane and of global space.
+///
+/// The ellipse coordinate of a point on the ellipse is an angle (in radians),
+/// which relates to the point like the angle on a circle does, if that circle
+/// had been transformed to fit the ellipse. Unless the ellipse is a circle, it
+/// is not the geometric angle between `a` and the vector from the center to the
+/// point.
+///
+/// The dimensionality of the ellipse is defined by the const generic `D`
+/// parameter.
+///
+/// [`Circle`]: crate::Circle
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Ellipse<const D: usize> {
+    center: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Ellipse<D> {
+    /// Construct an ellipse
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `a` and `b` are not linearly independent, meaning either of
+    /// them is of zero length, or they are parallel to each other.
+    pub fn new(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+
+        // The determinant of the Gram matrix is the squared area of the
+        // parallelogram spanned by `a` and `b`. We compare it relative to their
+        // lengths, as the ellipse can have any size.
+        let [aa, ab, bb] = [a.dot(&a), a.dot(&b), b.dot(&b)];
+        assert!(
+            aa * bb - ab * ab > aa * bb * Scalar::default_epsilon() * 16.,
+            "`a` and `b` must be linearly independent"
+        );
+
+        Self { center, a, b }
+    }
+
+    /// Access the center point of the ellipse
+    pub fn center(&self) -> Point<D> {
+        self.center
+    }
+
+    /// Access the vector that defines the starting point of the ellipse
+    ///
+    /// The point where this vector points from the ellipse center, is the zero
+    /// coordinate of the ellipse's coordinate system.
+    ///
+    /// Please also refer to [`Self::b`].
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the vector that defines the plane of the ellipse
+    ///
+    /// Also defines the direction of the ellipse's coordinate system. This
+    /// vector points from the center to the point at the ellipse coordinate
+    /// `PI / 2.`.
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Access the length of the major semi-axis
+    ///
+    /// This is the largest distance between the center and any point on the
+    /// ellipse.
+    pub fn major_radius(&self) -> Scalar {
+        let [_, major] = self.radii();
+        major
+    }
+
+    /// Access the length of the minor semi-axis
+    ///
+    /// This is the smallest distance between the center and any point on the
+    /// ellipse.
+    pub fn minor_radius(&self) -> Scalar {
+        let [minor, _] = self.radii();
+        minor
+    }
+
+    fn radii(&self) -> [Scalar; 2] {
+        // The squared radii are the eigenvalues of the Gram matrix of `a` and
+        // `b`.
+        let [aa, ab, bb] = [
+            self.a.dot(&self.a),
+            self.a.dot(&self.b),
+            self.b.dot(&self.b),
+        ];
+
+        let mean = (aa + bb) / 2.;
+        let offset = (((aa - bb) / 2.).powi(2) + ab * ab).sqrt();
+
+        [mean - offset, mean + offset]
+            .map(|radius_squared| radius_squared.max(Scalar::ZERO).sqrt())
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.b = -self.b;
+        self
+    }
+
+    /// Convert a `D`-dimensional point to ellipse coordinates
+    ///
+    /// Converts the provided point into ellipse coordinates between `0.`
+    /// (inclusive) and `PI * 2.` (exclusive).
+    ///
+    /// Projects the point into the plane of the ellipse, and onto the ellipse,
+    /// before computing the ellipse coordinate. Like for
+    /// [`Circle::point_to_circle_coords`], a point that is not on the ellipse
+    /// will not result in an error.
+    ///
+    /// [`Circle::point_to_circle_coords`]: crate::Circle::point_to_circle_coords
+    pub fn point_to_ellipse_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        let vector = point.into() - self.center;
+
+        // Solve `vector = a * cos + b * sin` in the least-squares sense, which
+        // projects the vector into the plane of the ellipse.
+        let [aa, ab, bb] = [
+            self.a.dot(&self.a),
+            self.a.dot(&self.b),
+            self.b.dot(&self.b),
+        ];
+        let [va, vb] = [vector.dot(&self.a), vector.dot(&self.b)];
+        let determinant = aa * bb - ab * ab;
+
+        let cos = (va * bb - vb * ab) / determinant;
+        let sin = (vb * aa - va * ab) / determinant;
+
+        let atan = Scalar::atan2(sin, cos);
+        let coord = if atan >= Scalar::ZERO {
+            atan
+        } else {
+            atan + Scalar::TAU
+        };
+        Point::from([coord])
+    }
+
+    /// Convert a point in ellipse coordinates into a `D`-dimensional point
+    pub fn point_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.center + self.vector_from_ellipse_coords(point.into().coords)
+    }
+
+    /// Convert a vector in ellipse coordinates into a `D`-dimensional point
+    pub fn vector_from_ellipse_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let angle = vector.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.a * cos + self.b * sin
+    }
+
+    /// Calculate an AABB for the ellipse
+    pub fn aabb(&self) -> Aabb<D> {
+        // Along each axis, the ellipse extends from the center by the length
+        // of the vector made up of the semi-axes' components along that axis.
+        let mut center_to_min_max = Vector::default();
+        for i in 0..D {
+            center_to_min_max.components[i] =
+                Vector::from([self.a.components[i], self.b.components[i]])
+                    .magnitude();
+        }
+
+        Aabb {
+            min: self.center() - center_to_min_max,
+            max: self.center() + center_to_min_max,
+        }
+    }
+}
+
+impl Ellipse<3> {
+    /// # Transform the ellipse
+    pub fn transform(&self, transform: &Transform) -> Self {
+        Ellipse::new(
+            transform.transform_point(&self.center()),
+            transform.transform_vector(&self.a()),
+            transform.transform_vector(&self.b()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Aabb, Ellipse, Point, Scalar};
+
+    #[test]
+    fn point_to_ellipse_coords() {
+        let ellipse = Ellipse::new([1., 2., 3.], [2., 0., 0.], [0., 1., 0.]);
+
+        assert_eq!(
+            ellipse.point_to_ellipse_coords([3., 2., 3.]),
+            Point::from([0.]),
+        );
+        assert_eq!(
+            ellipse.point_to_ellipse_coords([1., 3., 3.]),
+            Point::from([FRAC_PI_2]),
+        );
+        assert_eq!(
+            ellipse.point_to_ellipse_coords([-1., 2., 3.]),
+            Point::from([PI]),
+        );
+        assert_eq!(
+            ellipse.point_to_ellipse_coords([1., 1., 3.]),
+            Point::from([FRAC_PI_2 * 3.]),
+        );
+    }
+
+    #[test]
+    fn point_to_ellipse_coords_oblique() {
+        let ellipse = Ellipse::new([0., 0.], [2., 0.], [1., 1.]);
+
+        for t in [0., 1., 2., 3., 4., 5., 6.] {
+            let point = ellipse.point_from_ellipse_coords([t]);
+            assert_abs_diff_eq!(
+                ellipse.point_to_ellipse_coords(point),
+                Point::from([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn radii() {
+        let ellipse = Ellipse::new([0., 0., 0.], [0., 3., 0.], [0., 0., 1.]);
+        assert_eq!(ellipse.major_radius(), Scalar::from(3.));
+        assert_eq!(ellipse.minor_radius(), Scalar::from(1.));
+
+        // The semi-diameters are not the semi-axes. The squared radii are the
+        // eigenvalues of `[[4, 2], [2, 2]]`.
+        let ellipse = Ellipse::new([0., 0.], [2., 0.], [1., 1.]);
+        assert_abs_diff_eq!(
+            ellipse.major_radius(),
+            Scalar::from((3. + 5f64.sqrt()).sqrt()),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            ellipse.minor_radius(),
+            Scalar::from((3. - 5f64.sqrt()).sqrt()),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+
+    #[test]
+    fn aabb() {
+        let ellipse = Ellipse::new([0., 0.], [4., 0.], [0., 1.]);
+        assert_eq!(
+            ellipse.aabb(),
+            Aabb {
+                min: Point::from([-4., -1.]),
+                max: Point::from([4., 1.]),
+            },
+        );
+
+        // Tilted by 45 degrees.
+        let ellipse = Ellipse::new([0., 0.], [2., 2.], [-1., 1.]);
+        let extent = (4. + 1f64).sqrt();
+        assert_eq!(
+            ellipse.aabb(),
+            Aabb {
+                min: Point::from([-extent, -extent]),
+                max: Point::from([extent, extent]),
+            },
+        );
+    }
+}
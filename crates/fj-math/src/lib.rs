@@ -36,8 +36,10 @@ mod arc;
 mod bivector;
 mod circle;
 mod coordinates;
+mod ellipse;
 mod line;
 mod line_segment;
+mod nurbs;
 mod point;
 mod poly_chain;
 mod scalar;
@@ -51,8 +53,10 @@ pub use self::{
     bivector::Bivector,
     circle::Circle,
     coordinates::{Uv, Xyz, T},
+    ellipse::Ellipse,
     line::Line,
     line_segment::LineSegment,
+    nurbs::NurbsCurve,
     point::Point,
     poly_chain::PolyChain,
     scalar::{Scalar, Sign},
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use parry3d_f64::query::PointQuery as _;
+
 use crate::{Line, Scalar};
 
 use super::Point;
@@ -54,6 +56,69 @@ impl LineSegment<3> {
     pub fn to_parry(self) -> parry3d_f64::shape::Segment {
         self.points.map(|point| point.to_na()).into()
     }
+
+    /// Find the point on the segment that is closest to the provided point
+    pub fn closest_point(&self, point: impl Into<Point<3>>) -> Point<3> {
+        self.to_parry()
+            .project_local_point(&point.into().to_na(), true)
+            .point
+            .into()
+    }
+
+    /// Find the points on this segment and another that are closest together
+    ///
+    /// Returns the point on this segment first, the point on the other segment
+    /// second. If the segments are parallel, there are infinitely many such
+    /// pairs of points, and an arbitrary one is returned.
+    pub fn closest_points(&self, other: &Self) -> [Point<3>; 2] {
+        let [p1, q1] = self.points;
+        let [p2, q2] = other.points;
+
+        let d1 = q1 - p1;
+        let d2 = q2 - p2;
+        let r = p1 - p2;
+
+        let a = d1.dot(&d1);
+        let e = d2.dot(&d2);
+        let f = d2.dot(&r);
+
+        // Find the parameters of the closest points on the lines through both
+        // segments, clamping them to the segments as we go.
+        let (s, t) = if a == Scalar::ZERO && e == Scalar::ZERO {
+            (Scalar::ZERO, Scalar::ZERO)
+        } else if a == Scalar::ZERO {
+            (Scalar::ZERO, (f / e).clamp(Scalar::ZERO, Scalar::ONE))
+        } else {
+            let c = d1.dot(&r);
+
+            if e == Scalar::ZERO {
+                ((-c / a).clamp(Scalar::ZERO, Scalar::ONE), Scalar::ZERO)
+            } else {
+                let b = d1.dot(&d2);
+                let denom = a * e - b * b;
+
+                let s = if denom != Scalar::ZERO {
+                    ((b * f - c * e) / denom).clamp(Scalar::ZERO, Scalar::ONE)
+                } else {
+                    Scalar::ZERO
+                };
+
+                let t = (b * s + f) / e;
+                if t < Scalar::ZERO {
+                    ((-c / a).clamp(Scalar::ZERO, Scalar::ONE), Scalar::ZERO)
+                } else if t > Scalar::ONE {
+                    (
+                        ((b - c) / a).clamp(Scalar::ZERO, Scalar::ONE),
+                        Scalar::ONE,
+                    )
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        [p1 + d1 * s, p2 + d2 * t]
+    }
 }
 
 impl<P, const D: usize> From<[P; 2]> for LineSegment<D>
@@ -0,0 +1,406 @@
+use std::sync::Arc;
+
+use crate::{Aabb, Point, Scalar, Transform, Vector};
+
+/// An n-dimensional NURBS curve
+///
+/// A non-uniform rational B-spline curve, defined by its degree, a list of
+/// control points with associated weights, and a knot vector.
+///
+/// The dimensionality of the curve is defined by the const generic `D`
+/// parameter.
+///
+/// The curve coordinates of a point on the curve are the parameter value of the
+/// B-spline. The range of valid curve coordinates (the domain of the curve) is
+/// defined by the knot vector. See [`NurbsCurve::domain`].
+///
+/// The control points, weights, and knots are reference-counted, which makes
+/// the curve cheap to clone.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NurbsCurve<const D: usize> {
+    degree: usize,
+    control_points: Arc<[Point<D>]>,
+    weights: Arc<[Scalar]>,
+    knots: Arc<[Scalar]>,
+}
+
+impl<const D: usize> NurbsCurve<D> {
+    /// Construct a NURBS curve
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - The degree must not be zero.
+    /// - There must be more control points than the degree.
+    /// - There must be one weight per control point.
+    /// - All weights must be positive.
+    /// - The number of knots must be equal to the number of control points,
+    ///   plus the degree, plus one.
+    /// - The knots must not be decreasing.
+    /// - The domain of the curve, as defined by the knots, must not be empty.
+    pub fn new(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+        weights: impl IntoIterator<Item = impl Into<Scalar>>,
+        knots: impl IntoIterator<Item = impl Into<Scalar>>,
+    ) -> Self {
+        let control_points = control_points
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+        let weights = weights.into_iter().map(Into::into).collect::<Vec<_>>();
+        let knots = knots.into_iter().map(Into::into).collect::<Vec<_>>();
+
+        assert_ne!(degree, 0, "degree must not be zero");
+        assert!(
+            control_points.len() > degree,
+            "must have more control points than the degree"
+        );
+        assert_eq!(
+            control_points.len(),
+            weights.len(),
+            "must have one weight per control point"
+        );
+        assert!(
+            weights.iter().all(|&weight| weight > Scalar::ZERO),
+            "weights must be positive"
+        );
+        assert_eq!(
+            knots.len(),
+            control_points.len() + degree + 1,
+            "number of knots must be number of control points plus degree \
+            plus one"
+        );
+        assert!(
+            knots.windows(2).all(|knots| knots[0] <= knots[1]),
+            "knots must not be decreasing"
+        );
+
+        let self_ = Self {
+            degree,
+            control_points: control_points.into(),
+            weights: weights.into(),
+            knots: knots.into(),
+        };
+
+        let [start, end] = self_.domain();
+        assert!(start < end, "domain of curve must not be empty");
+
+        self_
+    }
+
+    /// Construct a clamped B-spline from control points
+    ///
+    /// All weights are one, which makes the curve non-rational. The knot vector
+    /// is uniform and clamped, meaning the curve starts at the first control
+    /// point and ends at the last one. The domain of the curve is `0` to `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the degree is zero, or if there aren't more control points
+    /// than the degree.
+    pub fn clamped(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+    ) -> Self {
+        let control_points = control_points
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+        let num_control_points = control_points.len();
+
+        Self::clamped_with_weights(
+            degree,
+            control_points,
+            vec![Scalar::ONE; num_control_points],
+        )
+    }
+
+    /// Construct a clamped NURBS curve from control points and weights
+    ///
+    /// Like [`NurbsCurve::clamped`], but with custom weights.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the requirements documented on [`NurbsCurve::new`] are not
+    /// met.
+    pub fn clamped_with_weights(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+        weights: impl IntoIterator<Item = impl Into<Scalar>>,
+    ) -> Self {
+        let control_points = control_points
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+
+        assert!(
+            control_points.len() > degree,
+            "must have more control points than the degree"
+        );
+
+        let num_spans = control_points.len() - degree;
+        let knots = (0..control_points.len() + degree + 1).map(|i| {
+            let i = i.saturating_sub(degree).min(num_spans);
+            Scalar::from(i as f64) / Scalar::from(num_spans as f64)
+        });
+
+        Self::new(degree, control_points, weights, knots)
+    }
+
+    /// Access the degree of the curve
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Access the control points of the curve
+    pub fn control_points(&self) -> &[Point<D>] {
+        &self.control_points
+    }
+
+    /// Access the weights of the control points
+    pub fn weights(&self) -> &[Scalar] {
+        &self.weights
+    }
+
+    /// Access the knot vector of the curve
+    pub fn knots(&self) -> &[Scalar] {
+        &self.knots
+    }
+
+    /// Access the domain of the curve
+    ///
+    /// Returns the first and last curve coordinate for which the curve is
+    /// defined.
+    pub fn domain(&self) -> [Scalar; 2] {
+        [
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        ]
+    }
+
+    /// Access the knot spans of the curve
+    ///
+    /// Returns the non-empty intervals between two subsequent knots, that are
+    /// within the domain of the curve. Within each of these, the curve is a
+    /// single (rational) polynomial.
+    pub fn spans(&self) -> impl Iterator<Item = [Scalar; 2]> + '_ {
+        self.knots[self.degree..=self.control_points.len()]
+            .windows(2)
+            .map(|knots| [knots[0], knots[1]])
+            .filter(|[start, end]| start < end)
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The reversed curve has the same domain. A point at curve coordinate `t`
+    /// on the original curve is at `start + end - t` on the reversed one.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        let [start, end] = self.domain();
+
+        Self {
+            degree: self.degree,
+            control_points: self.control_points.iter().rev().copied().collect(),
+            weights: self.weights.iter().rev().copied().collect(),
+            knots: self
+                .knots
+                .iter()
+                .rev()
+                .map(|&knot| start + end - knot)
+                .collect(),
+        }
+    }
+
+    /// Convert a point in curve coordinates into a `D`-dimensional point
+    ///
+    /// Points outside of the domain are computed by extending the first or
+    /// last knot span of the curve.
+    pub fn point_from_curve_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let t = point.into().t;
+        let p = self.degree;
+
+        // Find the knot span that contains the point. The knots within the
+        // domain are not decreasing, so this finds the last one whose start is
+        // not after the point.
+        let last = self.control_points.len() - 1;
+        let span = (p..=last)
+            .rev()
+            .find(|&i| self.knots[i] <= t && self.knots[i] < self.knots[i + 1])
+            .unwrap_or(p);
+
+        // This is de Boor's algorithm, applied to the control points in
+        // homogeneous coordinates.
+        let mut points = (0..=p)
+            .map(|j| {
+                let i = j + span - p;
+                let weight = self.weights[i];
+                (self.control_points[i].coords * weight, weight)
+            })
+            .collect::<Vec<_>>();
+
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let i = j + span - p;
+                let alpha = (t - self.knots[i])
+                    / (self.knots[i + 1 + p - r] - self.knots[i]);
+
+                let (a, a_weight) = points[j - 1];
+                let (b, b_weight) = points[j];
+
+                points[j] = (
+                    a * (Scalar::ONE - alpha) + b * alpha,
+                    a_weight * (Scalar::ONE - alpha) + b_weight * alpha,
+                );
+            }
+        }
+
+        let (coords, weight) = points[p];
+        Point {
+            coords: coords / weight,
+        }
+    }
+
+    /// Convert a vector in curve coordinates into a `D`-dimensional vector
+    ///
+    /// The vector is relative to the point at curve coordinate `0`.
+    pub fn vector_from_curve_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let t = vector.into().t;
+        self.point_from_curve_coords([t]) - self.point_from_curve_coords([0.])
+    }
+
+    /// Create a new curve by mapping the control points
+    ///
+    /// NURBS curves are invariant under affine transformations. If the provided
+    /// function is an affine map, the resulting curve is the map applied to
+    /// this one. This can be used to convert between curves of different
+    /// dimensionality.
+    pub fn map_control_points<const E: usize>(
+        &self,
+        f: impl FnMut(Point<D>) -> Point<E>,
+    ) -> NurbsCurve<E> {
+        NurbsCurve {
+            degree: self.degree,
+            control_points: self
+                .control_points
+                .iter()
+                .copied()
+                .map(f)
+                .collect(),
+            weights: self.weights.clone(),
+            knots: self.knots.clone(),
+        }
+    }
+
+    /// Calculate an AABB for the curve
+    ///
+    /// Relies on the fact that a NURBS curve with positive weights is contained
+    /// in the convex hull of its control points. The AABB might therefore be
+    /// larger than necessary, but is guaranteed to contain the curve.
+    pub fn aabb(&self) -> Aabb<D> {
+        let mut min = self.control_points[0];
+        let mut max = self.control_points[0];
+
+        for point in self.control_points.iter() {
+            for i in 0..D {
+                min.coords.components[i] =
+                    min.coords.components[i].min(point.coords.components[i]);
+                max.coords.components[i] =
+                    max.coords.components[i].max(point.coords.components[i]);
+            }
+        }
+
+        Aabb { min, max }
+    }
+}
+
+impl NurbsCurve<3> {
+    /// # Transform the curve
+    pub fn transform(&self, transform: &Transform) -> Self {
+        self.map_control_points(|point| transform.transform_point(&point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{NurbsCurve, Point, Scalar};
+
+    #[test]
+    fn point_from_curve_coords() {
+        // A quadratic Bézier curve.
+        let curve = NurbsCurve::clamped(2, [[0., 0.], [1., 2.], [2., 0.]]);
+
+        assert_eq!(curve.domain(), [Scalar::ZERO, Scalar::ONE]);
+        assert_eq!(curve.point_from_curve_coords([0.]), Point::from([0., 0.]));
+        assert_eq!(curve.point_from_curve_coords([0.5]), Point::from([1., 1.]));
+        assert_eq!(curve.point_from_curve_coords([1.]), Point::from([2., 0.]));
+    }
+
+    #[test]
+    fn rational_quarter_circle() {
+        let curve = NurbsCurve::clamped_with_weights(
+            2,
+            [[1., 0.], [1., 1.], [0., 1.]],
+            [1., FRAC_1_SQRT_2, 1.],
+        );
+
+        for i in 0..=10 {
+            let point = curve.point_from_curve_coords([i as f64 / 10.]);
+            assert_abs_diff_eq!(
+                point.coords.magnitude(),
+                Scalar::ONE,
+                epsilon = Scalar::from(1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn multiple_spans() {
+        // A clamped, piecewise linear curve goes through all control points,
+        // at evenly spaced curve coordinates.
+        let curve =
+            NurbsCurve::clamped(1, [[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+
+        assert_eq!(curve.spans().count(), 3);
+        assert_abs_diff_eq!(
+            curve.point_from_curve_coords([0.5]),
+            Point::from([1., 0.5]),
+            epsilon = Scalar::from(1e-12)
+        );
+        assert_eq!(curve.point_from_curve_coords([1.]), Point::from([0., 1.]));
+    }
+
+    #[test]
+    fn reverse() {
+        let curve = NurbsCurve::clamped(2, [[0., 0.], [1., 2.], [3., 0.]]);
+        let reversed = curve.clone().reverse();
+
+        for t in [0., 0.25, 0.5, 1.] {
+            assert_abs_diff_eq!(
+                curve.point_from_curve_coords([t]),
+                reversed.point_from_curve_coords([1. - t]),
+                epsilon = Scalar::from(1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn clones_share_their_data() {
+        let curve = NurbsCurve::clamped(2, [[0., 0.], [1., 2.], [3., 0.]]);
+        let clone = curve.clone();
+
+        assert!(std::ptr::eq(curve.control_points(), clone.control_points()));
+        assert!(std::ptr::eq(curve.knots(), clone.knots()));
+    }
+}
@@ -47,6 +47,21 @@ impl Transform {
         ))
     }
 
+    /// Construct a reflection
+    ///
+    /// Reflects across the plane that goes through the origin and is
+    /// perpendicular to `normal`. Unlike the other transforms, this changes the
+    /// handedness of anything it is applied to.
+    pub fn reflection(normal: impl Into<Vector<3>>) -> Self {
+        let normal = normal.into().normalize().to_na();
+        let matrix =
+            nalgebra::Matrix3::identity() - normal * normal.transpose() * 2.;
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            matrix.to_homogeneous(),
+        ))
+    }
+
     /// # Extract the "right" vector from the rotational component
     pub fn right(&self) -> Vector<3> {
         let d = self.data();
@@ -167,7 +182,7 @@ impl ops::Mul<Self> for Transform {
 mod tests {
     use approx::assert_abs_diff_eq;
 
-    use crate::{Scalar, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Transform;
 
@@ -201,4 +216,20 @@ mod tests {
             epsilon = 1e-8,
         );
     }
+
+    #[test]
+    fn reflection() {
+        let reflection = Transform::reflection([1., 1., 0.]);
+
+        assert_abs_diff_eq!(
+            reflection.transform_point(&Point::from([1., 0., 3.])),
+            Point::from([0., -1., 3.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            reflection.transform_vector(&Vector::from([1., 1., 0.])),
+            Vector::from([-1., -1., 0.]),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use approx::AbsDiffEq;
-use parry3d_f64::query::{Ray, RayCast as _};
+use parry3d_f64::query::{PointQuery as _, Ray, RayCast as _};
 
-use crate::Vector;
+use crate::{LineSegment, Vector};
 
 use super::{Point, Scalar};
 
@@ -114,6 +114,75 @@ impl Triangle<3> {
             .map(Into::into)
     }
 
+    /// Find the point on the triangle that is closest to the provided point
+    pub fn closest_point(&self, point: impl Into<Point<3>>) -> Point<3> {
+        self.to_parry()
+            .project_local_point(&point.into().to_na(), true)
+            .point
+            .into()
+    }
+
+    /// Find the points on the triangle and a segment that are closest together
+    ///
+    /// Returns the point on the triangle first, the point on the segment
+    /// second. If the segment passes through the triangle, both points are
+    /// the point where it does so.
+    pub fn closest_points_to_segment(
+        &self,
+        segment: &LineSegment<3>,
+    ) -> [Point<3>; 2] {
+        let [a, b] = segment.points;
+
+        if let Some(t) = self.cast_local_ray(a, b - a, 1., true) {
+            let point = a + (b - a) * t;
+            return [point; 2];
+        }
+
+        // The segment doesn't pass through the triangle, so at least one of
+        // the closest points lies on the boundary of either of them.
+        let [p, q, r] = self.points;
+        let candidates_from_edges = [[p, q], [q, r], [r, p]]
+            .map(|edge| LineSegment::from(edge).closest_points(segment));
+        let candidates_from_ends =
+            [a, b].map(|end| [self.closest_point(end), end]);
+
+        candidates_from_edges
+            .into_iter()
+            .chain(candidates_from_ends)
+            .min_by_key(|[on_triangle, on_segment]| {
+                on_triangle.distance_to(on_segment)
+            })
+            .expect("Candidates are not empty")
+    }
+
+    /// Find the points on this triangle and another that are closest together
+    ///
+    /// Returns the point on this triangle first, the point on the other
+    /// triangle second. If the triangles intersect, both points are a point
+    /// where they do so.
+    pub fn closest_points(&self, other: &Self) -> [Point<3>; 2] {
+        let edges = |triangle: &Self| {
+            let [p, q, r] = triangle.points;
+            [[p, q], [q, r], [r, p]].map(LineSegment::from)
+        };
+
+        // The closest points between two triangles always involve an edge of
+        // at least one of them. This includes the case of intersecting
+        // triangles, where an edge of one passes through the other.
+        let candidates_from_other =
+            edges(other).map(|edge| self.closest_points_to_segment(&edge));
+        let candidates_from_self = edges(self).map(|edge| {
+            let [on_other, on_self] = other.closest_points_to_segment(&edge);
+            [on_self, on_other]
+        });
+
+        candidates_from_other
+            .into_iter()
+            .chain(candidates_from_self)
+            .min_by_key(|[on_self, on_other]| on_self.distance_to(on_other))
+            .expect("Candidates are not empty")
+    }
+
     /// Compute the triangle's normal
     pub fn normal(&self) -> Vector<3> {
         self.to_parry()
@@ -157,7 +226,7 @@ impl Winding {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Point, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Triangle;
 
@@ -203,4 +272,54 @@ mod tests {
             Triangle::from([[0.0, 0.0, 0.0], [2.0, 1.0, 0.0], [2.0, 0.0, 0.0]]);
         assert_eq!(triangle.normal(), Vector::from([0.0, 0.0, -1.0]));
     }
+
+    #[test]
+    fn closest_point() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        // Above the triangle, closest to a point on its face.
+        assert_eq!(
+            triangle.closest_point([0.5, 0.5, 1.0]),
+            Point::from([0.5, 0.5, 0.0])
+        );
+
+        // Beside the triangle, closest to one of its vertices.
+        assert_eq!(
+            triangle.closest_point([-1.0, -1.0, 0.0]),
+            Point::from([0.0, 0.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn closest_points() {
+        let a =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        // Parallel, offset along the normal.
+        let b =
+            Triangle::from([[0.5, 0.5, 1.0], [1.0, 0.5, 1.0], [0.5, 1.0, 1.0]]);
+        let [on_a, on_b] = a.closest_points(&b);
+        assert_eq!(on_a.distance_to(&on_b), Scalar::ONE);
+
+        // Beside each other, closest at a vertex and an edge.
+        let c = Triangle::from([
+            [-1.0, 1.0, 0.0],
+            [-2.0, 0.0, 0.0],
+            [-2.0, 2.0, 0.0],
+        ]);
+        assert_eq!(
+            a.closest_points(&c),
+            [Point::from([0.0, 1.0, 0.0]), Point::from([-1.0, 1.0, 0.0])]
+        );
+
+        // Intersecting.
+        let d = Triangle::from([
+            [0.5, 0.5, -1.0],
+            [0.5, 0.5, 1.0],
+            [1.0, 1.0, 1.0],
+        ]);
+        let [on_a, on_d] = a.closest_points(&d);
+        assert_eq!(on_a.distance_to(&on_d), Scalar::ZERO);
+    }
 }
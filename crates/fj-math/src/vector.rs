@@ -95,7 +95,12 @@ impl<const D: usize> Vector<D> {
         if product.is_zero() {
             Scalar::ZERO
         } else {
-            (self.dot(other) / product).acos()
+            // For (nearly) parallel vectors, rounding errors can push the
+            // cosine slightly out of its valid range.
+            (self.dot(other) / product)
+                .max(-Scalar::ONE)
+                .min(Scalar::ONE)
+                .acos()
         }
     }
 
@@ -415,6 +420,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn angle_to() {
+        let v = Vector::from([1., 0.]);
+        assert_eq!(v.angle_to(&Vector::from([0., 2.])), Scalar::PI / 2.);
+
+        // For these vectors, the computed cosine of the angle between them is
+        // slightly larger than one.
+        let v = Vector::from([0.1, 0.7]);
+        assert_eq!(v.angle_to(&v), Scalar::ZERO);
+        assert_eq!(v.angle_to(&-v), Scalar::PI);
+    }
+
     #[test]
     fn is_between() {
         let v = Vector::from([1., 1.]);